@@ -1,4 +1,5 @@
-from typing import Final
+from collections.abc import Callable
+from typing import Any, Final
 
 WHITE: Final[int]
 BLACK: Final[int]
@@ -11,67 +12,341 @@ NUM_KNIGHT_DELTAS: Final[int]
 NUM_UNDERPROMO_DIRECTIONS: Final[int]
 NUM_UNDERPROMO_PIECES: Final[int]
 NUM_PROMOTION_ORIENTATIONS: Final[int]
+NUM_FAIRY_PROMO_PIECES: Final[int]
+FAIRY_PROMOTION_PLANES: Final[int]
+HEATMAP_PLANES: Final[int]
+
+class ChessError(ValueError): ...
+class InvalidFenError(ChessError): ...
+class InvalidDimensionsError(ChessError): ...
+class InvalidSquareError(ChessError): ...
+class InvalidMoveError(ChessError): ...
+class IllegalMoveError(ChessError): ...
+class InvalidPositionError(ChessError): ...
+class InvalidRulesError(ChessError): ...
 
 def parse_pgn(pgn: str) -> list[PgnGame]: ...
+def self_play(num_games: int, policy: Callable[[Any, list[list[int]]], list[int]] | None = None, game: Game | None = None, max_plies: int = 512, seed: int = 0, encoder_config: EncoderConfig | None = None, record_samples: bool = False, parallel: bool = True) -> list[tuple[list[Move], GameOutcome | None, tuple[Any, list[str], list[int], list[float]] | None]]: ...
+def play_match(first: Callable[[Game], Move | None], second: Callable[[Game], Move | None], num_games: int = 2, openings: list[Game] | None = None, max_plies: int = 512, resign: tuple[int, int] | None = None, draw: tuple[int, int, int] | None = None) -> dict[str, Any]: ...
+def label_with_engine(engine_path: str, fens: list[str], depth: int, model: WdlModel | None = None) -> tuple[tuple[list[float], int, int, int], list[int], list[float]]: ...
+def pgn_to_samples(pgn_path: str, output_path: str, every_nth: int = 1, skip_plies: int = 0, encoder_config: EncoderConfig | None = None) -> tuple[int, int]: ...
 
 class Game:
-    def __init__(self, width: int, height: int, fen: str, castling_enabled: bool) -> None: ...
+    def __init__(self, width: int, height: int, fen: str, castling_enabled: bool, history_length: int = 8) -> None: ...
+    @property
+    def history_length(self) -> int: ...
     @staticmethod
     def standard() -> Game: ...
+    @staticmethod
+    def capablanca() -> Game: ...
+    @staticmethod
+    def from_uci_position_string(width: int, height: int, position: str) -> Game: ...
     def turn(self) -> int: ...
     def fullmove_number(self) -> int: ...
     def halfmove_clock(self) -> int: ...
     def ply(self) -> int: ...
+    def move_count(self) -> int: ...
+    def last_move(self) -> Move | None: ...
+    def last_capture(self) -> Piece | None: ...
     def castling_enabled(self) -> bool: ...
+    def self_capture_enabled(self) -> bool: ...
+    def set_self_capture(self, enabled: bool) -> None: ...
+    def fairy_promotions_enabled(self) -> bool: ...
+    def set_fairy_promotions(self, enabled: bool) -> None: ...
+    def castling_king_cols(self) -> tuple[int, int] | None: ...
+    def set_castling_king_cols(self, cols: tuple[int, int] | None) -> None: ...
+    def double_push_row(self) -> int | None: ...
+    def set_double_push_row(self, row: int | None) -> None: ...
+    def draw_rules(self) -> tuple[int, int, int | None]: ...
+    def set_draw_rules(self, halfmove_limit: int = 150, repetition_limit: int = 5, ply_limit: int | None = None) -> None: ...
     def has_kingside_castling_rights(self, color: int) -> bool: ...
     def has_queenside_castling_rights(self, color: int) -> bool: ...
     def make_move(self, move_: Move) -> bool: ...
     def make_move_unchecked(self, move_: Move) -> None: ...
+    def make_null_move(self) -> bool: ...
     def move_history(self) -> list[Move]: ...
     def unmake_move(self) -> bool: ...
+    def truncate_to_ply(self, ply: int) -> bool: ...
+    def annotate_move(self, move_: Move) -> Move: ...
     def is_legal_move(self, move_: Move) -> bool: ...
     def legal_moves(self) -> list[Move]: ...
     def pseudo_legal_moves(self) -> list[Move]: ...
+    def legal_moves_sorted(self) -> list[Move]: ...
     def legal_moves_for_position(self, col: int, row: int) -> list[Move]: ...
     def move_to_lan(self, move_: Move) -> str: ...
     def move_from_lan(self, lan: str) -> Move: ...
     def move_to_san(self, move_: Move) -> str: ...
+    def legal_moves_san(self) -> list[str]: ...
     def move_from_san(self, san: str) -> Move: ...
     def is_check(self) -> bool: ...
+    def validate(self) -> list[str]: ...
+    def checks(self) -> list[Move]: ...
+    def legal_moves_with_checks(self) -> list[Move]: ...
+    def gives_check(self, move_: Move) -> bool: ...
+    def is_capture(self, move_: Move) -> bool: ...
+    def is_zeroing(self, move_: Move) -> bool: ...
+    def captures(self) -> list[Move]: ...
+    def capture_moves(self) -> list[Move]: ...
+    def evasion_moves(self) -> list[Move]: ...
+    def move_gen_stats(self) -> MoveGenStats: ...
+    def threatened_pieces(self, color: int) -> list[tuple[Position, Piece]]: ...
+    def attack_map(self, color: int) -> list[Position]: ...
+    def attack_counts(self, color: int) -> list[int]: ...
+    def attacks_from(self, col: int, row: int) -> list[Position]: ...
+    def checkers(self) -> list[Position]: ...
+    def pinned(self, color: int) -> list[Position]: ...
     def is_checkmate(self) -> bool: ...
     def is_stalemate(self) -> bool: ...
     def is_over(self) -> bool: ...
+    def has_any_legal_move(self) -> bool: ...
+    def perft(self, depth: int) -> int: ...
+    def perft_divide(self, depth: int) -> list[tuple[Move, int]]: ...
+    def repetition_count(self) -> int: ...
+    def is_threefold_repetition(self) -> bool: ...
+    def is_fivefold_repetition(self) -> bool: ...
+    def can_claim_fifty_moves(self) -> bool: ...
+    def can_claim_threefold(self) -> bool: ...
+    def can_claim_draw(self) -> bool: ...
+    def claim_draw(self) -> GameOutcome | None: ...
+    def resign(self, color: int) -> bool: ...
+    def agree_draw(self) -> bool: ...
+    def adjudicate(self, winner: int | None, termination: str = "adjudication") -> bool: ...
     def width(self) -> int: ...
     def height(self) -> int: ...
     def get_piece(self, col: int, row: int) -> Piece | None: ...
     def pieces(self, color: int) -> list[tuple[Position, Piece]]: ...
     def set_piece(self, col: int, row: int, piece: Piece | None = None) -> None: ...
+    def set_turn(self, color: int) -> None: ...
+    def set_castling_rights(self, color: int, kingside: bool, queenside: bool) -> None: ...
+    def set_en_passant(self, square: Position | None = None) -> None: ...
     def piece_count(self, piece_type: str, color: int) -> int: ...
+    def material(self, color: int) -> int: ...
+    def remove_all(self, piece_type: str, color: int) -> int: ...
+    def shift_board(self, dx: int, dy: int) -> None: ...
     def __getitem__(self, key: str | tuple[int, int]) -> Piece | None: ...
-    def legal_action_indices(self) -> list[int]: ...
-    def apply_action(self, action: int) -> bool: ...
-    def encode_game_planes(self) -> tuple[list[float], int, int, int]: ...
+    def legal_action_indices(self, config: EncoderConfig | None = None) -> list[int]: ...
+    def apply_action(self, action: int, config: EncoderConfig | None = None) -> bool: ...
+    def filter_legal_actions(self, actions: list[int]) -> list[bool]: ...
+    def filter_legal(self, moves: list[Move]) -> list[bool]: ...
+    def encode_game_planes(self, config: EncoderConfig | None = None) -> tuple[list[float], int, int, int]: ...
+    def encode_game_planes_array(self, config: EncoderConfig | None = None) -> Any: ...
+    def encode_game_planes_into(self, out: Any, config: EncoderConfig | None = None) -> None: ...
+    def board_planes(self) -> Any: ...
     def action_planes_count(self) -> int: ...
-    def decode_action(self, action: int) -> Move | None: ...
-    def total_actions(self) -> int: ...
+    def encode_action(self, move_: Move, config: EncoderConfig | None = None) -> int | None: ...
+    def decode_action(self, action: int, config: EncoderConfig | None = None) -> Move | None: ...
+    def action_size(self, config: EncoderConfig | None = None) -> int: ...
+    def total_actions(self, config: EncoderConfig | None = None) -> int: ...
     def board_shape(self) -> tuple[int, int]: ...
-    def input_plane_count(self) -> int: ...
-    def reward_absolute(self) -> float: ...
-    def reward_from_perspective(self, perspective: int) -> float: ...
+    def input_plane_count(self, config: EncoderConfig | None = None) -> int: ...
+    def reward_absolute(self, config: RewardConfig | None = None) -> float: ...
+    def reward_from_perspective(self, perspective: int, config: RewardConfig | None = None) -> float: ...
     def is_insufficient_material(self) -> bool: ...
     def has_legal_en_passant(self) -> bool: ...
     def en_passant_square(self) -> Position | None: ...
+    def pgn_result(self) -> str: ...
     def outcome(self) -> GameOutcome | None: ...
+    def random_playout(self, rng_seed: int, max_plies: int = 10000) -> GameOutcome | None: ...
     def turn_state(self) -> TurnState: ...
     def to_fen(self) -> str: ...
+    def to_uci_position_string(self) -> str: ...
+    def to_json_state(self) -> str: ...
+    def stats(self) -> GameStats: ...
+    def mirror(self) -> Game: ...
+    def canonical_form(self) -> tuple[Game, bool, bool]: ...
     def clone(self) -> Game: ...
+    def __reduce__(self) -> tuple[Any, tuple[Any, ...]]: ...
+    def __copy__(self) -> Game: ...
+    def __deepcopy__(self, memo: dict[int, Any]) -> Game: ...
+    def __eq__(self, other: Game) -> bool: ...
+    def render(self, mode: str = "ascii", perspective: int = ...) -> str: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def __hash__(self) -> int: ...
 
+class AecEnv:
+    def __init__(self, game: Game | None = None, encoder_config: EncoderConfig | None = None, reward_config: RewardConfig | None = None, max_plies: int | None = None) -> None: ...
+    def reset(self, seed: int | None = None, options: dict[str, Any] | None = None) -> None: ...
+    @property
+    def possible_agents(self) -> list[str]: ...
+    @property
+    def agents(self) -> list[str]: ...
+    @property
+    def agent_selection(self) -> str: ...
+    @property
+    def rewards(self) -> dict[str, float]: ...
+    @property
+    def _cumulative_rewards(self) -> dict[str, float]: ...
+    @property
+    def terminations(self) -> dict[str, bool]: ...
+    @property
+    def truncations(self) -> dict[str, bool]: ...
+    @property
+    def infos(self) -> dict[str, dict[str, Any]]: ...
+    @property
+    def game(self) -> Game: ...
+    def total_actions(self) -> int: ...
+    def observation_shape(self) -> tuple[int, int, int]: ...
+    def observe(self, agent: str) -> dict[str, Any]: ...
+    def last(self, observe: bool = True) -> tuple[dict[str, Any] | None, float, bool, bool, dict[str, Any]]: ...
+    def step(self, action: int | None) -> None: ...
+    def agent_iter(self, max_iter: int = ...) -> AecAgentIter: ...
+    def __repr__(self) -> str: ...
+
+class AecAgentIter:
+    def __iter__(self) -> AecAgentIter: ...
+    def __next__(self) -> str: ...
+
+class ChessEnv:
+    def __init__(self, game: Game | None = None, encoder_config: EncoderConfig | None = None, reward_config: RewardConfig | None = None, max_plies: int | None = None) -> None: ...
+    def reset(self, seed: int | None = None, options: dict[str, Any] | None = None) -> Any: ...
+    def step(self, action: int) -> tuple[Any, float, bool, dict[str, Any]]: ...
+    @property
+    def action_space(self) -> dict[str, Any]: ...
+    @property
+    def observation_space(self) -> dict[str, Any]: ...
+    @property
+    def done(self) -> bool: ...
+    @property
+    def game(self) -> Game: ...
+    def action_mask(self) -> list[int]: ...
+    def __repr__(self) -> str: ...
+
+class VecEnv:
+    def __init__(self, num_envs: int, game: Game | None = None, encoder_config: EncoderConfig | None = None, reward_config: RewardConfig | None = None, max_plies: int | None = None, parallel: bool = True) -> None: ...
+    @property
+    def num_envs(self) -> int: ...
+    def __len__(self) -> int: ...
+    def reset_all(self) -> Any: ...
+    def step_batch(self, actions: list[int]) -> tuple[Any, Any, Any]: ...
+    def action_masks(self) -> list[list[int]]: ...
+    @property
+    def action_space(self) -> dict[str, Any]: ...
+    @property
+    def observation_space(self) -> dict[str, Any]: ...
+    def game(self, index: int) -> Game: ...
+    def __repr__(self) -> str: ...
+
+class EncoderConfig:
+    def __init__(self, last_move_planes: bool = False, pass_action: bool = False, padded_size: int | None = None, flip_perspective: bool = False, history_length: int = 8, fairy_piece_planes: bool = False, repetition_planes: bool = False) -> None: ...
+    @property
+    def history_length(self) -> int: ...
+    @property
+    def last_move_planes(self) -> bool: ...
+    @property
+    def fairy_piece_planes(self) -> bool: ...
+    @property
+    def repetition_planes(self) -> bool: ...
+    @property
+    def pass_action(self) -> bool: ...
+    @property
+    def padded_size(self) -> int | None: ...
+    @property
+    def flip_perspective(self) -> bool: ...
+    def num_planes(self) -> int: ...
+    def plane_shape(self, width: int, height: int) -> tuple[int, int]: ...
+    def schema(self, width: int, height: int) -> dict[str, Any]: ...
+    def total_actions(self, width: int, height: int) -> int: ...
+    def pass_action_index(self, width: int, height: int) -> int | None: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: EncoderConfig) -> bool: ...
+
+class GameStats:
+    @property
+    def length(self) -> int: ...
+    @property
+    def material(self) -> list[int]: ...
+    def moves(self, color: int) -> int: ...
+    def captures(self, color: int) -> int: ...
+    def checks(self, color: int) -> int: ...
+    def average_mobility(self, color: int) -> float: ...
+    def __repr__(self) -> str: ...
+
+class MoveGenStats:
+    @property
+    def total(self) -> int: ...
+    @property
+    def captures(self) -> int: ...
+    @property
+    def checks(self) -> int: ...
+    @property
+    def promotions(self) -> int: ...
+    @property
+    def castles(self) -> int: ...
+    def piece_type(self, piece_type: str) -> int: ...
+    def __repr__(self) -> str: ...
+
+class Heatmap:
+    def __init__(self, width: int, height: int) -> None: ...
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    @property
+    def games(self) -> int: ...
+    @property
+    def positions(self) -> int: ...
+    def add_game(self, game: Game) -> None: ...
+    def add_position(self, game: Game) -> None: ...
+    def add_move(self, move: Move) -> None: ...
+    def merge(self, other: Heatmap) -> None: ...
+    def occupancy(self, color: int) -> list[int]: ...
+    def destinations(self) -> list[int]: ...
+    def attacks(self, color: int) -> list[int]: ...
+    def to_planes(self) -> tuple[list[float], int, int, int]: ...
+    def __repr__(self) -> str: ...
+
+class GameBatch:
+    def __init__(self, games: list[Game]) -> None: ...
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> Game: ...
+    def encode(self, config: EncoderConfig | None = None, parallel: bool = True) -> Any: ...
+    def encode_into(self, out: Any, config: EncoderConfig | None = None, parallel: bool = True) -> None: ...
+    def apply_actions(self, actions: list[int], config: EncoderConfig | None = None) -> list[bool]: ...
+    def __repr__(self) -> str: ...
+
+class SampleWriter:
+    def __init__(self, path: str, num_planes: int, height: int, width: int) -> None: ...
+    def write(self, planes: Any, policy: list[tuple[int, float]], value: float) -> None: ...
+    def __len__(self) -> int: ...
+    def close(self) -> None: ...
+    def __enter__(self) -> SampleWriter: ...
+    def __exit__(self, exc_type: Any, exc_value: Any, traceback: Any) -> bool: ...
+
+class SampleReader:
+    def __init__(self, path: str) -> None: ...
+    def plane_shape(self) -> tuple[int, int, int]: ...
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> tuple[Any, list[tuple[int, float]], float]: ...
+    def __iter__(self) -> SampleReader: ...
+    def __next__(self) -> tuple[Any, list[tuple[int, float]], float]: ...
+    def __repr__(self) -> str: ...
+
+class HistoryStacker:
+    def __init__(self, width: int, height: int, length: int = ...) -> None: ...
+    @property
+    def width(self) -> int: ...
+    @property
+    def height(self) -> int: ...
+    @property
+    def length(self) -> int: ...
+    def push_position(self, game: Game) -> None: ...
+    def get_stacked(self, perspective: int) -> tuple[list[float], int, int, int]: ...
+    def clear(self) -> None: ...
+    def clone(self) -> HistoryStacker: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+
 class Move:
     @staticmethod
     def from_rowcol(src_col: int, src_row: int, dst_col: int, dst_row: int) -> Move: ...
+    @staticmethod
+    def null() -> Move: ...
+    def __reduce__(self) -> tuple[Any, tuple[Any, ...]]: ...
     @classmethod
     def from_lan(cls, lan: str, board_width: int, board_height: int) -> Move: ...
     @property
@@ -94,7 +369,9 @@ class Move:
     def is_check(self) -> bool: ...
     @property
     def is_double_push(self) -> bool: ...
-    def encode(self, width: int, height: int) -> int | None: ...
+    @property
+    def is_null(self) -> bool: ...
+    def encode(self, width: int, height: int, config: EncoderConfig | None = None) -> int | None: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def __eq__(self, other: Move) -> bool: ...
@@ -130,18 +407,46 @@ class GameOutcome:
     def winner(self) -> int | None: ...
     def encode_winner_absolute(self) -> float: ...
     def encode_winner_from_perspective(self, perspective: int) -> float: ...
+    def reward(self, perspective: int, plies: int = 0, config: RewardConfig | None = None) -> float: ...
     def is_draw(self) -> bool: ...
+    def termination(self) -> str: ...
     def is_checkmate(self) -> bool: ...
     def is_stalemate(self) -> bool: ...
     def is_insufficient_material(self) -> bool: ...
     def is_threefold_repetition(self) -> bool: ...
     def is_fifty_move_rule(self) -> bool: ...
+    def is_fivefold_repetition(self) -> bool: ...
+    def is_seventy_five_move_rule(self) -> bool: ...
+    def to_pgn_result(self) -> str: ...
     def reason(self) -> str: ...
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def __eq__(self, other: GameOutcome) -> bool: ...
     def __hash__(self) -> int: ...
 
+class RewardConfig:
+    def __init__(self, draw_score: float = 0.0, termination_overrides: dict[str, float] | None = None, length_penalty: float = 0.0) -> None: ...
+    @property
+    def draw_score(self) -> float: ...
+    @property
+    def termination_overrides(self) -> dict[str, float]: ...
+    @property
+    def length_penalty(self) -> float: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: RewardConfig) -> bool: ...
+
+class WdlModel:
+    def __init__(self, draw_offset: float | None = None, scale: float | None = None) -> None: ...
+    @property
+    def draw_offset(self) -> float: ...
+    @property
+    def scale(self) -> float: ...
+    def wdl(self, cp: int) -> tuple[float, float, float]: ...
+    def value(self, cp: int) -> float: ...
+    def score_wdl(self, cp: int | None = None, mate: int | None = None) -> tuple[float, float, float] | None: ...
+    def __repr__(self) -> str: ...
+    def __eq__(self, other: WdlModel) -> bool: ...
+
 class TurnState:
     def is_over(self) -> bool: ...
     def outcome(self) -> GameOutcome | None: ...
@@ -157,12 +462,19 @@ class PgnGame:
     def site(self) -> str | None: ...
     def date(self) -> str | None: ...
     def result(self) -> str: ...
+    def annotations(self) -> list[tuple[list[int], list[str]]]: ...
+    def comments(self) -> list[str]: ...
     def moves(self) -> list[Move]: ...
     def starting_fen(self) -> str | None: ...
     def starting_game(self) -> Game: ...
     def game(self) -> Game: ...
     def __repr__(self) -> str: ...
 
+class PgnReader:
+    def __init__(self, path: str) -> None: ...
+    def __iter__(self) -> PgnReader: ...
+    def __next__(self) -> PgnGame: ...
+
 class SearchResult:
     best_move: Move
     best_move_lan: str
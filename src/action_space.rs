@@ -0,0 +1,249 @@
+//! The action space and input planes of a game, in one place.
+//!
+//! An `ActionSpace` is looked up for a board size and `EncoderConfig`, and
+//! answers everything the unified game protocol needs: the number of actions
+//! and input planes, the plane schema, and encoding, decoding and applying
+//! actions. Callers go through it rather than combining `EncoderConfig` and
+//! the `encode` functions themselves, so a new encoder option (or rule set)
+//! only has to be wired up here.
+//!
+//! The action space depends on the board size and configuration, and on the
+//! game's rules only through its promotion pieces: self-capture, castling
+//! columns and the double push row change which moves are legal but not how
+//! they are laid out, while promotions to archbishops and chancellors (see
+//! `Game::set_fairy_promotions`) need the fairy promotion planes of
+//! `EncoderConfig::fairy_piece_planes`. `for_game` checks that every move of
+//! the game has an action. The only non-move action is the optional pass.
+
+use crate::encode::{EncoderConfig, EncoderSchema};
+use crate::error::ChessError;
+use crate::game::Game;
+use crate::r#move::Move;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ActionSpace {
+    width: usize,
+    height: usize,
+    config: EncoderConfig,
+}
+
+#[hotpath::measure_all]
+impl ActionSpace {
+    /// The action space of `width` x `height` boards encoded with `config`.
+    /// Fails if the board doesn't fit the configuration (see
+    /// `EncoderConfig::check_board`).
+    pub fn new(width: usize, height: usize, config: EncoderConfig) -> Result<Self, ChessError> {
+        config.check_board(width, height)?;
+        Ok(ActionSpace {
+            width,
+            height,
+            config,
+        })
+    }
+
+    /// The action space of `game`'s board encoded with `config`. Also fails
+    /// if `config` can't encode the game (see `EncoderConfig::check_game`),
+    /// which covers its pawns promoting to fairy pieces without the action
+    /// planes for those promotions.
+    pub fn for_game<const W: usize, const H: usize>(
+        game: &Game<W, H>,
+        config: EncoderConfig,
+    ) -> Result<Self, ChessError>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        config.check_game(game)?;
+        Self::new(W, H, config)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn config(&self) -> &EncoderConfig {
+        &self.config
+    }
+
+    /// Number of action indices.
+    pub fn action_size(&self) -> usize {
+        self.config.total_actions(self.width, self.height)
+    }
+
+    /// Number of input planes of an encoded position.
+    pub fn input_plane_count(&self) -> usize {
+        self.config.num_planes()
+    }
+
+    /// (height, width) of every input plane.
+    pub fn plane_shape(&self) -> (usize, usize) {
+        self.config.plane_shape(self.width, self.height)
+    }
+
+    /// Description of every input plane and action block.
+    pub fn schema(&self) -> EncoderSchema {
+        self.config.schema(self.width, self.height)
+    }
+
+    /// The action index of `mv` in the current position of `game`, or None if
+    /// it has none (e.g. a pass without a pass action).
+    pub fn encode<const W: usize, const H: usize>(
+        &self,
+        game: &Game<W, H>,
+        mv: &Move,
+    ) -> Option<usize>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_game::<W, H>();
+        game.encode_action_with_config(mv, &self.config)
+    }
+
+    /// The move `action` stands for in the current position of `game`.
+    pub fn decode<const W: usize, const H: usize>(
+        &self,
+        game: &Game<W, H>,
+        action: usize,
+    ) -> Option<Move>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_game::<W, H>();
+        game.decode_action_with_config(action, &self.config)
+    }
+
+    /// Action indices of the legal moves of `game`, in `legal_moves()`
+    /// order, plus the pass action if there is one and the side to move isn't
+    /// in check.
+    ///
+    /// Every legal move must have an action, as `for_game` checks; in debug
+    /// builds a move without one panics rather than going missing from the
+    /// result.
+    pub fn legal_action_indices<const W: usize, const H: usize>(
+        &self,
+        game: &mut Game<W, H>,
+    ) -> Vec<usize>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_game::<W, H>();
+        let mut actions: Vec<usize> = game
+            .legal_moves()
+            .iter()
+            .filter_map(|mv| {
+                let action = game.encode_action_with_config(mv, &self.config);
+                debug_assert!(
+                    action.is_some(),
+                    "legal move {} has no action index in {:?}",
+                    mv.to_lan(),
+                    self.config
+                );
+                action
+            })
+            .collect();
+        if let Some(pass) = self.config.pass_action_index(W, H)
+            && !game.is_check()
+        {
+            actions.push(pass);
+        }
+        actions
+    }
+
+    /// Play `action` in `game`. Returns false if it doesn't decode to a move.
+    pub fn apply_action<const W: usize, const H: usize>(
+        &self,
+        game: &mut Game<W, H>,
+        action: usize,
+    ) -> bool
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_game::<W, H>();
+        game.apply_action_with_config(action, &self.config)
+    }
+
+    fn check_game<const W: usize, const H: usize>(&self) {
+        debug_assert_eq!(
+            (W, H),
+            (self.width, self.height),
+            "ActionSpace is {}x{}, but the board is {}x{}",
+            self.width,
+            self.height,
+            W,
+            H
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode::{TOTAL_INPUT_PLANES, get_total_actions};
+    use crate::game::StandardGame;
+
+    #[test]
+    fn test_action_space_matches_encoder() {
+        let mut game = StandardGame::standard();
+        let space = ActionSpace::for_game(&game, EncoderConfig::default()).expect("fits");
+        assert_eq!(space.action_size(), get_total_actions(8, 8));
+        assert_eq!(space.input_plane_count(), TOTAL_INPUT_PLANES);
+        assert_eq!(space.schema().planes.len(), space.input_plane_count());
+
+        let actions = space.legal_action_indices(&mut game);
+        assert_eq!(actions.len(), 20);
+        let e4 = game.move_from_lan("e2e4").expect("valid LAN");
+        let action = space.encode(&game, &e4).expect("encodable");
+        assert!(actions.contains(&action));
+        assert_eq!(space.decode(&game, action), Some(e4));
+        assert!(space.apply_action(&mut game, action));
+        assert_eq!(game.move_count(), 1);
+    }
+
+    #[test]
+    fn test_action_space_pass_and_padding() {
+        let config = EncoderConfig {
+            pass_action: true,
+            padded_size: Some(8),
+            ..EncoderConfig::default()
+        };
+        let mut game: Game<6, 6> = Game::new("k5/6/6/6/6/5K w - - 0 1", true).expect("valid FEN");
+        let space = ActionSpace::for_game(&game, config).expect("fits");
+        assert_eq!(space.plane_shape(), (8, 8));
+        assert_eq!(space.action_size(), get_total_actions(8, 8) + 1);
+
+        let pass = space.action_size() - 1;
+        assert!(space.legal_action_indices(&mut game).contains(&pass));
+        assert_eq!(space.encode(&game, &Move::null()), Some(pass));
+        assert!(space.apply_action(&mut game, pass));
+
+        let too_small = EncoderConfig {
+            padded_size: Some(4),
+            ..EncoderConfig::default()
+        };
+        assert!(ActionSpace::new(6, 6, too_small).is_err());
+    }
+
+    #[test]
+    fn test_action_space_fairy_promotions() {
+        let mut game = Game::<10, 8>::capablanca();
+        assert!(matches!(
+            ActionSpace::for_game(&game, EncoderConfig::default()),
+            Err(ChessError::InvalidRules(_))
+        ));
+
+        let config = EncoderConfig {
+            fairy_piece_planes: true,
+            ..EncoderConfig::default()
+        };
+        game = Game::new("4k5/P9/10/10/10/10/10/4K5 w - - 0 1", true).expect("valid FEN");
+        game.set_fairy_promotions(true);
+        let space = ActionSpace::for_game(&game, config).expect("fits");
+        let actions = space.legal_action_indices(&mut game);
+        assert_eq!(actions.len(), game.legal_moves().len());
+        let chancellor = game.move_from_lan("a7a8c").expect("legal promotion");
+        assert!(actions.contains(&space.encode(&game, &chancellor).expect("encodable")));
+    }
+}
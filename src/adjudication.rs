@@ -0,0 +1,132 @@
+//! Ending games early with the outcome given by an endgame tablebase.
+//!
+//! No tables ship with the crate. Implement `Tablebase` over whatever tables
+//! are available (a Syzygy prober for 8x8, or tables generated for a small
+//! board) and pass it to `adjudicate` after each move of a self-play game.
+
+use crate::game::Game;
+use crate::outcome::{GameOutcome, Termination};
+
+/// Result of a position with perfect play, from the side to move's
+/// perspective.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Wdl {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// A source of perfect-play results for positions with few pieces.
+pub trait Tablebase<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Largest number of pieces, kings included, that the tables cover.
+    fn max_pieces(&self) -> u32;
+
+    /// Result of `game`'s current position, or `None` if the tables don't
+    /// cover it. Tables that distinguish wins spoiled by the fifty-move rule
+    /// should report those as draws.
+    fn probe_wdl(&self, game: &Game<W, H>) -> Option<Wdl>;
+}
+
+/// The theoretical outcome of `game`'s current position according to
+/// `tablebase`, or `None` if the position isn't covered.
+///
+/// Positions with castling rights are never adjudicated, since tablebases
+/// assume there are none.
+#[hotpath::measure]
+pub fn adjudicate<const W: usize, const H: usize, T>(
+    game: &Game<W, H>,
+    tablebase: &T,
+) -> Option<GameOutcome>
+where
+    [(); (W * H).div_ceil(64)]:,
+    T: Tablebase<W, H> + ?Sized,
+{
+    if game.piece_counts().total() > tablebase.max_pieces() {
+        return None;
+    }
+    let rights = game.castling_rights();
+    let turn = game.turn();
+    for color in [turn, turn.opposite()] {
+        if rights.has_kingside(color) || rights.has_queenside(color) {
+            return None;
+        }
+    }
+
+    Some(match tablebase.probe_wdl(game)? {
+        Wdl::Win => GameOutcome::win(turn, Termination::Adjudication),
+        Wdl::Loss => GameOutcome::win(turn.opposite(), Termination::Adjudication),
+        Wdl::Draw => GameOutcome::draw(Termination::Adjudication),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::game::StandardGame;
+    use crate::pieces::PieceType;
+
+    /// Knows only that a lone queen beats a lone king.
+    struct QueenVsKing;
+
+    impl Tablebase<8, 8> for QueenVsKing {
+        fn max_pieces(&self) -> u32 {
+            3
+        }
+
+        fn probe_wdl(&self, game: &StandardGame) -> Option<Wdl> {
+            let counts = game.piece_counts();
+            let queen_side = [Color::White, Color::Black]
+                .into_iter()
+                .find(|&color| counts.get(PieceType::Queen, color) == 1)?;
+            Some(if queen_side == game.turn() {
+                Wdl::Win
+            } else {
+                Wdl::Loss
+            })
+        }
+    }
+
+    #[test]
+    fn test_adjudicate() {
+        let game = StandardGame::new("4k3/8/8/8/8/8/8/3QK3 b - - 0 1", true).expect("valid FEN");
+        let outcome = adjudicate(&game, &QueenVsKing).expect("covered");
+        assert_eq!(outcome.winner(), Some(Color::White));
+        assert_eq!(outcome.termination(), Termination::Adjudication);
+
+        // Too many pieces, or not in the tables
+        let game = StandardGame::new("4k3/8/8/8/8/8/7P/3QK3 w - - 0 1", true).expect("valid FEN");
+        assert_eq!(adjudicate(&game, &QueenVsKing), None);
+        let game = StandardGame::new("4k3/8/8/8/8/8/8/3RK3 w - - 0 1", true).expect("valid FEN");
+        assert_eq!(adjudicate(&game, &QueenVsKing), None);
+    }
+
+    #[test]
+    fn test_adjudicate_skips_castling_rights() {
+        /// Claims every position is drawn.
+        struct AllDrawn;
+
+        impl Tablebase<8, 8> for AllDrawn {
+            fn max_pieces(&self) -> u32 {
+                32
+            }
+
+            fn probe_wdl(&self, _game: &StandardGame) -> Option<Wdl> {
+                Some(Wdl::Draw)
+            }
+        }
+
+        let tablebase: &dyn Tablebase<8, 8> = &AllDrawn;
+        let game = StandardGame::new("4k3/8/8/8/8/8/8/4K2R w K - 0 1", true).expect("valid FEN");
+        assert_eq!(adjudicate(&game, tablebase), None);
+
+        let game = StandardGame::new("4k3/8/8/8/8/8/8/4K2R w - - 0 1", true).expect("valid FEN");
+        assert_eq!(
+            adjudicate(&game, tablebase),
+            Some(GameOutcome::draw(Termination::Adjudication))
+        );
+    }
+}
@@ -0,0 +1,324 @@
+//! Tactical motif detection: forks, pins, skewers and hanging pieces.
+//!
+//! Motifs are found statically from the attack pattern of the current position
+//! (via `Game::attackers_to` and `Game::see`); no search is done, so they
+//! describe threats that exist on the board, not whether they can be exploited.
+
+use std::fmt;
+
+use crate::color::Color;
+use crate::directions::{ALL_DIRS, DIAGONAL, ORTHOGONAL};
+use crate::game::Game;
+use crate::r#move::{Move, MoveFlags};
+use crate::pieces::PieceType;
+use crate::position::Position;
+
+/// A tactical pattern in which one side's pieces target the other's.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Motif {
+    /// A single piece attacks two or more pieces that are each worth attacking:
+    /// the king, a more valuable piece, or an undefended one.
+    Fork {
+        attacker: Position,
+        targets: Vec<Position>,
+    },
+    /// A slider attacks a piece that shields a more valuable piece behind it.
+    /// The pin is absolute if the piece behind is the king, so the pinned
+    /// piece may not legally leave the line.
+    Pin {
+        pinner: Position,
+        pinned: Position,
+        target: Position,
+        absolute: bool,
+    },
+    /// A slider attacks a valuable piece with a less valuable one behind it,
+    /// which is exposed once the front piece moves away.
+    Skewer {
+        attacker: Position,
+        front: Position,
+        behind: Position,
+    },
+    /// A piece can be captured for a material gain, as measured by static
+    /// exchange evaluation.
+    Hanging { square: Position, gain: i32 },
+}
+
+impl fmt::Display for Motif {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Motif::Fork { attacker, targets } => {
+                write!(f, "fork: {} attacks", attacker)?;
+                for (i, target) in targets.iter().enumerate() {
+                    write!(f, "{}{}", if i == 0 { " " } else { ", " }, target)?;
+                }
+                Ok(())
+            }
+            Motif::Pin {
+                pinner,
+                pinned,
+                target,
+                absolute,
+            } => write!(
+                f,
+                "{} pin: {} pins {} to {}",
+                if *absolute { "absolute" } else { "relative" },
+                pinner,
+                pinned,
+                target,
+            ),
+            Motif::Skewer {
+                attacker,
+                front,
+                behind,
+            } => write!(f, "skewer: {} skewers {} and {}", attacker, front, behind),
+            Motif::Hanging { square, gain } => write!(f, "hanging: {} (+{})", square, gain),
+        }
+    }
+}
+
+/// Value used to compare pieces, with the king above everything else.
+fn worth(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::King => i32::MAX,
+        _ => piece_type.value(),
+    }
+}
+
+/// Find the motifs in which `color`'s pieces target the opponent's, regardless
+/// of whose turn it is.
+///
+/// Motifs are returned grouped by kind (forks, pins, skewers, hanging pieces),
+/// each group ordered by the attacking piece's square.
+#[hotpath::measure]
+pub fn find_motifs<const W: usize, const H: usize>(game: &Game<W, H>, color: Color) -> Vec<Motif>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let mut motifs = find_forks(game, color);
+    motifs.extend(find_pins_and_skewers(game, color));
+    motifs.extend(find_hanging(game, color));
+    motifs
+}
+
+/// Forks by `color`'s pieces.
+#[hotpath::measure]
+pub fn find_forks<const W: usize, const H: usize>(game: &Game<W, H>, color: Color) -> Vec<Motif>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let opponent = color.opposite();
+    let mut motifs = Vec::new();
+
+    for (pos, piece) in game.pieces_iter(color) {
+        let targets: Vec<Position> = game
+            .piece_attacks(&pos, &piece)
+            .iter_ones()
+            .map(|idx| Position::from_index(idx, W))
+            .filter(|target| {
+                game.get_piece(target).is_some_and(|victim| {
+                    victim.color == opponent
+                        && (victim.piece_type == PieceType::King
+                            || worth(victim.piece_type) > worth(piece.piece_type)
+                            || game.attackers_to(target, opponent).is_empty())
+                })
+            })
+            .collect();
+
+        if targets.len() >= 2 {
+            motifs.push(Motif::Fork {
+                attacker: pos,
+                targets,
+            });
+        }
+    }
+    motifs
+}
+
+/// Pins and skewers by `color`'s bishops, rooks and queens.
+#[hotpath::measure]
+pub fn find_pins_and_skewers<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    color: Color,
+) -> Vec<Motif>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let opponent = color.opposite();
+    let mut motifs = Vec::new();
+
+    for (pos, piece) in game.pieces_iter(color) {
+        let directions: &[(i32, i32)] = match piece.piece_type {
+            PieceType::Bishop => &DIAGONAL,
+            PieceType::Rook => &ORTHOGONAL,
+            PieceType::Queen => &ALL_DIRS,
+            _ => continue,
+        };
+
+        for &(dc, dr) in directions {
+            let mut ray = RayIter::<W, H>::new(pos, dc, dr)
+                .filter_map(|p| game.get_piece(&p).map(|occupant| (p, occupant)));
+            let (Some((front, front_piece)), Some((behind, behind_piece))) =
+                (ray.next(), ray.next())
+            else {
+                continue;
+            };
+            if front_piece.color != opponent || behind_piece.color != opponent {
+                continue;
+            }
+
+            let front_worth = worth(front_piece.piece_type);
+            let behind_worth = worth(behind_piece.piece_type);
+            if behind_worth > front_worth {
+                motifs.push(Motif::Pin {
+                    pinner: pos,
+                    pinned: front,
+                    target: behind,
+                    absolute: behind_piece.piece_type == PieceType::King,
+                });
+            } else if front_worth > behind_worth {
+                motifs.push(Motif::Skewer {
+                    attacker: pos,
+                    front,
+                    behind,
+                });
+            }
+        }
+    }
+    motifs
+}
+
+/// Opponent pieces that `color` can capture for a material gain.
+#[hotpath::measure]
+pub fn find_hanging<const W: usize, const H: usize>(game: &Game<W, H>, color: Color) -> Vec<Motif>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let mut motifs = Vec::new();
+
+    for (square, victim) in game.pieces_iter(color.opposite()) {
+        if victim.piece_type == PieceType::King {
+            continue;
+        }
+        let gain = game
+            .attackers_to(&square, color)
+            .iter_ones()
+            .map(|idx| {
+                let src = Position::from_index(idx, W);
+                game.see(&Move::from_position(src, square, MoveFlags::CAPTURE))
+            })
+            .max();
+        if let Some(gain) = gain
+            && gain > 0
+        {
+            motifs.push(Motif::Hanging { square, gain });
+        }
+    }
+    motifs
+}
+
+/// Squares along a ray from (but excluding) `start`, until the board edge.
+struct RayIter<const W: usize, const H: usize> {
+    col: i32,
+    row: i32,
+    dc: i32,
+    dr: i32,
+}
+
+impl<const W: usize, const H: usize> RayIter<W, H> {
+    fn new(start: Position, dc: i32, dr: i32) -> Self {
+        RayIter {
+            col: i32::from(start.col),
+            row: i32::from(start.row),
+            dc,
+            dr,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Iterator for RayIter<W, H> {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Position> {
+        self.col += self.dc;
+        self.row += self.dr;
+        if self.col < 0 || self.row < 0 || self.col >= W as i32 || self.row >= H as i32 {
+            return None;
+        }
+        Some(Position::new(self.col as u8, self.row as u8))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    fn sq(s: &str) -> Position {
+        Position::from_algebraic(s).expect("valid square")
+    }
+
+    #[test]
+    fn test_knight_fork() {
+        let game = StandardGame::new("r3k3/8/8/1N6/8/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+        assert!(find_forks(&game, Color::White).is_empty());
+
+        let game = StandardGame::new("r3k3/2N5/8/8/8/8/8/4K3 b - - 0 1", true).expect("valid FEN");
+        assert_eq!(
+            find_forks(&game, Color::White),
+            vec![Motif::Fork {
+                attacker: sq("c7"),
+                targets: vec![sq("a8"), sq("e8")],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_pins() {
+        // Bishop pins the knight to the king, rook pins the knight to the queen
+        let game =
+            StandardGame::new("3qk3/8/2n5/1B6/8/8/3n4/3RK3 w - - 0 1", true).expect("valid FEN");
+        let motifs = find_pins_and_skewers(&game, Color::White);
+        assert!(motifs.contains(&Motif::Pin {
+            pinner: sq("b5"),
+            pinned: sq("c6"),
+            target: sq("e8"),
+            absolute: true,
+        }));
+        assert!(motifs.contains(&Motif::Pin {
+            pinner: sq("d1"),
+            pinned: sq("d2"),
+            target: sq("d8"),
+            absolute: false,
+        }));
+    }
+
+    #[test]
+    fn test_skewer() {
+        // The rook checks the king, exposing the queen behind it
+        let game = StandardGame::new("R3k2q/8/8/8/8/8/8/4K3 b - - 0 1", true).expect("valid FEN");
+        assert_eq!(
+            find_pins_and_skewers(&game, Color::White),
+            vec![Motif::Skewer {
+                attacker: sq("a8"),
+                front: sq("e8"),
+                behind: sq("h8"),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_hanging() {
+        // The d5 knight is undefended; the defended e5 pawn isn't attacked
+        let game =
+            StandardGame::new("4k3/8/5p2/3np3/2P1P3/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+        let motifs = find_hanging(&game, Color::White);
+        assert_eq!(
+            motifs,
+            vec![Motif::Hanging {
+                square: sq("d5"),
+                gain: 300,
+            }]
+        );
+        assert_eq!(motifs[0].to_string(), "hanging: d5 (+300)");
+    }
+}
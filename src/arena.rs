@@ -0,0 +1,481 @@
+//! Matches between two players, with Elo estimates.
+//!
+//! `play_match` plays games between two move callbacks from a list of opening
+//! positions, each opening twice with the colors swapped, so neither player
+//! gets the better side of an opening more often. Games that drag on can be
+//! adjudicated on the static evaluation, as tournament managers do on engine
+//! scores. `MatchReport` sums up the results as the first player's score,
+//! Elo difference and likelihood of superiority.
+
+use std::fmt;
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::r#move::Move;
+use crate::outcome::{GameOutcome, Termination};
+use crate::search;
+
+/// Adjudicate a win once the static evaluation has favored one side by at
+/// least `score` centipawns for `plies` plies in a row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ResignAdjudication {
+    pub score: i32,
+    pub plies: usize,
+}
+
+/// Adjudicate a draw once the static evaluation has stayed within `score`
+/// centipawns of 0 for `plies` plies in a row, from ply `min_ply` on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DrawAdjudication {
+    pub score: i32,
+    pub plies: usize,
+    pub min_ply: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaConfig {
+    /// Number of games. Game `i` starts from opening `(i / 2) % openings`,
+    /// with the first player white in even games and black in odd ones.
+    pub num_games: usize,
+    /// Plies after which an unfinished game is adjudicated a draw.
+    pub max_plies: usize,
+    pub resign: Option<ResignAdjudication>,
+    pub draw: Option<DrawAdjudication>,
+}
+
+impl Default for ArenaConfig {
+    fn default() -> Self {
+        ArenaConfig {
+            num_games: 2,
+            max_plies: 512,
+            resign: None,
+            draw: None,
+        }
+    }
+}
+
+/// One game of a match.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchGame {
+    /// Index of the opening the game started from.
+    pub opening: usize,
+    /// Color the first player had.
+    pub first_color: Color,
+    pub moves: Vec<Move>,
+    pub outcome: GameOutcome,
+}
+
+impl MatchGame {
+    /// The first player's score: 1 for a win, 0.5 for a draw, 0 for a loss.
+    pub fn first_score(&self) -> f64 {
+        match self.outcome.winner() {
+            Some(winner) if winner == self.first_color => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        }
+    }
+}
+
+/// Results of `play_match`, in the order the games were played. Statistics
+/// are from the first player's point of view.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchReport {
+    pub games: Vec<MatchGame>,
+}
+
+/// Quantile of the standard normal distribution for a 95% interval.
+const Z_95: f64 = 1.959964;
+
+/// Elo difference for an expected score strictly between 0 and 1.
+fn elo_from_score(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// The error function, to within 1.5e-7 (Abramowitz and Stegun 7.1.26).
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x.abs());
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+#[hotpath::measure_all]
+impl MatchReport {
+    /// Games the first player won.
+    pub fn wins(&self) -> usize {
+        self.games.iter().filter(|g| g.first_score() == 1.0).count()
+    }
+
+    /// Games the first player lost.
+    pub fn losses(&self) -> usize {
+        self.games.iter().filter(|g| g.first_score() == 0.0).count()
+    }
+
+    pub fn draws(&self) -> usize {
+        self.games.len() - self.wins() - self.losses()
+    }
+
+    /// The first player's average score, or None without games.
+    pub fn score(&self) -> Option<f64> {
+        if self.games.is_empty() {
+            return None;
+        }
+        let total: f64 = self.games.iter().map(MatchGame::first_score).sum();
+        Some(total / self.games.len() as f64)
+    }
+
+    /// Estimated Elo difference of the first player over the second, and the
+    /// half-width of its 95% confidence interval. None without games, or
+    /// when one player scored every point and the difference is unbounded.
+    pub fn elo(&self) -> Option<(f64, f64)> {
+        let score = self.score()?;
+        if score <= 0.0 || score >= 1.0 {
+            return None;
+        }
+        let n = self.games.len() as f64;
+        let variance: f64 = self
+            .games
+            .iter()
+            .map(|g| (g.first_score() - score).powi(2))
+            .sum::<f64>()
+            / n;
+        let margin = Z_95 * (variance / n).sqrt();
+        let low = (score - margin).max(f64::MIN_POSITIVE);
+        let high = (score + margin).min(1.0 - f64::EPSILON);
+        Some((
+            elo_from_score(score),
+            (elo_from_score(high) - elo_from_score(low)) / 2.0,
+        ))
+    }
+
+    /// Likelihood of superiority: the probability that the first player is
+    /// the stronger one, from the wins and losses (draws don't count). None
+    /// if every game was drawn.
+    pub fn los(&self) -> Option<f64> {
+        let (wins, losses) = (self.wins() as f64, self.losses() as f64);
+        if wins + losses == 0.0 {
+            return None;
+        }
+        Some(0.5 * (1.0 + erf((wins - losses) / (2.0 * (wins + losses)).sqrt())))
+    }
+}
+
+impl fmt::Display for MatchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "+{} -{} ={} ({} games)",
+            self.wins(),
+            self.losses(),
+            self.draws(),
+            self.games.len()
+        )?;
+        if let Some(score) = self.score() {
+            write!(f, ", score {:.1}%", 100.0 * score)?;
+        }
+        match self.elo() {
+            Some((elo, margin)) => write!(f, ", Elo {:+.1} +/- {:.1}", elo, margin)?,
+            None => write!(f, ", Elo unbounded")?,
+        }
+        if let Some(los) = self.los() {
+            write!(f, ", LOS {:.1}%", 100.0 * los)?;
+        }
+        Ok(())
+    }
+}
+
+/// Consecutive plies the evaluation has met each adjudication rule for.
+#[derive(Default)]
+struct AdjudicationStreaks {
+    /// Plies in a row favoring `Some(color)` by the resign score.
+    resign: (Option<Color>, usize),
+    draw: usize,
+}
+
+impl AdjudicationStreaks {
+    /// Update the streaks with the position of `game` and return the outcome
+    /// they adjudicate, if any.
+    fn update<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+        config: &ArenaConfig,
+    ) -> Option<GameOutcome>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        if config.resign.is_none() && config.draw.is_none() {
+            return None;
+        }
+        let score = search::evaluate(game);
+        let white_score = if game.turn() == Color::White {
+            score
+        } else {
+            -score
+        };
+
+        if let Some(resign) = config.resign {
+            let leader = if white_score >= resign.score {
+                Some(Color::White)
+            } else if white_score <= -resign.score {
+                Some(Color::Black)
+            } else {
+                None
+            };
+            self.resign = match (leader, self.resign) {
+                (Some(color), (Some(previous), plies)) if color == previous => (leader, plies + 1),
+                (Some(_), _) => (leader, 1),
+                (None, _) => (None, 0),
+            };
+            if let (Some(winner), plies) = self.resign
+                && plies >= resign.plies
+            {
+                return Some(GameOutcome::win(winner, Termination::Adjudication));
+            }
+        }
+
+        if let Some(draw) = config.draw {
+            let ply = game.move_count();
+            self.draw = if ply >= draw.min_ply && white_score.abs() <= draw.score {
+                self.draw + 1
+            } else {
+                0
+            };
+            if self.draw >= draw.plies {
+                return Some(GameOutcome::draw(Termination::Adjudication));
+            }
+        }
+        None
+    }
+}
+
+/// Play one game from `opening`, with `white` and `black` choosing the moves.
+fn play_game<const W: usize, const H: usize>(
+    opening: &Game<W, H>,
+    config: &ArenaConfig,
+    white: &mut dyn FnMut(&mut Game<W, H>) -> Result<Option<Move>, String>,
+    black: &mut dyn FnMut(&mut Game<W, H>) -> Result<Option<Move>, String>,
+) -> Result<(Vec<Move>, GameOutcome), String>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let mut game = opening.clone();
+    let mut moves = Vec::new();
+    let mut streaks = AdjudicationStreaks::default();
+    loop {
+        if let Some(outcome) = game.outcome() {
+            return Ok((moves, outcome));
+        }
+        if moves.len() >= config.max_plies {
+            return Ok((moves, GameOutcome::draw(Termination::Adjudication)));
+        }
+
+        let turn = game.turn();
+        // The player gets a copy, so it can't tamper with the game
+        let mut copy = game.clone();
+        let chosen = if turn == Color::White {
+            white(&mut copy)?
+        } else {
+            black(&mut copy)?
+        };
+        let Some(mv) = chosen else {
+            game.resign(turn);
+            continue;
+        };
+        if !game.make_move(&mv) {
+            // An illegal move forfeits the game
+            game.adjudicate(GameOutcome::win(turn.opposite(), Termination::Adjudication));
+            continue;
+        }
+        moves.push(mv);
+
+        if let Some(outcome) = streaks.update(&game, config) {
+            game.adjudicate(outcome);
+        }
+    }
+}
+
+/// Play `config.num_games` games between `first` and `second`, alternating
+/// colors and cycling through `openings` (see `ArenaConfig::num_games`).
+///
+/// Each player is called with a copy of the game whenever it is its turn and
+/// returns its move, or None to resign. An illegal move loses the game by
+/// adjudication. Fails if `openings` is empty or a player fails.
+#[hotpath::measure]
+pub fn play_match<const W: usize, const H: usize>(
+    openings: &[Game<W, H>],
+    config: &ArenaConfig,
+    mut first: impl FnMut(&mut Game<W, H>) -> Result<Option<Move>, String>,
+    mut second: impl FnMut(&mut Game<W, H>) -> Result<Option<Move>, String>,
+) -> Result<MatchReport, String>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    if openings.is_empty() {
+        return Err("play_match needs at least one opening".to_string());
+    }
+    let mut report = MatchReport::default();
+    for i in 0..config.num_games {
+        let opening = (i / 2) % openings.len();
+        let first_color = if i % 2 == 0 {
+            Color::White
+        } else {
+            Color::Black
+        };
+        let (moves, outcome) = if first_color == Color::White {
+            play_game(&openings[opening], config, &mut first, &mut second)?
+        } else {
+            play_game(&openings[opening], config, &mut second, &mut first)?
+        };
+        report.games.push(MatchGame {
+            opening,
+            first_color,
+            moves,
+            outcome,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::search::SearchLimits;
+
+    fn game_with(first_color: Color, outcome: GameOutcome) -> MatchGame {
+        MatchGame {
+            opening: 0,
+            first_color,
+            moves: Vec::new(),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn test_match_report_statistics() {
+        let mut report = MatchReport::default();
+        assert_eq!(report.score(), None);
+        assert_eq!(report.elo(), None);
+
+        // 6 wins, 2 losses and 2 draws
+        for _ in 0..3 {
+            report
+                .games
+                .push(game_with(Color::White, GameOutcome::WhiteWin));
+            report
+                .games
+                .push(game_with(Color::Black, GameOutcome::BlackWin));
+        }
+        report
+            .games
+            .push(game_with(Color::White, GameOutcome::BlackWin));
+        report
+            .games
+            .push(game_with(Color::Black, GameOutcome::WhiteWin));
+        report
+            .games
+            .push(game_with(Color::White, GameOutcome::Stalemate));
+        report
+            .games
+            .push(game_with(Color::Black, GameOutcome::Stalemate));
+
+        assert_eq!((report.wins(), report.losses(), report.draws()), (6, 2, 2));
+        assert_eq!(report.score(), Some(0.7));
+        let (elo, margin) = report.elo().expect("bounded score");
+        assert!((elo - 147.2).abs() < 0.1, "{}", elo);
+        assert!(margin > 100.0 && margin < 400.0, "{}", margin);
+        let los = report.los().expect("decisive games");
+        assert!((los - 0.9214).abs() < 1e-3, "{}", los);
+        assert!(
+            report
+                .to_string()
+                .starts_with("+6 -2 =2 (10 games), score 70.0%, Elo +147.2")
+        );
+
+        // A clean sweep has no finite Elo
+        report.games.truncate(6);
+        assert_eq!(report.elo(), None);
+        assert!(report.to_string().contains("Elo unbounded"));
+    }
+
+    #[test]
+    fn test_play_match() {
+        let openings = [
+            StandardGame::standard(),
+            StandardGame::new("4k3/8/8/8/8/8/8/3QK3 w - - 0 1", true).expect("valid FEN"),
+        ];
+        let limits = SearchLimits {
+            depth: 2,
+            ..SearchLimits::default()
+        };
+        let searcher = |game: &mut StandardGame| Ok(search::search(game, &limits).best_move);
+        let resigner = |_: &mut StandardGame| Ok(None);
+
+        let config = ArenaConfig {
+            num_games: 4,
+            ..ArenaConfig::default()
+        };
+        let report = play_match(&openings, &config, searcher, resigner).expect("match played");
+        let colors: Vec<Color> = report.games.iter().map(|g| g.first_color).collect();
+        assert_eq!(
+            colors,
+            [Color::White, Color::Black, Color::White, Color::Black]
+        );
+        let used: Vec<usize> = report.games.iter().map(|g| g.opening).collect();
+        assert_eq!(used, [0, 0, 1, 1]);
+        assert_eq!(report.wins(), 4);
+        assert!(
+            report
+                .games
+                .iter()
+                .all(|g| g.outcome.termination() == Termination::Resignation)
+        );
+
+        // A queen up for long enough is adjudicated a win; an illegal move
+        // loses
+        let config = ArenaConfig {
+            num_games: 2,
+            resign: Some(ResignAdjudication {
+                score: 600,
+                plies: 4,
+            }),
+            ..ArenaConfig::default()
+        };
+        let report = play_match(&openings[1..], &config, searcher, searcher).expect("match played");
+        for game in &report.games {
+            assert_eq!(
+                game.outcome,
+                GameOutcome::win(Color::White, Termination::Adjudication)
+            );
+            assert_eq!(game.moves.len(), 4);
+        }
+        let config = ArenaConfig {
+            num_games: 1,
+            draw: Some(DrawAdjudication {
+                score: 10_000,
+                plies: 3,
+                min_ply: 2,
+            }),
+            ..ArenaConfig::default()
+        };
+        let report = play_match(&openings, &config, searcher, searcher).expect("match played");
+        assert_eq!(report.games[0].moves.len(), 4);
+        assert_eq!(
+            report.games[0].outcome,
+            GameOutcome::draw(Termination::Adjudication)
+        );
+
+        let illegal =
+            |_: &mut StandardGame| Ok(Some(Move::from_lan("e1e3", 8, 8).expect("valid LAN")));
+        let config = ArenaConfig::default();
+        let report = play_match(&openings[1..], &config, searcher, illegal).expect("match played");
+        assert_eq!(report.wins(), 2);
+
+        // Player failures and missing openings are errors
+        let failing = |_: &mut StandardGame| Err("engine crashed".to_string());
+        assert!(play_match(&openings, &config, searcher, failing).is_err());
+        assert!(play_match::<8, 8>(&[], &config, searcher, searcher).is_err());
+    }
+}
@@ -0,0 +1,21 @@
+#![feature(generic_const_exprs)]
+#![allow(incomplete_features)]
+
+//! A UCI engine for testing GUI and tournament manager setups (e.g.
+//! CuteChess) against spooky_chess. Plays with the alpha-beta search, or
+//! random legal moves if started with `--random`.
+
+use std::io;
+
+use spooky_chess::game::StandardGame;
+use spooky_chess::search::AlphaBeta;
+use spooky_chess::uci::{RandomMover, UciServer};
+
+fn main() -> io::Result<()> {
+    let (input, output) = (io::stdin().lock(), io::stdout());
+    if std::env::args().any(|arg| arg == "--random") {
+        UciServer::new(RandomMover::new(0), StandardGame::standard()).run(input, output)
+    } else {
+        UciServer::new(AlphaBeta::default(), StandardGame::standard()).run(input, output)
+    }
+}
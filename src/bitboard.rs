@@ -1,3 +1,4 @@
+use std::fmt;
 use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
 
 /// Number of bits per word in the bitboard storage.
@@ -251,6 +252,140 @@ impl<const NW: usize> Bitboard<NW> {
             word_index: 0,
         }
     }
+
+    /// Render the bits as a `width` x `height` grid, laid out like the board
+    /// diagram: rank 1 at the bottom, set bits as `x` and clear bits as `.`.
+    /// Bits beyond `width * height` are not shown.
+    pub fn display(&self, width: usize, height: usize) -> BitboardDisplay<'_, NW> {
+        BitboardDisplay {
+            bitboard: self,
+            width,
+            height,
+        }
+    }
+}
+
+impl<const NW: usize> Bitboard<NW> {
+    /// Hexadecimal representation, most significant word first: `0x`
+    /// followed by 16 digits per word.
+    pub fn to_hex(&self) -> String {
+        let mut hex = String::with_capacity(2 + 16 * NW);
+        hex.push_str("0x");
+        for word in self.words.iter().rev() {
+            hex.push_str(&format!("{:016x}", word));
+        }
+        hex
+    }
+
+    /// Parse a hexadecimal bitboard as written by `to_hex`. The `0x` prefix is
+    /// optional, `_` separators are ignored and leading zeros may be omitted.
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let digits = hex.strip_prefix("0x").unwrap_or(hex);
+        let digits: Vec<u8> = digits.bytes().filter(|&b| b != b'_').collect();
+        if digits.is_empty() {
+            return Err("Empty hex bitboard".to_string());
+        }
+
+        let mut words = [0u64; NW];
+        for (i, &digit) in digits.iter().rev().enumerate() {
+            let value = (digit as char)
+                .to_digit(16)
+                .ok_or_else(|| format!("Invalid hex digit '{}' in bitboard", digit as char))?;
+            if value == 0 {
+                continue;
+            }
+            let word = i / 16;
+            if word >= NW {
+                return Err(format!("Hex bitboard {} doesn't fit in {} words", hex, NW));
+            }
+            words[word] |= u64::from(value) << (4 * (i % 16));
+        }
+        Ok(Bitboard { words })
+    }
+}
+
+impl Bitboard<1> {
+    /// Single-word bitboard from its `u64` value, e.g. for boards of up to 64
+    /// squares.
+    #[inline]
+    pub const fn from_u64(value: u64) -> Self {
+        Bitboard { words: [value] }
+    }
+
+    #[inline]
+    pub const fn to_u64(self) -> u64 {
+        self.words[0]
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const NW: usize> serde::Serialize for Bitboard<NW> {
+    /// Serializes as a hex string (see `to_hex`).
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const NW: usize> serde::Deserialize<'de> for Bitboard<NW> {
+    /// Deserializes a hex string (see `from_hex`).
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct HexVisitor<const NW: usize>;
+
+        impl<const NW: usize> serde::de::Visitor<'_> for HexVisitor<NW> {
+            type Value = Bitboard<NW>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a hex bitboard of at most {} words", NW)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, hex: &str) -> Result<Bitboard<NW>, E> {
+                Bitboard::from_hex(hex).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(HexVisitor::<NW>)
+    }
+}
+
+/// Grid rendering of a bitboard, returned by `Bitboard::display`.
+pub struct BitboardDisplay<'a, const NW: usize> {
+    bitboard: &'a Bitboard<NW>,
+    width: usize,
+    height: usize,
+}
+
+#[hotpath::measure_all]
+impl<const NW: usize> fmt::Display for BitboardDisplay<'_, NW> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in (0..self.height).rev() {
+            write!(f, "{:2} ", row + 1)?;
+            for col in 0..self.width {
+                let index = row * self.width + col;
+                let set = index < NW * WORD_BITS && self.bitboard.get(index);
+                write!(f, "{} ", if set { 'x' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+
+        write!(f, "   ")?;
+        for col in 0..self.width {
+            if col < 26 {
+                write!(f, "{} ", (b'a' + col as u8) as char)?;
+            } else {
+                write!(f, "{} ", col)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[hotpath::measure_all]
+impl<const NW: usize> fmt::Debug for BitboardDisplay<'_, NW> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        fmt::Display::fmt(self, f)
+    }
 }
 
 #[hotpath::measure_all]
@@ -403,6 +538,23 @@ where
     pub(crate) ray_diagonal: [[Bitboard<{ (W * H).div_ceil(64) }>; W * H]; 4],
 }
 
+// Spot-check the standard 8x8 tables, which `Game::<8, 8>` reads straight
+// from `INSTANCE` with no runtime initialization, against their well-known
+// values. Being `const`, these fail the build rather than a test if the tables
+// go wrong.
+const _: () = {
+    let geo = &BoardGeometry::<8, 8>::INSTANCE;
+    // Knight on b1: a3, c3, d2
+    assert!(geo.knight_attacks_table[1].words[0] == 0x0005_0800);
+    // King on e1: d1, f1, d2, e2, f2
+    assert!(geo.king_attacks_table[4].words[0] == 0x3828);
+    // Pawns on e2 and e7 capture on d3/f3 and d6/f6
+    assert!(geo.pawn_attacks_white_table[12].words[0] == 0x0028_0000);
+    assert!(geo.pawn_attacks_black_table[52].words[0] == 0x2800_0000_0000);
+    // North ray from a1: a2 to a8
+    assert!(geo.ray_orthogonal[0][0].words[0] == 0x0101_0101_0101_0100);
+};
+
 impl<const W: usize, const H: usize> Default for BoardGeometry<W, H>
 where
     [(); (W * H).div_ceil(64)]:,
@@ -758,6 +910,64 @@ where
             | Self::sliding_ray_attacks(sq_idx, 3, &self.ray_diagonal, false, occupied)
     }
 
+    /// Full unblocked ray from `sq_idx` (exclusive) to the board edge in
+    /// direction `(dx, dy)`, whose components are taken by sign. Empty for
+    /// `(0, 0)`.
+    #[inline]
+    pub fn ray(&self, sq_idx: usize, (dx, dy): (i32, i32)) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        debug_assert!(
+            sq_idx < W * H,
+            "ray: sq_idx {} out of bounds for {}x{} board",
+            sq_idx,
+            W,
+            H,
+        );
+        // Table order: N, S, E, W and NE, NW, SE, SW
+        match (dx.signum(), dy.signum()) {
+            (0, 1) => self.ray_orthogonal[0][sq_idx],
+            (0, -1) => self.ray_orthogonal[1][sq_idx],
+            (1, 0) => self.ray_orthogonal[2][sq_idx],
+            (-1, 0) => self.ray_orthogonal[3][sq_idx],
+            (1, 1) => self.ray_diagonal[0][sq_idx],
+            (-1, 1) => self.ray_diagonal[1][sq_idx],
+            (1, -1) => self.ray_diagonal[2][sq_idx],
+            (-1, -1) => self.ray_diagonal[3][sq_idx],
+            _ => Bitboard::empty(),
+        }
+    }
+
+    /// Direction from `a` to `b` as unit steps, if they share a row, column
+    /// or diagonal and differ.
+    #[inline]
+    fn alignment(a: usize, b: usize) -> Option<(i32, i32)> {
+        let dx = (b % W) as i32 - (a % W) as i32;
+        let dy = (b / W) as i32 - (a / W) as i32;
+        let aligned = dx == 0 || dy == 0 || dx.abs() == dy.abs();
+        (a != b && aligned).then_some((dx.signum(), dy.signum()))
+    }
+
+    /// Squares strictly between `a` and `b` on their shared row, column or
+    /// diagonal; empty if they aren't aligned. Built from two ray lookups
+    /// rather than a square-by-square table, which would be too large for
+    /// the bigger boards.
+    #[inline]
+    pub fn between(&self, a: usize, b: usize) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        match Self::alignment(a, b) {
+            Some((dx, dy)) => self.ray(a, (dx, dy)) & self.ray(b, (-dx, -dy)),
+            None => Bitboard::empty(),
+        }
+    }
+
+    /// Every square on the row, column or diagonal through `a` and `b`, edge
+    /// to edge and including both; empty if they aren't aligned.
+    #[inline]
+    pub fn line(&self, a: usize, b: usize) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        match Self::alignment(a, b) {
+            Some((dx, dy)) => self.ray(a, (dx, dy)) | self.ray(a, (-dx, -dy)) | Bitboard::single(a),
+            None => Bitboard::empty(),
+        }
+    }
+
     /// Compute the set of all orthogonal neighbors of every bit in `bb`.
     #[inline]
     pub fn neighbors(
@@ -1189,6 +1399,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_display_grid() {
+        let geo = &BoardGeometry::<5, 5>::INSTANCE;
+        let attacks = geo.knight_attacks(0);
+        assert_eq!(
+            attacks.display(5, 5).to_string(),
+            " 5 . . . . . \n 4 . . . . . \n 3 . x . . . \n 2 . . x . . \n 1 . . . . . \n   a b c d e "
+        );
+
+        // Bits past the board are ignored
+        let mut bb = Bitboard::<1>::single(63);
+        bb.set(0);
+        assert_eq!(bb.display(2, 1).to_string(), " 1 x . \n   a b ");
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let mut bb = Bitboard::<2>::single(0);
+        bb.set(68);
+        let hex = bb.to_hex();
+        assert_eq!(hex, "0x00000000000000100000000000000001");
+        assert_eq!(Bitboard::<2>::from_hex(&hex), Ok(bb));
+        assert_eq!(Bitboard::<2>::from_hex("10_0000_0000_0000_0001"), Ok(bb));
+
+        assert!(Bitboard::<1>::from_hex("0x1_0000_0000_0000_0000").is_err());
+        assert_eq!(
+            Bitboard::<1>::from_hex("0x0_ffff_ffff_ffff_ffff"),
+            Ok(Bitboard::from_u64(u64::MAX))
+        );
+        assert!(Bitboard::<1>::from_hex("0xg").is_err());
+        assert!(Bitboard::<1>::from_hex("0x").is_err());
+
+        let standard = BoardGeometry::<8, 8>::INSTANCE.board_mask;
+        assert_eq!(standard.to_u64(), u64::MAX);
+        assert_eq!(Bitboard::from_u64(0x3828).to_hex(), "0x0000000000003828");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_hex() {
+        use serde::Deserialize;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+
+        let de: StrDeserializer<'_, Error> = "0x3828".into_deserializer();
+        assert_eq!(
+            Bitboard::<1>::deserialize(de),
+            Ok(Bitboard::from_u64(0x3828))
+        );
+        let de: StrDeserializer<'_, Error> = "0x1_0000_0000_0000_0000".into_deserializer();
+        assert!(Bitboard::<1>::deserialize(de).is_err());
+    }
+
+    #[test]
+    fn test_between_and_line() {
+        let geo = &BoardGeometry::<8, 8>::INSTANCE;
+        // a1 to h8: b2..g7 between, the long diagonal as the line
+        assert_eq!(geo.between(0, 63).to_u64(), 0x0040_2010_0804_0200);
+        assert_eq!(geo.line(0, 63).to_u64(), 0x8040_2010_0804_0201);
+        // e1 to e8 and back
+        assert_eq!(geo.between(4, 60), geo.between(60, 4));
+        assert_eq!(geo.between(4, 60).count(), 6);
+        assert_eq!(geo.line(4, 12).to_u64(), 0x1010_1010_1010_1010);
+        // Adjacent, identical and unaligned squares
+        assert!(geo.between(4, 12).is_empty());
+        assert!(geo.line(4, 4).is_empty());
+        assert!(geo.between(1, 18).is_empty());
+        assert!(geo.line(1, 18).is_empty());
+        assert_eq!(geo.ray(4, (0, 0)), Bitboard::empty());
+        assert_eq!(geo.ray(4, (-3, 0)).to_u64(), 0x0f);
+    }
+
+    #[test]
+    fn test_between_matches_stepping() {
+        // Walk from every square to every other on a non-square board
+        const W: usize = 9;
+        const H: usize = 7;
+        let geo = &BoardGeometry::<W, H>::INSTANCE;
+        for a in 0..W * H {
+            for b in 0..W * H {
+                let dx = (b % W) as i32 - (a % W) as i32;
+                let dy = (b / W) as i32 - (a / W) as i32;
+                let mut expected = Bitboard::empty();
+                if a != b && (dx == 0 || dy == 0 || dx.abs() == dy.abs()) {
+                    let (mut col, mut row) = ((a % W) as i32, (a / W) as i32);
+                    loop {
+                        col += dx.signum();
+                        row += dy.signum();
+                        let idx = row as usize * W + col as usize;
+                        if idx == b {
+                            break;
+                        }
+                        expected.set(idx);
+                    }
+                    assert!(geo.line(a, b).get(a) && geo.line(a, b).get(b));
+                    assert_eq!(geo.line(a, b), geo.line(b, a));
+                } else {
+                    assert!(geo.line(a, b).is_empty());
+                }
+                assert_eq!(geo.between(a, b), expected, "between {} and {}", a, b);
+            }
+        }
+    }
+
     #[test]
     fn test_8x8_word_boundary() {
         // 8x8 = 64 bits = exactly 1 word. shift_left(1) of bit 63 spills beyond.
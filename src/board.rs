@@ -1,13 +1,15 @@
 use arrayvec::ArrayVec;
 
-use crate::bitboard::Bitboard;
+use crate::bitboard::{Bitboard, BoardGeometry};
 use crate::color::Color;
+use crate::error::ChessError;
+use crate::game::Violation;
 use crate::pieces::{Piece, PieceType};
 use crate::position::Position;
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug)]
 pub(crate) struct Board<const W: usize, const H: usize>
 where
     [(); (W * H).div_ceil(64)]:,
@@ -18,8 +20,34 @@ where
     rooks: Bitboard<{ (W * H).div_ceil(64) }>,
     queens: Bitboard<{ (W * H).div_ceil(64) }>,
     kings: Bitboard<{ (W * H).div_ceil(64) }>,
+    archbishops: Bitboard<{ (W * H).div_ceil(64) }>,
+    chancellors: Bitboard<{ (W * H).div_ceil(64) }>,
     white: Bitboard<{ (W * H).div_ceil(64) }>,
     black: Bitboard<{ (W * H).div_ceil(64) }>,
+    /// Zobrist key of the pieces, updated as they are placed and removed.
+    key: u64,
+}
+
+/// Zobrist key of `piece` standing on square `idx`. A splitmix64 hash of the
+/// two rather than a table lookup, so no table has to be sized for the
+/// largest board.
+#[inline]
+fn square_key(piece: &Piece, idx: usize) -> u64 {
+    let code = (piece.piece_type as u64) << 1 | u64::from(piece.color == Color::White);
+    let mut z = ((code << 32) | idx as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Footer label of column `col` in a board diagram: its file letter, or the
+/// column number past the z-file.
+fn file_label(col: usize) -> String {
+    if col < 26 {
+        ((b'a' + col as u8) as char).to_string()
+    } else {
+        col.to_string()
+    }
 }
 
 #[hotpath::measure_all]
@@ -34,6 +62,8 @@ where
             && self.rooks == other.rooks
             && self.queens == other.queens
             && self.kings == other.kings
+            && self.archbishops == other.archbishops
+            && self.chancellors == other.chancellors
             && self.white == other.white
             && self.black == other.black
     }
@@ -53,6 +83,8 @@ where
         self.rooks.hash(state);
         self.queens.hash(state);
         self.kings.hash(state);
+        self.archbishops.hash(state);
+        self.chancellors.hash(state);
         self.white.hash(state);
         self.black.hash(state);
     }
@@ -63,7 +95,7 @@ impl<const W: usize, const H: usize> Board<W, H>
 where
     [(); (W * H).div_ceil(64)]:,
 {
-    pub(crate) fn new(fen: &str) -> Result<Self, String> {
+    pub(crate) fn new(fen: &str) -> Result<Self, ChessError> {
         let mut board = Self::empty();
         board.load_fen(fen)?;
         Ok(board)
@@ -77,11 +109,20 @@ where
             rooks: Bitboard::empty(),
             queens: Bitboard::empty(),
             kings: Bitboard::empty(),
+            archbishops: Bitboard::empty(),
+            chancellors: Bitboard::empty(),
             white: Bitboard::empty(),
             black: Bitboard::empty(),
+            key: 0,
         }
     }
 
+    /// Zobrist key of the pieces on the board. Equal boards have equal keys.
+    #[inline]
+    pub(crate) fn key(&self) -> u64 {
+        self.key
+    }
+
     #[inline]
     fn index(col: usize, row: usize) -> usize {
         row * W + col
@@ -113,9 +154,45 @@ where
             PieceType::Rook => self.rooks,
             PieceType::Queen => self.queens,
             PieceType::King => self.kings,
+            PieceType::Archbishop => self.archbishops,
+            PieceType::Chancellor => self.chancellors,
         }
     }
 
+    /// Number of pieces of `color` and type `pt`.
+    #[inline]
+    pub(crate) fn piece_count(&self, color: Color, pt: PieceType) -> u32 {
+        (self.piece_type_bb(pt) & self.color_bb(color)).count()
+    }
+
+    /// Total value of the pieces of `color`, with `values` giving the value of
+    /// each piece type.
+    pub(crate) fn material(&self, color: Color, values: impl Fn(PieceType) -> i32) -> i32 {
+        PieceType::ALL
+            .iter()
+            .map(|&pt| self.piece_count(color, pt) as i32 * values(pt))
+            .sum()
+    }
+
+    /// Pieces that slide along rows and columns: rooks, queens and
+    /// chancellors.
+    #[inline]
+    pub(crate) fn orthogonal_sliders(&self) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.rooks | self.queens | self.chancellors
+    }
+
+    /// Pieces that slide along diagonals: bishops, queens and archbishops.
+    #[inline]
+    pub(crate) fn diagonal_sliders(&self) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.bishops | self.queens | self.archbishops
+    }
+
+    /// Pieces that move as a knight: knights, archbishops and chancellors.
+    #[inline]
+    pub(crate) fn knight_jumpers(&self) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.knights | self.archbishops | self.chancellors
+    }
+
     #[inline]
     fn piece_type_bb_mut(&mut self, pt: PieceType) -> &mut Bitboard<{ (W * H).div_ceil(64) }> {
         match pt {
@@ -125,6 +202,8 @@ where
             PieceType::Rook => &mut self.rooks,
             PieceType::Queen => &mut self.queens,
             PieceType::King => &mut self.kings,
+            PieceType::Archbishop => &mut self.archbishops,
+            PieceType::Chancellor => &mut self.chancellors,
         }
     }
 
@@ -139,29 +218,33 @@ where
     #[inline]
     pub(crate) fn piece_type_at(&self, index: usize) -> Option<PieceType> {
         // Branchless: extract one bit from each piece-type bitboard in parallel,
-        // combine into a 6-bit key, and do a single table lookup.
+        // combine into an 8-bit key, and do a single table lookup.
         let key = self.pawns.bit_at(index)
             | (self.knights.bit_at(index) << 1)
             | (self.bishops.bit_at(index) << 2)
             | (self.rooks.bit_at(index) << 3)
             | (self.queens.bit_at(index) << 4)
-            | (self.kings.bit_at(index) << 5);
+            | (self.kings.bit_at(index) << 5)
+            | (self.archbishops.bit_at(index) << 6)
+            | (self.chancellors.bit_at(index) << 7);
 
         // Only keys with a single bit set (or 0) are valid on a correct board.
         debug_assert!(
             key == 0 || key.is_power_of_two(),
-            "board corruption: multiple piece types at index {} (key=0b{:06b})",
+            "board corruption: multiple piece types at index {} (key=0b{:08b})",
             index,
             key,
         );
-        const TABLE: [Option<PieceType>; 64] = {
-            let mut t: [Option<PieceType>; 64] = [None; 64];
+        const TABLE: [Option<PieceType>; 256] = {
+            let mut t: [Option<PieceType>; 256] = [None; 256];
             t[1] = Some(PieceType::Pawn);
             t[2] = Some(PieceType::Knight);
             t[4] = Some(PieceType::Bishop);
             t[8] = Some(PieceType::Rook);
             t[16] = Some(PieceType::Queen);
             t[32] = Some(PieceType::King);
+            t[64] = Some(PieceType::Archbishop);
+            t[128] = Some(PieceType::Chancellor);
             t
         };
 
@@ -193,6 +276,73 @@ where
         Some(Piece::new(pt, color))
     }
 
+    /// Squares attacked by the piece on `pos`, with sliders blocked by
+    /// `occupied`. Empty if there is no piece on `pos`.
+    pub(crate) fn attacks_from(
+        &self,
+        pos: &Position,
+        occupied: Bitboard<{ (W * H).div_ceil(64) }>,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        match self.get_piece(pos) {
+            Some(piece) => Self::piece_attacks(pos, &piece, occupied),
+            None => Bitboard::empty(),
+        }
+    }
+
+    /// Squares attacked by `piece` standing on `pos`, with sliders blocked by
+    /// `occupied`.
+    pub(crate) fn piece_attacks(
+        pos: &Position,
+        piece: &Piece,
+        occupied: Bitboard<{ (W * H).div_ceil(64) }>,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let geo = &BoardGeometry::<W, H>::INSTANCE;
+        let square_idx = pos.to_index(W);
+        match piece.piece_type {
+            PieceType::Pawn => geo.pawn_attacks(square_idx, piece.color == Color::White),
+            PieceType::Knight => geo.knight_attacks(square_idx),
+            PieceType::Bishop => geo.diagonal_attacks(square_idx, occupied),
+            PieceType::Rook => geo.orthogonal_attacks(square_idx, occupied),
+            PieceType::Queen => {
+                geo.diagonal_attacks(square_idx, occupied)
+                    | geo.orthogonal_attacks(square_idx, occupied)
+            }
+            PieceType::King => geo.king_attacks(square_idx),
+            PieceType::Archbishop => {
+                geo.diagonal_attacks(square_idx, occupied) | geo.knight_attacks(square_idx)
+            }
+            PieceType::Chancellor => {
+                geo.orthogonal_attacks(square_idx, occupied) | geo.knight_attacks(square_idx)
+            }
+        }
+    }
+
+    /// Sliders of `color` that attack `square` through exactly one blocker of
+    /// either color: along ranks and files or along diagonals, for the pieces
+    /// that slide that way.
+    pub(crate) fn xray_attackers_to(
+        &self,
+        square: &Position,
+        color: Color,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let geo = &BoardGeometry::<W, H>::INSTANCE;
+        let square_idx = square.to_index(W);
+        let occupied = self.occupied();
+        let own = self.color_bb(color);
+
+        // Lifting the first blocker on each ray reveals what stands behind it
+        let ortho = geo.orthogonal_attacks(square_idx, occupied);
+        let ortho_xray = geo
+            .orthogonal_attacks(square_idx, occupied.andnot(ortho & occupied))
+            .andnot(ortho);
+        let diag = geo.diagonal_attacks(square_idx, occupied);
+        let diag_xray = geo
+            .diagonal_attacks(square_idx, occupied.andnot(diag & occupied))
+            .andnot(diag);
+
+        ((ortho_xray & self.orthogonal_sliders()) | (diag_xray & self.diagonal_sliders())) & own
+    }
+
     pub(crate) fn set_piece(&mut self, pos: &Position, piece: Option<Piece>) {
         if !pos.is_valid(W, H) {
             return;
@@ -233,6 +383,7 @@ where
         );
         self.piece_type_bb_mut(piece.piece_type).clear(idx);
         self.color_bb_mut(piece.color).clear(idx);
+        self.key ^= square_key(piece, idx);
     }
 
     /// Place a piece on the board. The target square must be empty.
@@ -255,6 +406,7 @@ where
         );
         self.piece_type_bb_mut(piece.piece_type).set(idx);
         self.color_bb_mut(piece.color).set(idx);
+        self.key ^= square_key(piece, idx);
     }
 
     pub(crate) fn clear(&mut self) {
@@ -264,11 +416,122 @@ where
         self.rooks = Bitboard::empty();
         self.queens = Bitboard::empty();
         self.kings = Bitboard::empty();
+        self.archbishops = Bitboard::empty();
+        self.chancellors = Bitboard::empty();
         self.white = Bitboard::empty();
         self.black = Bitboard::empty();
+        self.key = 0;
     }
 
-    pub(crate) fn to_fen(&self) -> String {
+    /// Remove every piece of the given type and color. Returns how many were removed.
+    pub(crate) fn remove_all(&mut self, piece_type: PieceType, color: Color) -> usize {
+        let targets = self.piece_type_bb(piece_type) & self.color_bb(color);
+        let removed = targets.count() as usize;
+        *self.piece_type_bb_mut(piece_type) = self.piece_type_bb(piece_type).andnot(targets);
+        *self.color_bb_mut(color) = self.color_bb(color).andnot(targets);
+        let piece = Piece::new(piece_type, color);
+        for idx in targets.iter_ones() {
+            self.key ^= square_key(&piece, idx);
+        }
+        removed
+    }
+
+    /// Move every piece by `dx` columns and `dy` rows. Fails, leaving the board
+    /// unchanged, if any piece would leave the board.
+    pub(crate) fn shift(&mut self, dx: i32, dy: i32) -> Result<(), ChessError> {
+        let mut shifted = Self::empty();
+        for color in [Color::White, Color::Black] {
+            for (pos, piece) in self.pieces_iter(color) {
+                let col = i32::from(pos.col) + dx;
+                let row = i32::from(pos.row) + dy;
+                if col < 0 || row < 0 || col >= W as i32 || row >= H as i32 {
+                    return Err(ChessError::InvalidPosition(format!(
+                        "Shifting by ({}, {}) moves the piece on {} off the board",
+                        dx,
+                        dy,
+                        pos.to_algebraic()
+                    )));
+                }
+                shifted.place_piece(&Position::new(col as u8, row as u8), &piece);
+            }
+        }
+        *self = shifted;
+        Ok(())
+    }
+
+    /// Move every piece to `map(pos)`, which must be a permutation of the
+    /// board's squares.
+    fn remap(&mut self, map: impl Fn(Position) -> Position) {
+        let mut remapped = Self::empty();
+        for color in [Color::White, Color::Black] {
+            for (pos, piece) in self.pieces_iter(color) {
+                remapped.place_piece(&map(pos), &piece);
+            }
+        }
+        *self = remapped;
+    }
+
+    /// Reverse the rows, so the first row becomes the last.
+    pub(crate) fn flip_vertical(&mut self) {
+        self.remap(|pos| Position::new(pos.col, (H - 1) as u8 - pos.row));
+    }
+
+    /// Reverse the columns, so the first column becomes the last.
+    pub(crate) fn flip_horizontal(&mut self) {
+        self.remap(|pos| Position::new((W - 1) as u8 - pos.col, pos.row));
+    }
+
+    /// Turn the board half a turn: both flips together.
+    pub(crate) fn rotate180(&mut self) {
+        self.remap(|pos| Position::new((W - 1) as u8 - pos.col, (H - 1) as u8 - pos.row));
+    }
+
+    /// Give every piece the other color, leaving it on its square.
+    pub(crate) fn swap_colors(&mut self) {
+        std::mem::swap(&mut self.white, &mut self.black);
+        self.key = [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| self.pieces_iter(color))
+            .fold(0, |key, (pos, piece)| {
+                key ^ square_key(&piece, pos.to_index(W))
+            });
+    }
+
+    /// Place each of `pieces` on a random empty square. Pawns are never placed
+    /// on the first or last row. Fails, leaving the board unchanged, if some
+    /// piece has no valid square left.
+    ///
+    /// Returns the squares chosen, in the same order as `pieces`.
+    #[cfg(feature = "rand")]
+    pub(crate) fn place_random<R: rand::Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        pieces: &[Piece],
+    ) -> Result<Vec<Position>, ChessError> {
+        use rand::prelude::IndexedRandom;
+
+        let mut placed = *self;
+        let mut squares = Vec::with_capacity(pieces.len());
+        for piece in pieces {
+            let candidates: Vec<usize> = (0..W * H)
+                .filter(|&idx| !placed.occupied().get(idx))
+                .filter(|&idx| {
+                    let row = idx / W;
+                    piece.piece_type != PieceType::Pawn || (row != 0 && row != H - 1)
+                })
+                .collect();
+            let idx = *candidates.choose(rng).ok_or_else(|| {
+                ChessError::InvalidPosition(format!("No empty square left for {:?}", piece))
+            })?;
+            let pos = Position::from_index(idx, W);
+            placed.place_piece(&pos, piece);
+            squares.push(pos);
+        }
+        *self = placed;
+        Ok(squares)
+    }
+
+    pub(crate) fn fen(&self) -> String {
         let mut fen = String::new();
 
         for row in (0..H).rev() {
@@ -299,17 +562,17 @@ where
         fen
     }
 
-    fn load_fen(&mut self, fen: &str) -> Result<(), String> {
+    fn load_fen(&mut self, fen: &str) -> Result<(), ChessError> {
         self.clear();
 
         let parts: ArrayVec<&str, 32> = fen.split('/').collect();
 
         if parts.len() != H {
-            return Err(format!(
-                "Invalid FEN: expected {} rows, got {}",
+            return Err(ChessError::fen(format!(
+                "expected {} rows, got {}",
                 H,
                 parts.len()
-            ));
+            )));
         }
 
         for (row_idx, row_str) in parts.iter().enumerate() {
@@ -333,32 +596,115 @@ where
                         }
                     }
 
-                    let skip = num_str
-                        .parse::<usize>()
-                        .map_err(|_| format!("Invalid FEN number: {}", num_str))?;
+                    let skip = num_str.parse::<usize>().map_err(|_| {
+                        ChessError::fen_row(row_idx, format!("invalid number {}", num_str))
+                    })?;
                     col += skip;
                 } else if let Some(piece) = Piece::from_char(c) {
                     if col >= W {
-                        return Err("Invalid FEN: col index out of bounds".to_string());
+                        return Err(ChessError::fen_row(row_idx, "col index out of bounds"));
                     }
                     self.set_piece(&Position::from_usize(col, row), Some(piece));
                     col += 1;
                 } else {
-                    return Err(format!("Invalid FEN character: {}", c));
+                    return Err(ChessError::fen_row(
+                        row_idx,
+                        format!("invalid character {}", c),
+                    ));
                 }
             }
 
             if col != W {
-                return Err(format!(
-                    "Invalid FEN: row {} has wrong number of squares",
-                    row
-                ));
+                return Err(ChessError::fen_row(row_idx, "wrong number of squares"));
             }
         }
 
         Ok(())
     }
 
+    /// A board holding `pieces`; a later piece on the same square replaces an
+    /// earlier one.
+    pub(crate) fn from_pieces(pieces: impl IntoIterator<Item = (Position, Piece)>) -> Self {
+        let mut board = Self::empty();
+        for (pos, piece) in pieces {
+            board.set_piece(&pos, Some(piece));
+        }
+        board
+    }
+
+    /// Parse a board from the grid format printed by `Display`.
+    ///
+    /// Rows are listed top-down, with `.` for empty squares and FEN piece letters
+    /// otherwise. Whitespace between squares is optional, and the rank-number
+    /// prefix and file-letter footer that `Display` emits are both ignored.
+    pub(crate) fn from_ascii(diagram: &str) -> Result<Self, ChessError> {
+        let mut board = Self::empty();
+        let mut row_idx = 0;
+
+        for line in diagram.lines() {
+            // File-letter footer, after the last row. Checked as a whole line,
+            // since a row of black fairy pieces can start with 'a' too.
+            if row_idx == H && line.split_whitespace().eq((0..W).map(file_label)) {
+                continue;
+            }
+
+            let mut tokens = line.split_whitespace().peekable();
+
+            match tokens.peek() {
+                // Blank line
+                None => continue,
+                // Rank-number prefix
+                Some(t) if t.chars().all(|c| c.is_ascii_digit()) => {
+                    tokens.next();
+                }
+                Some(_) => {}
+            }
+
+            if row_idx >= H {
+                return Err(ChessError::InvalidPosition(format!(
+                    "Invalid diagram: expected {} rows, got more",
+                    H
+                )));
+            }
+            let row = H - 1 - row_idx;
+
+            let mut col = 0;
+            for c in tokens.flat_map(str::chars) {
+                if col >= W {
+                    return Err(ChessError::InvalidPosition(format!(
+                        "Invalid diagram: row {} has more than {} squares",
+                        row + 1,
+                        W
+                    )));
+                }
+                if c != '.' {
+                    let piece = Piece::from_char(c).ok_or_else(|| {
+                        ChessError::InvalidPosition(format!("Invalid diagram character: {}", c))
+                    })?;
+                    board.place_piece(&Position::from_usize(col, row), &piece);
+                }
+                col += 1;
+            }
+
+            if col != W {
+                return Err(ChessError::InvalidPosition(format!(
+                    "Invalid diagram: row {} has wrong number of squares",
+                    row + 1
+                )));
+            }
+            row_idx += 1;
+        }
+
+        if row_idx != H {
+            return Err(ChessError::InvalidPosition(format!(
+                "Invalid diagram: expected {} rows, got {}",
+                H, row_idx
+            )));
+        }
+
+        Ok(board)
+    }
+
     pub(crate) fn pieces(&self, color: Color) -> Vec<(Position, Piece)> {
         self.pieces_iter(color).collect()
     }
@@ -378,6 +724,25 @@ where
             .lowest_bit_index()
             .map(|idx| Position::from_index(idx, W))
     }
+
+    /// The rule violations visible from the pieces alone: a king count other
+    /// than one per side, and pawns on the first or last row.
+    pub(crate) fn validate(&self) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for color in [Color::White, Color::Black] {
+            let count = (self.kings & self.color_bb(color)).count();
+            if count != 1 {
+                violations.push(Violation::KingCount { color, count });
+            }
+        }
+        for idx in self.pawns.iter_ones() {
+            let pos = Position::from_index(idx, W);
+            if pos.row == 0 || usize::from(pos.row) == H - 1 {
+                violations.push(Violation::PawnOnBackRank(pos));
+            }
+        }
+        violations
+    }
 }
 
 pub(crate) struct PieceIterator<'a, const W: usize, const H: usize>
@@ -418,7 +783,12 @@ impl<const W: usize, const H: usize> fmt::Display for Board<W, H>
 where
     [(); (W * H).div_ceil(64)]:,
 {
+    /// `{}` prints a diagram of the board; `{:#}` prints the FEN piece placement.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            return write!(f, "{}", self.fen());
+        }
+
         for row in (0..H).rev() {
             write!(f, "{:2} ", row + 1)?;
             for col in 0..W {
@@ -435,23 +805,38 @@ where
         write!(f, "   ")?;
 
         for col in 0..W {
-            if col < 26 {
-                write!(f, "{} ", (b'a' + col as u8) as char)?;
-            } else {
-                write!(f, "{} ", col)?;
-            }
+            write!(f, "{} ", file_label(col))?;
         }
 
         Ok(())
     }
 }
 
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> std::str::FromStr for Board<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    type Err = ChessError;
+
+    /// Parse the piece-placement field of a FEN string.
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Self::new(fen)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     type StdBoard = Board<8, 8>;
 
+    /// The incrementally kept key matches that of the board built afresh.
+    fn assert_key_current(board: &StdBoard) {
+        let rebuilt = StdBoard::new(&board.fen()).expect("board's own FEN");
+        assert_eq!(board.key(), rebuilt.key(), "{}", board.fen());
+    }
+
     #[test]
     fn test_custom_board_creation() {
         let _board: Board<6, 6> =
@@ -464,6 +849,26 @@ mod tests {
         assert!(board.is_err(), "Expected error for invalid FEN");
     }
 
+    #[test]
+    fn test_board_flips() {
+        let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR";
+        let mut board = StdBoard::new(fen).expect("standard FEN");
+
+        board.flip_vertical();
+        assert_eq!(board.fen(), "RNBQKBNR/PPPP1PPP/8/4P3/8/8/pppppppp/rnbqkbnr");
+        board.swap_colors();
+        assert_eq!(board.fen(), "rnbqkbnr/pppp1ppp/8/4p3/8/8/PPPPPPPP/RNBQKBNR");
+        assert_key_current(&board);
+        assert_ne!(board.key(), StdBoard::new(fen).expect("standard FEN").key());
+
+        let mut board = StdBoard::new(fen).expect("standard FEN");
+        board.flip_horizontal();
+        assert_eq!(board.fen(), "rnbkqbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBKQBNR");
+        board.flip_horizontal();
+        board.rotate180();
+        assert_eq!(board.fen(), "RNBKQBNR/PPP1PPPP/8/3P4/8/8/pppppppp/rnbkqbnr");
+    }
+
     #[test]
     fn test_board_piece_placement() {
         let mut board = StdBoard::empty();
@@ -570,7 +975,7 @@ mod tests {
         let board =
             StdBoard::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").expect("standard FEN");
 
-        let fen = board.to_fen();
+        let fen = board.fen();
         assert_eq!(fen, "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR");
 
         let new_board = StdBoard::new(&fen).expect("Failed to parse FEN string");
@@ -583,4 +988,107 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_board_from_ascii_display_round_trip() {
+        let board = StdBoard::new("r3k2r/pp1n1ppp/8/2b5/4P3/8/PPP2PPP/RNBQK2R").expect("valid FEN");
+        let parsed = StdBoard::from_ascii(&board.to_string()).expect("Display output parses");
+        assert_eq!(parsed, board);
+
+        let wide: Board<10, 6> = Board::new("rnbqkbnrqr/pppppppppp/10/10/PPPPPPPPPP/RNBQKBNRQR")
+            .expect("valid 10x6 FEN");
+        let parsed: Board<10, 6> = Board::from_ascii(&wide.to_string()).expect("parses");
+        assert_eq!(parsed, wide);
+    }
+
+    #[test]
+    fn test_board_from_ascii_compact() {
+        let board: Board<6, 6> = Board::from_ascii(
+            "
+            ....k.
+            ......
+            ......
+            ..P...
+            ......
+            ....K.
+            ",
+        )
+        .expect("compact diagram parses");
+        assert_eq!(board.fen(), "4k1/6/6/2P3/6/4K1");
+    }
+
+    #[test]
+    fn test_board_from_ascii_archbishop_row() {
+        // A row starting with a black archbishop isn't the file-letter footer
+        let board: Board<6, 6> = Board::from_ascii(
+            "
+            a . . . k .
+            . . . . . .
+            . . . . . .
+            . . . . . .
+            . . . . . .
+            . . . . K .
+            a b c d e f
+            ",
+        )
+        .expect("diagram parses");
+        assert_eq!(board.fen(), "a3k1/6/6/6/6/4K1");
+    }
+
+    #[test]
+    fn test_board_from_ascii_invalid() {
+        assert!(Board::<6, 6>::from_ascii("....k.\n......").is_err());
+        assert!(
+            Board::<6, 6>::from_ascii("....k.\n......\n......\n......\n......\n...x.K").is_err()
+        );
+        assert!(
+            Board::<6, 6>::from_ascii("....k.\n......\n......\n......\n......\n....K").is_err()
+        );
+    }
+
+    #[test]
+    fn test_board_from_str_and_fen_display() {
+        let fen = "r3k2r/pp1n1ppp/8/2b5/4P3/8/PPP2PPP/RNBQK2R";
+        let board: StdBoard = fen.parse().expect("valid FEN");
+        assert_eq!(format!("{:#}", board), fen);
+        assert!("8/8".parse::<StdBoard>().is_err());
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut board =
+            StdBoard::new("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR").expect("valid board FEN");
+        assert_eq!(board.remove_all(PieceType::Pawn, Color::Black), 8);
+        assert_eq!(board.remove_all(PieceType::Pawn, Color::Black), 0);
+        assert_eq!(board.fen(), "rnbqkbnr/8/8/8/8/8/PPPPPPPP/RNBQKBNR");
+        assert_key_current(&board);
+    }
+
+    #[test]
+    fn test_shift() {
+        let mut board = StdBoard::new("8/8/8/3k4/8/2K5/8/8").expect("valid board FEN");
+        board.shift(1, -2).expect("pieces stay on the board");
+        assert_eq!(board.fen(), "8/8/8/8/8/4k3/8/3K4");
+
+        assert!(board.shift(0, -2).is_err());
+        assert_eq!(board.fen(), "8/8/8/8/8/4k3/8/3K4");
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_place_random() {
+        use rand::SeedableRng;
+        use rand::rngs::SmallRng;
+
+        let mut rng = SmallRng::seed_from_u64(7);
+        let mut board = StdBoard::new("8/8/8/8/8/8/8/8").expect("valid board FEN");
+        let pawn = Piece::new(PieceType::Pawn, Color::White);
+        let squares = board
+            .place_random(&mut rng, &[pawn; 48])
+            .expect("48 pawns fit on the middle rows");
+        assert_eq!(squares.len(), 48);
+        assert!(squares.iter().all(|p| p.row != 0 && p.row != 7));
+        assert!(board.place_random(&mut rng, &[pawn]).is_err());
+        assert_eq!(board.occupied().count(), 48);
+    }
 }
@@ -0,0 +1,729 @@
+//! Supervised training data labelled by a UCI engine.
+//!
+//! `label_with_engine` asks an engine (e.g. Stockfish) for its best move and
+//! evaluation in each position, and returns the encoded positions with those
+//! labels as one `LabeledShard`: input planes, the best move as an action
+//! index for the policy target, and the evaluation as a value target (see
+//! `WdlModel`).
+//!
+//! `SampleWriter` and `SampleReader` store `TrainingSample`s (planes, policy
+//! and value targets) in an indexed binary file, for self-play pipelines that
+//! would otherwise pickle their samples.
+//!
+//! `PgnSamples` turns a PGN database into `TrainingSample`s for supervised
+//! pretraining: the move played in each position as a one-hot policy target
+//! and the game's result as the value target.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::color::Color;
+use crate::encode::{self, EncoderConfig, encode_game_planes};
+use crate::game::StandardGame;
+use crate::pgn::{PgnError, PgnGame, PgnReader, PgnResult};
+use crate::uci::{InfoLine, UciEngine, UciError};
+use crate::wdl::WdlModel;
+
+/// Positions encoded by `encode_game_planes` with their engine labels, stacked
+/// for transfer to Python/numpy.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LabeledShard {
+    /// Input planes of every position, `len() * num_planes * height * width`
+    /// values in row-major order.
+    pub planes: Vec<f32>,
+    pub num_planes: usize,
+    pub height: usize,
+    pub width: usize,
+    /// The FEN of each labelled position.
+    pub fens: Vec<String>,
+    /// The engine's best move in each position, as an action index.
+    pub policy: Vec<usize>,
+    /// The engine's evaluation of each position in [-1, 1], from the
+    /// perspective of the side to move.
+    pub value: Vec<f32>,
+}
+
+#[hotpath::measure_all]
+impl LabeledShard {
+    /// Number of labelled positions.
+    pub fn len(&self) -> usize {
+        self.policy.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.policy.is_empty()
+    }
+}
+
+/// The value of the last scored `info` line of a search, converted with
+/// `model`: a forced mate is worth 1 (or -1 if the side to move is mated).
+#[hotpath::measure]
+pub fn info_value(info: &[InfoLine], model: &WdlModel) -> Option<f32> {
+    info.iter()
+        .rev()
+        .find_map(|line| model.score_wdl(line.score_cp, line.score_mate))
+        .map(|wdl| wdl.value())
+}
+
+/// Label standard-board `positions` (given as FEN) by searching each one to
+/// `depth` with the UCI engine at `engine_path`.
+///
+/// Finished positions, which have no best move, are skipped, as are positions
+/// for which the engine reports no score. Scores are converted to values with
+/// the default `WdlModel`.
+#[hotpath::measure]
+pub fn label_with_engine(
+    engine_path: &str,
+    positions: &[&str],
+    depth: u32,
+) -> Result<LabeledShard, UciError> {
+    label_with_engine_with_model(engine_path, positions, depth, &WdlModel::default())
+}
+
+/// Like `label_with_engine`, converting scores to values with `model`.
+#[hotpath::measure]
+pub fn label_with_engine_with_model(
+    engine_path: &str,
+    positions: &[&str],
+    depth: u32,
+    model: &WdlModel,
+) -> Result<LabeledShard, UciError> {
+    let mut engine = UciEngine::new(engine_path, &[])?;
+    let mut shard = LabeledShard::default();
+
+    for fen in positions {
+        let mut game = StandardGame::new(fen, true)
+            .map_err(|e| UciError::ProtocolError(format!("Invalid FEN: {}", e)))?;
+        if game.is_over() {
+            continue;
+        }
+
+        engine.new_game_from_fen(fen)?;
+        let result = engine.go_depth(depth)?;
+        let Some(value) = info_value(&result.info, model) else {
+            continue;
+        };
+        let policy = encode::encode_action(&result.best_move, game.width(), game.height())
+            .ok_or_else(|| {
+                UciError::IllegalMove(format!("{} has no action index", result.best_move_lan))
+            })?;
+
+        let (planes, num_planes, height, width) = encode_game_planes(&mut game);
+        shard.planes.extend_from_slice(&planes);
+        (shard.num_planes, shard.height, shard.width) = (num_planes, height, width);
+        shard.fens.push(fen.to_string());
+        shard.policy.push(policy);
+        shard.value.push(value);
+    }
+
+    engine.quit()?;
+    Ok(shard)
+}
+
+/// Magic bytes at the start of a training sample file, followed by the format
+/// version.
+const SAMPLE_MAGIC: &[u8; 8] = b"SPKYSMPL";
+const SAMPLE_VERSION: u32 = 1;
+/// Magic, version, then the plane count, height and width.
+const SAMPLE_HEADER_LEN: u64 = 8 + 4 * 4;
+
+/// One training example: encoded input planes, a policy target and a value
+/// target in [-1, 1] from the perspective of the side to move.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrainingSample {
+    /// `num_planes * height * width` values in row-major order.
+    pub planes: Vec<f32>,
+    /// Sparse policy target as (action index, probability) pairs; a single
+    /// pair with probability 1 for a one-hot target.
+    pub policy: Vec<(u32, f32)>,
+    pub value: f32,
+}
+
+fn io_err(context: &str, e: std::io::Error) -> String {
+    format!("{}: {}", context, e)
+}
+
+/// Writes `TrainingSample`s to a compact binary file.
+///
+/// The file is a header (magic, version, plane shape), the samples back to
+/// back (value, policy length, policy pairs, planes; all little-endian), and
+/// an index of sample offsets written by `finish`, so `SampleReader` can seek
+/// to any sample. A file that was never finished has no index and can't be
+/// read.
+pub struct SampleWriter<W: Write> {
+    inner: W,
+    shape: (usize, usize, usize),
+    offsets: Vec<u64>,
+    position: u64,
+}
+
+#[hotpath::measure_all]
+impl SampleWriter<BufWriter<File>> {
+    /// Create (or truncate) the file at `path` for samples of
+    /// `num_planes * height * width` planes.
+    pub fn create(
+        path: impl AsRef<Path>,
+        num_planes: usize,
+        height: usize,
+        width: usize,
+    ) -> Result<Self, String> {
+        let file = File::create(path.as_ref())
+            .map_err(|e| io_err(&format!("Failed to create {}", path.as_ref().display()), e))?;
+        SampleWriter::new(BufWriter::new(file), num_planes, height, width)
+    }
+}
+
+#[hotpath::measure_all]
+impl<W: Write> SampleWriter<W> {
+    pub fn new(
+        mut inner: W,
+        num_planes: usize,
+        height: usize,
+        width: usize,
+    ) -> Result<Self, String> {
+        let mut header = Vec::with_capacity(SAMPLE_HEADER_LEN as usize);
+        header.extend_from_slice(SAMPLE_MAGIC);
+        for field in [SAMPLE_VERSION as usize, num_planes, height, width] {
+            let field = u32::try_from(field).map_err(|_| format!("{} doesn't fit a u32", field))?;
+            header.extend_from_slice(&field.to_le_bytes());
+        }
+        inner
+            .write_all(&header)
+            .map_err(|e| io_err("Failed to write sample header", e))?;
+        Ok(SampleWriter {
+            inner,
+            shape: (num_planes, height, width),
+            offsets: Vec::new(),
+            position: SAMPLE_HEADER_LEN,
+        })
+    }
+
+    /// Number of samples written so far.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Append `sample`, whose planes must match the file's plane shape.
+    pub fn write(&mut self, sample: &TrainingSample) -> Result<(), String> {
+        let (num_planes, height, width) = self.shape;
+        if sample.planes.len() != num_planes * height * width {
+            return Err(format!(
+                "expected {} plane values, got {}",
+                num_planes * height * width,
+                sample.planes.len()
+            ));
+        }
+
+        let mut record = Vec::with_capacity(8 + 8 * sample.policy.len() + 4 * sample.planes.len());
+        record.extend_from_slice(&sample.value.to_le_bytes());
+        record.extend_from_slice(&(sample.policy.len() as u32).to_le_bytes());
+        for &(action, probability) in &sample.policy {
+            record.extend_from_slice(&action.to_le_bytes());
+            record.extend_from_slice(&probability.to_le_bytes());
+        }
+        for value in &sample.planes {
+            record.extend_from_slice(&value.to_le_bytes());
+        }
+        self.inner
+            .write_all(&record)
+            .map_err(|e| io_err("Failed to write sample", e))?;
+
+        self.offsets.push(self.position);
+        self.position += record.len() as u64;
+        Ok(())
+    }
+
+    /// Write the index and flush, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W, String> {
+        let mut index = Vec::with_capacity(8 * self.offsets.len() + 16);
+        for offset in &self.offsets {
+            index.extend_from_slice(&offset.to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        index.extend_from_slice(&self.position.to_le_bytes());
+        self.inner
+            .write_all(&index)
+            .and_then(|_| self.inner.flush())
+            .map_err(|e| io_err("Failed to write sample index", e))?;
+        Ok(self.inner)
+    }
+}
+
+/// Reads a file written by `SampleWriter`, by index with `get` or in order by
+/// iterating.
+pub struct SampleReader<R: Read + Seek> {
+    inner: R,
+    shape: (usize, usize, usize),
+    offsets: Vec<u64>,
+    /// Where the index starts, i.e. the end of the last sample.
+    index_start: u64,
+    next: usize,
+}
+
+#[hotpath::measure_all]
+impl SampleReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, String> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| io_err(&format!("Failed to open {}", path.as_ref().display()), e))?;
+        SampleReader::new(BufReader::new(file))
+    }
+}
+
+#[hotpath::measure_all]
+impl<R: Read + Seek> SampleReader<R> {
+    pub fn new(mut inner: R) -> Result<Self, String> {
+        let mut header = [0u8; SAMPLE_HEADER_LEN as usize];
+        inner
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| inner.read_exact(&mut header))
+            .map_err(|e| io_err("Failed to read sample header", e))?;
+        if &header[..8] != SAMPLE_MAGIC {
+            return Err("Not a training sample file".to_string());
+        }
+        let field = |i: usize| read_u32(&header[8 + 4 * i..]) as usize;
+        if field(0) != SAMPLE_VERSION as usize {
+            return Err(format!("Unsupported sample file version {}", field(0)));
+        }
+        let shape = (field(1), field(2), field(3));
+        let plane_bytes = shape
+            .0
+            .checked_mul(shape.1)
+            .and_then(|n| n.checked_mul(shape.2))
+            .and_then(|n| n.checked_mul(4))
+            .filter(|&n| n > 0)
+            .ok_or_else(|| format!("Invalid sample plane shape {:?}", shape))?;
+
+        let mut footer = [0u8; 16];
+        let end = inner
+            .seek(SeekFrom::End(-16))
+            .and_then(|end| inner.read_exact(&mut footer).map(|_| end))
+            .map_err(|e| io_err("Failed to read sample index (was the writer finished?)", e))?;
+        let count = read_u64(&footer);
+        let index_start = read_u64(&footer[8..]);
+        let index_len = end.checked_sub(index_start);
+        if index_start < SAMPLE_HEADER_LEN
+            || index_len.is_none()
+            || index_len != count.checked_mul(8)
+        {
+            return Err("Corrupt sample index".to_string());
+        }
+        let mut index = vec![0u8; 8 * count as usize];
+        inner
+            .seek(SeekFrom::Start(index_start))
+            .and_then(|_| inner.read_exact(&mut index))
+            .map_err(|e| io_err("Failed to read sample index", e))?;
+        let offsets: Vec<u64> = index.chunks_exact(8).map(read_u64).collect();
+
+        // Every sample must lie between the header and the index, with room
+        // for its value, policy length and planes, so `get` never allocates
+        // more than the file holds
+        let min_record_len = 8 + plane_bytes as u64;
+        let mut start = SAMPLE_HEADER_LEN;
+        for (i, &offset) in offsets.iter().enumerate() {
+            let end = offsets.get(i + 1).copied().unwrap_or(index_start);
+            if offset != start
+                || end
+                    .checked_sub(offset)
+                    .is_none_or(|len| len < min_record_len)
+            {
+                return Err("Corrupt sample index".to_string());
+            }
+            start = end;
+        }
+        if start != index_start {
+            return Err("Corrupt sample index".to_string());
+        }
+
+        Ok(SampleReader {
+            inner,
+            shape,
+            offsets,
+            index_start,
+            next: 0,
+        })
+    }
+
+    /// Number of samples in the file.
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// (num_planes, height, width) of every sample's planes.
+    pub fn plane_shape(&self) -> (usize, usize, usize) {
+        self.shape
+    }
+
+    /// The sample at `index`.
+    pub fn get(&mut self, index: usize) -> Result<TrainingSample, String> {
+        let offset = *self.offsets.get(index).ok_or_else(|| {
+            format!(
+                "sample index {} out of range for {} samples",
+                index,
+                self.offsets.len()
+            )
+        })?;
+        let end = self
+            .offsets
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.index_start);
+        let read = |inner: &mut R| -> std::io::Result<TrainingSample> {
+            inner.seek(SeekFrom::Start(offset))?;
+            let mut head = [0u8; 8];
+            inner.read_exact(&mut head)?;
+            let value = f32::from_le_bytes(head[..4].try_into().expect("get: 4 bytes"));
+            let policy_len = read_u32(&head[4..]) as usize;
+
+            // The policy and planes fill the rest of the record exactly
+            let (num_planes, height, width) = self.shape;
+            let body_len = 8 * policy_len + 4 * num_planes * height * width;
+            if body_len as u64 != end - offset - 8 {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("policy length {} doesn't match the record size", policy_len),
+                ));
+            }
+            let mut body = vec![0u8; body_len];
+            inner.read_exact(&mut body)?;
+            let (policy_bytes, plane_bytes) = body.split_at(8 * policy_len);
+            let policy = policy_bytes
+                .chunks_exact(8)
+                .map(|pair| (read_u32(pair), read_f32(&pair[4..])))
+                .collect();
+            let planes = plane_bytes.chunks_exact(4).map(read_f32).collect();
+            Ok(TrainingSample {
+                planes,
+                policy,
+                value,
+            })
+        };
+        read(&mut self.inner).map_err(|e| io_err(&format!("Failed to read sample {}", index), e))
+    }
+}
+
+impl<R: Read + Seek> Iterator for SampleReader<R> {
+    type Item = Result<TrainingSample, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.offsets.len() {
+            return None;
+        }
+        self.next += 1;
+        Some(self.get(self.next - 1))
+    }
+}
+
+/// Which positions of each game `PgnSamples` keeps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PgnSampleConfig {
+    /// Keep every `every_nth` position (1 keeps them all), counting from the
+    /// first one kept.
+    pub every_nth: usize,
+    /// Skip the positions before the first `skip_plies` plies, which are
+    /// usually opening book moves.
+    pub skip_plies: usize,
+    pub encoder: EncoderConfig,
+}
+
+impl Default for PgnSampleConfig {
+    fn default() -> Self {
+        PgnSampleConfig {
+            every_nth: 1,
+            skip_plies: 0,
+            encoder: EncoderConfig::default(),
+        }
+    }
+}
+
+/// Training samples from the games of a PGN source, produced a game at a
+/// time: the planes of each kept position, the move played there as a one-hot
+/// policy target, and the game's result for the side to move as the value.
+///
+/// Games that fail to parse or replay, or that have no result (`*`), are
+/// skipped and counted by `skipped_games`.
+pub struct PgnSamples<I> {
+    games: I,
+    config: PgnSampleConfig,
+    pending: VecDeque<TrainingSample>,
+    games_read: usize,
+    skipped_games: usize,
+}
+
+impl<R: BufRead> PgnSamples<PgnReader<R>> {
+    /// Samples from the PGN read from `reader`, e.g. a database file.
+    pub fn from_reader(reader: R, config: PgnSampleConfig) -> Self {
+        PgnSamples::new(PgnReader::new(reader), config)
+    }
+}
+
+#[hotpath::measure_all]
+impl<I: Iterator<Item = Result<PgnGame, PgnError>>> PgnSamples<I> {
+    pub fn new(games: I, config: PgnSampleConfig) -> Self {
+        PgnSamples {
+            games,
+            config,
+            pending: VecDeque::new(),
+            games_read: 0,
+            skipped_games: 0,
+        }
+    }
+
+    /// Games read so far, skipped ones included.
+    pub fn games_read(&self) -> usize {
+        self.games_read
+    }
+
+    pub fn skipped_games(&self) -> usize {
+        self.skipped_games
+    }
+
+    /// Replay `game`, queueing the samples of the positions kept. Returns
+    /// false, queueing nothing, if the game can't be used.
+    fn queue_game(&mut self, pgn: &PgnGame) -> bool {
+        let winner = match pgn.result {
+            PgnResult::WhiteWin => Some(Color::White),
+            PgnResult::BlackWin => Some(Color::Black),
+            PgnResult::Draw => None,
+            PgnResult::Unknown => return false,
+        };
+        let Ok(mut game) = pgn.starting_game() else {
+            return false;
+        };
+        if self.config.encoder.check_game(&game).is_err() {
+            return false;
+        }
+
+        let every_nth = self.config.every_nth.max(1);
+        let mut samples = Vec::new();
+        for (ply, mv) in pgn.moves.iter().enumerate() {
+            let kept = ply >= self.config.skip_plies
+                && (ply - self.config.skip_plies).is_multiple_of(every_nth);
+            if kept {
+                let Some(action) = game.encode_action_with_config(mv, &self.config.encoder) else {
+                    return false;
+                };
+                let value = match winner {
+                    Some(winner) if winner == game.turn() => 1.0,
+                    Some(_) => -1.0,
+                    None => 0.0,
+                };
+                let (planes, ..) =
+                    encode::encode_game_planes_with_config(&mut game, &self.config.encoder);
+                samples.push(TrainingSample {
+                    planes,
+                    policy: vec![(action as u32, 1.0)],
+                    value,
+                });
+            }
+            if !game.make_move(mv) {
+                return false;
+            }
+        }
+        self.pending.extend(samples);
+        true
+    }
+}
+
+impl<I: Iterator<Item = Result<PgnGame, PgnError>>> Iterator for PgnSamples<I> {
+    type Item = TrainingSample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() {
+            let game = self.games.next()?;
+            self.games_read += 1;
+            let used = match game {
+                Ok(game) => self.queue_game(&game),
+                Err(_) => false,
+            };
+            if !used {
+                self.skipped_games += 1;
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+fn read_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes(bytes[..4].try_into().expect("read_u32: 4 bytes"))
+}
+
+fn read_f32(bytes: &[u8]) -> f32 {
+    f32::from_le_bytes(bytes[..4].try_into().expect("read_f32: 4 bytes"))
+}
+
+fn read_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes(bytes[..8].try_into().expect("read_u64: 8 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_support::play;
+
+    fn info(score_cp: Option<i32>, score_mate: Option<i32>) -> InfoLine {
+        InfoLine {
+            depth: Some(1),
+            score_cp,
+            score_mate,
+            nodes: None,
+            nps: None,
+            time_ms: None,
+            pv: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_info_value() {
+        let model = WdlModel::default();
+
+        // The deepest scored line wins
+        let lines = [info(Some(30), None), info(None, Some(-3)), info(None, None)];
+        assert_eq!(info_value(&lines, &model), Some(-1.0));
+        assert_eq!(info_value(&lines[..1], &model), Some(model.value(30)));
+        assert_eq!(info_value(&[info(None, None)], &model), None);
+    }
+
+    #[test]
+    fn test_sample_file_round_trip() {
+        let samples: Vec<TrainingSample> = (0..3)
+            .map(|i| TrainingSample {
+                planes: (0..2 * 3 * 4).map(|v| (v * (i + 1)) as f32).collect(),
+                policy: (0..i).map(|a| (a as u32 * 7, 0.25 * a as f32)).collect(),
+                value: i as f32 - 1.0,
+            })
+            .collect();
+
+        let mut writer = SampleWriter::new(Cursor::new(Vec::new()), 2, 3, 4).expect("header");
+        for sample in &samples {
+            writer.write(sample).expect("write");
+        }
+        let bad = TrainingSample {
+            planes: vec![0.0; 5],
+            ..TrainingSample::default()
+        };
+        assert!(writer.write(&bad).is_err());
+        assert_eq!(writer.len(), 3);
+        let bytes = writer.finish().expect("finish").into_inner();
+
+        let mut reader = SampleReader::new(Cursor::new(bytes.clone())).expect("valid file");
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.plane_shape(), (2, 3, 4));
+        assert_eq!(reader.get(2).expect("read"), samples[2]);
+        assert!(reader.get(3).is_err());
+        let read: Vec<TrainingSample> = reader.map(|s| s.expect("read")).collect();
+        assert_eq!(read, samples);
+
+        // Without the index the file can't be opened
+        let unfinished = bytes[..bytes.len() - 16 - 8 * 3].to_vec();
+        assert!(SampleReader::new(Cursor::new(unfinished)).is_err());
+        assert!(SampleReader::new(Cursor::new(b"not a sample file".to_vec())).is_err());
+    }
+
+    #[test]
+    fn test_sample_reader_rejects_corrupt_sizes() {
+        let sample = TrainingSample {
+            planes: vec![1.0; 2 * 3 * 4],
+            policy: vec![(5, 1.0)],
+            value: 0.5,
+        };
+        let mut writer = SampleWriter::new(Cursor::new(Vec::new()), 2, 3, 4).expect("header");
+        writer.write(&sample).expect("write");
+        writer.write(&sample).expect("write");
+        let bytes = writer.finish().expect("finish").into_inner();
+        let first = SAMPLE_HEADER_LEN as usize;
+
+        // A huge policy length fails instead of allocating it
+        let mut corrupt = bytes.clone();
+        corrupt[first + 4..first + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+        let mut reader = SampleReader::new(Cursor::new(corrupt)).expect("valid index");
+        assert!(reader.get(0).is_err());
+        assert_eq!(reader.get(1).expect("untouched"), sample);
+
+        // So does a plane shape bigger than the samples, or an empty one
+        for (field, value) in [(1, u32::MAX), (3, 0)] {
+            let mut corrupt = bytes.clone();
+            corrupt[8 + 4 * field..12 + 4 * field].copy_from_slice(&value.to_le_bytes());
+            assert!(SampleReader::new(Cursor::new(corrupt)).is_err());
+        }
+    }
+
+    /// A parsed game, built by hand since parsing needs the PGN grammar.
+    fn pgn_game(lans: &[&str], result: PgnResult) -> PgnGame {
+        let mut game = StandardGame::standard();
+        let moves = play(&mut game, lans);
+        PgnGame {
+            headers: Default::default(),
+            annotations: vec![Default::default(); moves.len()],
+            moves,
+            comments: Vec::new(),
+            result,
+            final_game: game,
+        }
+    }
+
+    #[test]
+    fn test_pgn_samples() {
+        let fools_mate = pgn_game(&["f2f3", "e7e5", "g2g4", "d8h4"], PgnResult::BlackWin);
+        let unfinished = pgn_game(&["e2e4"], PgnResult::Unknown);
+        let mut illegal = pgn_game(&["e2e4", "e7e5"], PgnResult::Draw);
+        illegal.moves.swap(0, 1);
+        let games = vec![
+            Ok(fools_mate.clone()),
+            Err(PgnError::ParseError("bad game".to_string())),
+            Ok(unfinished),
+            Ok(illegal),
+            Ok(pgn_game(&["e2e4", "e7e5"], PgnResult::Draw)),
+        ];
+
+        let mut samples = PgnSamples::new(games.clone().into_iter(), PgnSampleConfig::default());
+        let all: Vec<TrainingSample> = samples.by_ref().collect();
+        assert_eq!(samples.games_read(), 5);
+        assert_eq!(samples.skipped_games(), 3);
+        assert_eq!(all.len(), 4 + 2);
+
+        // Values follow the result from the side to move, policies the move
+        let values: Vec<f32> = all.iter().map(|s| s.value).collect();
+        assert_eq!(values, vec![-1.0, 1.0, -1.0, 1.0, 0.0, 0.0]);
+        let mut game = StandardGame::standard();
+        for (sample, mv) in all.iter().zip(&fools_mate.moves) {
+            let action = encode::encode_action(mv, 8, 8).expect("encodable") as u32;
+            assert_eq!(sample.policy, vec![(action, 1.0)]);
+            assert_eq!(sample.planes, encode_game_planes(&mut game).0);
+            assert!(game.make_move(mv));
+        }
+
+        // Skipping the book moves, then keeping every other position
+        let config = PgnSampleConfig {
+            every_nth: 2,
+            skip_plies: 1,
+            ..PgnSampleConfig::default()
+        };
+        let kept: Vec<TrainingSample> = PgnSamples::new(games.into_iter(), config).collect();
+        assert_eq!(kept.len(), 2 + 1);
+        assert_eq!(kept[0], all[1]);
+        assert_eq!(kept[1], all[3]);
+        assert_eq!(kept[2], all[5]);
+    }
+
+    #[test]
+    fn test_label_with_engine_missing_engine() {
+        let result = label_with_engine(
+            "/nonexistent/uci-engine",
+            &["rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"],
+            1,
+        );
+        assert!(matches!(result, Err(UciError::IoError(_))));
+    }
+}
@@ -0,0 +1,416 @@
+//! A game whose board size is chosen at runtime.
+//!
+//! `Game` takes its dimensions as const generics, so code that learns the
+//! board size from its input (a FEN, a config file, a Python caller) would
+//! otherwise need a match over every supported size. `DynGame` is that match,
+//! written once: one variant per supported size, with `Game`'s methods
+//! forwarded to whichever variant is held.
+
+use std::fmt;
+
+use crate::color::Color;
+use crate::encode::EncoderConfig;
+use crate::error::ChessError;
+use crate::game::{
+    CastlingRights, Game, GameObserver, GameRecord, MoveGenStats, MoveHistoryEntry, PieceCounts,
+    Transform, Violation,
+};
+use crate::r#move::{Move, MoveFlags};
+use crate::outcome::{DrawRules, GameOutcome, MoveList, RewardConfig, TurnState};
+use crate::pieces::{Piece, PieceType};
+use crate::position::Position;
+
+/// Generates the cartesian product of W and H ranges, then invokes $mac with all (W, H) pairs.
+macro_rules! cartesian_dispatch {
+    ($mac:ident, [$($w:literal),*], $hs:tt) => {
+        cartesian_dispatch!(@acc $mac, $hs, [] ; $($w),*);
+    };
+    // Base case: no more W values, invoke the target macro with accumulated pairs.
+    (@acc $mac:ident, $hs:tt, [$($pairs:tt)*] ; ) => {
+        $mac!($($pairs)*);
+    };
+    // Recursive case: peel off one W, expand all H for it, then continue.
+    (@acc $mac:ident, [$($h:literal),*], [$($pairs:tt)*] ; $w:literal $(, $rest:literal)*) => {
+        cartesian_dispatch!(@acc $mac, [$($h),*], [$($pairs)* $(($w, $h),)*] ; $($rest),*);
+    };
+}
+
+macro_rules! define_wh_dispatch {
+    ($(($w:literal, $h:literal)),* $(,)?) => {
+        paste::paste! {
+            /// A `Game` of any supported board size, e.g. `DynGame::W8H8` for
+            /// the standard board.
+            #[derive(Clone)]
+            pub enum DynGame {
+                $( [<W $w H $h>](Game<$w, $h>), )*
+            }
+
+            /// Evaluate `$body` with `$g` bound to the `Game` inside a
+            /// `DynGame`.
+            macro_rules! dispatch_game {
+                ($self_:expr, $g:ident => $body:expr) => {
+                    match $self_ {
+                        $( crate::dyn_game::DynGame::[<W $w H $h>]($g) => $body, )*
+                    }
+                };
+            }
+
+            $(
+                impl From<Game<$w, $h>> for DynGame {
+                    fn from(game: Game<$w, $h>) -> Self {
+                        DynGame::[<W $w H $h>](game)
+                    }
+                }
+            )*
+
+            impl DynGame {
+                /// Parse `fen` on a `width` x `height` board.
+                pub fn new(
+                    width: usize,
+                    height: usize,
+                    fen: &str,
+                    castling_enabled: bool,
+                ) -> Result<Self, ChessError> {
+                    match (width, height) {
+                        $( ($w, $h) => new_sized::<$w, $h>(fen, castling_enabled), )*
+                        _ => Err(unsupported_size(width, height)),
+                    }
+                }
+
+                /// Parse a UCI `position` command on a `width` x `height`
+                /// board, as `Game::from_uci_position_string`.
+                pub fn from_uci_position_string(
+                    width: usize,
+                    height: usize,
+                    position: &str,
+                ) -> Result<Self, ChessError> {
+                    match (width, height) {
+                        $( ($w, $h) => uci_position_sized::<$w, $h>(position), )*
+                        _ => Err(unsupported_size(width, height)),
+                    }
+                }
+
+                /// Rebuild a game from its record, on the board size it
+                /// names, as `Game::from_record`.
+                pub fn from_record(record: &GameRecord) -> Result<Self, ChessError> {
+                    match (record.width, record.height) {
+                        $( ($w, $h) => record_sized::<$w, $h>(record), )*
+                        _ => Err(unsupported_size(record.width, record.height)),
+                    }
+                }
+            }
+        }
+    }
+}
+
+cartesian_dispatch!(
+    define_wh_dispatch,
+    [6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16],
+    [6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16]
+);
+
+fn unsupported_size(width: usize, height: usize) -> ChessError {
+    ChessError::InvalidDimensions(format!("Unsupported board size: {}x{}", width, height))
+}
+
+// The constructors build each size out of line: a `Game` temporary in every
+// arm of a 121-way match makes a frame big enough to overflow a thread's
+// stack in debug builds.
+
+fn new_sized<const W: usize, const H: usize>(
+    fen: &str,
+    castling_enabled: bool,
+) -> Result<DynGame, ChessError>
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    Ok(Game::<W, H>::new(fen, castling_enabled)?.into())
+}
+
+fn uci_position_sized<const W: usize, const H: usize>(position: &str) -> Result<DynGame, ChessError>
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    Ok(Game::<W, H>::from_uci_position_string(position)?.into())
+}
+
+fn record_sized<const W: usize, const H: usize>(record: &GameRecord) -> Result<DynGame, ChessError>
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    Ok(Game::<W, H>::from_record(record)?.into())
+}
+
+fn canonical_form_sized<const W: usize, const H: usize>(game: &Game<W, H>) -> (DynGame, Transform)
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    let (canonical, transform) = game.canonical_form();
+    (canonical.into(), transform)
+}
+
+fn start_position_sized<const W: usize, const H: usize>(game: &Game<W, H>) -> DynGame
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    game.start_position().into()
+}
+
+fn mirror_sized<const W: usize, const H: usize>(game: &Game<W, H>) -> DynGame
+where
+    [(); (W * H).div_ceil(64)]:,
+    Game<W, H>: Into<DynGame>,
+{
+    game.mirror().into()
+}
+
+/// Forward each listed method to the `Game` held by `self`.
+macro_rules! delegate {
+    (&self; $($(#[$meta:meta])* fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&self, $($arg: $ty),*) $(-> $ret)? {
+                dispatch_game!(self, g => g.$name($($arg),*))
+            }
+        )*
+    };
+    (&mut self; $($(#[$meta:meta])* fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+        $(
+            $(#[$meta])*
+            pub fn $name(&mut self, $($arg: $ty),*) $(-> $ret)? {
+                dispatch_game!(self, g => g.$name($($arg),*))
+            }
+        )*
+    };
+}
+
+impl DynGame {
+    /// The standard 8x8 starting position.
+    pub fn standard() -> Self {
+        DynGame::W8H8(Game::standard())
+    }
+
+    /// The 10x8 Capablanca chess starting position.
+    pub fn capablanca() -> Self {
+        DynGame::W10H8(Game::capablanca())
+    }
+
+    /// The game as it was before any of its moves were played.
+    pub fn start_position(&self) -> Self {
+        dispatch_game!(self, g => start_position_sized(g))
+    }
+
+    /// The position with the colors swapped, as `Game::mirror`.
+    pub fn mirror(&self) -> Self {
+        dispatch_game!(self, g => mirror_sized(g))
+    }
+
+    /// The position in canonical orientation, and the transform that maps
+    /// this game onto it, as `Game::canonical_form`.
+    pub fn canonical_form(&self) -> (Self, Transform) {
+        dispatch_game!(self, g => canonical_form_sized(g))
+    }
+
+    /// The 8x8 game, if that's the size held.
+    pub fn as_standard(&self) -> Option<&Game<8, 8>> {
+        match self {
+            DynGame::W8H8(game) => Some(game),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to the 8x8 game, if that's the size held.
+    pub fn as_standard_mut(&mut self) -> Option<&mut Game<8, 8>> {
+        match self {
+            DynGame::W8H8(game) => Some(game),
+            _ => None,
+        }
+    }
+
+    delegate! {
+        &self;
+        fn width() -> usize;
+        fn height() -> usize;
+        fn turn() -> Color;
+        fn fullmove_number() -> u32;
+        fn halfmove_clock() -> u32;
+        fn move_count() -> usize;
+        fn ply() -> usize;
+        fn absolute_ply() -> usize;
+        fn last_move() -> Option<&Move>;
+        fn last_capture() -> Option<Piece>;
+        fn move_history() -> &[MoveHistoryEntry];
+        fn castling_enabled() -> bool;
+        fn castling_rights() -> &CastlingRights;
+        fn castling_king_cols() -> Option<(u8, u8)>;
+        fn double_push_row() -> Option<u8>;
+        fn self_capture_enabled() -> bool;
+        fn fairy_promotions_enabled() -> bool;
+        fn promotion_pieces() -> &'static [PieceType];
+        fn has_fairy_pieces() -> bool;
+        fn draw_rules() -> &DrawRules;
+        fn en_passant_square() -> Option<Position>;
+        fn piece_counts() -> &PieceCounts;
+        fn piece_count(color: Color, piece_type: PieceType) -> u32;
+        fn material(color: Color, values: impl Fn(PieceType) -> i32) -> i32;
+        fn has_observer() -> bool;
+        fn get_piece(pos: &Position) -> Option<Piece>;
+        fn pieces(color: Color) -> Vec<(Position, Piece)>;
+        fn threatened_pieces(color: Color) -> Vec<(Position, Piece)>;
+        fn attack_counts(color: Color) -> Vec<u8>;
+        fn is_check() -> bool;
+        fn is_insufficient_material() -> bool;
+        fn can_claim_fifty_moves() -> bool;
+        fn pseudo_legal_moves() -> MoveList;
+        fn infer_move_flags(src: &Position, dst: &Position, piece: &Piece) -> MoveFlags;
+        fn annotate_move(mv: &Move) -> Move;
+        fn see(mv: &Move) -> i32;
+        fn is_capture(mv: &Move) -> bool;
+        fn is_zeroing(mv: &Move) -> bool;
+        fn move_from_lan(lan: &str) -> Result<Move, ChessError>;
+        fn move_to_lan(mv: &Move) -> String;
+        fn encode_action(mv: &Move) -> Option<usize>;
+        fn encode_action_with_config(mv: &Move, config: &EncoderConfig) -> Option<usize>;
+        fn decode_action(action: usize) -> Option<Move>;
+        fn decode_action_with_config(action: usize, config: &EncoderConfig) -> Option<Move>;
+        fn to_uci_position_string() -> String;
+        fn to_record() -> GameRecord;
+        fn validate() -> Vec<Violation>;
+    }
+
+    delegate! {
+        &mut self;
+        fn to_fen() -> String;
+        fn to_json_state() -> String;
+        fn legal_moves() -> MoveList;
+        fn legal_moves_into(moves: &mut MoveList);
+        fn legal_moves_sorted() -> MoveList;
+        fn legal_moves_san() -> Vec<String>;
+        fn legal_moves_for_position(src: &Position) -> MoveList;
+        fn capture_moves() -> MoveList;
+        fn evasion_moves() -> MoveList;
+        fn captures() -> MoveList;
+        fn checks() -> MoveList;
+        fn legal_moves_with_checks() -> MoveList;
+        fn gives_check(mv: &Move) -> bool;
+        fn has_any_legal_move() -> bool;
+        fn has_legal_en_passant() -> bool;
+        fn is_legal_move(mv: &Move) -> bool;
+        fn filter_legal(moves: &[Move]) -> Vec<bool>;
+        fn filter_legal_actions(actions: &[usize]) -> Vec<bool>;
+        fn is_legal_action_with_config(action: usize, config: &EncoderConfig) -> bool;
+        fn make_move(mv: &Move) -> bool;
+        fn make_move_unchecked(mv: &Move);
+        fn make_null_move() -> bool;
+        fn unmake_move() -> bool;
+        fn truncate_to_ply(ply: usize) -> bool;
+        fn move_from_san(san: &str) -> Result<Move, ChessError>;
+        fn move_to_san(mv: &Move) -> String;
+        fn apply_action(action: usize) -> bool;
+        fn apply_action_with_config(action: usize, config: &EncoderConfig) -> bool;
+        fn is_checkmate() -> bool;
+        fn is_stalemate() -> bool;
+        fn is_over() -> bool;
+        fn is_threefold_repetition() -> bool;
+        fn can_claim_threefold() -> bool;
+        fn can_claim_draw() -> bool;
+        fn claim_draw() -> Option<GameOutcome>;
+        fn pgn_result() -> &'static str;
+        fn resign(color: Color) -> bool;
+        fn agree_draw() -> bool;
+        fn adjudicate(outcome: GameOutcome) -> bool;
+        fn is_fivefold_repetition() -> bool;
+        fn repetition_count() -> usize;
+        fn position_key() -> u64;
+        fn outcome() -> Option<GameOutcome>;
+        fn reward(perspective: Color, config: &RewardConfig) -> Option<f32>;
+        fn turn_state() -> TurnState;
+        fn perft(depth: u32) -> u64;
+        fn perft_divide(depth: u32) -> Vec<(Move, u64)>;
+        fn random_playout(rng_seed: u64, max_plies: usize) -> Option<GameOutcome>;
+        fn move_gen_stats() -> MoveGenStats;
+        fn set_piece(pos: &Position, piece: Option<Piece>);
+        fn set_turn(turn: Color) -> Result<(), ChessError>;
+        fn set_castling_rights(color: Color, kingside: bool, queenside: bool) -> Result<(), ChessError>;
+        fn set_en_passant(square: Option<Position>) -> Result<(), ChessError>;
+        fn clear_board();
+        fn remove_all(piece_type: PieceType, color: Color) -> Result<usize, ChessError>;
+        fn shift_board(dx: i32, dy: i32) -> Result<(), ChessError>;
+        fn set_castling_king_cols(cols: Option<(u8, u8)>) -> Result<(), ChessError>;
+        fn set_double_push_row(row: Option<u8>) -> Result<(), ChessError>;
+        fn set_self_capture(enabled: bool);
+        fn set_fairy_promotions(enabled: bool);
+        fn set_draw_rules(rules: DrawRules) -> Result<(), ChessError>;
+        fn sync_piece_counts();
+        fn set_observer(observer: Box<dyn GameObserver>);
+        fn take_observer() -> Option<Box<dyn GameObserver>>;
+    }
+}
+
+impl fmt::Display for DynGame {
+    /// As `Game`: `{}` prints a diagram of the board, `{:#}` the FEN.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        dispatch_game!(self, g => fmt::Display::fmt(g, f))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for DynGame {
+    /// Serializes as a `GameRecord`, like `Game`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_record().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for DynGame {
+    /// Deserializes a `GameRecord` of any supported board size.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GameRecord::deserialize(deserializer)?;
+        DynGame::from_record(&record).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_game_sizes() {
+        let mut game = DynGame::new(10, 8, "4k5/10/10/10/10/10/P9/4K5 w - - 0 1", false)
+            .expect("10x8 FEN should parse");
+        assert!(matches!(game, DynGame::W10H8(_)));
+        assert_eq!((game.width(), game.height()), (10, 8));
+
+        let mv = game.move_from_lan("a2a4").expect("a2a4 parses");
+        assert!(game.make_move(&mv));
+        assert_eq!(game.turn(), Color::Black);
+        assert_eq!(game.start_position().move_count(), 0);
+        let record = game.to_record();
+        assert_eq!((record.width, record.height), (10, 8));
+        let rebuilt = DynGame::from_record(&record).expect("record replays");
+        assert!(matches!(rebuilt, DynGame::W10H8(_)));
+        assert_eq!(rebuilt.move_count(), 1);
+        assert!(game.unmake_move());
+
+        assert!(matches!(
+            DynGame::new(5, 8, "8/8/8/8/8/8/8/8 w - - 0 1", false),
+            Err(ChessError::InvalidDimensions(_))
+        ));
+    }
+
+    #[test]
+    fn test_dyn_game_matches_game() {
+        let mut dynamic = DynGame::standard();
+        let mut game = Game::<8, 8>::standard();
+        assert_eq!(dynamic.legal_moves(), game.legal_moves());
+        assert_eq!(dynamic.perft(3), game.perft(3));
+        assert_eq!(dynamic.to_fen(), game.to_fen());
+        assert!(DynGame::from(game).as_standard().is_some());
+    }
+}
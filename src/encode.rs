@@ -1,8 +1,10 @@
 use crate::color::Color;
 use crate::directions::{KNIGHT_DELTAS, direction_index};
+use crate::error::ChessError;
 use crate::game::Game;
 use crate::r#move::Move;
 use crate::pieces::PieceType;
+use rayon::prelude::*;
 
 /// Number of planes for piece positions (6 for WHITE + 6 for BLACK)
 pub const PIECE_PLANES: usize = 6 + 6;
@@ -31,6 +33,430 @@ pub const NUM_UNDERPROMO_PIECES: usize = 3;
 /// Number of promotion move directions (forward, backward)
 pub const NUM_PROMOTION_ORIENTATIONS: usize = 2;
 
+/// Number of fairy promotion piece types (archbishop, chancellor)
+pub const NUM_FAIRY_PROMO_PIECES: usize = 2;
+
+/// Number of optional move planes for promotions to fairy pieces (3
+/// directions × 2 piece types × 2 orientations)
+pub const FAIRY_PROMOTION_PLANES: usize =
+    NUM_UNDERPROMO_DIRECTIONS * NUM_FAIRY_PROMO_PIECES * NUM_PROMOTION_ORIENTATIONS;
+
+/// Number of optional last-move planes per history step (from-square + to-square)
+pub const LAST_MOVE_PLANES: usize = 2;
+
+/// Number of optional validity-mask planes added by padded encoding
+pub const MASK_PLANES: usize = 1;
+
+/// Number of optional fairy-piece planes per history step (archbishop +
+/// chancellor, for each side)
+pub const FAIRY_PIECE_PLANES: usize = 2 + 2;
+
+/// Number of optional repetition planes per history step (seen once before +
+/// seen twice before)
+pub const REPETITION_PLANES: usize = 2;
+
+/// Optional extensions to the input plane layout and the action space.
+///
+/// The default configuration produces exactly `TOTAL_INPUT_PLANES` planes and
+/// `get_total_actions` actions. Enabled extensions are appended after the base
+/// layout, so it is the same regardless of configuration, apart from the
+/// number of history steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EncoderConfig {
+    /// Number of positions encoded, the current one included, each with
+    /// `PIECE_PLANES` planes (and last-move and fairy-piece planes, if
+    /// enabled). At least 1;
+    /// `HISTORY_LENGTH` by default. Small boards can use fewer steps to cut
+    /// the input size.
+    pub history_length: usize,
+    /// Add `LAST_MOVE_PLANES` planes per history step, marking the source and
+    /// destination squares of the move that led to that position.
+    pub last_move_planes: bool,
+    /// Add `FAIRY_PIECE_PLANES` planes per history step for archbishops and
+    /// chancellors, which the piece planes leave out, and
+    /// `FAIRY_PROMOTION_PLANES` move planes after the others for promotions
+    /// to them. Needed for Capablanca chess and other variants with fairy
+    /// pieces.
+    pub fairy_piece_planes: bool,
+    /// Add `REPETITION_PLANES` planes per history step, set if that position
+    /// had occurred once and twice before, as in AlphaZero. The constant
+    /// repetition planes of the base layout only cover the current position.
+    pub repetition_planes: bool,
+    /// Reserve one action index after the move actions for passing, mapped to
+    /// the null move.
+    pub pass_action: bool,
+    /// Embed the board in the bottom-left corner of a fixed `size` x `size`
+    /// canvas, so boards of different sizes share one input shape and action
+    /// space. Adds `MASK_PLANES` planes (after any other optional planes)
+    /// marking the squares that are on the board, and lays out actions as if
+    /// the board were `size` x `size`.
+    pub padded_size: Option<usize>,
+    /// Encode from the side to move's point of view: when black is to move,
+    /// the rows of every plane are reversed so that the side to move always
+    /// advances up the board, and `Game::encode_action_with_config` and
+    /// `Game::decode_action_with_config` mirror actions to match.
+    pub flip_perspective: bool,
+}
+
+impl Default for EncoderConfig {
+    fn default() -> Self {
+        EncoderConfig {
+            history_length: HISTORY_LENGTH,
+            last_move_planes: false,
+            fairy_piece_planes: false,
+            repetition_planes: false,
+            pass_action: false,
+            padded_size: None,
+            flip_perspective: false,
+        }
+    }
+}
+
+impl EncoderConfig {
+    /// Total number of input planes produced with this configuration.
+    pub fn num_planes(&self) -> usize {
+        let mut planes = self.base_planes();
+        if self.last_move_planes {
+            planes += self.history_length * LAST_MOVE_PLANES;
+        }
+        if self.fairy_piece_planes {
+            planes += self.history_length * FAIRY_PIECE_PLANES;
+        }
+        if self.repetition_planes {
+            planes += self.history_length * REPETITION_PLANES;
+        }
+        if self.padded_size.is_some() {
+            planes += MASK_PLANES;
+        }
+        planes
+    }
+
+    /// Number of history and constant planes, before any optional planes.
+    fn base_planes(&self) -> usize {
+        self.history_length * PIECE_PLANES + CONSTANT_PLANES
+    }
+
+    /// Check that a `width` x `height` board can be encoded with this
+    /// configuration, i.e. that it fits on the padded canvas (and that the
+    /// history length is at least 1).
+    pub fn check_board(&self, width: usize, height: usize) -> Result<(), ChessError> {
+        if self.history_length == 0 {
+            return Err(ChessError::InvalidDimensions(
+                "history_length must be at least 1".to_string(),
+            ));
+        }
+        match self.padded_size {
+            Some(size) if width > size || height > size => {
+                Err(ChessError::InvalidDimensions(format!(
+                    "A {}x{} board doesn't fit in a {}x{} padded encoding",
+                    width, height, size, size
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that `game` can be encoded with this configuration: its board
+    /// passes `check_board`, and if it has archbishops or chancellors (see
+    /// `Game::has_fairy_pieces`), `fairy_piece_planes` is set so that they
+    /// aren't left out of the planes.
+    pub fn check_game<const W: usize, const H: usize>(
+        &self,
+        game: &Game<W, H>,
+    ) -> Result<(), ChessError>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_board(W, H)?;
+        if game.has_fairy_pieces() && !self.fairy_piece_planes {
+            return Err(ChessError::InvalidRules(
+                "Games with fairy pieces need fairy_piece_planes in the encoder configuration"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// (height, width) of the encoded planes for a `width` x `height` board.
+    pub fn plane_shape(&self, width: usize, height: usize) -> (usize, usize) {
+        match self.padded_size {
+            Some(size) => (size, size),
+            None => (height, width),
+        }
+    }
+
+    /// Number of move planes with this configuration, for a `width` x
+    /// `height` board.
+    pub fn move_planes_count(&self, width: usize, height: usize) -> usize {
+        let (rows, cols) = self.plane_shape(width, height);
+        get_move_planes_count(cols, rows)
+            + if self.fairy_piece_planes {
+                FAIRY_PROMOTION_PLANES
+            } else {
+                0
+            }
+    }
+
+    /// Total number of action indices with this configuration.
+    pub fn total_actions(&self, width: usize, height: usize) -> usize {
+        let (rows, cols) = self.plane_shape(width, height);
+        self.move_planes_count(width, height) * cols * rows + usize::from(self.pass_action)
+    }
+
+    /// The action index reserved for passing, if enabled.
+    pub fn pass_action_index(&self, width: usize, height: usize) -> Option<usize> {
+        let (rows, cols) = self.plane_shape(width, height);
+        self.pass_action
+            .then(|| self.move_planes_count(width, height) * cols * rows)
+    }
+}
+
+/// How the values of an input plane are produced.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlaneNormalization {
+    /// 1 on the marked squares, 0 elsewhere.
+    Binary,
+    /// The same value, 0 or 1, on every square.
+    Constant,
+    /// The same value on every square: a count divided by the scale.
+    Scaled(f32),
+}
+
+/// Description of one input plane, as returned by `EncoderConfig::schema`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlaneSchema {
+    pub index: usize,
+    pub name: String,
+    pub description: String,
+    pub normalization: PlaneNormalization,
+}
+
+/// Description of a contiguous block of the action space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ActionBlockSchema {
+    pub name: String,
+    pub description: String,
+    /// First action index in the block.
+    pub start: usize,
+    /// Number of action indices in the block.
+    pub len: usize,
+}
+
+/// Layout of the input planes and the action space for one board size and
+/// encoder configuration.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncoderSchema {
+    /// (height, width) of every plane.
+    pub plane_shape: (usize, usize),
+    /// Every input plane, in order.
+    pub planes: Vec<PlaneSchema>,
+    /// Every action block, in order. Move blocks cover `board_size` action
+    /// indices per move plane: `plane * board_size + src_index`.
+    pub actions: Vec<ActionBlockSchema>,
+}
+
+impl EncoderConfig {
+    /// Describe every input plane and action block produced with this
+    /// configuration for a `width` x `height` board.
+    pub fn schema(&self, width: usize, height: usize) -> EncoderSchema {
+        let mut planes = Vec::with_capacity(self.num_planes());
+        let mut plane = |name: String, description: String, normalization| {
+            planes.push(PlaneSchema {
+                index: planes.len(),
+                name,
+                description,
+                normalization,
+            });
+        };
+
+        for t in 0..self.history_length {
+            for (side, owner) in [("own", "side to move"), ("opponent", "opponent")] {
+                for pt in PieceType::STANDARD {
+                    plane(
+                        format!("t{}_{}_{}", t, side, pt.name()),
+                        format!(
+                            "Squares holding a {} of the {}, {} plies ago",
+                            pt.name(),
+                            owner,
+                            t
+                        ),
+                        PlaneNormalization::Binary,
+                    );
+                }
+            }
+        }
+
+        plane(
+            "repetition_1".to_string(),
+            "1 if the current position has occurred before".to_string(),
+            PlaneNormalization::Constant,
+        );
+        plane(
+            "repetition_2".to_string(),
+            "1 if the current position has occurred at least twice before".to_string(),
+            PlaneNormalization::Constant,
+        );
+        plane(
+            "color".to_string(),
+            "1 if white is to move, 0 if black is".to_string(),
+            PlaneNormalization::Constant,
+        );
+        plane(
+            "move_count".to_string(),
+            "Fullmove number".to_string(),
+            PlaneNormalization::Scaled(FULLMOVE_SCALE),
+        );
+        for (side, owner) in [("own", "side to move"), ("opponent", "opponent")] {
+            for castle in ["kingside", "queenside"] {
+                plane(
+                    format!("{}_{}_castling", side, castle),
+                    format!("1 if the {} can castle {}", owner, castle),
+                    PlaneNormalization::Constant,
+                );
+            }
+        }
+        plane(
+            "no_progress".to_string(),
+            "Halfmove clock (plies since the last capture or pawn move)".to_string(),
+            PlaneNormalization::Scaled(HALFMOVE_SCALE),
+        );
+
+        if self.last_move_planes {
+            for t in 0..self.history_length {
+                plane(
+                    format!("t{}_last_move_from", t),
+                    format!(
+                        "Source square of the move that led to the position {} plies ago",
+                        t
+                    ),
+                    PlaneNormalization::Binary,
+                );
+                plane(
+                    format!("t{}_last_move_to", t),
+                    format!(
+                        "Destination square of the move that led to the position {} plies ago",
+                        t
+                    ),
+                    PlaneNormalization::Binary,
+                );
+            }
+        }
+        if self.fairy_piece_planes {
+            for t in 0..self.history_length {
+                for (side, owner) in [("own", "side to move"), ("opponent", "opponent")] {
+                    for pt in PieceType::FAIRY {
+                        plane(
+                            format!("t{}_{}_{}", t, side, pt.name()),
+                            format!(
+                                "Squares holding a {} of the {}, {} plies ago",
+                                pt.name(),
+                                owner,
+                                t
+                            ),
+                            PlaneNormalization::Binary,
+                        );
+                    }
+                }
+            }
+        }
+        if self.repetition_planes {
+            for t in 0..self.history_length {
+                plane(
+                    format!("t{}_repetition_1", t),
+                    format!("1 if the position {} plies ago had occurred before", t),
+                    PlaneNormalization::Constant,
+                );
+                plane(
+                    format!("t{}_repetition_2", t),
+                    format!(
+                        "1 if the position {} plies ago had occurred at least twice before",
+                        t
+                    ),
+                    PlaneNormalization::Constant,
+                );
+            }
+        }
+        if self.padded_size.is_some() {
+            plane(
+                "valid_square".to_string(),
+                "1 on squares of the board, 0 on padding".to_string(),
+                PlaneNormalization::Binary,
+            );
+        }
+
+        let plane_shape = self.plane_shape(width, height);
+        EncoderSchema {
+            plane_shape,
+            planes,
+            actions: self.action_blocks(plane_shape.1, plane_shape.0),
+        }
+    }
+
+    /// Action blocks for moves laid out on a `cols` x `rows` board.
+    fn action_blocks(&self, cols: usize, rows: usize) -> Vec<ActionBlockSchema> {
+        let board_size = cols * rows;
+        let max_distance = cols.max(rows) - 1;
+        let mut blocks = Vec::new();
+        let mut start = 0;
+        let mut block = |name: String, description: String, len: usize| {
+            blocks.push(ActionBlockSchema {
+                name,
+                description,
+                start,
+                len,
+            });
+            start += len;
+        };
+
+        for direction in ["n", "ne", "e", "se", "s", "sw", "w", "nw"] {
+            block(
+                format!("slide_{}", direction),
+                format!(
+                    "Moves towards {} by 1 to {} squares (queen promotions included), one plane per distance",
+                    direction.to_uppercase(),
+                    max_distance
+                ),
+                max_distance * board_size,
+            );
+        }
+        block(
+            "knight".to_string(),
+            "Knight moves, one plane per jump".to_string(),
+            NUM_KNIGHT_DELTAS * board_size,
+        );
+        for orientation in ["forward", "backward"] {
+            block(
+                format!("underpromotion_{}", orientation),
+                format!(
+                    "Promotions to knight, bishop or rook moving {} one row, one plane per direction (left, straight, right) and piece",
+                    orientation
+                ),
+                NUM_UNDERPROMO_DIRECTIONS * NUM_UNDERPROMO_PIECES * board_size,
+            );
+        }
+        if self.fairy_piece_planes {
+            for orientation in ["forward", "backward"] {
+                block(
+                    format!("fairy_promotion_{}", orientation),
+                    format!(
+                        "Promotions to archbishop or chancellor moving {} one row, one plane per direction (left, straight, right) and piece",
+                        orientation
+                    ),
+                    NUM_UNDERPROMO_DIRECTIONS * NUM_FAIRY_PROMO_PIECES * board_size,
+                );
+            }
+        }
+        if self.pass_action {
+            block(
+                "pass".to_string(),
+                "Pass the turn (the null move)".to_string(),
+                1,
+            );
+        }
+        blocks
+    }
+}
+
 /// Normalization divisor for fullmove number in the NN input planes.
 const FULLMOVE_SCALE: f32 = 100.0;
 
@@ -46,34 +472,93 @@ pub fn encode_game_planes<const W: usize, const H: usize>(
 where
     [(); (W * H).div_ceil(64)]:,
 {
-    let num_planes = TOTAL_INPUT_PLANES;
+    encode_game_planes_with_config(game, &EncoderConfig::default())
+}
+
+/// Like `encode_game_planes`, with the optional planes selected by `config`.
+///
+/// Panics if the game can't be encoded with `config` (see
+/// `EncoderConfig::check_game`), e.g. if the board doesn't fit the padded size.
+#[hotpath::measure]
+pub fn encode_game_planes_with_config<const W: usize, const H: usize>(
+    game: &mut Game<W, H>,
+    config: &EncoderConfig,
+) -> (Vec<f32>, usize, usize, usize)
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    if let Err(e) = config.check_game(game) {
+        panic!("encode_game_planes_with_config: {}", e);
+    }
+    let num_planes = config.num_planes();
+    let board_planes = num_planes
+        - if config.padded_size.is_some() {
+            MASK_PLANES
+        } else {
+            0
+        };
     let board_size = H * W;
-    let total_size = num_planes * board_size;
+    let total_size = board_planes * board_size;
     let mut data = vec![0.0f32; total_size];
 
     let perspective = game.turn();
     let opponent = perspective.opposite();
 
     let history_len = game.move_count();
-    let steps_back = (HISTORY_LENGTH - 1).min(history_len);
+    let steps_back = (config.history_length - 1).min(history_len);
 
     let moves_to_replay: Vec<Move> = game.move_history()[(history_len - steps_back)..]
         .iter()
         .map(|e| e.mv)
         .collect();
 
+    let fairy_start = config.base_planes()
+        + if config.last_move_planes {
+            config.history_length * LAST_MOVE_PLANES
+        } else {
+            0
+        };
+    let repetition_start = fairy_start
+        + if config.fairy_piece_planes {
+            config.history_length * FAIRY_PIECE_PLANES
+        } else {
+            0
+        };
+
     // T=0: current position
     fill_chess_planes::<W, H>(&mut data, game, perspective, 0);
+    if config.fairy_piece_planes {
+        fill_fairy_planes::<W, H>(&mut data, game, perspective, fairy_start);
+    }
+    if config.repetition_planes {
+        fill_repetition_planes(&mut data, game, repetition_start, board_size);
+    }
 
     // T=1..steps_back: walk backward through history
     for t in 1..=steps_back {
-        game.unmake_move();
+        game.unmake_move_silent();
         fill_chess_planes::<W, H>(&mut data, game, perspective, t);
+        if config.fairy_piece_planes {
+            fill_fairy_planes::<W, H>(
+                &mut data,
+                game,
+                perspective,
+                fairy_start + t * FAIRY_PIECE_PLANES,
+            );
+        }
+        if config.repetition_planes {
+            fill_repetition_planes(
+                &mut data,
+                game,
+                repetition_start + t * REPETITION_PLANES,
+                board_size,
+            );
+        }
     }
 
     // Replay saved moves to restore game state
     for mv in &moves_to_replay {
-        game.make_move_unchecked(mv);
+        game.make_move_silent(mv);
     }
 
     debug_assert_eq!(
@@ -85,8 +570,7 @@ where
     );
 
     // Constant plane layout (relative to constant_start):
-    const PLANE_REPETITION_1: usize = 0;
-    const PLANE_REPETITION_2: usize = 1;
+    const PLANE_REPETITIONS: usize = 0; // Seen once before, then seen twice before
     const PLANE_COLOR: usize = 2;
     const PLANE_MOVE_COUNT: usize = 3;
     const PLANE_P1_KINGSIDE: usize = 4;
@@ -95,10 +579,15 @@ where
     const PLANE_P2_QUEENSIDE: usize = 7;
     const PLANE_NO_PROGRESS: usize = 8;
 
-    let constant_start = HISTORY_LENGTH * PIECE_PLANES;
+    let constant_start = config.history_length * PIECE_PLANES;
 
-    // Repetition count planes - zeros for now (PLANE_REPETITION_1, PLANE_REPETITION_2)
-    let _ = (PLANE_REPETITION_1, PLANE_REPETITION_2);
+    // Repetition planes: whether the position has been seen once and twice before
+    fill_repetition_planes(
+        &mut data,
+        game,
+        constant_start + PLANE_REPETITIONS,
+        board_size,
+    );
 
     // Color plane
     let color_value = if perspective == Color::White {
@@ -182,7 +671,153 @@ where
         board_size,
     );
 
-    (data, num_planes, H, W)
+    // Last-move planes: for each history step, the move that led to that position
+    if config.last_move_planes {
+        let last_move_start = config.base_planes();
+        let history = game.move_history();
+        for (t, entry) in history.iter().rev().take(config.history_length).enumerate() {
+            // A pass leaves both planes empty
+            if entry.mv.is_null() {
+                continue;
+            }
+            let src_offset = (last_move_start + t * LAST_MOVE_PLANES) * board_size;
+            let dst_offset = src_offset + board_size;
+            data[src_offset + entry.mv.src.to_index(W)] = 1.0;
+            data[dst_offset + entry.mv.dst.to_index(W)] = 1.0;
+        }
+    }
+
+    if config.flip_perspective && perspective == Color::Black {
+        flip_rows(&mut data, board_planes, W, H);
+    }
+
+    match config.padded_size {
+        Some(size) => (
+            pad_planes(&data, board_planes, W, H, size),
+            num_planes,
+            size,
+            size,
+        ),
+        None => (data, num_planes, H, W),
+    }
+}
+
+/// Encode `games` with `encode_game_planes_with_config` into one contiguous
+/// buffer of shape (games.len(), num_planes, height, width), ready to be
+/// handed to the GPU as a batch. Returns (flat_data, num_planes, height,
+/// width). With `parallel`, the games are split between the available cores.
+///
+/// Panics if the board doesn't fit the padded size (see
+/// `EncoderConfig::check_board`).
+#[hotpath::measure]
+pub fn encode_games_batch<const W: usize, const H: usize>(
+    games: &mut [Game<W, H>],
+    config: &EncoderConfig,
+    parallel: bool,
+) -> (Vec<f32>, usize, usize, usize)
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    if let Err(e) = config.check_board(W, H) {
+        panic!("encode_games_batch: {}", e);
+    }
+    let num_planes = config.num_planes();
+    let (height, width) = config.plane_shape(W, H);
+    let mut data = vec![0.0f32; games.len() * num_planes * height * width];
+    fill_batch(games, &mut data, parallel, |game, out| {
+        out.copy_from_slice(&encode_game_planes_with_config(game, config).0);
+    });
+    (data, num_planes, height, width)
+}
+
+/// Fill `data`, split into one equal slot per item, by calling `fill` on each
+/// item and its slot; spread over rayon's global thread pool if `parallel`.
+pub(crate) fn fill_batch<T: Send, V: Send>(
+    items: &mut [T],
+    data: &mut [V],
+    parallel: bool,
+    fill: impl Fn(&mut T, &mut [V]) + Sync,
+) {
+    if items.is_empty() {
+        return;
+    }
+    let slot = data.len() / items.len();
+    debug_assert_eq!(slot * items.len(), data.len(), "fill_batch: uneven slots");
+    if slot == 0 {
+        for item in items {
+            fill(item, &mut []);
+        }
+    } else if parallel && items.len() > 1 {
+        items
+            .par_iter_mut()
+            .zip(data.par_chunks_exact_mut(slot))
+            .for_each(|(item, out)| fill(item, out));
+    } else {
+        for (item, out) in items.iter_mut().zip(data.chunks_exact_mut(slot)) {
+            fill(item, out);
+        }
+    }
+}
+
+/// Encode just the pieces of the current position as `PIECE_PLANES` one-hot
+/// planes: white pawn, knight, bishop, rook, queen and king, then the same for
+/// black. Games with archbishops or chancellors (see `Game::has_fairy_pieces`)
+/// get `FAIRY_PIECE_PLANES` more: white archbishop and chancellor, then black.
+/// Unlike `encode_game_planes` there is no history, no constant planes and no
+/// perspective flip.
+///
+/// Returns (flat_data, num_planes, height, width), where flat_data is in
+/// row-major order with row 0 first.
+#[hotpath::measure]
+pub fn encode_board_planes<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+) -> (Vec<f32>, usize, usize, usize)
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let planes = if game.has_fairy_pieces() {
+        PIECE_PLANES + FAIRY_PIECE_PLANES
+    } else {
+        PIECE_PLANES
+    };
+    let mut data = vec![0.0f32; planes * W * H];
+    fill_chess_planes::<W, H>(&mut data, game, Color::White, 0);
+    if planes > PIECE_PLANES {
+        fill_fairy_planes::<W, H>(&mut data, game, Color::White, PIECE_PLANES);
+    }
+    (data, planes, H, W)
+}
+
+/// Reverse the rows of each of `planes` planes of a `width` x `height` board.
+#[hotpath::measure]
+fn flip_rows(data: &mut [f32], planes: usize, width: usize, height: usize) {
+    for plane in data.chunks_exact_mut(width * height).take(planes) {
+        for row in 0..height / 2 {
+            let (top, bottom) = plane.split_at_mut((height - 1 - row) * width);
+            top[row * width..(row + 1) * width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
+}
+
+/// Copy `planes` planes of a `width` x `height` board into the bottom-left
+/// corner of `size` x `size` planes, followed by the validity mask.
+#[hotpath::measure]
+fn pad_planes(data: &[f32], planes: usize, width: usize, height: usize, size: usize) -> Vec<f32> {
+    let canvas_size = size * size;
+    let mut padded = vec![0.0f32; (planes + MASK_PLANES) * canvas_size];
+    for plane in 0..planes {
+        for row in 0..height {
+            let src = (plane * height + row) * width;
+            let dst = plane * canvas_size + row * size;
+            padded[dst..dst + width].copy_from_slice(&data[src..src + width]);
+        }
+    }
+    let mask_start = planes * canvas_size;
+    for row in 0..height {
+        let start = mask_start + row * size;
+        padded[start..start + width].fill(1.0);
+    }
+    padded
 }
 
 #[hotpath::measure]
@@ -191,20 +826,23 @@ fn fill_constant_plane(data: &mut [f32], plane: usize, value: f32, board_size: u
     data[offset..offset + board_size].fill(value);
 }
 
+/// Plane of `pt` among a side's piece planes, or None for fairy pieces,
+/// which have planes of their own.
 #[inline]
-fn piece_type_plane_index(pt: PieceType) -> usize {
+fn piece_type_plane_index(pt: PieceType) -> Option<usize> {
     match pt {
-        PieceType::Pawn => 0,
-        PieceType::Knight => 1,
-        PieceType::Bishop => 2,
-        PieceType::Rook => 3,
-        PieceType::Queen => 4,
-        PieceType::King => 5,
+        PieceType::Pawn => Some(0),
+        PieceType::Knight => Some(1),
+        PieceType::Bishop => Some(2),
+        PieceType::Rook => Some(3),
+        PieceType::Queen => Some(4),
+        PieceType::King => Some(5),
+        PieceType::Archbishop | PieceType::Chancellor => None,
     }
 }
 
 #[hotpath::measure]
-fn fill_chess_planes<const W: usize, const H: usize>(
+pub(crate) fn fill_chess_planes<const W: usize, const H: usize>(
     data: &mut [f32],
     game: &Game<W, H>,
     perspective: Color,
@@ -214,15 +852,17 @@ fn fill_chess_planes<const W: usize, const H: usize>(
 {
     let board_size = H * W;
     debug_assert!(
-        t < HISTORY_LENGTH,
-        "history timestep t={} exceeds HISTORY_LENGTH={}",
+        (t + 1) * PIECE_PLANES * board_size <= data.len(),
+        "history timestep t={} doesn't fit in {} values",
         t,
-        HISTORY_LENGTH,
+        data.len(),
     );
     let base_plane = t * PIECE_PLANES;
 
     for (pos, piece) in game.pieces_iter(perspective) {
-        let plane_idx = piece_type_plane_index(piece.piece_type);
+        let Some(plane_idx) = piece_type_plane_index(piece.piece_type) else {
+            continue;
+        };
         let offset = (base_plane + plane_idx) * board_size;
         let idx = pos.to_index(W);
         debug_assert!(
@@ -235,7 +875,9 @@ fn fill_chess_planes<const W: usize, const H: usize>(
     }
 
     for (pos, piece) in game.pieces_iter(perspective.opposite()) {
-        let plane_idx = piece_type_plane_index(piece.piece_type);
+        let Some(plane_idx) = piece_type_plane_index(piece.piece_type) else {
+            continue;
+        };
         let offset = (base_plane + 6 + plane_idx) * board_size;
         let idx = pos.to_index(W);
         debug_assert!(
@@ -248,6 +890,54 @@ fn fill_chess_planes<const W: usize, const H: usize>(
     }
 }
 
+/// Mark the archbishops and chancellors of `perspective`, then of the
+/// opponent, on the `FAIRY_PIECE_PLANES` planes starting at `start_plane`.
+#[hotpath::measure]
+fn fill_fairy_planes<const W: usize, const H: usize>(
+    data: &mut [f32],
+    game: &Game<W, H>,
+    perspective: Color,
+    start_plane: usize,
+) where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let board_size = H * W;
+    for (side, color) in [perspective, perspective.opposite()]
+        .into_iter()
+        .enumerate()
+    {
+        for (pos, piece) in game.pieces_iter(color) {
+            let plane_idx = match piece.piece_type {
+                PieceType::Archbishop => 0,
+                PieceType::Chancellor => 1,
+                _ => continue,
+            };
+            let offset = (start_plane + side * 2 + plane_idx) * board_size;
+            data[offset + pos.to_index(W)] = 1.0;
+        }
+    }
+}
+
+/// Fill the two planes starting at `start_plane` with 1 if the current
+/// position of `game` has occurred once, and twice, before.
+#[hotpath::measure]
+fn fill_repetition_planes<const W: usize, const H: usize>(
+    data: &mut [f32],
+    game: &mut Game<W, H>,
+    start_plane: usize,
+    board_size: usize,
+) where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let repetitions = game.repetition_count();
+    if repetitions >= 2 {
+        fill_constant_plane(data, start_plane, 1.0, board_size);
+    }
+    if repetitions >= 3 {
+        fill_constant_plane(data, start_plane + 1, 1.0, board_size);
+    }
+}
+
 /// Encode a move as a full action index (plane * board_size + src_index)
 #[hotpath::measure]
 pub fn encode_action(move_: &Move, width: usize, height: usize) -> Option<usize> {
@@ -273,6 +963,38 @@ pub fn encode_action(move_: &Move, width: usize, height: usize) -> Option<usize>
     Some(plane * board_size + src_index)
 }
 
+/// Like `encode_action`, with the action space selected by `config`: the null
+/// move encodes to the pass action, if enabled, promotions to fairy pieces to
+/// the fairy promotion planes, if enabled, and padded encoding lays out
+/// actions on the padded canvas. Returns None for moves off the canvas.
+///
+/// The move is encoded as given, even with `flip_perspective`; use
+/// `Game::encode_action_with_config` to mirror it when black is to move.
+#[hotpath::measure]
+pub fn encode_action_with_config(
+    move_: &Move,
+    width: usize,
+    height: usize,
+    config: &EncoderConfig,
+) -> Option<usize> {
+    if move_.is_null() {
+        return config.pass_action_index(width, height);
+    }
+    let (rows, cols) = config.plane_shape(width, height);
+    if !move_.src.is_valid(cols, rows) || !move_.dst.is_valid(cols, rows) {
+        return None;
+    }
+    if move_.promotion.is_some_and(PieceType::is_fairy) {
+        if !config.fairy_piece_planes {
+            return None;
+        }
+        let plane = get_move_planes_count(cols, rows) + encode_fairy_promotion_plane(move_)?;
+        let src_index = usize::from(move_.src.row) * cols + usize::from(move_.src.col);
+        return Some(plane * cols * rows + src_index);
+    }
+    encode_action(move_, cols, rows)
+}
+
 /// Get the total number of action indices for a given board size
 #[hotpath::measure]
 pub fn get_total_actions(width: usize, height: usize) -> usize {
@@ -362,6 +1084,52 @@ pub(crate) fn encode_move_plane(move_: &Move, width: usize, height: usize) -> Op
     })
 }
 
+/// Encode a promotion to a fairy piece as a plane index relative to the
+/// first fairy promotion plane, laid out like the underpromotion planes.
+fn encode_fairy_promotion_plane(move_: &Move) -> Option<usize> {
+    let dx = move_.dst.col as i32 - move_.src.col as i32;
+    let dy = move_.dst.row as i32 - move_.src.row as i32;
+    if !(-1..=1).contains(&dx) || dy.abs() != 1 {
+        return None;
+    }
+    let direction_idx = (dx + 1) as usize;
+    let piece_idx = match move_.promotion? {
+        PieceType::Archbishop => 0,
+        PieceType::Chancellor => 1,
+        _ => return None,
+    };
+    let dir_offset = if dy > 0 {
+        0
+    } else {
+        NUM_UNDERPROMO_DIRECTIONS * NUM_FAIRY_PROMO_PIECES
+    };
+    Some(dir_offset + direction_idx * NUM_FAIRY_PROMO_PIECES + piece_idx)
+}
+
+/// Like `decode_move_plane`, with the fairy promotion planes after the other
+/// move planes if `config` enables them.
+pub(crate) fn decode_move_plane_with_config(
+    plane_idx: usize,
+    width: usize,
+    height: usize,
+    config: &EncoderConfig,
+) -> Option<(i32, i32, Option<PieceType>)> {
+    let base_planes = get_move_planes_count(width, height);
+    if plane_idx < base_planes {
+        return decode_move_plane(plane_idx, width, height);
+    }
+    let fairy_idx = plane_idx - base_planes;
+    if !config.fairy_piece_planes || fairy_idx >= FAIRY_PROMOTION_PLANES {
+        return None;
+    }
+    let forward_planes = NUM_UNDERPROMO_DIRECTIONS * NUM_FAIRY_PROMO_PIECES;
+    let dy = if fairy_idx < forward_planes { 1 } else { -1 };
+    let idx_within_direction = fairy_idx % forward_planes;
+    let dx = (idx_within_direction / NUM_FAIRY_PROMO_PIECES) as i32 - 1;
+    let promo = PieceType::FAIRY[idx_within_direction % NUM_FAIRY_PROMO_PIECES];
+    Some((dx, dy, Some(promo)))
+}
+
 /// Decode a plane index back to move deltas
 /// Returns (dx, dy, promotion) for the given plane index and board dimensions
 #[hotpath::measure]
@@ -451,7 +1219,9 @@ pub fn get_move_planes_count(width: usize, height: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::r#move::MoveFlags;
     use crate::position::Position;
+    use crate::test_support::play;
 
     fn get_plane_value(
         data: &[f32],
@@ -512,6 +1282,554 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_fairy_piece_planes() {
+        let mut game = Game::capablanca();
+        play(&mut game, &["c1d3", "h8g6"]);
+        let config = EncoderConfig {
+            history_length: 2,
+            last_move_planes: true,
+            fairy_piece_planes: true,
+            ..EncoderConfig::default()
+        };
+        let (data, num_planes, height, width) = encode_game_planes_with_config(&mut game, &config);
+        assert_eq!(
+            num_planes,
+            2 * (PIECE_PLANES + LAST_MOVE_PLANES + FAIRY_PIECE_PLANES) + CONSTANT_PLANES
+        );
+        assert_eq!((height, width), (8, 10));
+
+        // The piece planes leave the fairy pieces out
+        let board_size = height * width;
+        let pieces: f32 = data[..PIECE_PLANES * board_size].iter().sum();
+        assert_eq!(pieces, 36.0);
+
+        let fairy_start = 2 * (PIECE_PLANES + LAST_MOVE_PLANES) + CONSTANT_PLANES;
+        let schema = config.schema(10, 8);
+        assert_eq!(schema.planes.len(), num_planes);
+        assert_eq!(schema.planes[fairy_start].name, "t0_own_archbishop");
+        assert_eq!(
+            schema.planes[fairy_start + 3].name,
+            "t0_opponent_chancellor"
+        );
+
+        // Own archbishop on d3 at both steps; the opponent's chancellor moved
+        // from h8 to g6
+        let fairy = |t: usize, plane: usize, row: usize, col: usize| {
+            get_plane_value(
+                &data,
+                fairy_start + t * FAIRY_PIECE_PLANES + plane,
+                row,
+                col,
+                height,
+                width,
+            )
+        };
+        assert_eq!(fairy(0, 0, 2, 3), 1.0);
+        assert_eq!(fairy(0, 3, 5, 6), 1.0);
+        assert_eq!(fairy(1, 0, 2, 3), 1.0);
+        assert_eq!(fairy(1, 3, 7, 7), 1.0);
+        assert_eq!(fairy(1, 3, 5, 6), 0.0);
+        let fairy_total: f32 = data
+            [fairy_start * board_size..(fairy_start + 2 * FAIRY_PIECE_PLANES) * board_size]
+            .iter()
+            .sum();
+        assert_eq!(fairy_total, 8.0);
+    }
+
+    #[test]
+    fn test_fairy_promotion_actions() {
+        let mut game: Game<10, 8> =
+            Game::new("1r2k5/P9/10/10/10/10/7p2/4K5 w - - 0 1", true).expect("valid FEN");
+        game.set_fairy_promotions(true);
+        let config = EncoderConfig {
+            fairy_piece_planes: true,
+            pass_action: true,
+            ..EncoderConfig::default()
+        };
+        let base_actions = get_total_actions(10, 8);
+        assert_eq!(
+            config.total_actions(10, 8),
+            base_actions + FAIRY_PROMOTION_PLANES * 80 + 1
+        );
+        assert_eq!(
+            config.pass_action_index(10, 8),
+            Some(base_actions + FAIRY_PROMOTION_PLANES * 80)
+        );
+        let blocks = config.schema(10, 8).actions;
+        assert_eq!(
+            blocks.iter().map(|b| b.len).sum::<usize>(),
+            config.total_actions(10, 8)
+        );
+        assert_eq!(blocks[blocks.len() - 2].name, "fairy_promotion_backward");
+
+        // Every legal move, fairy promotions included, gets its own action
+        let moves = game.legal_moves();
+        let mut actions: Vec<usize> = moves
+            .iter()
+            .map(|mv| {
+                let action = game
+                    .encode_action_with_config(mv, &config)
+                    .expect("encodable");
+                assert_eq!(game.decode_action_with_config(action, &config), Some(*mv));
+                action
+            })
+            .collect();
+        actions.sort_unstable();
+        actions.dedup();
+        assert_eq!(actions.len(), moves.len());
+        assert!(
+            actions
+                .iter()
+                .all(|&a| a < base_actions + FAIRY_PROMOTION_PLANES * 80)
+        );
+
+        // Without the planes they have no action
+        let chancellor = game.move_from_lan("a7b8c").expect("legal capture");
+        assert_eq!(game.encode_action(&chancellor), None);
+        assert!(
+            game.apply_action_with_config(
+                game.encode_action_with_config(&chancellor, &config)
+                    .expect("encodable"),
+                &config
+            )
+        );
+
+        // Black promotes backward, on the planes after white's
+        let archbishop = game.move_from_lan("h2h1a").expect("legal promotion");
+        let action = game
+            .encode_action_with_config(&archbishop, &config)
+            .expect("encodable");
+        assert_eq!(
+            action / 80,
+            get_move_planes_count(10, 8) + NUM_UNDERPROMO_DIRECTIONS * NUM_FAIRY_PROMO_PIECES + 2
+        );
+        assert_eq!(game.decode_action(action), None);
+    }
+
+    #[test]
+    fn test_encode_games_batch() {
+        let mut games: Vec<Game<8, 8>> = Vec::new();
+        let mut game = Game::standard();
+        for lan in ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5"] {
+            games.push(game.clone());
+            play(&mut game, &[lan]);
+        }
+        let config = EncoderConfig {
+            last_move_planes: true,
+            ..EncoderConfig::default()
+        };
+
+        let (serial, num_planes, height, width) = encode_games_batch(&mut games, &config, false);
+        let (parallel, ..) = encode_games_batch(&mut games, &config, true);
+        let size = num_planes * height * width;
+        assert_eq!((num_planes, height, width), (config.num_planes(), 8, 8));
+        assert_eq!(serial.len(), games.len() * size);
+        assert_eq!(serial, parallel);
+        for (i, game) in games.iter_mut().enumerate() {
+            let (single, ..) = encode_game_planes_with_config(game, &config);
+            assert_eq!(serial[i * size..(i + 1) * size], single[..]);
+        }
+
+        let (empty, ..) = encode_games_batch::<8, 8>(&mut [], &config, true);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_fill_batch_without_data() {
+        let mut items = [0u32; 5];
+        for parallel in [false, true] {
+            fill_batch(&mut items, &mut [] as &mut [f32], parallel, |item, out| {
+                assert!(out.is_empty());
+                *item += 1;
+            });
+        }
+        assert_eq!(items, [2; 5]);
+    }
+
+    #[test]
+    fn test_encode_history_length() {
+        let config = EncoderConfig {
+            history_length: 2,
+            last_move_planes: true,
+            ..EncoderConfig::default()
+        };
+        assert_eq!(
+            config.num_planes(),
+            2 * PIECE_PLANES + CONSTANT_PLANES + 2 * LAST_MOVE_PLANES
+        );
+        assert_eq!(config.schema(8, 8).planes.len(), config.num_planes());
+
+        let mut game = Game::standard();
+        play(&mut game, &["e2e4", "e7e5", "g1f3"]);
+        let (data, num_planes, ..) = encode_game_planes_with_config(&mut game, &config);
+        let (full, ..) = encode_game_planes_with_config(
+            &mut game,
+            &EncoderConfig {
+                last_move_planes: true,
+                ..EncoderConfig::default()
+            },
+        );
+        assert_eq!(data.len(), num_planes * 64);
+
+        // The two most recent positions, the constant planes and the two most
+        // recent moves, as in the full encoding
+        let plane = |p: usize| p * 64..(p + 1) * 64;
+        let history = 2 * PIECE_PLANES;
+        assert_eq!(data[..history * 64], full[..history * 64]);
+        let full_constants = HISTORY_LENGTH * PIECE_PLANES;
+        for p in 0..CONSTANT_PLANES {
+            assert_eq!(data[plane(history + p)], full[plane(full_constants + p)]);
+        }
+        for p in 0..2 * LAST_MOVE_PLANES {
+            assert_eq!(
+                data[plane(history + CONSTANT_PLANES + p)],
+                full[plane(TOTAL_INPUT_PLANES + p)]
+            );
+        }
+
+        let no_history = EncoderConfig {
+            history_length: 0,
+            ..EncoderConfig::default()
+        };
+        assert!(matches!(
+            no_history.check_board(8, 8),
+            Err(ChessError::InvalidDimensions(_))
+        ));
+    }
+
+    #[test]
+    fn test_encode_repetition_planes() {
+        let mut game = Game::standard();
+        let repetition_1 = HISTORY_LENGTH * PIECE_PLANES;
+        let repetition_2 = repetition_1 + 1;
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+        let (data, ..) = encode_game_planes(&mut game);
+        assert_eq!(get_plane_value(&data, repetition_1, 3, 3, 8, 8), 0.0);
+
+        play(&mut game, &shuffle);
+        let (data, ..) = encode_game_planes(&mut game);
+        assert_eq!(get_plane_value(&data, repetition_1, 3, 3, 8, 8), 1.0);
+        assert_eq!(get_plane_value(&data, repetition_2, 3, 3, 8, 8), 0.0);
+
+        play(&mut game, &shuffle);
+        let (data, ..) = encode_game_planes(&mut game);
+        assert_eq!(get_plane_value(&data, repetition_1, 0, 7, 8, 8), 1.0);
+        assert_eq!(get_plane_value(&data, repetition_2, 0, 7, 8, 8), 1.0);
+    }
+
+    #[test]
+    fn test_encode_history_repetition_planes() {
+        let config = EncoderConfig {
+            repetition_planes: true,
+            ..EncoderConfig::default()
+        };
+        assert_eq!(
+            config.num_planes(),
+            TOTAL_INPUT_PLANES + HISTORY_LENGTH * REPETITION_PLANES
+        );
+
+        let mut game = Game::standard();
+        let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+        play(&mut game, &shuffle.repeat(2));
+        let fen = game.to_fen();
+        let (data, num_planes, height, width) = encode_game_planes_with_config(&mut game, &config);
+        assert_eq!(num_planes, config.num_planes());
+        assert_eq!(game.to_fen(), fen);
+
+        // The current position occurred twice before, the ones 1 to 4 plies
+        // ago once before, and those 5 to 7 plies ago not at all
+        let expected = [
+            (1.0, 1.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (1.0, 0.0),
+            (0.0, 0.0),
+            (0.0, 0.0),
+            (0.0, 0.0),
+        ];
+        for (t, &(once, twice)) in expected.iter().enumerate() {
+            let plane = TOTAL_INPUT_PLANES + t * REPETITION_PLANES;
+            assert_eq!(
+                get_plane_value(&data, plane, 4, 4, height, width),
+                once,
+                "t={}",
+                t
+            );
+            assert_eq!(
+                get_plane_value(&data, plane + 1, 4, 4, height, width),
+                twice,
+                "t={}",
+                t
+            );
+        }
+
+        let schema = config.schema(8, 8);
+        assert_eq!(schema.planes.len(), num_planes);
+        assert_eq!(schema.planes[TOTAL_INPUT_PLANES].name, "t0_repetition_1");
+    }
+
+    #[test]
+    fn test_encode_last_move_planes() {
+        let config = EncoderConfig {
+            last_move_planes: true,
+            ..EncoderConfig::default()
+        };
+        assert_eq!(
+            config.num_planes(),
+            TOTAL_INPUT_PLANES + HISTORY_LENGTH * LAST_MOVE_PLANES
+        );
+
+        let mut game = Game::standard();
+        let (data, num_planes, height, width) = encode_game_planes_with_config(&mut game, &config);
+        assert_eq!(num_planes, config.num_planes());
+        assert!(
+            data[TOTAL_INPUT_PLANES * height * width..]
+                .iter()
+                .all(|&v| v == 0.0),
+            "no moves played, last-move planes must be empty"
+        );
+
+        play(&mut game, &["e2e4", "g8f6"]);
+        let (data, _, height, width) = encode_game_planes_with_config(&mut game, &config);
+        let base = TOTAL_INPUT_PLANES;
+
+        // t=0: g8f6
+        assert_eq!(get_plane_value(&data, base, 7, 6, height, width), 1.0);
+        assert_eq!(get_plane_value(&data, base + 1, 5, 5, height, width), 1.0);
+        // t=1: e2e4
+        assert_eq!(get_plane_value(&data, base + 2, 1, 4, height, width), 1.0);
+        assert_eq!(get_plane_value(&data, base + 3, 3, 4, height, width), 1.0);
+        // t=2: no move before the starting position
+        let t2 = (base + 4) * height * width;
+        assert!(data[t2..].iter().all(|&v| v == 0.0));
+
+        // Base planes are unaffected by the extension
+        let (plain, _, _, _) = encode_game_planes(&mut game);
+        assert_eq!(&data[..TOTAL_INPUT_PLANES * height * width], &plain[..]);
+    }
+
+    #[test]
+    fn test_encode_board_planes() {
+        let mut game = Game::standard();
+        let mv = game.move_from_lan("e2e4").expect("valid LAN");
+        assert!(game.make_move(&mv));
+
+        let (data, num_planes, height, width) = encode_board_planes(&game);
+        assert_eq!((num_planes, height, width), (PIECE_PLANES, 8, 8));
+        assert_eq!(data.iter().sum::<f32>(), 32.0);
+        // White pieces come first even with black to move
+        assert_eq!(get_plane_value(&data, 0, 3, 4, height, width), 1.0);
+        assert_eq!(get_plane_value(&data, 0, 1, 4, height, width), 0.0);
+        assert_eq!(get_plane_value(&data, 6 + 5, 7, 4, height, width), 1.0);
+    }
+
+    #[test]
+    fn test_encode_board_planes_capablanca() {
+        let game = Game::capablanca();
+        let (data, num_planes, height, width) = encode_board_planes(&game);
+        assert_eq!(
+            (num_planes, height, width),
+            (PIECE_PLANES + FAIRY_PIECE_PLANES, 8, 10)
+        );
+        // Every piece is on some plane, archbishops and chancellors included
+        assert_eq!(data.iter().sum::<f32>(), 40.0);
+        assert_eq!(
+            get_plane_value(&data, PIECE_PLANES, 0, 2, height, width),
+            1.0
+        );
+        assert_eq!(
+            get_plane_value(&data, PIECE_PLANES + 3, 7, 7, height, width),
+            1.0
+        );
+
+        // The full encoding refuses to leave them out
+        assert!(matches!(
+            EncoderConfig::default().check_game(&game),
+            Err(ChessError::InvalidRules(_))
+        ));
+        let config = EncoderConfig {
+            fairy_piece_planes: true,
+            ..EncoderConfig::default()
+        };
+        assert_eq!(config.check_game(&game), Ok(()));
+        assert_eq!(
+            EncoderConfig::default().check_game(&Game::standard()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fairy_piece_planes")]
+    fn test_encode_game_planes_rejects_missing_fairy_planes() {
+        encode_game_planes(&mut Game::capablanca());
+    }
+
+    #[test]
+    fn test_encoder_schema() {
+        let schema = EncoderConfig::default().schema(8, 8);
+        assert_eq!(schema.plane_shape, (8, 8));
+        assert_eq!(schema.planes.len(), TOTAL_INPUT_PLANES);
+        assert!(schema.planes.iter().enumerate().all(|(i, p)| p.index == i));
+
+        // Plane names agree with the encoder's layout
+        let mut game = Game::standard();
+        let (data, _, height, width) = encode_game_planes(&mut game);
+        let index_of = |name: &str| {
+            schema
+                .planes
+                .iter()
+                .position(|p| p.name == name)
+                .expect("plane exists")
+        };
+        assert_eq!(
+            get_plane_value(&data, index_of("t0_own_pawn"), 1, 0, height, width),
+            1.0
+        );
+        assert_eq!(
+            get_plane_value(&data, index_of("t0_opponent_king"), 7, 4, height, width),
+            1.0
+        );
+        assert_eq!(
+            get_plane_value(&data, index_of("color"), 0, 0, height, width),
+            1.0
+        );
+        assert_eq!(
+            schema.planes[index_of("no_progress")].normalization,
+            PlaneNormalization::Scaled(HALFMOVE_SCALE)
+        );
+
+        // Action blocks tile the action space
+        let total: usize = schema.actions.iter().map(|b| b.len).sum();
+        assert_eq!(total, get_total_actions(8, 8));
+        assert!(
+            schema
+                .actions
+                .windows(2)
+                .all(|w| w[0].start + w[0].len == w[1].start)
+        );
+        let knight = schema
+            .actions
+            .iter()
+            .find(|b| b.name == "knight")
+            .expect("knight block");
+        let g1f3 = game.move_from_lan("g1f3").expect("valid LAN");
+        let action = encode_action(&g1f3, 8, 8).expect("encodable");
+        assert!((knight.start..knight.start + knight.len).contains(&action));
+
+        let config = EncoderConfig {
+            last_move_planes: true,
+            pass_action: true,
+            padded_size: Some(10),
+            ..EncoderConfig::default()
+        };
+        let schema = config.schema(8, 6);
+        assert_eq!(schema.plane_shape, (10, 10));
+        assert_eq!(schema.planes.len(), config.num_planes());
+        assert_eq!(
+            schema.planes.last().map(|p| p.name.as_str()),
+            Some("valid_square")
+        );
+        let pass = schema.actions.last().expect("pass block");
+        assert_eq!((pass.name.as_str(), pass.len), ("pass", 1));
+        assert_eq!(Some(pass.start), config.pass_action_index(8, 6));
+    }
+
+    #[test]
+    fn test_encode_flip_perspective() {
+        let config = EncoderConfig {
+            flip_perspective: true,
+            ..EncoderConfig::default()
+        };
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 0 1";
+        let mut game: Game<8, 8> = Game::new(fen, true).expect("valid FEN");
+        let (mut white, transform) = game.canonical_form();
+        assert!(transform.color_swap && !transform.mirror);
+
+        // Black to move sees the board as white would after swapping colors
+        let (flipped, ..) = encode_game_planes_with_config(&mut game, &config);
+        let (expected, ..) = encode_game_planes(&mut white);
+        assert_eq!(flipped[..PIECE_PLANES * 64], expected[..PIECE_PLANES * 64]);
+        let (plain, ..) = encode_game_planes(&mut game);
+        assert_ne!(flipped[..PIECE_PLANES * 64], plain[..PIECE_PLANES * 64]);
+
+        for mv in game.legal_moves() {
+            let action = game
+                .encode_action_with_config(&mv, &config)
+                .expect("encodable");
+            let mirrored = transform.apply_move(&mv, 8, 8);
+            assert_eq!(white.encode_action(&mirrored), Some(action));
+            assert_eq!(game.decode_action_with_config(action, &config), Some(mv));
+        }
+
+        // White to move is encoded as without flipping
+        let mut start = Game::standard();
+        let (flipped, ..) = encode_game_planes_with_config(&mut start, &config);
+        assert_eq!(flipped, encode_game_planes(&mut start).0);
+    }
+
+    #[test]
+    fn test_encode_padded() {
+        let config = EncoderConfig {
+            padded_size: Some(10),
+            ..EncoderConfig::default()
+        };
+        assert_eq!(config.num_planes(), TOTAL_INPUT_PLANES + MASK_PLANES);
+        assert_eq!(config.plane_shape(8, 6), (10, 10));
+
+        let mut game: Game<8, 6> =
+            Game::new("rnbqkbnr/pppppppp/8/8/PPPPPPPP/RNBQKBNR w - - 0 1", false)
+                .expect("valid FEN");
+        let (plain, _, _, _) = encode_game_planes(&mut game);
+        let (data, num_planes, height, width) = encode_game_planes_with_config(&mut game, &config);
+        assert_eq!((num_planes, height, width), (config.num_planes(), 10, 10));
+        assert_eq!(data.len(), num_planes * 100);
+
+        // Every base plane is embedded in the bottom-left corner
+        for plane in 0..TOTAL_INPUT_PLANES {
+            for row in 0..10 {
+                for col in 0..10 {
+                    let expected = if row < 6 && col < 8 {
+                        get_plane_value(&plain, plane, row, col, 6, 8)
+                    } else {
+                        0.0
+                    };
+                    assert_eq!(get_plane_value(&data, plane, row, col, 10, 10), expected);
+                }
+            }
+        }
+        let mask = &data[TOTAL_INPUT_PLANES * 100..];
+        assert_eq!(mask.iter().sum::<f32>(), 48.0);
+        assert_eq!(
+            get_plane_value(&data, TOTAL_INPUT_PLANES, 5, 7, 10, 10),
+            1.0
+        );
+        assert_eq!(
+            get_plane_value(&data, TOTAL_INPUT_PLANES, 6, 0, 10, 10),
+            0.0
+        );
+
+        // Actions are laid out on the canvas and decode back onto the board
+        assert_eq!(config.total_actions(8, 6), get_total_actions(10, 10));
+        for mv in game.legal_moves() {
+            let action = encode_action_with_config(&mv, 8, 6, &config).expect("on the canvas");
+            assert_eq!(game.decode_action_with_config(action, &config), Some(mv));
+        }
+        // A knight jump from h1 lands off the board but on the canvas
+        let off_board =
+            Move::from_position(Position::new(7, 0), Position::new(9, 1), MoveFlags::empty());
+        let action = encode_action_with_config(&off_board, 8, 6, &config).expect("on the canvas");
+        assert_eq!(game.decode_action_with_config(action, &config), None);
+
+        assert!(config.check_board(8, 6).is_ok());
+        assert!(matches!(
+            config.check_board(12, 8),
+            Err(ChessError::InvalidDimensions(_))
+        ));
+    }
+
     #[test]
     fn test_encode_move_plane_horizontal_vertical() {
         use crate::r#move::MoveFlags;
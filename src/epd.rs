@@ -0,0 +1,390 @@
+//! Extended Position Description (EPD) records and a runner for test suites
+//! such as WAC or STS.
+//!
+//! A record is the first four FEN fields followed by operations, each an
+//! opcode and its operands ended by a semicolon:
+//!
+//! ```text
+//! 6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id "back rank";
+//! ```
+//!
+//! `run_suite` asks a `SearchBackend` for a move in each position and checks
+//! it against the record's `bm` (best moves) and `am` (avoid moves).
+
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+
+use crate::game::Game;
+use crate::r#move::Move;
+use crate::uci::{GoLimits, InfoLine, SearchBackend};
+
+/// One EPD line: a position and its operations, in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpdRecord {
+    /// Full FEN of the position, with the move clocks from the `hmvc` and
+    /// `fmvn` operations, or `0 1` without them.
+    pub fen: String,
+    pub operations: Vec<(String, Vec<String>)>,
+}
+
+/// Split the operations after the position into `(opcode, operands)` pairs.
+/// Operands may be double-quoted strings, which can hold spaces and
+/// semicolons.
+fn parse_operations(text: &str) -> Result<Vec<(String, Vec<String>)>, String> {
+    let mut operations = Vec::new();
+    let mut chars = text.chars().peekable();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        if chars.peek().is_none() {
+            return Ok(operations);
+        }
+
+        let mut opcode = String::new();
+        while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+            opcode.push(c);
+        }
+        if opcode.is_empty() {
+            return Err("EPD operation without an opcode".to_string());
+        }
+
+        let mut operands = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            match chars.peek() {
+                // The last operation's semicolon is often left off
+                None | Some(';') => {
+                    chars.next();
+                    break;
+                }
+                Some('"') => {
+                    chars.next();
+                    let mut operand = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('"') => break,
+                            Some(c) => operand.push(c),
+                            None => {
+                                return Err(format!(
+                                    "unterminated string in EPD operation '{}'",
+                                    opcode
+                                ));
+                            }
+                        }
+                    }
+                    operands.push(operand);
+                }
+                Some(_) => {
+                    let mut operand = String::new();
+                    while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != ';') {
+                        operand.push(c);
+                    }
+                    operands.push(operand);
+                }
+            }
+        }
+        operations.push((opcode, operands));
+    }
+}
+
+#[hotpath::measure_all]
+impl EpdRecord {
+    pub fn parse(line: &str) -> Result<Self, String> {
+        let line = line.trim();
+        let mut fields = Vec::with_capacity(4);
+        let mut rest = line;
+        for _ in 0..4 {
+            let (field, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            if field.is_empty() {
+                return Err(format!(
+                    "EPD record needs 4 position fields, found {}: '{}'",
+                    fields.len(),
+                    line
+                ));
+            }
+            fields.push(field);
+            rest = tail.trim_start();
+        }
+        let mut record = EpdRecord {
+            fen: String::new(),
+            operations: parse_operations(rest)?,
+        };
+        let clock = |opcode: &str, default: &str| -> Result<String, String> {
+            match record.get(opcode) {
+                None => Ok(default.to_string()),
+                Some([value]) if value.parse::<u32>().is_ok() => Ok(value.clone()),
+                Some(_) => Err(format!("invalid EPD '{}' operation", opcode)),
+            }
+        };
+        let fen = format!(
+            "{} {} {}",
+            fields.join(" "),
+            clock("hmvc", "0")?,
+            clock("fmvn", "1")?
+        );
+        record.fen = fen;
+        Ok(record)
+    }
+
+    /// Operands of the first operation with `opcode`.
+    pub fn get(&self, opcode: &str) -> Option<&[String]> {
+        self.operations
+            .iter()
+            .find(|(op, _)| op == opcode)
+            .map(|(_, operands)| operands.as_slice())
+    }
+
+    /// The `id` operation, naming the position.
+    pub fn id(&self) -> Option<&str> {
+        self.get("id")?.first().map(String::as_str)
+    }
+
+    /// The position, with castling enabled.
+    pub fn game<const W: usize, const H: usize>(&self) -> Result<Game<W, H>, String>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        Ok(Game::new(&self.fen, true)?)
+    }
+
+    /// The moves of `opcode` (e.g. `bm`), in SAN or LAN, as moves of `game`.
+    pub fn moves<const W: usize, const H: usize>(
+        &self,
+        opcode: &str,
+        game: &mut Game<W, H>,
+    ) -> Result<Vec<Move>, String>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.get(opcode)
+            .unwrap_or_default()
+            .iter()
+            .map(|text| {
+                game.move_from_san(text)
+                    .or_else(|_| game.move_from_lan(text))
+                    .map_err(|e| format!("invalid '{}' move '{}': {}", opcode, text, e))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for EpdRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position: Vec<&str> = self.fen.split_whitespace().take(4).collect();
+        write!(f, "{}", position.join(" "))?;
+        for (opcode, operands) in &self.operations {
+            write!(f, " {}", opcode)?;
+            for operand in operands {
+                if operand.is_empty() || operand.contains([' ', ';', '"']) {
+                    write!(f, " \"{}\"", operand)?;
+                } else {
+                    write!(f, " {}", operand)?;
+                }
+            }
+            write!(f, ";")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse every non-blank line of `text` as an EPD record. Errors name the
+/// line at fault.
+pub fn parse_epd(text: &str) -> Result<Vec<EpdRecord>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| EpdRecord::parse(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+/// How a backend did on one position of a suite.
+#[derive(Clone, Debug)]
+pub struct EpdResult {
+    pub id: Option<String>,
+    pub fen: String,
+    /// None if the backend found no move.
+    pub played: Option<Move>,
+    /// `played` in SAN.
+    pub played_san: Option<String>,
+    /// Whether `played` is one of the `bm` moves, if there are any, and none
+    /// of the `am` moves. None if the record has neither.
+    pub solved: Option<bool>,
+    /// The last `info` the backend reported.
+    pub info: Option<InfoLine>,
+}
+
+/// Results of `run_suite`, in the order of the records.
+#[derive(Clone, Debug, Default)]
+pub struct SuiteReport {
+    pub results: Vec<EpdResult>,
+}
+
+impl SuiteReport {
+    pub fn solved(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.solved == Some(true))
+            .count()
+    }
+
+    /// Positions with a `bm` or `am` to check against.
+    pub fn scored(&self) -> usize {
+        self.results.iter().filter(|r| r.solved.is_some()).count()
+    }
+}
+
+impl fmt::Display for SuiteReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, result) in self.results.iter().enumerate() {
+            let status = match result.solved {
+                Some(true) => "ok",
+                Some(false) => "FAIL",
+                None => "-",
+            };
+            let name = result.id.clone().unwrap_or_else(|| format!("#{}", i + 1));
+            let played = result.played_san.as_deref().unwrap_or("(none)");
+            writeln!(f, "{:<4} {}: {}", status, name, played)?;
+        }
+        write!(f, "solved {}/{}", self.solved(), self.scored())
+    }
+}
+
+/// Search each record's position with `backend` under `limits` and check the
+/// move it plays. Records whose position or moves don't parse are errors.
+#[hotpath::measure]
+pub fn run_suite<const W: usize, const H: usize, B>(
+    records: &[EpdRecord],
+    backend: &mut B,
+    limits: &GoLimits,
+) -> Result<SuiteReport, String>
+where
+    [(); (W * H).div_ceil(64)]:,
+    B: SearchBackend<W, H> + ?Sized,
+{
+    let stop = AtomicBool::new(false);
+    let mut report = SuiteReport::default();
+    for record in records {
+        let name = record.id().unwrap_or(&record.fen).to_string();
+        let mut game: Game<W, H> = record.game().map_err(|e| format!("{}: {}", name, e))?;
+        let best = record
+            .moves("bm", &mut game)
+            .map_err(|e| format!("{}: {}", name, e))?;
+        let avoid = record
+            .moves("am", &mut game)
+            .map_err(|e| format!("{}: {}", name, e))?;
+
+        backend.new_game();
+        let mut info = None;
+        let played = backend.search(&mut game.clone(), limits, &stop, &mut |line| {
+            info = Some(line.clone())
+        });
+        let solved = (!best.is_empty() || !avoid.is_empty()).then(|| {
+            played.is_some_and(|mv| {
+                let same = |other: &Move| {
+                    (other.src, other.dst, other.promotion) == (mv.src, mv.dst, mv.promotion)
+                };
+                (best.is_empty() || best.iter().any(same)) && !avoid.iter().any(same)
+            })
+        });
+        report.results.push(EpdResult {
+            id: record.id().map(str::to_string),
+            fen: record.fen.clone(),
+            played,
+            played_san: played.map(|mv| game.move_to_san(&mv)),
+            solved,
+            info,
+        });
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::search::AlphaBeta;
+
+    #[test]
+    fn test_parse_epd_record() {
+        let record = EpdRecord::parse(
+            r#"r1b1k2r/ppp2ppp/8/8/8/8/PPP2PPP/R3K2R b KQkq - bm O-O Kf8; am Ke7; id "odd; name"; hmvc 4; fmvn 12; c0 "";"#,
+        )
+        .expect("valid EPD");
+        assert_eq!(
+            record.fen,
+            "r1b1k2r/ppp2ppp/8/8/8/8/PPP2PPP/R3K2R b KQkq - 4 12"
+        );
+        assert_eq!(record.id(), Some("odd; name"));
+        assert_eq!(
+            record.get("bm"),
+            Some(&["O-O".to_string(), "Kf8".to_string()][..])
+        );
+        assert_eq!(record.get("c0"), Some(&[String::new()][..]));
+        assert_eq!(record.get("dm"), None);
+
+        let mut game: StandardGame = record.game().expect("valid position");
+        assert_eq!(game.fullmove_number(), 12);
+        let best: Vec<String> = record
+            .moves("bm", &mut game)
+            .expect("valid moves")
+            .iter()
+            .map(Move::to_lan)
+            .collect();
+        assert_eq!(best, vec!["e8g8", "e8f8"]);
+
+        // Printing and parsing again gives the same record
+        assert_eq!(
+            EpdRecord::parse(&record.to_string()).expect("valid EPD"),
+            record
+        );
+
+        // No operations at all, and a last operation without its semicolon
+        let bare = EpdRecord::parse("8/8/8/8/8/8/8/K6k w - -").expect("valid EPD");
+        assert!(bare.operations.is_empty());
+        assert_eq!(bare.fen, "8/8/8/8/8/8/8/K6k w - - 0 1");
+        let open = EpdRecord::parse("8/8/8/8/8/8/8/K6k w - - id x").expect("valid EPD");
+        assert_eq!(open.id(), Some("x"));
+    }
+
+    #[test]
+    fn test_parse_epd_errors() {
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/K6k w -").is_err());
+        assert!(EpdRecord::parse(r#"8/8/8/8/8/8/8/K6k w - - id "open"#).is_err());
+        assert!(EpdRecord::parse("8/8/8/8/8/8/8/K6k w - - hmvc x;").is_err());
+
+        let err = parse_epd("8/8/8/8/8/8/8/K6k w - -\n\n8/8 w").expect_err("short record");
+        assert!(err.starts_with("line 3:"), "{}", err);
+
+        let record = EpdRecord::parse("8/8/8/8/8/8/8/K6k w - - bm Qh8;").expect("valid EPD");
+        let mut game: StandardGame = record.game().expect("valid position");
+        assert!(record.moves("bm", &mut game).is_err());
+        let mut backend = AlphaBeta::default();
+        assert!(run_suite::<8, 8, _>(&[record], &mut backend, &GoLimits::default()).is_err());
+    }
+
+    #[test]
+    fn test_run_suite() {
+        let records = parse_epd(concat!(
+            "6k1/5ppp/8/8/8/8/8/R5K1 w - - bm Ra8#; id \"mate\";\n",
+            "4k3/8/2p5/1p1q4/2P5/8/8/4K3 w - - am cxb5; id \"queen\";\n",
+            "4k3/8/2p5/1p1q4/2P5/8/8/4K3 w - - bm Kf2; id \"wrong\";\n",
+            "4k3/8/8/8/8/8/8/4K2R w K - id \"unscored\";\n",
+        ))
+        .expect("valid EPD");
+        let mut backend = AlphaBeta::default();
+        let report = run_suite::<8, 8, _>(&records, &mut backend, &GoLimits::default())
+            .expect("valid suite");
+
+        let solved: Vec<Option<bool>> = report.results.iter().map(|r| r.solved).collect();
+        assert_eq!(solved, vec![Some(true), Some(true), Some(false), None]);
+        assert_eq!(report.results[0].played_san.as_deref(), Some("Ra8#"));
+        assert_eq!(report.results[1].played_san.as_deref(), Some("cxd5"));
+        assert!(report.results[0].info.is_some());
+        assert_eq!(report.solved(), 2);
+        assert_eq!(report.scored(), 3);
+
+        let text = report.to_string();
+        assert!(text.starts_with("ok   mate: Ra8#\n"), "{}", text);
+        assert!(text.contains("FAIL wrong: cxd5\n"), "{}", text);
+        assert!(text.ends_with("solved 2/3"), "{}", text);
+    }
+}
@@ -0,0 +1,145 @@
+use thiserror::Error;
+
+/// Why a FEN, square, move or position edit was rejected.
+///
+/// Converts into a `String` (its message), so functions still reporting
+/// errors as strings can use `?` on it.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum ChessError {
+    /// A malformed FEN. `row` is the board row the problem is in, counted
+    /// from the top as written (so 0 is the first row of the FEN), when the
+    /// problem is confined to one.
+    #[error("Invalid FEN{}: {reason}", row.map(|r| format!(" row {}", r)).unwrap_or_default())]
+    InvalidFen { row: Option<usize>, reason: String },
+
+    /// Board dimensions outside the supported range.
+    #[error("{0}")]
+    InvalidDimensions(String),
+
+    /// A square name that isn't a file letter followed by a rank number.
+    #[error("Invalid square: {0}")]
+    InvalidSquare(String),
+
+    /// A move string that can't be parsed, or that leaves the board.
+    #[error("Invalid move: {0}")]
+    InvalidMove(String),
+
+    /// A well-formed move that can't be played in the position.
+    #[error("Illegal move: {0}")]
+    IllegalMove(String),
+
+    /// A malformed board diagram, or an edit that would leave the position
+    /// invalid, e.g. setting the turn while the other side is in check.
+    #[error("{0}")]
+    InvalidPosition(String),
+
+    /// Castling columns or draw rules the game can't be played with.
+    #[error("{0}")]
+    InvalidRules(String),
+
+    /// Something another library has no equivalent for, e.g. a fairy piece
+    /// converted to `shakmaty`.
+    #[error("{0}")]
+    Unsupported(String),
+}
+
+impl ChessError {
+    pub(crate) fn fen(reason: impl Into<String>) -> Self {
+        ChessError::InvalidFen {
+            row: None,
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn fen_row(row: usize, reason: impl Into<String>) -> Self {
+        ChessError::InvalidFen {
+            row: Some(row),
+            reason: reason.into(),
+        }
+    }
+}
+
+impl From<ChessError> for String {
+    fn from(e: ChessError) -> Self {
+        e.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::game::StandardGame;
+    use crate::r#move::Move;
+    use crate::outcome::DrawRules;
+    use crate::pieces::PieceType;
+    use crate::position::Position;
+
+    #[test]
+    fn test_error_kinds() {
+        let err = StandardGame::new(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1",
+            true,
+        )
+        .err()
+        .expect("bad side to move");
+        assert!(matches!(err, ChessError::InvalidFen { row: None, .. }));
+
+        let err = StandardGame::new(
+            "rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            true,
+        )
+        .err()
+        .expect("short row");
+        assert_eq!(err, ChessError::fen_row(1, "wrong number of squares"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid FEN row 1: wrong number of squares"
+        );
+
+        assert_eq!(
+            Position::from_algebraic("9a"),
+            Err(ChessError::InvalidSquare("9a".to_string()))
+        );
+        assert!(matches!(
+            Move::from_lan("e2", 8, 8),
+            Err(ChessError::InvalidMove(_))
+        ));
+        assert!(matches!(
+            Move::from_lan("e2e9", 8, 8),
+            Err(ChessError::InvalidMove(_))
+        ));
+
+        let mut game = StandardGame::standard();
+        assert!(matches!(
+            game.move_from_lan("e3e4"),
+            Err(ChessError::IllegalMove(_))
+        ));
+        assert!(matches!(
+            game.move_from_san("e5"),
+            Err(ChessError::IllegalMove(_))
+        ));
+        assert!(matches!(
+            game.move_from_san("Zz9"),
+            Err(ChessError::InvalidMove(_))
+        ));
+        assert!(matches!(
+            game.remove_all(PieceType::King, Color::White),
+            Err(ChessError::InvalidPosition(_))
+        ));
+        assert!(matches!(
+            game.set_draw_rules(DrawRules {
+                halfmove_limit: 0,
+                ..DrawRules::default()
+            }),
+            Err(ChessError::InvalidRules(_))
+        ));
+        assert!(matches!(
+            StandardGame::from_uci_position_string("startpos moves e2e5"),
+            Err(ChessError::IllegalMove(_))
+        ));
+
+        let message: String = ChessError::IllegalMove("e2e5".to_string()).into();
+        assert_eq!(message, "Illegal move: e2e5");
+    }
+}
@@ -1,8 +1,10 @@
+use crate::color::Color;
+use crate::encode::EncoderConfig;
 use crate::r#move::{Move, MoveFlags};
 use crate::pieces::PieceType;
 use crate::position::Position;
 
-use super::Game;
+use super::{Game, Transform};
 
 impl<const W: usize, const H: usize> Game<W, H>
 where
@@ -10,14 +12,31 @@ where
 {
     /// Decode a full action index into a Move, inferring flags from board state.
     pub fn decode_action(&self, action: usize) -> Option<Move> {
-        let board_size = W * H;
+        self.decode_action_with_config(action, &EncoderConfig::default())
+    }
+
+    /// Like `decode_action`, with the action space selected by `config`: the
+    /// pass action, if enabled, decodes to the null move, padded encoding
+    /// decodes actions laid out on the padded canvas, and `flip_perspective`
+    /// mirrors actions when black is to move.
+    pub fn decode_action_with_config(&self, action: usize, config: &EncoderConfig) -> Option<Move> {
+        if config.pass_action_index(W, H) == Some(action) {
+            return Some(Move::null());
+        }
+        let (height, width) = config.plane_shape(W, H);
+        let flip = self.flips_perspective(config);
+        let board_size = width * height;
 
         let plane_idx = action / board_size;
         let src_index = action % board_size;
-        let src_col = src_index % W;
-        let src_row = src_index / W;
+        let src_col = src_index % width;
+        let src_row = src_index / width;
+        if src_col >= W || src_row >= H {
+            return None;
+        }
 
-        let (dx, dy, promo) = crate::encode::decode_move_plane(plane_idx, W, H)?;
+        let (dx, dy, promo) =
+            crate::encode::decode_move_plane_with_config(plane_idx, width, height, config)?;
 
         let dst_col_i = src_col as i32 + dx;
         let dst_row_i = src_row as i32 + dy;
@@ -29,6 +48,12 @@ where
             return None;
         }
 
+        let (src_row, dst_row) = if flip {
+            (H - 1 - src_row, H - 1 - dst_row)
+        } else {
+            (src_row, dst_row)
+        };
+
         let src = Position::from_usize(src_col, src_row);
         let dst = Position::from_usize(dst_col, dst_row);
         let piece = self.board.get_piece(&src)?;
@@ -46,25 +71,93 @@ where
             None
         };
 
-        Some(Move {
+        let mut mv = Move {
             src,
             dst,
             flags,
             promotion,
-        })
+        };
+        mv.flags |= self.check_flag(&mv, &piece);
+        Some(mv)
     }
 
-    /// Apply an action index to the game
-    /// Returns false if the action is invalid (no piece at source, off-board, etc.).
-    pub fn apply_action(&mut self, action: usize) -> bool {
-        let mv = match self.decode_action(action) {
-            Some(mv) => mv,
-            None => return false,
+    /// Like `encode_action`, with the action space selected by `config` (see
+    /// `encode::encode_action_with_config`). With `flip_perspective`, the
+    /// move is mirrored vertically first when black is to move.
+    pub fn encode_action_with_config(&self, mv: &Move, config: &EncoderConfig) -> Option<usize> {
+        if mv.is_null() || !self.flips_perspective(config) {
+            return crate::encode::encode_action_with_config(mv, W, H, config);
+        }
+        let flip = Transform {
+            color_swap: true,
+            mirror: false,
         };
-        self.make_move_unchecked(&mv);
+        crate::encode::encode_action_with_config(&flip.apply_move(mv, W, H), W, H, config)
+    }
+
+    fn flips_perspective(&self, config: &EncoderConfig) -> bool {
+        config.flip_perspective && self.turn == Color::Black
+    }
+
+    /// Apply an action index to the game.
+    ///
+    /// The action is decoded straight into a move, which is then checked with
+    /// `is_legal_move` rather than searched for among the legal moves.
+    /// Returns false, leaving the game unchanged, if the action is invalid (no
+    /// piece of the side to move at the source, off-board, illegal, etc.).
+    pub fn apply_action(&mut self, action: usize) -> bool {
+        match self.decode_action(action) {
+            Some(mv) => self.make_decoded_move(&mv),
+            None => false,
+        }
+    }
+
+    /// Like `apply_action`, with the action space selected by `config`. The
+    /// pass action makes a null move, which fails if the side to move is in
+    /// check.
+    pub fn apply_action_with_config(&mut self, action: usize, config: &EncoderConfig) -> bool {
+        match self.decode_action_with_config(action, config) {
+            Some(mv) if mv.is_null() => self.make_null_move(),
+            Some(mv) => self.make_decoded_move(&mv),
+            None => false,
+        }
+    }
+
+    /// Whether `apply_action_with_config` would play `action`, without playing
+    /// it.
+    pub fn is_legal_action_with_config(&mut self, action: usize, config: &EncoderConfig) -> bool {
+        match self.decode_action_with_config(action, config) {
+            Some(mv) if mv.is_null() => !self.is_check(),
+            Some(mv) => self.is_legal_move(&mv),
+            None => false,
+        }
+    }
+
+    /// Make a move decoded from an action, whose flags were inferred from the
+    /// board, if it is legal.
+    fn make_decoded_move(&mut self, mv: &Move) -> bool {
+        if !self.is_legal_move(mv) {
+            return false;
+        }
+        self.make_move_unchecked(mv);
         true
     }
 
+    /// Whether each action index is the encoding of a legal move, computed in
+    /// one pass over the legal moves.
+    pub fn filter_legal_actions(&mut self, actions: &[usize]) -> Vec<bool> {
+        let mut legal: Vec<usize> = self
+            .legal_moves()
+            .iter()
+            .filter_map(|m| crate::encode::encode_action(m, W, H))
+            .collect();
+        legal.sort_unstable();
+        actions
+            .iter()
+            .map(|action| legal.binary_search(action).is_ok())
+            .collect()
+    }
+
     /// Encode a move as a full action index. Convenience wrapper.
     pub fn encode_action(&self, mv: &Move) -> Option<usize> {
         crate::encode::encode_action(mv, W, H)
@@ -0,0 +1,362 @@
+use smallvec::SmallVec;
+
+use crate::bitboard::Bitboard;
+use crate::board::Board;
+use crate::color::Color;
+use crate::r#move::{Move, MoveFlags};
+use crate::pieces::{Piece, PieceType};
+use crate::position::Position;
+
+use super::Game;
+
+/// Value used for the king when trading material, so that exchanges which
+/// leave the king capturable are never considered good.
+pub(crate) const KING_EXCHANGE_VALUE: i32 = 20_000;
+
+/// Piece value for exchange calculations: the material value, with the king
+/// worth more than everything else combined.
+#[inline]
+pub(crate) const fn exchange_value(piece_type: PieceType) -> i32 {
+    match piece_type {
+        PieceType::King => KING_EXCHANGE_VALUE,
+        _ => piece_type.value(),
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Squares attacked by `piece` standing on `pos`, given the current
+    /// occupancy. `pos` doesn't have to hold `piece`.
+    pub(crate) fn piece_attacks(
+        &self,
+        pos: &Position,
+        piece: &Piece,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        Board::<W, H>::piece_attacks(pos, piece, self.board.occupied())
+    }
+
+    /// Squares attacked by the piece on `pos`, whether or not a capture there
+    /// would be legal. Empty if there is no piece on `pos`.
+    pub fn attacks_from(&self, pos: &Position) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.board.attacks_from(pos, self.board.occupied())
+    }
+
+    /// Pieces of `by_color` within `occupied` that attack `square_idx`.
+    pub(crate) fn attackers_to_on(
+        &self,
+        square_idx: usize,
+        by_color: Color,
+        occupied: Bitboard<{ (W * H).div_ceil(64) }>,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let geo = Self::geo();
+        let own = self.board.color_bb(by_color) & occupied;
+
+        let pawns = geo.pawn_attacks(square_idx, by_color != Color::White)
+            & self.board.piece_type_bb(PieceType::Pawn);
+        let knights = geo.knight_attacks(square_idx) & self.board.knight_jumpers();
+        let kings = geo.king_attacks(square_idx) & self.board.piece_type_bb(PieceType::King);
+        let ortho = geo.orthogonal_attacks(square_idx, occupied) & self.board.orthogonal_sliders();
+        let diag = geo.diagonal_attacks(square_idx, occupied) & self.board.diagonal_sliders();
+
+        (pawns | knights | kings | ortho | diag) & own
+    }
+
+    /// All pieces of `by_color` attacking `square`, whether or not the capture
+    /// would be legal.
+    pub fn attackers_to(
+        &self,
+        square: &Position,
+        by_color: Color,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.attackers_to_on(square.to_index(W), by_color, self.board.occupied())
+    }
+
+    /// Pieces giving check to the side to move.
+    pub fn checkers(&self) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.attackers_to(&self.king_pos(self.turn), self.turn.opposite())
+    }
+
+    /// Pieces of `color` absolutely pinned against their own king: moving
+    /// one off the line it shares with an enemy slider would expose the king.
+    pub fn pinned(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let geo = Self::geo();
+        let king_idx = self.king_pos(color).to_index(W);
+        let occupied = self.board.occupied();
+        let enemy = self.board.color_bb(color.opposite());
+
+        // Enemy sliders that would attack the king on an empty board pin the
+        // piece between them when it is the only one there
+        let orthogonal =
+            geo.orthogonal_attacks(king_idx, Bitboard::empty()) & self.board.orthogonal_sliders();
+        let diagonal =
+            geo.diagonal_attacks(king_idx, Bitboard::empty()) & self.board.diagonal_sliders();
+        let snipers = (orthogonal | diagonal) & enemy;
+        let mut pinned = Bitboard::empty();
+        for idx in snipers.iter_ones() {
+            let blockers = geo.between(king_idx, idx) & occupied;
+            if blockers.count() == 1 {
+                pinned |= blockers & self.board.color_bb(color);
+            }
+        }
+        pinned
+    }
+
+    fn king_pos(&self, color: Color) -> Position {
+        match color {
+            Color::White => self.white_king_pos,
+            Color::Black => self.black_king_pos,
+        }
+    }
+
+    /// Every square attacked by a piece of `color`, whether or not a capture
+    /// there would be legal. Squares holding `color`'s own pieces are included
+    /// when they are defended.
+    pub fn attack_map(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.board
+            .pieces_iter(color)
+            .fold(Bitboard::empty(), |attacks, (pos, piece)| {
+                attacks | self.piece_attacks(&pos, &piece)
+            })
+    }
+
+    /// Number of pieces of `color` attacking each square, indexed by
+    /// `Position::to_index`.
+    pub fn attack_counts(&self, color: Color) -> Vec<u8> {
+        let mut counts = vec![0; W * H];
+        for (pos, piece) in self.board.pieces_iter(color) {
+            for idx in self.piece_attacks(&pos, &piece).iter_ones() {
+                counts[idx] += 1;
+            }
+        }
+        counts
+    }
+
+    /// Sliders of `by_color` attacking `square` through exactly one blocker of
+    /// either color, such as the rear piece of a battery or a pinning piece.
+    pub fn xray_attackers_to(
+        &self,
+        square: &Position,
+        by_color: Color,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.board.xray_attackers_to(square, by_color)
+    }
+
+    /// Static exchange evaluation: the material balance in centipawns for the
+    /// side making `mv` after the full sequence of captures and recaptures on
+    /// the destination square, with each side always recapturing with its least
+    /// valuable attacker and stopping when continuing would lose material.
+    ///
+    /// Pins are ignored, so the result is an estimate. Returns 0 if there is no
+    /// piece on the source square.
+    pub fn see(&self, mv: &Move) -> i32 {
+        let Some(piece) = self.board.get_piece(&mv.src) else {
+            return 0;
+        };
+        let dst_idx = mv.dst.to_index(W);
+        let mut occupied = self.board.occupied();
+        occupied.clear(mv.src.to_index(W));
+
+        let mut gains: SmallVec<[i32; 32]> = SmallVec::new();
+        let captured = if mv.flags.contains(MoveFlags::EN_PASSANT) {
+            let victim = Position::new(mv.dst.col, mv.src.row);
+            occupied.clear(victim.to_index(W));
+            PieceType::Pawn.value()
+        } else {
+            // A self-capture loses the captured piece instead of winning it
+            self.board.get_piece(&mv.dst).map_or(0, |p| {
+                let value = exchange_value(p.piece_type);
+                if p.color == piece.color {
+                    -value
+                } else {
+                    value
+                }
+            })
+        };
+
+        let mut on_square = exchange_value(piece.piece_type);
+        let mut first_gain = captured;
+        if let Some(promo) = mv.promotion {
+            on_square = exchange_value(promo);
+            first_gain += promo.value() - PieceType::Pawn.value();
+        }
+        gains.push(first_gain);
+
+        let mut side = piece.color.opposite();
+        loop {
+            let attackers = self.attackers_to_on(dst_idx, side, occupied);
+            let Some((idx, pt)) = PieceType::ALL.iter().find_map(|&pt| {
+                (attackers & self.board.piece_type_bb(pt))
+                    .lowest_bit_index()
+                    .map(|idx| (idx, pt))
+            }) else {
+                break;
+            };
+
+            let previous = *gains.last().expect("see: gains is never empty");
+            gains.push(on_square - previous);
+            on_square = exchange_value(pt);
+            occupied.clear(idx);
+            side = side.opposite();
+        }
+
+        // Each side may stop capturing instead of continuing the exchange
+        while gains.len() > 1 {
+            let last = gains.pop().expect("see: gains has more than one entry");
+            let previous = gains.last_mut().expect("see: gains is never empty");
+            *previous = -(-*previous).max(last);
+        }
+        gains[0]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bitboard::Bitboard;
+    use crate::color::Color;
+    use crate::game::{Game, StandardGame};
+    use crate::position::Position;
+
+    fn squares(bb: Bitboard<1>) -> Vec<String> {
+        bb.iter_ones()
+            .map(|idx| Position::from_index(idx, 8).to_algebraic())
+            .collect()
+    }
+
+    #[test]
+    fn test_attackers_to() {
+        let game =
+            StandardGame::new("4k3/8/2n5/3p4/2B5/1Q6/8/3RK3 w - - 0 1", true).expect("valid FEN");
+        let d5 = Position::from_algebraic("d5").expect("valid square");
+        // The queen on b3 is behind the bishop, so it isn't a direct attacker
+        assert_eq!(
+            squares(game.attackers_to(&d5, Color::White)),
+            vec!["d1", "c4"]
+        );
+        assert!(game.attackers_to(&d5, Color::Black).is_empty());
+    }
+
+    #[test]
+    fn test_attacks_from() {
+        let game = StandardGame::new("4k3/8/8/3p4/8/1B6/8/4K3 w - - 0 1", true).expect("valid FEN");
+        let square = |s: &str| Position::from_algebraic(s).expect("valid square");
+        // The bishop's diagonal towards the pawn stops at the pawn
+        assert_eq!(
+            squares(game.attacks_from(&square("b3"))),
+            vec!["d1", "a2", "c2", "a4", "c4", "d5"]
+        );
+        assert_eq!(squares(game.attacks_from(&square("d5"))), vec!["c4", "e4"]);
+        assert!(game.attacks_from(&square("h4")).is_empty());
+    }
+
+    #[test]
+    fn test_checkers_and_pinned() {
+        // The knight checks and the bishop pins the knight on d7; the pawn on
+        // e7 isn't pinned because the rook on e6 also stands in the way
+        let game =
+            StandardGame::new("4k3/3np3/4rN2/1B6/8/8/8/4R2K b - - 0 1", true).expect("valid FEN");
+        assert_eq!(squares(game.checkers()), vec!["f6"]);
+        assert_eq!(squares(game.pinned(Color::Black)), vec!["d7"]);
+        assert!(game.pinned(Color::White).is_empty());
+
+        // Double check, with the bishop pinned by the queen
+        let game =
+            StandardGame::new("4k3/8/8/8/q7/4n3/2B5/r2K4 w - - 0 1", true).expect("valid FEN");
+        assert_eq!(squares(game.checkers()), vec!["a1", "e3"]);
+        assert_eq!(squares(game.pinned(Color::White)), vec!["c2"]);
+        assert!(game.pinned(Color::Black).is_empty());
+
+        assert!(StandardGame::standard().checkers().is_empty());
+
+        // A chancellor pinning along the first rank and an archbishop along a
+        // diagonal, on a 10x8 board
+        let game = Game::<10, 8>::new("4k5/10/10/10/7a2/6B3/10/c1N1K5 w - - 0 1", true)
+            .expect("valid FEN");
+        let pinned: Vec<String> = game
+            .pinned(Color::White)
+            .iter_ones()
+            .map(|idx| Position::from_index(idx, 10).to_algebraic())
+            .collect();
+        assert_eq!(pinned, vec!["c1", "g3"]);
+    }
+
+    #[test]
+    fn test_attack_map() {
+        let game = StandardGame::new("4k3/8/8/8/8/8/1P6/R3K3 w - - 0 1", true).expect("valid FEN");
+        let attacks = game.attack_map(Color::White);
+        // The pawn covers a3 and c3, the rook the first rank up to the king and
+        // the a-file, and the king its neighbours; a1 itself is undefended
+        assert_eq!(
+            squares(attacks),
+            vec![
+                "b1", "c1", "d1", "e1", "f1", "a2", "d2", "e2", "f2", "a3", "c3", "a4", "a5", "a6",
+                "a7", "a8"
+            ]
+        );
+
+        let counts = game.attack_counts(Color::White);
+        let count = |s: &str| {
+            counts[Position::from_algebraic(s)
+                .expect("valid square")
+                .to_index(8)]
+        };
+        assert_eq!(count("d1"), 2);
+        assert_eq!(count("a3"), 2);
+        assert_eq!(count("e2"), 1);
+        assert_eq!(count("h8"), 0);
+        assert_eq!(
+            counts.iter().filter(|&&c| c > 0).count(),
+            attacks.count() as usize
+        );
+    }
+
+    #[test]
+    fn test_xray_attackers_to() {
+        let game =
+            StandardGame::new("4k3/8/2n5/3p4/2B5/1Q6/8/3RK3 w - - 0 1", true).expect("valid FEN");
+        let d5 = Position::from_algebraic("d5").expect("valid square");
+        // The queen attacks through the bishop; the rook already attacks directly
+        assert_eq!(
+            squares(game.xray_attackers_to(&d5, Color::White)),
+            vec!["b3"]
+        );
+
+        // The bishop pins the knight against the king through one blocker,
+        // but two blockers hide the rook
+        let game =
+            StandardGame::new("4k3/3n4/8/1B6/4p3/8/4P3/4R1K1 w - - 0 1", true).expect("valid FEN");
+        let e8 = Position::from_algebraic("e8").expect("valid square");
+        assert_eq!(
+            squares(game.xray_attackers_to(&e8, Color::White)),
+            vec!["b5"]
+        );
+    }
+
+    #[test]
+    fn test_see() {
+        // Rook takes a pawn defended by a pawn: loses the exchange
+        let game =
+            StandardGame::new("4k3/8/2p5/3p4/8/8/8/3RK3 w - - 0 1", true).expect("valid FEN");
+        let mv = game.move_from_lan("d1d5").expect("valid LAN");
+        assert_eq!(game.see(&mv), 100 - 500);
+
+        // Bishop takes an undefended knight
+        let game = StandardGame::new("4k3/8/8/3n4/8/8/6B1/4K3 w - - 0 1", true).expect("valid FEN");
+        let mv = game.move_from_lan("g2d5").expect("valid LAN");
+        assert_eq!(game.see(&mv), 300);
+
+        // Pawn takes a knight defended by a pawn, and the queen wins the recapturing pawn
+        let game =
+            StandardGame::new("4k3/2p5/3n4/4P3/8/8/8/3QK3 w - - 0 1", true).expect("valid FEN");
+        let mv = game.move_from_lan("e5d6").expect("valid LAN");
+        assert_eq!(game.see(&mv), 300 - 100 + 100);
+
+        // Rook and queen battery against a single defender wins the pawn
+        let game =
+            StandardGame::new("3rk3/8/8/3p4/8/8/3R4/3QK3 w - - 0 1", true).expect("valid FEN");
+        let mv = game.move_from_lan("d2d5").expect("valid LAN");
+        assert_eq!(game.see(&mv), 100);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::board::Board;
+use crate::color::Color;
+use crate::error::ChessError;
+use crate::pieces::Piece;
+use crate::position::Position;
+
+use super::{CastlingRights, Game};
+
+/// Sets up a position square by square, for tests, puzzles and editors.
+///
+/// Starts from an empty board with white to move, no castling rights, no en
+/// passant square and fresh clocks. `build` checks the result as `Game::new`
+/// checks a FEN.
+#[derive(Clone, Debug)]
+pub struct BoardBuilder<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    board: Board<W, H>,
+    turn: Color,
+    castling_rights: CastlingRights,
+    castling_enabled: bool,
+    en_passant: Option<Position>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+impl<const W: usize, const H: usize> Default for BoardBuilder<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> BoardBuilder<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    pub fn new() -> Self {
+        BoardBuilder {
+            board: Board::empty(),
+            turn: Color::White,
+            castling_rights: CastlingRights::none(),
+            castling_enabled: true,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        }
+    }
+
+    /// Put `piece` on `pos`, replacing whatever was there.
+    pub fn piece(mut self, pos: Position, piece: Piece) -> Self {
+        self.board.set_piece(&pos, Some(piece));
+        self
+    }
+
+    /// Put each of `pieces` on its square.
+    pub fn pieces(mut self, pieces: impl IntoIterator<Item = (Position, Piece)>) -> Self {
+        for (pos, piece) in pieces {
+            self.board.set_piece(&pos, Some(piece));
+        }
+        self
+    }
+
+    pub fn turn(mut self, turn: Color) -> Self {
+        self.turn = turn;
+        self
+    }
+
+    /// Set which sides `color` may castle to.
+    pub fn castling(mut self, color: Color, kingside: bool, queenside: bool) -> Self {
+        self.castling_rights.set_kingside(color, kingside);
+        self.castling_rights.set_queenside(color, queenside);
+        self
+    }
+
+    /// Whether the game allows castling at all, as the `castling_enabled`
+    /// argument of `Game::new`. Enabled by default.
+    pub fn castling_enabled(mut self, enabled: bool) -> Self {
+        self.castling_enabled = enabled;
+        self
+    }
+
+    /// The square a pawn just skipped over with a double push.
+    pub fn en_passant(mut self, square: Option<Position>) -> Self {
+        self.en_passant = square;
+        self
+    }
+
+    pub fn clocks(mut self, halfmove_clock: u32, fullmove_number: u32) -> Self {
+        self.halfmove_clock = halfmove_clock;
+        self.fullmove_number = fullmove_number;
+        self
+    }
+
+    /// The FEN of the position set up so far.
+    pub fn to_fen(&self) -> String {
+        let rights = &self.castling_rights;
+        let mut castling: String = [
+            (rights.white_kingside, 'K'),
+            (rights.white_queenside, 'Q'),
+            (rights.black_kingside, 'k'),
+            (rights.black_queenside, 'q'),
+        ]
+        .iter()
+        .filter_map(|&(allowed, c)| allowed.then_some(c))
+        .collect();
+        if castling.is_empty() {
+            castling.push('-');
+        }
+
+        format!(
+            "{} {} {} {} {} {}",
+            self.board.fen(),
+            if self.turn == Color::White { 'w' } else { 'b' },
+            castling,
+            self.en_passant
+                .map_or_else(|| "-".to_string(), |ep| ep.to_algebraic()),
+            self.halfmove_clock,
+            self.fullmove_number,
+        )
+    }
+
+    /// The game in the position set up. Fails as `Game::new` would on the
+    /// FEN, e.g. if a king is missing or the en passant square doesn't follow
+    /// a double push.
+    pub fn build(&self) -> Result<Game<W, H>, ChessError> {
+        Game::new(&self.to_fen(), self.castling_enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::pieces::PieceType;
+
+    fn square(name: &str) -> Position {
+        Position::from_algebraic(name).expect("valid square")
+    }
+
+    #[test]
+    fn test_board_builder() {
+        let white_king = Piece::new(PieceType::King, Color::White);
+        let black_king = Piece::new(PieceType::King, Color::Black);
+        let mut game = BoardBuilder::<8, 8>::new()
+            .piece(square("e1"), white_king)
+            .piece(square("h1"), Piece::new(PieceType::Rook, Color::White))
+            .pieces([
+                (square("e8"), black_king),
+                (square("d4"), Piece::new(PieceType::Pawn, Color::Black)),
+                (square("e4"), Piece::new(PieceType::Pawn, Color::White)),
+            ])
+            .turn(Color::Black)
+            .castling(Color::White, true, false)
+            .en_passant(Some(square("e3")))
+            .clocks(0, 12)
+            .build()
+            .expect("valid position");
+        assert_eq!(game.to_fen(), "4k3/8/8/8/3pP3/8/8/4K2R b K e3 0 12");
+
+        // Built positions are checked like FENs
+        let builder = BoardBuilder::<8, 8>::new().piece(square("e1"), white_king);
+        assert!(builder.build().is_err());
+        let builder = builder
+            .piece(square("e8"), black_king)
+            .en_passant(Some(square("d6")));
+        assert!(builder.build().is_err());
+
+        let mut game = StandardGame::from_pieces(
+            [(square("a1"), white_king), (square("h8"), black_king)],
+            Color::White,
+        )
+        .expect("valid position");
+        assert_eq!(game.to_fen(), "7k/8/8/8/8/8/8/K7 w - - 0 1");
+    }
+}
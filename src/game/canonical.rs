@@ -0,0 +1,238 @@
+use smallvec::SmallVec;
+
+use crate::color::Color;
+use crate::r#move::Move;
+use crate::position::Position;
+
+use super::{CastlingRights, Game, ObserverSlot, PieceCounts};
+
+/// Symmetry mapping a position to its canonical form, as returned by
+/// `Game::canonical_form`.
+///
+/// Both parts are their own inverse, so the same transform maps squares and
+/// moves into the canonical position and back out again.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Transform {
+    /// Colors swapped and the board flipped vertically, so that white is to move.
+    pub color_swap: bool,
+    /// Board mirrored horizontally, reversing the files.
+    pub mirror: bool,
+}
+
+#[hotpath::measure_all]
+impl Transform {
+    /// Whether this is the identity transform.
+    pub fn is_identity(&self) -> bool {
+        !self.color_swap && !self.mirror
+    }
+
+    /// Apply the transform to a square on a `width` x `height` board.
+    pub fn apply_position(&self, pos: &Position, width: usize, height: usize) -> Position {
+        let col = if self.mirror {
+            (width - 1) as u8 - pos.col
+        } else {
+            pos.col
+        };
+        let row = if self.color_swap {
+            (height - 1) as u8 - pos.row
+        } else {
+            pos.row
+        };
+        Position::new(col, row)
+    }
+
+    /// Apply the transform to a move's squares, keeping its flags and promotion.
+    pub fn apply_move(&self, mv: &Move, width: usize, height: usize) -> Move {
+        Move {
+            src: self.apply_position(&mv.src, width, height),
+            dst: self.apply_position(&mv.dst, width, height),
+            ..*mv
+        }
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// The canonical representative of this position under symmetry, and the
+    /// transform that produces it from this position.
+    ///
+    /// Colors are swapped (flipping the board vertically) so that white is to
+    /// move. If neither side can castle, the board is also mirrored horizontally
+    /// when that gives the smaller position, so mirror-image positions share a
+    /// canonical form; with castling rights the files aren't interchangeable.
+    ///
+    /// The canonical game keeps the clocks but not the move history.
+    pub fn canonical_form(&self) -> (Self, Transform) {
+        let transform = Transform {
+            color_swap: self.turn == Color::Black,
+            mirror: false,
+        };
+        let game = self.transformed(transform);
+        if self.castling_rights != CastlingRights::none() {
+            return (game, transform);
+        }
+
+        let mirrored_transform = Transform {
+            mirror: true,
+            ..transform
+        };
+        let mirrored = self.transformed(mirrored_transform);
+        if mirrored.canonical_key() < game.canonical_key() {
+            (mirrored, mirrored_transform)
+        } else {
+            (game, transform)
+        }
+    }
+
+    /// This position with `transform` applied, without move history. Castling
+    /// rights follow the colors; mirroring a position with castling rights isn't
+    /// meaningful.
+    fn transformed(&self, transform: Transform) -> Self {
+        debug_assert!(
+            !transform.mirror || self.castling_rights == CastlingRights::none(),
+            "transformed: can't mirror a position with castling rights"
+        );
+
+        let mut board = self.board;
+        match (transform.color_swap, transform.mirror) {
+            (true, true) => board.rotate180(),
+            (true, false) => board.flip_vertical(),
+            (false, true) => board.flip_horizontal(),
+            (false, false) => {}
+        }
+        if transform.color_swap {
+            board.swap_colors();
+        }
+
+        let (turn, castling_rights) = if transform.color_swap {
+            let rights = CastlingRights {
+                white_kingside: self.castling_rights.black_kingside,
+                white_queenside: self.castling_rights.black_queenside,
+                black_kingside: self.castling_rights.white_kingside,
+                black_queenside: self.castling_rights.white_queenside,
+            };
+            (self.turn.opposite(), rights)
+        } else {
+            (self.turn, self.castling_rights)
+        };
+
+        let (white_king_pos, black_king_pos) = if transform.color_swap {
+            (self.black_king_pos, self.white_king_pos)
+        } else {
+            (self.white_king_pos, self.black_king_pos)
+        };
+
+        let piece_counts = PieceCounts::from_board(&board);
+        Game {
+            board,
+            turn,
+            move_history: SmallVec::new(),
+            castling_rights,
+            castling_enabled: self.castling_enabled,
+            self_capture: self.self_capture,
+            fairy_promotions: self.fairy_promotions,
+            castling_king_cols: self.castling_king_cols,
+            double_push_row: self.double_push_row,
+            draw_rules: self.draw_rules,
+            en_passant: self
+                .en_passant
+                .map(|ep| transform.apply_position(&ep, W, H)),
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            white_king_pos: transform.apply_position(&white_king_pos, W, H),
+            black_king_pos: transform.apply_position(&black_king_pos, W, H),
+            piece_counts,
+            legal_moves_cache: None,
+            declared_outcome: None,
+            observer: ObserverSlot::default(),
+        }
+    }
+
+    /// The position with the colors swapped and the board flipped vertically,
+    /// so the other side is to move with the roles reversed. Castling rights
+    /// and the en passant square follow; the clocks are kept but not the move
+    /// history.
+    pub fn mirror(&self) -> Self {
+        self.transformed(Transform {
+            color_swap: true,
+            mirror: false,
+        })
+    }
+
+    /// Ordering key for choosing between symmetric positions.
+    fn canonical_key(&self) -> (String, Option<(u8, u8)>) {
+        (self.board.fen(), self.en_passant.map(|ep| (ep.col, ep.row)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    #[test]
+    fn test_canonical_form_swaps_colors() {
+        let mut white = StandardGame::standard();
+        let mv = white.move_from_lan("e2e4").expect("valid LAN");
+        assert!(white.make_move(&mv));
+
+        let (mut canonical, transform) = white.canonical_form();
+        assert_eq!(
+            transform,
+            Transform {
+                color_swap: true,
+                mirror: false,
+            }
+        );
+        assert_eq!(
+            canonical.to_fen(),
+            "rnbqkbnr/pppp1ppp/8/4p3/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+        assert_eq!(canonical.legal_moves().len(), 20);
+
+        // The reply e7e5 maps to e2e4 in the canonical position
+        let reply = white.move_from_lan("e7e5").expect("valid LAN");
+        assert_eq!(transform.apply_move(&reply, 8, 8).to_lan(), "e2e4");
+
+        // White to move is already canonical
+        let (_, transform) = StandardGame::standard().canonical_form();
+        assert!(transform.is_identity());
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mut game =
+            StandardGame::new("r3k2r/ppp2ppp/8/3pP3/8/8/PPP2PPP/R3K2R w Kq d6 0 5", true)
+                .expect("valid FEN");
+        let mut mirrored = game.mirror();
+        assert_eq!(
+            mirrored.to_fen(),
+            "r3k2r/ppp2ppp/8/8/3Pp3/8/PPP2PPP/R3K2R b Qk d3 0 5"
+        );
+        assert_eq!(mirrored.legal_moves().len(), game.legal_moves().len());
+        assert_eq!(mirrored.mirror().to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn test_canonical_form_merges_mirrored_positions() {
+        let a = StandardGame::new("8/8/3k4/8/8/8/1P6/1K6 b - - 0 1", true).expect("valid FEN");
+        let b = StandardGame::new("6k1/6p1/8/8/8/4K3/8/8 w - - 0 1", true).expect("valid FEN");
+        let c = StandardGame::new("1k6/1p6/8/8/8/3K4/8/8 w - - 0 1", true).expect("valid FEN");
+
+        let (mut canon_a, _) = a.canonical_form();
+        let (mut canon_b, _) = b.canonical_form();
+        let (mut canon_c, transform_c) = c.canonical_form();
+        assert!(!transform_c.color_swap);
+        assert_eq!(canon_a.to_fen(), canon_b.to_fen());
+        assert_eq!(canon_a.to_fen(), canon_c.to_fen());
+
+        // Castling rights prevent mirroring
+        let castling =
+            StandardGame::new("4k3/8/8/8/8/8/8/4K2R w K - 0 1", true).expect("valid FEN");
+        let (_, transform) = castling.canonical_form();
+        assert!(transform.is_identity());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::bitboard::Bitboard;
 use crate::color::Color;
+use crate::r#move::{Move, MoveFlags};
 use crate::pieces::PieceType;
 
 use super::Game;
@@ -25,6 +26,22 @@ impl<const NW: usize, const N: usize> CheckPinInfo<NW, N> {
     }
 }
 
+/// What it takes for a move by the side to move to check the opposing king,
+/// gathered once per position so each generated move is tested cheaply.
+pub(super) struct CheckSquares<const NW: usize> {
+    king_idx: usize,
+    /// Squares from which each piece type, indexed by `PieceType as usize`,
+    /// attacks the opposing king.
+    squares: [Bitboard<NW>; PieceType::ALL.len()],
+    /// Our pieces standing alone between one of our sliders and the opposing
+    /// king, which give a discovered check by leaving that line.
+    discoverers: Bitboard<NW>,
+    occupied: Bitboard<NW>,
+    orthogonal_sliders: Bitboard<NW>,
+    diagonal_sliders: Bitboard<NW>,
+    is_white: bool,
+}
+
 #[hotpath::measure_all]
 impl<const W: usize, const H: usize> Game<W, H>
 where
@@ -53,9 +70,7 @@ where
 
         // Orthogonal: [0]=N(left), [1]=S(right), [2]=E(left), [3]=W(right)
         const ORTHO_IS_LEFT: [bool; 4] = [true, false, true, false];
-        let rooks_queens = (self.board.piece_type_bb(PieceType::Rook)
-            | self.board.piece_type_bb(PieceType::Queen))
-            & enemy;
+        let rooks_queens = self.board.orthogonal_sliders() & enemy;
         if !rooks_queens.is_empty() {
             for (dir, &is_left) in ORTHO_IS_LEFT.iter().enumerate() {
                 Self::scan_ray_for_check_pin(
@@ -76,9 +91,7 @@ where
 
         // Diagonal: [0]=NE(left), [1]=NW(left), [2]=SE(right), [3]=SW(right)
         const DIAG_IS_LEFT: [bool; 4] = [true, true, false, false];
-        let bishops_queens = (self.board.piece_type_bb(PieceType::Bishop)
-            | self.board.piece_type_bb(PieceType::Queen))
-            & enemy;
+        let bishops_queens = self.board.diagonal_sliders() & enemy;
         if !bishops_queens.is_empty() {
             for (dir, &is_left) in DIAG_IS_LEFT.iter().enumerate() {
                 Self::scan_ray_for_check_pin(
@@ -97,8 +110,8 @@ where
             }
         }
 
-        // Knight checks
-        let knights = self.board.piece_type_bb(PieceType::Knight) & enemy;
+        // Knight checks, archbishops and chancellors included
+        let knights = self.board.knight_jumpers() & enemy;
         if !knights.is_empty() {
             let knight_checks = geo.knight_attacks(king_idx) & knights;
             if !knight_checks.is_empty() {
@@ -201,6 +214,119 @@ where
         }
     }
 
+    pub(super) fn compute_check_squares(&self) -> CheckSquares<{ (W * H).div_ceil(64) }> {
+        let geo = Self::geo();
+        let color = self.turn;
+        let king_idx = match color {
+            Color::White => self.black_king_pos,
+            Color::Black => self.white_king_pos,
+        }
+        .to_index(W);
+        let occupied = self.board.occupied();
+        let own = self.board.color_bb(color);
+
+        let orthogonal = geo.orthogonal_attacks(king_idx, occupied);
+        let diagonal = geo.diagonal_attacks(king_idx, occupied);
+        let knight = geo.knight_attacks(king_idx);
+        let mut squares = [Bitboard::empty(); PieceType::ALL.len()];
+        squares[PieceType::Pawn as usize] = geo.pawn_attacks(king_idx, color != Color::White);
+        squares[PieceType::Knight as usize] = knight;
+        squares[PieceType::Bishop as usize] = diagonal;
+        squares[PieceType::Rook as usize] = orthogonal;
+        squares[PieceType::Queen as usize] = orthogonal | diagonal;
+        squares[PieceType::Archbishop as usize] = diagonal | knight;
+        squares[PieceType::Chancellor as usize] = orthogonal | knight;
+
+        // Our sliders that would attack the king on an empty board, with
+        // exactly one piece in the way, and that piece ours
+        let orthogonal_sliders = self.board.orthogonal_sliders() & own;
+        let diagonal_sliders = self.board.diagonal_sliders() & own;
+        let snipers = (geo.orthogonal_attacks(king_idx, Bitboard::empty()) & orthogonal_sliders)
+            | (geo.diagonal_attacks(king_idx, Bitboard::empty()) & diagonal_sliders);
+        let mut discoverers = Bitboard::empty();
+        for idx in snipers.iter_ones() {
+            let blockers = geo.between(king_idx, idx) & occupied;
+            if blockers.count() == 1 {
+                discoverers |= blockers & own;
+            }
+        }
+
+        CheckSquares {
+            king_idx,
+            squares,
+            discoverers,
+            occupied,
+            orthogonal_sliders,
+            diagonal_sliders,
+            is_white: color == Color::White,
+        }
+    }
+
+    /// Whether the legal move `mv`, made by a piece of type `piece_type`,
+    /// checks the opposing king.
+    pub(super) fn move_gives_check(
+        checks: &CheckSquares<{ (W * H).div_ceil(64) }>,
+        mv: &Move,
+        piece_type: PieceType,
+    ) -> bool {
+        let geo = Self::geo();
+        let src_idx = mv.src.to_index(W);
+        let dst_idx = mv.dst.to_index(W);
+
+        if !mv
+            .flags
+            .intersects(MoveFlags::PROMOTION | MoveFlags::EN_PASSANT | MoveFlags::CASTLE)
+        {
+            return checks.squares[piece_type as usize].get(dst_idx)
+                || (checks.discoverers.get(src_idx)
+                    && !geo.line(checks.king_idx, src_idx).get(dst_idx));
+        }
+
+        // Promotions, en passant and castling change more than one square or
+        // the piece type, so look at the position after the move instead
+        let placed = mv.promotion.unwrap_or(piece_type);
+        let moved = Bitboard::single(src_idx) | Bitboard::single(dst_idx);
+        let mut occupied =
+            checks.occupied.andnot(Bitboard::single(src_idx)) | Bitboard::single(dst_idx);
+        let mut orthogonal_sliders = checks.orthogonal_sliders.andnot(moved);
+        let mut diagonal_sliders = checks.diagonal_sliders.andnot(moved);
+        if matches!(
+            placed,
+            PieceType::Rook | PieceType::Queen | PieceType::Chancellor
+        ) {
+            orthogonal_sliders |= Bitboard::single(dst_idx);
+        }
+        if matches!(
+            placed,
+            PieceType::Bishop | PieceType::Queen | PieceType::Archbishop
+        ) {
+            diagonal_sliders |= Bitboard::single(dst_idx);
+        }
+        if mv.flags.contains(MoveFlags::EN_PASSANT) {
+            occupied = occupied.andnot(Bitboard::single(
+                usize::from(mv.src.row) * W + usize::from(mv.dst.col),
+            ));
+        }
+        if mv.flags.contains(MoveFlags::CASTLE) {
+            let (rook_from, rook_to) = mv.castling_rook_positions(W);
+            let rook_from = Bitboard::single(rook_from.to_index(W));
+            let rook_to = Bitboard::single(rook_to.to_index(W));
+            occupied = (occupied.andnot(rook_from) | Bitboard::single(dst_idx)) | rook_to;
+            orthogonal_sliders = orthogonal_sliders.andnot(rook_from) | rook_to;
+        }
+
+        let leaper = match placed {
+            PieceType::Pawn => geo.pawn_attacks(dst_idx, checks.is_white),
+            PieceType::Knight | PieceType::Archbishop | PieceType::Chancellor => {
+                geo.knight_attacks(dst_idx)
+            }
+            _ => Bitboard::empty(),
+        };
+        leaper.get(checks.king_idx)
+            || !(geo.orthogonal_attacks(checks.king_idx, occupied) & orthogonal_sliders).is_empty()
+            || !(geo.diagonal_attacks(checks.king_idx, occupied) & diagonal_sliders).is_empty()
+    }
+
     /// Compute the union of all squares attacked by pieces of `enemy_color`,
     /// using the given `occupied` bitboard (typically with our king removed).
     fn compute_enemy_attacks(
@@ -219,8 +345,8 @@ where
             attacks |= geo.pawn_attacks(idx, is_white);
         }
 
-        // Knights
-        let knights = self.board.piece_type_bb(PieceType::Knight) & enemy;
+        // Knights, archbishops and chancellors
+        let knights = self.board.knight_jumpers() & enemy;
         for idx in knights.iter_ones() {
             attacks |= geo.knight_attacks(idx);
         }
@@ -232,15 +358,14 @@ where
         };
         attacks |= geo.king_attacks(enemy_king_idx);
 
-        // Rooks + Queens (orthogonal)
-        let queens = self.board.piece_type_bb(PieceType::Queen) & enemy;
-        let rooks_queens = (self.board.piece_type_bb(PieceType::Rook) & enemy) | queens;
+        // Rooks + Queens + Chancellors (orthogonal)
+        let rooks_queens = self.board.orthogonal_sliders() & enemy;
         for idx in rooks_queens.iter_ones() {
             attacks |= geo.orthogonal_attacks(idx, occupied);
         }
 
-        // Bishops + Queens (diagonal)
-        let bishops_queens = (self.board.piece_type_bb(PieceType::Bishop) & enemy) | queens;
+        // Bishops + Queens + Archbishops (diagonal)
+        let bishops_queens = self.board.diagonal_sliders() & enemy;
         for idx in bishops_queens.iter_ones() {
             attacks |= geo.diagonal_attacks(idx, occupied);
         }
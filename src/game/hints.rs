@@ -0,0 +1,259 @@
+use crate::color::Color;
+use crate::r#move::{Move, MoveFlags};
+use crate::outcome::MoveList;
+use crate::pieces::{Piece, PieceType};
+use crate::position::Position;
+
+use super::Game;
+use super::attacks::exchange_value;
+
+/// Counts of the legal moves in a position, by kind, as returned by
+/// `Game::move_gen_stats`. A move can count towards several kinds, e.g. a
+/// capturing promotion that gives check.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveGenStats {
+    /// Legal moves.
+    pub total: u32,
+    /// Moves that capture a piece, including en passant.
+    pub captures: u32,
+    /// Moves that give check.
+    pub checks: u32,
+    /// Promotions, counting each promotion piece separately.
+    pub promotions: u32,
+    pub castles: u32,
+    /// Moves by the type of the moving piece, indexed by `PieceType as usize`.
+    pub by_piece_type: [u32; PieceType::ALL.len()],
+}
+
+#[hotpath::measure_all]
+impl MoveGenStats {
+    /// Moves made by pieces of type `piece_type`.
+    pub fn piece_type(&self, piece_type: PieceType) -> u32 {
+        self.by_piece_type[piece_type as usize]
+    }
+
+    fn record(&mut self, mv: &Move, piece_type: PieceType) {
+        self.total += 1;
+        self.captures += u32::from(mv.flags.contains(MoveFlags::CAPTURE));
+        self.checks += u32::from(mv.flags.contains(MoveFlags::CHECK));
+        self.promotions += u32::from(mv.promotion.is_some());
+        self.castles += u32::from(mv.flags.contains(MoveFlags::CASTLE));
+        self.by_piece_type[piece_type as usize] += 1;
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Legal moves that give check, in `legal_moves()` order.
+    pub fn checks(&mut self) -> MoveList {
+        let mut checks = self.legal_moves();
+        checks.retain(|mv| mv.flags.contains(MoveFlags::CHECK));
+        checks
+    }
+
+    /// `legal_moves()`, which already sets `MoveFlags::CHECK` on the moves
+    /// that give check.
+    pub fn legal_moves_with_checks(&mut self) -> MoveList {
+        self.legal_moves()
+    }
+
+    /// Whether `mv` is legal and gives check. Flags on `mv` are ignored.
+    pub fn gives_check(&mut self, mv: &Move) -> bool {
+        self.legal_moves().iter().any(|m| {
+            m.src == mv.src
+                && m.dst == mv.dst
+                && m.promotion == mv.promotion
+                && m.flags.contains(MoveFlags::CHECK)
+        })
+    }
+
+    /// Whether `mv` captures a piece in this position, including en passant.
+    /// Flags on `mv` are ignored.
+    pub fn is_capture(&self, mv: &Move) -> bool {
+        self.annotate_move(mv).flags.contains(MoveFlags::CAPTURE)
+    }
+
+    /// Whether `mv` resets the halfmove clock: a pawn move or a capture.
+    pub fn is_zeroing(&self, mv: &Move) -> bool {
+        self.board
+            .get_piece(&mv.src)
+            .is_some_and(|piece| piece.piece_type == PieceType::Pawn)
+            || self.is_capture(mv)
+    }
+
+    /// Counts of the legal moves in the current position by kind, gathered in
+    /// one pass over them.
+    pub fn move_gen_stats(&mut self) -> MoveGenStats {
+        let mut stats = MoveGenStats::default();
+        for mv in self.legal_moves() {
+            let piece_type = self
+                .board
+                .get_piece(&mv.src)
+                .expect("move_gen_stats: legal move has a piece on its source")
+                .piece_type;
+            stats.record(&mv, piece_type);
+        }
+        stats
+    }
+
+    /// Legal moves that capture a piece (including en passant), in
+    /// `legal_moves()` order.
+    pub fn captures(&mut self) -> MoveList {
+        let mut moves = self.legal_moves();
+        moves.retain(|mv| mv.flags.contains(MoveFlags::CAPTURE));
+        moves
+    }
+
+    /// Pieces of `color` (other than the king) that are attacked and
+    /// underdefended: attacked by a less valuable piece, undefended, or attacked
+    /// more times than they are defended. Ordered by square.
+    pub fn threatened_pieces(&self, color: Color) -> Vec<(Position, Piece)> {
+        let opponent = color.opposite();
+        self.pieces_iter(color)
+            .filter(|(pos, piece)| {
+                if piece.piece_type == PieceType::King {
+                    return false;
+                }
+                let attackers = self.attackers_to(pos, opponent);
+                if attackers.is_empty() {
+                    return false;
+                }
+                let defenders = self.attackers_to(pos, color);
+                let cheapest_attacker = attackers
+                    .iter_ones()
+                    .filter_map(|idx| self.board.piece_type_at(idx))
+                    .map(exchange_value)
+                    .min()
+                    .expect("threatened_pieces: attackers is non-empty");
+
+                cheapest_attacker < exchange_value(piece.piece_type)
+                    || defenders.is_empty()
+                    || attackers.count() > defenders.count()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::game::StandardGame;
+    use crate::r#move::MoveFlags;
+    use crate::pieces::PieceType;
+
+    fn lans(moves: &[crate::r#move::Move]) -> Vec<String> {
+        moves.iter().map(|m| m.to_lan()).collect()
+    }
+
+    #[test]
+    fn test_checks_and_captures() {
+        let mut game =
+            StandardGame::new("4k3/8/3p4/8/1B6/8/8/R3K3 w Q - 0 1", true).expect("valid FEN");
+        let legal = game.legal_moves();
+
+        assert_eq!(lans(&game.checks()), vec!["a1a8"]);
+        assert_eq!(lans(&game.captures()), vec!["b4d6"]);
+
+        // Probing doesn't disturb the position or its cached moves
+        assert!(game.legal_moves_cache.is_some());
+        assert_eq!(game.legal_moves(), legal);
+        assert_eq!(game.move_count(), 0);
+    }
+
+    #[test]
+    fn test_move_gen_stats() {
+        let mut game =
+            StandardGame::new("4k3/8/3p4/8/1B6/8/8/R3K3 w Q - 0 1", true).expect("valid FEN");
+        let stats = game.move_gen_stats();
+        assert_eq!(stats.total as usize, game.legal_moves().len());
+        assert_eq!(stats.captures, 1);
+        assert_eq!(stats.checks, 1);
+        assert_eq!(stats.castles, 1);
+        assert_eq!(stats.promotions, 0);
+        assert_eq!(stats.piece_type(PieceType::Pawn), 0);
+        // Queenside castling counts as a king move
+        assert_eq!(stats.piece_type(PieceType::King), 5 + 1);
+        assert_eq!(stats.by_piece_type.iter().sum::<u32>(), stats.total);
+        assert!(game.legal_moves_cache.is_some());
+
+        // Each promotion piece is a separate move
+        let mut game =
+            StandardGame::new("8/1P2k3/8/8/8/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+        let stats = game.move_gen_stats();
+        assert_eq!(stats.promotions, 4);
+        assert_eq!(stats.piece_type(PieceType::Pawn), 4);
+        assert_eq!(stats.checks, 0);
+    }
+
+    #[test]
+    fn test_move_predicates() {
+        let mut game = StandardGame::new("4k3/8/8/3p4/4P3/8/8/R3K3 w Q - 0 1", true)
+            .expect("test_move_predicates: valid FEN");
+        let mv = |game: &StandardGame, lan: &str| {
+            game.move_from_lan(lan)
+                .expect("test_move_predicates: valid LAN")
+        };
+
+        let capture = mv(&game, "e4d5");
+        assert!(game.is_capture(&capture));
+        assert!(game.is_zeroing(&capture));
+        assert!(!game.gives_check(&capture));
+
+        let rook_check = mv(&game, "a1a8");
+        assert!(game.gives_check(&rook_check));
+        assert!(!game.is_capture(&rook_check));
+        assert!(!game.is_zeroing(&rook_check));
+        assert!(game.is_zeroing(&mv(&game, "e4e5")));
+
+        // Illegal moves never give check
+        assert!(!game.gives_check(&mv(&game, "e1e3")));
+
+        let legal = game.legal_moves();
+        assert_eq!(game.legal_moves_with_checks(), legal);
+        let checks: Vec<_> = legal
+            .iter()
+            .filter(|m| m.flags.contains(MoveFlags::CHECK))
+            .copied()
+            .collect();
+        assert_eq!(checks.as_slice(), game.checks().as_slice());
+        assert_eq!(lans(&checks), vec!["a1a8"]);
+        assert_eq!(game.legal_moves(), legal);
+    }
+
+    #[test]
+    fn test_checks_include_castling_and_discovered() {
+        // Castling puts the rook on f1, checking the king on f8; moving the
+        // knight off the diagonal discovers a check from the queen
+        let mut game =
+            StandardGame::new("5k2/8/8/2N5/8/Q7/8/4K2R w K - 0 1", true).expect("valid FEN");
+        let checks = lans(&game.checks());
+        assert!(checks.contains(&"e1g1".to_string()));
+        assert!(checks.contains(&"c5e4".to_string()));
+        assert!(!checks.contains(&"a3b3".to_string()));
+    }
+
+    #[test]
+    fn test_threatened_pieces() {
+        // The knight is attacked by a pawn and the rook by a bishop, both
+        // cheaper than their targets; no black piece is attacked
+        let game =
+            StandardGame::new("4k3/8/8/3p4/2N1b3/8/2R5/4K3 w - - 0 1", true).expect("valid FEN");
+        let threatened: Vec<(String, PieceType)> = game
+            .threatened_pieces(Color::White)
+            .into_iter()
+            .map(|(pos, piece)| (pos.to_algebraic(), piece.piece_type))
+            .collect();
+        assert_eq!(
+            threatened,
+            vec![
+                ("c2".to_string(), PieceType::Rook),
+                ("c4".to_string(), PieceType::Knight),
+            ]
+        );
+        assert!(game.threatened_pieces(Color::Black).is_empty());
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Write;
+
+use crate::color::Color;
+use crate::pieces::PieceType;
+
+use super::Game;
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "white",
+        Color::Black => "black",
+    }
+}
+
+fn piece_type_name(piece_type: PieceType) -> &'static str {
+    match piece_type {
+        PieceType::Pawn => "pawn",
+        PieceType::Knight => "knight",
+        PieceType::Bishop => "bishop",
+        PieceType::Rook => "rook",
+        PieceType::Queen => "queen",
+        PieceType::King => "king",
+        PieceType::Archbishop => "archbishop",
+        PieceType::Chancellor => "chancellor",
+    }
+}
+
+/// Write a JSON string literal. Every value we emit is ASCII without quotes or
+/// backslashes (square names, LAN, FEN, enum names), so no escaping is needed.
+fn push_json_str(out: &mut String, s: &str) {
+    debug_assert!(
+        !s.contains(['"', '\\']) && s.is_ascii(),
+        "push_json_str: value {:?} needs escaping",
+        s,
+    );
+    out.push('"');
+    out.push_str(s);
+    out.push('"');
+}
+
+fn push_json_opt_str(out: &mut String, s: Option<&str>) {
+    match s {
+        Some(s) => push_json_str(out, s),
+        None => out.push_str("null"),
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Export the current state as a JSON document for GUI front-ends.
+    ///
+    /// The document contains the board dimensions, FEN, side to move, castling
+    /// rights, en passant square (if capturable), clocks, a list of pieces with
+    /// their squares, the legal moves in LAN, the last move played, and the
+    /// outcome (or `null` if the game is ongoing).
+    pub fn to_json_state(&mut self) -> String {
+        let mut out = String::new();
+
+        write!(out, "{{\"width\":{},\"height\":{},\"fen\":", W, H)
+            .expect("to_json_state: writing to a String cannot fail");
+        let fen = self.to_fen();
+        push_json_str(&mut out, &fen);
+
+        out.push_str(",\"turn\":");
+        push_json_str(&mut out, color_name(self.turn));
+
+        let rights = self.castling_rights;
+        write!(
+            out,
+            ",\"castling\":{{\"white_kingside\":{},\"white_queenside\":{},\"black_kingside\":{},\"black_queenside\":{}}}",
+            rights.white_kingside,
+            rights.white_queenside,
+            rights.black_kingside,
+            rights.black_queenside,
+        )
+        .expect("to_json_state: writing to a String cannot fail");
+
+        // Like FEN, only report the en passant square if the capture is legal
+        out.push_str(",\"en_passant\":");
+        let ep = if self.has_legal_en_passant() {
+            self.en_passant.map(|p| p.to_algebraic())
+        } else {
+            None
+        };
+        push_json_opt_str(&mut out, ep.as_deref());
+
+        write!(
+            out,
+            ",\"halfmove_clock\":{},\"fullmove_number\":{},\"is_check\":{}",
+            self.halfmove_clock,
+            self.fullmove_number,
+            self.is_check(),
+        )
+        .expect("to_json_state: writing to a String cannot fail");
+
+        out.push_str(",\"pieces\":[");
+        let mut first = true;
+        for color in [Color::White, Color::Black] {
+            for (pos, piece) in self.pieces_iter(color) {
+                if !first {
+                    out.push(',');
+                }
+                first = false;
+                out.push_str("{\"square\":");
+                push_json_str(&mut out, &pos.to_algebraic());
+                out.push_str(",\"color\":");
+                push_json_str(&mut out, color_name(piece.color));
+                out.push_str(",\"type\":");
+                push_json_str(&mut out, piece_type_name(piece.piece_type));
+                out.push_str(",\"symbol\":");
+                push_json_str(&mut out, &piece.to_char().to_string());
+                out.push('}');
+            }
+        }
+        out.push(']');
+
+        out.push_str(",\"legal_moves\":[");
+        for (i, mv) in self.legal_moves().iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            push_json_str(&mut out, &mv.to_lan());
+        }
+        out.push(']');
+
+        out.push_str(",\"last_move\":");
+        let last = self.move_history.last().map(|e| e.mv.to_lan());
+        push_json_opt_str(&mut out, last.as_deref());
+
+        out.push_str(",\"outcome\":");
+        let outcome = self.outcome().map(|o| o.to_string());
+        push_json_opt_str(&mut out, outcome.as_deref());
+
+        out.push('}');
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::StandardGame;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_json_state_standard() {
+        let mut game = StandardGame::standard();
+        let mv = game.move_from_lan("e2e4").expect("valid LAN");
+        assert!(game.make_move(&mv));
+
+        let json = game.to_json_state();
+        assert!(json.starts_with(
+            "{\"width\":8,\"height\":8,\"fen\":\"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1\",\"turn\":\"black\""
+        ));
+        assert!(json.contains(
+            "\"castling\":{\"white_kingside\":true,\"white_queenside\":true,\"black_kingside\":true,\"black_queenside\":true}"
+        ));
+        assert!(json.contains("\"en_passant\":null"));
+        assert!(json.contains(
+            "{\"square\":\"e4\",\"color\":\"white\",\"type\":\"pawn\",\"symbol\":\"P\"}"
+        ));
+        assert!(json.contains("\"e7e5\""));
+        assert_eq!(json.matches("\"square\"").count(), 32);
+        assert!(json.ends_with("\"last_move\":\"e2e4\",\"outcome\":null}"));
+    }
+
+    #[test]
+    fn test_json_state_checkmate() {
+        let mut game = StandardGame::standard();
+        play(&mut game, &["f2f3", "e7e5", "g2g4", "d8h4"]);
+
+        let json = game.to_json_state();
+        assert!(json.contains("\"is_check\":true"));
+        assert!(json.contains("\"legal_moves\":[]"));
+        assert!(json.ends_with("\"last_move\":\"d8h4\",\"outcome\":\"black_win\"}"));
+    }
+}
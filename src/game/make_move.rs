@@ -10,6 +10,9 @@ impl<const W: usize, const H: usize> Game<W, H>
 where
     [(); (W * H).div_ceil(64)]:,
 {
+    /// Flags on `mv` are ignored and re-derived from the position (see
+    /// `annotate_move`), so moves built without game context are handled correctly.
+    ///
     /// Returns: whether the move was successfully made
     pub fn make_move(&mut self, mv: &Move) -> bool {
         // Validate the move is from a piece of the correct color
@@ -18,18 +21,79 @@ where
             _ => return false,
         };
 
+        let mv = self.annotate_move(mv);
+
         // Check if the move is legal
-        if !self.is_legal_move(mv) {
+        if !self.is_legal_move(&mv) {
             return false;
         }
 
-        self.apply_move(mv, &piece);
+        self.apply_move(&mv, &piece);
+        self.notify_move(&mv);
         true
     }
 
     /// Apply a move that is already known to be legal. Skips legality checking.
-    /// Caller must guarantee the move came from `legal_moves()` or equivalent.
+    /// Caller must guarantee the move came from `legal_moves()` or equivalent,
+    /// so its flags are already correct (see `annotate_move`).
+    ///
+    /// This is the variant for search and self-play inner loops: `make_move`
+    /// re-derives flags and generates the source piece's moves to validate the
+    /// input, which costs several times more than applying the move itself.
+    /// Illegal or mis-flagged moves are caught by debug assertions, but in release
+    /// builds they silently corrupt the game state.
     pub fn make_move_unchecked(&mut self, mv: &Move) {
+        debug_assert!(
+            self.board
+                .get_piece(&mv.src)
+                .is_some_and(|p| p.color == self.turn),
+            "make_move_unchecked: no {:?} piece at source of {}",
+            self.turn,
+            mv.to_lan(),
+        );
+        debug_assert!(
+            self.is_legal_move(mv),
+            "make_move_unchecked: illegal move {}",
+            mv.to_lan(),
+        );
+        debug_assert_eq!(
+            mv.flags & Self::STATEFUL_FLAGS,
+            self.annotate_move(mv).flags & Self::STATEFUL_FLAGS,
+            "make_move_unchecked: move {} has wrong castle/en passant/promotion flags",
+            mv.to_lan(),
+        );
+        self.make_move_silent(mv);
+        self.notify_move(mv);
+    }
+
+    /// Flags that change how `apply_move` updates the board.
+    const STATEFUL_FLAGS: MoveFlags = MoveFlags::CASTLE
+        .union(MoveFlags::EN_PASSANT)
+        .union(MoveFlags::PROMOTION);
+
+    /// Pass the turn without moving a piece, for variants and setups that allow
+    /// passing. The null move is recorded in the move history like any other
+    /// move and can be taken back with `unmake_move`.
+    ///
+    /// Returns false, leaving the game unchanged, if the side to move is in
+    /// check: passing would leave its king en prise.
+    pub fn make_null_move(&mut self) -> bool {
+        if self.is_check() {
+            return false;
+        }
+        let mv = Move::null();
+        self.apply_null_move();
+        self.notify_move(&mv);
+        true
+    }
+
+    /// Like `make_move_unchecked`, but without notifying the observer.
+    /// Used for internal make/unmake probes that shouldn't be reported.
+    pub(crate) fn make_move_silent(&mut self, mv: &Move) {
+        if mv.is_null() {
+            self.apply_null_move();
+            return;
+        }
         let piece = self
             .board
             .get_piece(&mv.src)
@@ -37,6 +101,37 @@ where
         self.apply_move(mv, &piece);
     }
 
+    fn notify_move(&mut self, mv: &Move) {
+        // Take the observer out while it runs so `outcome()` can borrow `self` mutably.
+        if let Some(mut observer) = self.observer.0.take() {
+            observer.on_move(mv);
+            if let Some(outcome) = self.outcome() {
+                observer.on_game_over(outcome);
+            }
+            self.observer.0 = Some(observer);
+        }
+    }
+
+    fn apply_null_move(&mut self) {
+        self.invalidate_legal_moves();
+        let repetition_key = self.repetition_key();
+        self.move_history.push(MoveHistoryEntry {
+            mv: Move::null(),
+            captured: None,
+            castling_rights: self.castling_rights,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            piece_counts: self.piece_counts,
+            repetition_key,
+        });
+        self.en_passant = None;
+        self.halfmove_clock += 1;
+        if self.turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.opposite();
+    }
+
     pub(super) fn apply_move(&mut self, mv: &Move, piece: &Piece) {
         debug_assert!(
             piece.color == self.turn,
@@ -53,6 +148,8 @@ where
             self.board.get_piece(&mv.src),
         );
 
+        self.invalidate_legal_moves();
+
         // Store state for unmake.
         // Castle moves never capture — the destination may overlap with the
         // castling rook on small boards, but that rook is moved, not captured.
@@ -65,6 +162,7 @@ where
         let old_en_passant = self.en_passant;
         let old_halfmove = self.halfmove_clock;
         let old_piece_counts = self.piece_counts;
+        let old_repetition_key = self.repetition_key();
 
         // Handle castling rook first: move rook before placing king so pieces
         // don't overlap on the same square (which would corrupt bitboards on
@@ -180,6 +278,7 @@ where
             en_passant: old_en_passant,
             halfmove_clock: old_halfmove,
             piece_counts: old_piece_counts,
+            repetition_key: old_repetition_key,
         });
 
         // Verify king position cache consistency
@@ -203,7 +302,40 @@ where
     }
 
     pub fn unmake_move(&mut self) -> bool {
+        let Some(mv) = self.unmake_move_silent() else {
+            return false;
+        };
+        if self
+            .declared_outcome
+            .is_some_and(|(plies, _)| self.move_history.len() < plies)
+        {
+            self.declared_outcome = None;
+        }
+        if let Some(observer) = self.observer.0.as_mut() {
+            observer.on_undo(&mv);
+        }
+        true
+    }
+
+    /// Take back moves until `ply()` is `ply`, restoring the position after the
+    /// first `ply` moves of the history. Each move taken back is reported to
+    /// the observer as with `unmake_move`. Returns false, leaving the game
+    /// unchanged, if fewer moves than that have been played.
+    pub fn truncate_to_ply(&mut self, ply: usize) -> bool {
+        if ply > self.move_history.len() {
+            return false;
+        }
+        while self.move_history.len() > ply {
+            self.unmake_move();
+        }
+        true
+    }
+
+    /// Like `unmake_move`, but without notifying the observer.
+    /// Returns the move that was taken back, if any.
+    pub(crate) fn unmake_move_silent(&mut self) -> Option<Move> {
         if let Some(entry) = self.move_history.pop() {
+            self.invalidate_legal_moves();
             let mv = entry.mv;
             let captured = entry.captured;
             let old_castling = entry.castling_rights;
@@ -213,6 +345,15 @@ where
             // Switch turn back
             self.turn = self.turn.opposite();
 
+            if mv.is_null() {
+                self.en_passant = old_en_passant;
+                self.halfmove_clock = old_halfmove;
+                if self.turn == Color::Black {
+                    self.fullmove_number -= 1;
+                }
+                return Some(mv);
+            }
+
             // Remove the piece from its destination
             let dst_piece = self
                 .board
@@ -300,9 +441,9 @@ where
                 self.black_king_pos.row,
             );
 
-            true
+            Some(mv)
         } else {
-            false
+            None
         }
     }
 
@@ -4,18 +4,44 @@ use smallvec::SmallVec;
 use crate::bitboard::BoardGeometry;
 use crate::board::Board;
 use crate::color::Color;
+use crate::error::ChessError;
 use crate::limits::validate_board_dimensions;
-use crate::r#move::Move;
+use crate::r#move::{Move, MoveFlags};
+use crate::outcome::{DrawRules, GameOutcome, MoveList};
 use crate::pieces::{Piece, PieceType};
 use crate::position::Position;
 use std::hash::Hash;
 
 mod action;
+mod attacks;
+mod builder;
+mod canonical;
 mod check_pin;
+mod hints;
+mod json_state;
 mod make_move;
 #[macro_use]
 mod movegen;
+mod observer;
+mod pawn_structure;
+mod perft;
+mod playout;
+mod position_state;
+mod record;
+mod repetition;
 mod state;
+mod uci_position;
+mod validate;
+
+pub use builder::BoardBuilder;
+pub use canonical::Transform;
+pub use hints::MoveGenStats;
+pub use observer::GameObserver;
+use observer::ObserverSlot;
+pub(crate) use playout::PlayoutRng;
+pub use position_state::PositionState;
+pub use record::GameRecord;
+pub use validate::Violation;
 
 #[cfg(test)]
 mod tests_standard;
@@ -29,10 +55,10 @@ mod tests_san;
 #[cfg(test)]
 mod tests_actions;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct PieceCounts {
     /// counts[piece_type as usize][color_index] where color_index: White=0, Black=1
-    counts: [[u8; 2]; 6],
+    counts: [[u8; 2]; PieceType::ALL.len()],
 }
 
 impl Default for PieceCounts {
@@ -52,7 +78,7 @@ impl PieceCounts {
 
     pub fn new() -> Self {
         PieceCounts {
-            counts: [[0; 2]; 6],
+            counts: [[0; 2]; PieceType::ALL.len()],
         }
     }
 
@@ -61,19 +87,11 @@ impl PieceCounts {
         [(); (W * H).div_ceil(64)]:,
     {
         let mut counts = PieceCounts::new();
-        for piece_type in [
-            PieceType::Pawn,
-            PieceType::Knight,
-            PieceType::Bishop,
-            PieceType::Rook,
-            PieceType::Queen,
-            PieceType::King,
-        ] {
-            let bb = board.piece_type_bb(piece_type);
-            let white_count = (bb & board.color_bb(Color::White)).count() as u8;
-            let black_count = (bb & board.color_bb(Color::Black)).count() as u8;
-            counts.counts[piece_type as usize][0] = white_count;
-            counts.counts[piece_type as usize][1] = black_count;
+        for piece_type in PieceType::ALL {
+            counts.counts[piece_type as usize][0] =
+                board.piece_count(Color::White, piece_type) as u8;
+            counts.counts[piece_type as usize][1] =
+                board.piece_count(Color::Black, piece_type) as u8;
         }
         counts
     }
@@ -98,6 +116,15 @@ impl PieceCounts {
     pub fn get(&self, piece_type: PieceType, color: Color) -> u8 {
         self.counts[piece_type as usize][Self::color_idx(color)]
     }
+
+    /// Number of pieces of both colors, kings included.
+    pub fn total(&self) -> u32 {
+        self.counts
+            .iter()
+            .flatten()
+            .map(|&count| u32::from(count))
+            .sum()
+    }
 }
 
 #[derive(Clone)]
@@ -108,6 +135,8 @@ pub struct MoveHistoryEntry {
     en_passant: Option<Position>,
     halfmove_clock: u32,
     piece_counts: PieceCounts,
+    /// `repetition_key()` of the position before the move.
+    repetition_key: u64,
 }
 
 #[derive(Clone)]
@@ -121,6 +150,19 @@ where
 
     castling_rights: CastlingRights,
     castling_enabled: bool,
+    /// Whether pieces may capture their own side's pieces (other than the
+    /// king), as in the self-capture variant.
+    self_capture: bool,
+    /// Whether pawns may also promote to archbishops and chancellors, as in
+    /// Capablanca chess.
+    fairy_promotions: bool,
+    /// Columns the king lands on when castling (kingside, queenside), or
+    /// `None` for two squares towards the rook.
+    castling_king_cols: Option<(u8, u8)>,
+    /// Row, counted from each side's back row, from which pawns may double
+    /// push, or `None` for the second row.
+    double_push_row: Option<u8>,
+    draw_rules: DrawRules,
 
     en_passant: Option<Position>,
 
@@ -131,9 +173,22 @@ where
     black_king_pos: Position,
 
     piece_counts: PieceCounts,
+
+    /// Legal moves for the current position, filled lazily by `legal_moves()`
+    /// and cleared whenever the position changes.
+    legal_moves_cache: Option<MoveList>,
+
+    /// An outcome that ended the game other than over the board (a claimed
+    /// or agreed draw, a resignation or an adjudication), and the number of
+    /// moves played when it was declared.
+    /// Taking back any of those moves clears it.
+    declared_outcome: Option<(usize, GameOutcome)>,
+
+    observer: ObserverSlot,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CastlingRights {
     white_kingside: bool,
     white_queenside: bool,
@@ -212,6 +267,31 @@ impl CastlingRights {
     }
 }
 
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Row from which pawns of `color` may double push, as set with
+    /// `set_double_push_row`.
+    #[inline]
+    pub fn pawn_start_row(&self, color: Color) -> usize {
+        let row = self.double_push_row.map_or(1, usize::from);
+        match color {
+            Color::White => row,
+            Color::Black => H - 1 - row,
+        }
+    }
+
+    /// Row on which pawns of `color` promote.
+    #[inline]
+    pub const fn promotion_row(color: Color) -> usize {
+        match color {
+            Color::White => H - 1,
+            Color::Black => 0,
+        }
+    }
+}
+
 #[hotpath::measure_all]
 impl<const W: usize, const H: usize> Game<W, H>
 where
@@ -222,21 +302,65 @@ where
         &BoardGeometry::<W, H>::INSTANCE
     }
 
-    pub fn new(fen: &str, castling_enabled: bool) -> Result<Self, String> {
+    /// Square of the pawn that can be captured en passant on `ep`, i.e. the pawn
+    /// of the side not to move that just double pushed past `ep`. This depends
+    /// only on the ep square, not on which row the pawn started from.
+    pub(super) fn en_passant_victim(&self, ep: &Position) -> Option<Position> {
+        let row = match self.turn {
+            Color::White => ep.row.checked_sub(1)?,
+            Color::Black => ep.row + 1,
+        };
+        let pos = Position::new(ep.col, row);
+        pos.is_valid(W, H).then_some(pos)
+    }
+
+    /// Whether `ep` is consistent with the opponent's last move having been a
+    /// double push past it: the pawn's origin is on its start row, the square
+    /// itself and the origin are empty, and an opponent pawn sits just beyond
+    /// it.
+    fn is_valid_en_passant_square(&self, ep: &Position) -> bool {
+        if !ep.is_valid(W, H) || self.board.get_piece(ep).is_some() {
+            return false;
+        }
+        let Some(victim) = self.en_passant_victim(ep) else {
+            return false;
+        };
+        // The origin is the mirror of the victim square across `ep`
+        let origin_row = 2 * i32::from(ep.row) - i32::from(victim.row);
+        if origin_row < 0 || origin_row as usize != self.pawn_start_row(self.turn.opposite()) {
+            return false;
+        }
+        let origin = Position::new(ep.col, origin_row as u8);
+        self.board.get_piece(&victim) == Some(Piece::new(PieceType::Pawn, self.turn.opposite()))
+            && self.board.get_piece(&origin).is_none()
+    }
+
+    pub fn new(fen: &str, castling_enabled: bool) -> Result<Self, ChessError> {
+        Self::parse_fen(fen, castling_enabled, None)
+    }
+
+    /// Like `new`, but with pawns double pushing from `double_push_row`, which
+    /// the en passant square is checked against.
+    pub(super) fn parse_fen(
+        fen: &str,
+        castling_enabled: bool,
+        double_push_row: Option<u8>,
+    ) -> Result<Self, ChessError> {
         validate_board_dimensions(W, H)?;
+        Self::check_double_push_row(double_push_row)?;
 
         let parts: ArrayVec<&str, 6> = fen.split(' ').collect();
 
         if parts.is_empty() {
-            return Err("Empty FEN string".to_string());
+            return Err(ChessError::fen("empty FEN string"));
         }
 
         // FEN must have exactly 6 parts: position, turn, castling, en_passant, halfmove, fullmove
         if parts.len() != 6 {
-            return Err(format!(
-                "Invalid FEN: expected 6 parts, got {}",
+            return Err(ChessError::fen(format!(
+                "expected 6 parts, got {}",
                 parts.len()
-            ));
+            )));
         }
 
         let board = Board::new(parts[0])?;
@@ -245,7 +369,7 @@ where
         let turn = match parts[1] {
             "w" => Color::White,
             "b" => Color::Black,
-            _ => return Err("Invalid turn in FEN".to_string()),
+            _ => return Err(ChessError::fen(format!("invalid turn {}", parts[1]))),
         };
 
         // Castling rights
@@ -258,35 +382,43 @@ where
                     'k' => castling_rights.black_kingside = true,
                     'q' => castling_rights.black_queenside = true,
                     '-' => {}
-                    _ => return Err("Invalid castling rights in FEN".to_string()),
+                    _ => {
+                        return Err(ChessError::fen(format!(
+                            "invalid castling rights {}",
+                            parts[2]
+                        )));
+                    }
                 }
             }
         }
 
         // En passant
-        let en_passant = if parts[3] != "-" {
-            Some(Position::from_algebraic(parts[3])?)
-        } else {
-            None
-        };
+        let en_passant =
+            if parts[3] != "-" {
+                Some(Position::from_algebraic(parts[3]).map_err(|_| {
+                    ChessError::fen(format!("invalid en passant square {}", parts[3]))
+                })?)
+            } else {
+                None
+            };
 
         // Halfmove clock
         let halfmove_clock = parts[4]
             .parse()
-            .map_err(|_| "Invalid halfmove clock in FEN".to_string())?;
+            .map_err(|_| ChessError::fen(format!("invalid halfmove clock {}", parts[4])))?;
 
         // Fullmove number
         let fullmove_number = parts[5]
             .parse()
-            .map_err(|_| "Invalid fullmove number in FEN".to_string())?;
+            .map_err(|_| ChessError::fen(format!("invalid fullmove number {}", parts[5])))?;
 
         // Find king positions
         let white_king_pos = board
             .find_king(Color::White)
-            .ok_or("No white king found in FEN position".to_string())?;
+            .ok_or_else(|| ChessError::fen("no white king"))?;
         let black_king_pos = board
             .find_king(Color::Black)
-            .ok_or("No black king found in FEN position".to_string())?;
+            .ok_or_else(|| ChessError::fen("no black king"))?;
 
         debug_assert!(
             board.get_piece(&white_king_pos)
@@ -304,19 +436,63 @@ where
         // Count pieces from the board
         let piece_counts = PieceCounts::from_board(&board);
 
-        Ok(Game {
+        let game = Game {
             board,
             turn,
             move_history: SmallVec::new(),
             castling_rights,
             castling_enabled,
+            self_capture: false,
+            fairy_promotions: false,
+            castling_king_cols: None,
+            double_push_row,
+            draw_rules: DrawRules::default(),
             en_passant,
             halfmove_clock,
             fullmove_number,
             white_king_pos,
             black_king_pos,
             piece_counts,
-        })
+            legal_moves_cache: None,
+            declared_outcome: None,
+            observer: ObserverSlot::default(),
+        };
+
+        if let Some(ep) = en_passant
+            && !game.is_valid_en_passant_square(&ep)
+        {
+            return Err(ChessError::fen(format!(
+                "invalid en passant square {}",
+                parts[3]
+            )));
+        }
+
+        Ok(game)
+    }
+
+    /// Create a game from an ASCII diagram in the format printed by `Display`.
+    ///
+    /// The position starts with no castling rights, no en passant square and
+    /// fresh move clocks.
+    pub fn from_ascii(diagram: &str, turn: Color) -> Result<Self, ChessError> {
+        let board = Board::<W, H>::from_ascii(diagram)?;
+        let turn_char = if turn == Color::White { 'w' } else { 'b' };
+        Self::new(&format!("{} {} - - 0 1", board.fen(), turn_char), true)
+    }
+
+    /// Create a game with `pieces` on their squares, like `from_ascii`.
+    pub fn from_pieces(
+        pieces: impl IntoIterator<Item = (Position, Piece)>,
+        turn: Color,
+    ) -> Result<Self, ChessError> {
+        let board = Board::<W, H>::from_pieces(pieces);
+        let turn_char = if turn == Color::White { 'w' } else { 'b' };
+        Self::new(&format!("{} {} - - 0 1", board.fen(), turn_char), true)
+    }
+
+    #[inline]
+    fn invalidate_legal_moves(&mut self) {
+        self.legal_moves_cache = None;
     }
 
     pub fn width(&self) -> usize {
@@ -332,8 +508,10 @@ where
     }
 
     pub fn set_piece(&mut self, pos: &Position, piece: Option<Piece>) {
+        self.invalidate_legal_moves();
+        let existing = self.board.get_piece(pos);
         // Update piece counts for the removed piece
-        if let Some(existing) = self.board.get_piece(pos) {
+        if let Some(existing) = existing {
             self.piece_counts
                 .decrement(existing.piece_type, existing.color);
         }
@@ -341,25 +519,185 @@ where
         if let Some(ref p) = piece {
             self.piece_counts.increment(p.piece_type, p.color);
         }
-        self.board.set_piece(pos, piece)
+        self.board.set_piece(pos, piece);
+        if [existing, piece]
+            .iter()
+            .flatten()
+            .any(|p| p.piece_type == PieceType::King)
+        {
+            self.sync_king_positions();
+        }
+    }
+
+    /// Point the cached king squares at the kings on the board. A side left
+    /// without a king keeps its old square.
+    fn sync_king_positions(&mut self) {
+        if let Some(pos) = self.board.find_king(Color::White) {
+            self.white_king_pos = pos;
+        }
+        if let Some(pos) = self.board.find_king(Color::Black) {
+            self.black_king_pos = pos;
+        }
+    }
+
+    /// Set the side to move, for setting up positions. The en passant square
+    /// is cleared, as it only applies to the side that was to move. Fails,
+    /// leaving the game unchanged, if the other side would be in check.
+    pub fn set_turn(&mut self, turn: Color) -> Result<(), ChessError> {
+        if turn == self.turn {
+            return Ok(());
+        }
+        if let Some(king) = self.board.find_king(turn.opposite())
+            && self.is_square_attacked(&king, turn)
+        {
+            return Err(ChessError::InvalidPosition(
+                Violation::OpponentInCheck.to_string(),
+            ));
+        }
+        self.turn = turn;
+        self.en_passant = None;
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// Set which sides `color` may castle to. Fails, leaving the game
+    /// unchanged, if castling is disabled or a right is granted without the
+    /// king on its home row and the rook in the corner.
+    pub fn set_castling_rights(
+        &mut self,
+        color: Color,
+        kingside: bool,
+        queenside: bool,
+    ) -> Result<(), ChessError> {
+        if (kingside || queenside) && !self.castling_enabled {
+            return Err(ChessError::InvalidPosition(
+                "Castling is disabled in this game".to_string(),
+            ));
+        }
+        for (side, granted) in [(true, kingside), (false, queenside)] {
+            if granted && !self.castling_pieces_in_place(color, side) {
+                return Err(ChessError::InvalidPosition(
+                    Violation::InvalidCastlingRights {
+                        color,
+                        kingside: side,
+                    }
+                    .to_string(),
+                ));
+            }
+        }
+        self.castling_rights.set_kingside(color, kingside);
+        self.castling_rights.set_queenside(color, queenside);
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// Set the en passant square, the square a pawn of the side not to move
+    /// just skipped over. Fails, leaving the game unchanged, if no pawn could
+    /// have double pushed past `square`.
+    pub fn set_en_passant(&mut self, square: Option<Position>) -> Result<(), ChessError> {
+        if let Some(ep) = square
+            && !self.is_valid_en_passant_square(&ep)
+        {
+            return Err(ChessError::InvalidPosition(
+                Violation::InvalidEnPassant(ep).to_string(),
+            ));
+        }
+        self.en_passant = square;
+        self.invalidate_legal_moves();
+        Ok(())
     }
 
     /// Clear the board and reset piece counts.
     pub fn clear_board(&mut self) {
+        self.invalidate_legal_moves();
         self.board.clear();
         self.piece_counts = PieceCounts::new();
     }
 
+    /// Remove every piece of the given type and color, returning how many were
+    /// removed. Kings can't be removed. Castling rights and the en passant
+    /// square that no longer apply are cleared.
+    pub fn remove_all(&mut self, piece_type: PieceType, color: Color) -> Result<usize, ChessError> {
+        if piece_type == PieceType::King {
+            return Err(ChessError::InvalidPosition(
+                "Kings can't be removed".to_string(),
+            ));
+        }
+        let removed = self.board.remove_all(piece_type, color);
+        self.sync_piece_counts();
+        self.drop_stale_rights();
+        Ok(removed)
+    }
+
+    /// Move every piece by `dx` columns and `dy` rows. Fails, leaving the game
+    /// unchanged, if any piece would leave the board. Castling rights and the en
+    /// passant square no longer apply to the shifted position and are cleared.
+    pub fn shift_board(&mut self, dx: i32, dy: i32) -> Result<(), ChessError> {
+        self.board.shift(dx, dy)?;
+        self.sync_king_positions();
+        if dx != 0 || dy != 0 {
+            self.castling_rights = CastlingRights::none();
+            self.en_passant = None;
+        }
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// Place each of `pieces` on a random empty square (pawns never on the first
+    /// or last row), returning the squares chosen. Kings can't be placed, since a
+    /// game always has exactly one per side. Fails, leaving the game unchanged,
+    /// if some piece has no valid square left.
+    #[cfg(feature = "rand")]
+    pub fn place_random<R: rand::Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        pieces: &[Piece],
+    ) -> Result<Vec<Position>, ChessError> {
+        if pieces.iter().any(|p| p.piece_type == PieceType::King) {
+            return Err(ChessError::InvalidPosition(
+                "Kings can't be placed randomly".to_string(),
+            ));
+        }
+        let squares = self.board.place_random(rng, pieces)?;
+        self.sync_piece_counts();
+        self.drop_stale_rights();
+        Ok(squares)
+    }
+
+    /// Revoke the castling rights whose king or rook is no longer in place,
+    /// and clear an en passant square that no longer applies, after a bulk
+    /// edit of the board.
+    fn drop_stale_rights(&mut self) {
+        for color in [Color::White, Color::Black] {
+            if !self.castling_pieces_in_place(color, true) {
+                self.castling_rights.set_kingside(color, false);
+            }
+            if !self.castling_pieces_in_place(color, false) {
+                self.castling_rights.set_queenside(color, false);
+            }
+        }
+        if let Some(ep) = self.en_passant
+            && !self.is_valid_en_passant_square(&ep)
+        {
+            self.en_passant = None;
+        }
+    }
+
     /// Recompute piece counts from the board. Use after direct board manipulation.
     pub fn sync_piece_counts(&mut self) {
+        self.invalidate_legal_moves();
         self.piece_counts = PieceCounts::from_board(&self.board);
     }
 
+    /// The pieces of `color` and their squares, ordered by square. Collects
+    /// `pieces_iter`.
     pub fn pieces(&self, color: Color) -> Vec<(Position, Piece)> {
         self.board.pieces(color)
     }
 
-    pub(crate) fn pieces_iter(&self, color: Color) -> crate::board::PieceIterator<'_, W, H> {
+    /// Iterate over the pieces of `color` and their squares, ordered by
+    /// square, without allocating.
+    pub fn pieces_iter(&self, color: Color) -> impl Iterator<Item = (Position, Piece)> + '_ {
         self.board.pieces_iter(color)
     }
 
@@ -383,6 +721,37 @@ where
         self.move_history.len()
     }
 
+    /// Half-moves played since the game was set up, including null moves.
+    pub fn ply(&self) -> usize {
+        self.move_history.len()
+    }
+
+    /// Half-moves played since the start of the game, derived from the fullmove
+    /// number and side to move. Unlike `ply()`, this also counts moves made
+    /// before the starting FEN.
+    pub fn absolute_ply(&self) -> usize {
+        let completed = (self.fullmove_number.max(1) as usize - 1) * 2;
+        match self.turn {
+            Color::White => completed,
+            Color::Black => completed + 1,
+        }
+    }
+
+    /// The most recently played move, if any.
+    pub fn last_move(&self) -> Option<&Move> {
+        self.move_history.last().map(|entry| &entry.mv)
+    }
+
+    /// The piece captured by the most recently played move, if any.
+    pub fn last_capture(&self) -> Option<Piece> {
+        let entry = self.move_history.last()?;
+        if entry.mv.flags.contains(MoveFlags::EN_PASSANT) {
+            Some(Piece::new(PieceType::Pawn, self.turn))
+        } else {
+            entry.captured
+        }
+    }
+
     pub fn move_history(&self) -> &[MoveHistoryEntry] {
         &self.move_history
     }
@@ -391,6 +760,163 @@ where
         self.castling_enabled
     }
 
+    pub fn self_capture_enabled(&self) -> bool {
+        self.self_capture
+    }
+
+    /// Allow or forbid capturing one's own pieces (never the king). Off by
+    /// default; the rule applies from the current position on.
+    pub fn set_self_capture(&mut self, enabled: bool) {
+        self.self_capture = enabled;
+        self.invalidate_legal_moves();
+    }
+
+    pub fn fairy_promotions_enabled(&self) -> bool {
+        self.fairy_promotions
+    }
+
+    /// Let pawns also promote to archbishops and chancellors, as in
+    /// Capablanca chess. Off by default; the rule applies from the current
+    /// position on.
+    pub fn set_fairy_promotions(&mut self, enabled: bool) {
+        self.fairy_promotions = enabled;
+        self.invalidate_legal_moves();
+    }
+
+    /// The pieces pawns may promote to, in the order promotions are
+    /// generated.
+    pub fn promotion_pieces(&self) -> &'static [PieceType] {
+        if self.fairy_promotions {
+            &PieceType::PROMOTABLE_WITH_FAIRY
+        } else {
+            &PieceType::PROMOTABLE
+        }
+    }
+
+    /// Whether archbishops or chancellors are part of the game: on the board,
+    /// captured earlier, or reachable by promotion.
+    pub fn has_fairy_pieces(&self) -> bool {
+        self.fairy_promotions
+            || !(self.board.piece_type_bb(PieceType::Archbishop)
+                | self.board.piece_type_bb(PieceType::Chancellor))
+            .is_empty()
+            || self.move_history.iter().any(|entry| {
+                entry
+                    .captured
+                    .is_some_and(|piece| piece.piece_type.is_fairy())
+            })
+    }
+
+    /// Columns the king lands on when castling (kingside, queenside), if set
+    /// with `set_castling_king_cols`.
+    pub fn castling_king_cols(&self) -> Option<(u8, u8)> {
+        self.castling_king_cols
+    }
+
+    /// Land the king on the given columns when castling kingside and
+    /// queenside, e.g. the i- and c-files in Capablanca chess, instead of two
+    /// squares towards the rook. The rook always lands next to the king, on
+    /// the side it came from. `None` restores the default.
+    pub fn set_castling_king_cols(&mut self, cols: Option<(u8, u8)>) -> Result<(), ChessError> {
+        if let Some((kingside, queenside)) = cols
+            && (usize::from(kingside) >= W - 1 || queenside == 0 || queenside >= kingside)
+        {
+            return Err(ChessError::InvalidRules(format!(
+                "Invalid castling columns {} and {} for a board {} wide",
+                kingside, queenside, W
+            )));
+        }
+        self.castling_king_cols = cols;
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// Row, counted from each side's back row, from which pawns may double
+    /// push, if set with `set_double_push_row`.
+    pub fn double_push_row(&self) -> Option<u8> {
+        self.double_push_row
+    }
+
+    /// Let pawns double push from the given row, counted from each side's
+    /// back row (so 1 is the second rank), instead of the second row. `None`
+    /// restores the default. Fails, leaving the game unchanged, if a double
+    /// push from the row would reach the promotion row, or the current en
+    /// passant square doesn't fit the new row.
+    pub fn set_double_push_row(&mut self, row: Option<u8>) -> Result<(), ChessError> {
+        Self::check_double_push_row(row)?;
+        let old = std::mem::replace(&mut self.double_push_row, row);
+        if let Some(ep) = self.en_passant
+            && !self.is_valid_en_passant_square(&ep)
+        {
+            self.double_push_row = old;
+            return Err(ChessError::InvalidRules(format!(
+                "En passant square {} can't follow a double push from row {}",
+                ep.to_algebraic(),
+                row.map_or(1, usize::from)
+            )));
+        }
+        self.invalidate_legal_moves();
+        Ok(())
+    }
+
+    /// A double push from `row` must stop short of the promotion row.
+    fn check_double_push_row(row: Option<u8>) -> Result<(), ChessError> {
+        if let Some(row) = row
+            && usize::from(row) + 2 >= H - 1
+        {
+            return Err(ChessError::InvalidRules(format!(
+                "Invalid double push row {} for a board {} high",
+                row, H
+            )));
+        }
+        Ok(())
+    }
+
+    /// Column a king on `king_col` lands on when castling towards the
+    /// kingside or queenside rook, or `None` if it can't castle that way from
+    /// there. The king always moves at least two squares.
+    pub(super) fn castling_king_dst(&self, kingside: bool, king_col: usize) -> Option<usize> {
+        let dst = match (self.castling_king_cols, kingside) {
+            (Some((col, _)), true) => usize::from(col),
+            (Some((_, col)), false) => usize::from(col),
+            (None, true) => king_col + 2,
+            (None, false) => king_col.checked_sub(2)?,
+        };
+        let valid = if kingside {
+            dst >= king_col + 2 && dst < W - 1
+        } else {
+            dst + 2 <= king_col
+        };
+        valid.then_some(dst)
+    }
+
+    /// Whether `piece` moving from `src` to `dst` is a castle: a king moving
+    /// along its row onto the column `castling_king_dst` gives.
+    pub(super) fn is_castling_move(&self, src: &Position, dst: &Position, piece: &Piece) -> bool {
+        piece.piece_type == PieceType::King
+            && src.row == dst.row
+            && self.castling_king_dst(dst.col > src.col, usize::from(src.col))
+                == Some(usize::from(dst.col))
+    }
+
+    /// When the game is drawn automatically.
+    pub fn draw_rules(&self) -> &DrawRules {
+        &self.draw_rules
+    }
+
+    /// Change when the game is drawn automatically, e.g. to cap self-play
+    /// games at 200 plies. Applies from the current position on.
+    pub fn set_draw_rules(&mut self, rules: DrawRules) -> Result<(), ChessError> {
+        if rules.halfmove_limit == 0 || rules.repetition_limit < 2 {
+            return Err(ChessError::InvalidRules(format!(
+                "Invalid draw rules: halfmove limit {} must be positive and repetition limit {} at least 2",
+                rules.halfmove_limit, rules.repetition_limit
+            )));
+        }
+        self.draw_rules = rules;
+        Ok(())
+    }
+
     pub fn castling_rights(&self) -> &CastlingRights {
         &self.castling_rights
     }
@@ -398,6 +924,32 @@ where
     pub fn piece_counts(&self) -> &PieceCounts {
         &self.piece_counts
     }
+
+    /// Number of pieces of `color` and type `piece_type`, counted on the
+    /// board.
+    pub fn piece_count(&self, color: Color, piece_type: PieceType) -> u32 {
+        self.board.piece_count(color, piece_type)
+    }
+
+    /// Total value of the pieces of `color`, with `values` giving the value of
+    /// each piece type, e.g. `PieceType::value`.
+    pub fn material(&self, color: Color, values: impl Fn(PieceType) -> i32) -> i32 {
+        self.board.material(color, values)
+    }
+
+    /// Attach an observer, replacing any previously attached one.
+    pub fn set_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observer.0 = Some(observer);
+    }
+
+    /// Detach and return the current observer, if any.
+    pub fn take_observer(&mut self) -> Option<Box<dyn GameObserver>> {
+        self.observer.0.take()
+    }
+
+    pub fn has_observer(&self) -> bool {
+        self.observer.0.is_some()
+    }
 }
 
 /// Type alias for a standard 8x8 game
@@ -414,12 +966,52 @@ impl StandardGame {
     }
 }
 
+#[hotpath::measure_all]
+impl Game<10, 8> {
+    /// The starting position of Capablanca chess: a 10x8 board with an
+    /// archbishop (bishop + knight) and a chancellor (rook + knight) per side,
+    /// castling taking the king to the i- or c-file, and pawns promoting to
+    /// any piece but a king, the compound pieces included.
+    pub fn capablanca() -> Self {
+        let mut game = Self::new(
+            "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1",
+            true,
+        )
+        .expect("Failed to create Capablanca game");
+        game.set_castling_king_cols(Some((8, 2)))
+            .expect("Failed to set Capablanca castling columns");
+        game.set_fairy_promotions(true);
+        game
+    }
+}
+
 #[hotpath::measure_all]
 impl<const W: usize, const H: usize> std::fmt::Display for Game<W, H>
 where
     [(); (W * H).div_ceil(64)]:,
 {
+    /// `{}` prints a diagram of the board; `{:#}` prints the FEN.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Game(current_player: {})\n{}", self.turn(), self.board)
+        if f.alternate() {
+            // to_fen() needs &mut self to probe en passant legality; work on a
+            // history-free copy instead.
+            let mut copy = Self::from_position_state(&self.position_state());
+            write!(f, "{}", copy.to_fen())
+        } else {
+            write!(f, "Game(current_player: {})\n{}", self.turn(), self.board)
+        }
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> std::str::FromStr for Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    type Err = ChessError;
+
+    /// Parse a FEN string, with castling enabled.
+    fn from_str(fen: &str) -> Result<Self, Self::Err> {
+        Self::new(fen, true)
     }
 }
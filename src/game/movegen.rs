@@ -1,4 +1,7 @@
+use smallvec::SmallVec;
+
 use crate::bitboard::Bitboard;
+use crate::board::Board;
 use crate::color::Color;
 use crate::r#move::{Move, MoveFlags};
 use crate::outcome::MoveList;
@@ -18,22 +21,72 @@ where
             _ => return false,
         };
 
+        // The promotion piece must match exactly: a pawn move to the last row
+        // needs one of the promotable pieces, and no other move may carry one
+        let matches = |m: &Move| m.src == mv.src && m.dst == mv.dst && m.promotion == mv.promotion;
+
+        if let Some(ref cached) = self.legal_moves_cache {
+            return cached.iter().any(matches);
+        }
+
+        // Pieces other than pawns move to the squares they attack, and only a
+        // king can castle, so those moves are checked without generating any
+        if piece.piece_type != PieceType::Pawn && !self.is_castling_move(&mv.src, &mv.dst, &piece) {
+            if mv.promotion.is_some() || !mv.dst.is_valid(W, H) {
+                return false;
+            }
+            let dst_idx = mv.dst.to_index(W);
+            if !self.piece_attacks(&mv.src, &piece).get(dst_idx) {
+                return false;
+            }
+            let flags = if self.board.occupied().get(dst_idx) {
+                if !self.capturable(piece.color).get(dst_idx) {
+                    return false;
+                }
+                MoveFlags::CAPTURE
+            } else {
+                MoveFlags::empty()
+            };
+            return self
+                .is_pseudo_legal_move_legal(&Move::from_position(mv.src, mv.dst, flags), &piece);
+        }
+
         // Generate pseudo-legal moves only for the source piece
         let mut pseudo_legal = MoveList::new();
         self.generate_pseudo_legal_moves_for_piece_into(&mv.src, &piece, &mut pseudo_legal);
 
         // Find the matching pseudo-legal move (which has correct flags/promotion)
         // then check only that one for legality
-        if let Some(m) = pseudo_legal
-            .iter()
-            .find(|m| m.src == mv.src && m.dst == mv.dst)
-        {
+        if let Some(m) = pseudo_legal.iter().find(|m| matches(m)) {
             self.is_pseudo_legal_move_legal(m, &piece)
         } else {
             false
         }
     }
 
+    /// Check many candidate moves against the current position at once,
+    /// returning whether each is legal. Candidates match on source,
+    /// destination and promotion, as in `is_legal_move`. The legal moves (and
+    /// the check and pin analysis behind them) are computed once and shared by
+    /// every candidate.
+    pub fn filter_legal(&mut self, moves: &[Move]) -> Vec<bool> {
+        let mut legal: SmallVec<[(usize, usize, usize); 256]> = self
+            .legal_moves()
+            .iter()
+            .map(|m| m.canonical_key(W))
+            .collect();
+        legal.sort_unstable();
+
+        moves
+            .iter()
+            .map(|m| {
+                m.src.is_valid(W, H)
+                    && m.dst.is_valid(W, H)
+                    && legal.binary_search(&m.canonical_key(W)).is_ok()
+            })
+            .collect()
+    }
+
     /// Test whether a pseudo-legal move is actually legal (doesn't leave own king in check).
     /// Temporarily makes the move on the board, checks, then unmakes.
     pub(super) fn is_pseudo_legal_move_legal(&mut self, mv: &Move, piece: &Piece) -> bool {
@@ -51,18 +104,14 @@ where
         );
         let opponent = piece.color.opposite();
 
+        // With self-capture enabled the captured piece may be our own
         let captured =
             if mv.flags.contains(MoveFlags::CAPTURE) && !mv.flags.contains(MoveFlags::EN_PASSANT) {
-                let dst_idx = mv.dst.to_index(W);
-                debug_assert!(
-                    self.board.piece_type_at(dst_idx).is_some(),
-                    "CAPTURE flag set but no piece at destination index {}",
-                    dst_idx,
-                );
-                let pt = self.board.piece_type_at(dst_idx).expect(
-                    "is_pseudo_legal_move_legal: piece type must exist at capture destination",
-                );
-                Some(Piece::new(pt, opponent))
+                let cap = self
+                    .board
+                    .get_piece(&mv.dst)
+                    .expect("is_pseudo_legal_move_legal: piece must exist at capture destination");
+                Some(cap)
             } else {
                 None
             };
@@ -160,20 +209,115 @@ where
         !in_check
     }
 
+    /// All legal moves in the current position, with `MoveFlags::CHECK` set on
+    /// those that give check.
+    /// The result is cached until the position changes, so repeated calls are cheap.
+    ///
+    /// The order is deterministic for a given position: king moves, then
+    /// castling (kingside first), then the remaining pieces by ascending square
+    /// index, each piece's moves by ascending destination index, with promotions
+    /// in `promotion_pieces()` order. It depends only on the position, not on
+    /// the move history or platform. Use `legal_moves_sorted()` for an order that
+    /// is also independent of how moves are generated.
     pub fn legal_moves(&mut self) -> MoveList {
+        if let Some(ref cached) = self.legal_moves_cache {
+            return cached.clone();
+        }
         let mut moves = MoveList::new();
         self.for_each_legal_move(|mv| {
             moves.push(mv);
             false
         });
+        self.legal_moves_cache = Some(moves.clone());
+        moves
+    }
+
+    /// Replace the contents of `moves` with the legal moves, in
+    /// `legal_moves()` order. Unlike `legal_moves()`, this neither copies
+    /// into nor fills the legal move cache, so a search can reuse one buffer
+    /// per ply.
+    pub fn legal_moves_into(&mut self, moves: &mut MoveList) {
+        moves.clear();
+        if let Some(ref cached) = self.legal_moves_cache {
+            moves.extend_from_slice(cached);
+            return;
+        }
+        self.for_each_legal_move(|mv| {
+            moves.push(mv);
+            false
+        });
+    }
+
+    /// All legal moves in canonical order: by source square, then destination
+    /// square, then promotion piece (see `Move::canonical_key`).
+    pub fn legal_moves_sorted(&mut self) -> MoveList {
+        let mut moves = self.legal_moves();
+        moves.sort_unstable_by_key(|m| m.canonical_key(W));
+        moves
+    }
+
+    /// Legal moves that capture a piece (including en passant) or promote,
+    /// in `legal_moves()` order: the moves a quiescence search plays. Only
+    /// those moves are generated, unless the legal moves are already cached.
+    pub fn capture_moves(&mut self) -> MoveList {
+        let mut moves = MoveList::new();
+        if let Some(ref cached) = self.legal_moves_cache {
+            moves.extend(cached.iter().copied().filter(|mv| {
+                mv.flags
+                    .intersects(MoveFlags::CAPTURE | MoveFlags::PROMOTION)
+            }));
+            return moves;
+        }
+        self.for_each_legal_move_of(true, true, |mv| {
+            moves.push(mv);
+            false
+        });
+        moves
+    }
+
+    /// The legal moves if the side to move is in check, and none otherwise.
+    /// In check the generator only produces evasions (king moves, captures
+    /// of a single checker and blocks), so this is cheaper than it looks.
+    pub fn evasion_moves(&mut self) -> MoveList {
+        let mut moves = MoveList::new();
+        if self.is_check() {
+            self.legal_moves_into(&mut moves);
+        }
         moves
     }
 
     /// Iterates over all legal moves, invoking `f` for each.
     /// `f` returns `true` to stop iteration (short-circuit), `false` to continue.
     /// Returns `true` if short-circuited, `false` otherwise.
-    pub(super) fn for_each_legal_move(&mut self, mut f: impl FnMut(Move) -> bool) -> bool {
+    pub(super) fn for_each_legal_move(&mut self, f: impl FnMut(Move) -> bool) -> bool {
+        self.for_each_legal_move_of(false, true, f)
+    }
+
+    /// Like `for_each_legal_move`, leaving `MoveFlags::CHECK` unset, for
+    /// callers that only count the moves or look for one.
+    pub(super) fn for_each_legal_move_unflagged(&mut self, f: impl FnMut(Move) -> bool) -> bool {
+        self.for_each_legal_move_of(false, false, f)
+    }
+
+    /// Like `for_each_legal_move`, skipping moves that neither capture nor
+    /// promote if `captures_only`, and only testing for checks if
+    /// `flag_checks`.
+    fn for_each_legal_move_of(
+        &mut self,
+        captures_only: bool,
+        flag_checks: bool,
+        mut f: impl FnMut(Move) -> bool,
+    ) -> bool {
         let info = self.compute_check_pin_info();
+        let checks = flag_checks.then(|| self.compute_check_squares());
+        let mut f = |mut mv: Move, piece_type: PieceType| {
+            if let Some(checks) = &checks
+                && Self::move_gives_check(checks, &mv, piece_type)
+            {
+                mv.flags |= MoveFlags::CHECK;
+            }
+            f(mv)
+        };
         let color = self.turn;
         let opponent = color.opposite();
         let king_pos = match color {
@@ -181,9 +325,16 @@ where
             Color::Black => self.black_king_pos,
         };
         let king_idx = king_pos.to_index(W);
-        let own = self.board.color_bb(color);
         let occupied = self.board.occupied();
+        let capturable = self.capturable(color);
+        let blocked = occupied.andnot(capturable);
         let occupied_no_king = occupied.andnot(Bitboard::single(king_idx));
+        // Destinations allowed for moves other than pawn pushes
+        let target_mask = if captures_only {
+            capturable
+        } else {
+            !Bitboard::empty()
+        };
         let geo = Self::geo();
 
         // -----------------------------------------------------------------
@@ -191,8 +342,9 @@ where
         // -----------------------------------------------------------------
         let targets = geo
             .king_attacks(king_idx)
-            .andnot(own)
-            .andnot(info.king_danger_squares);
+            .andnot(blocked)
+            .andnot(info.king_danger_squares)
+            & target_mask;
         for dst_idx in targets.iter_ones() {
             let is_capture = occupied.get(dst_idx);
             if is_capture {
@@ -207,7 +359,7 @@ where
             } else {
                 MoveFlags::empty()
             };
-            if f(Move::from_position(king_pos, dst, flags)) {
+            if f(Move::from_position(king_pos, dst, flags), PieceType::King) {
                 return true;
             }
         }
@@ -215,38 +367,36 @@ where
         // -----------------------------------------------------------------
         // King castling
         // -----------------------------------------------------------------
-        if self.castling_enabled && W >= 5 && info.num_checkers == 0 {
+        if self.castling_enabled && W >= 5 && info.num_checkers == 0 && !captures_only {
             let row = usize::from(king_pos.row);
-            if self.castling_rights.has_kingside(color) {
-                let king_dst_col = usize::from(king_pos.col) + 2;
-                let rook_col = W - 1;
-                if king_dst_col < rook_col
-                    && let Some(mv) = self.try_castle_legal(
-                        &king_pos,
-                        row,
-                        king_dst_col,
-                        rook_col,
-                        opponent,
-                        occupied_no_king,
-                    )
-                    && f(mv)
-                {
-                    return true;
-                }
+            let king_col = usize::from(king_pos.col);
+            if self.castling_rights.has_kingside(color)
+                && let Some(king_dst_col) = self.castling_king_dst(true, king_col)
+                && let Some(mv) = self.try_castle_legal(
+                    &king_pos,
+                    row,
+                    king_dst_col,
+                    W - 1,
+                    opponent,
+                    occupied_no_king,
+                )
+                && f(mv, PieceType::King)
+            {
+                return true;
             }
-            if self.castling_rights.has_queenside(color) && king_pos.col >= 2 {
-                let king_dst_col = usize::from(king_pos.col) - 2;
-                if let Some(mv) = self.try_castle_legal(
+            if self.castling_rights.has_queenside(color)
+                && let Some(king_dst_col) = self.castling_king_dst(false, king_col)
+                && let Some(mv) = self.try_castle_legal(
                     &king_pos,
                     row,
                     king_dst_col,
                     0,
                     opponent,
                     occupied_no_king,
-                ) && f(mv)
-                {
-                    return true;
-                }
+                )
+                && f(mv, PieceType::King)
+            {
+                return true;
             }
         }
 
@@ -257,8 +407,6 @@ where
             return false;
         }
 
-        let enemy = self.board.color_bb(opponent);
-
         for idx in self.board.color_bb(color).iter_ones() {
             if idx == king_idx {
                 continue;
@@ -287,7 +435,7 @@ where
 
             match pt {
                 PieceType::Knight => {
-                    let targets = geo.knight_attacks(idx).andnot(own) & move_mask;
+                    let targets = geo.knight_attacks(idx).andnot(blocked) & move_mask & target_mask;
                     for dst_idx in targets.iter_ones() {
                         let dst = Position::from_index(dst_idx, W);
                         let flags = if occupied.get(dst_idx) {
@@ -295,7 +443,7 @@ where
                         } else {
                             MoveFlags::empty()
                         };
-                        if f(Move::from_position(pos, dst, flags)) {
+                        if f(Move::from_position(pos, dst, flags), pt) {
                             return true;
                         }
                     }
@@ -304,80 +452,75 @@ where
                     let piece = Piece::new(PieceType::Pawn, color);
                     let is_white = color == Color::White;
                     let src_bb = Bitboard::single(idx);
-                    let start_row = if is_white { 1 } else { H - 2 };
-                    let promo_row = if is_white { H - 2 } else { 1 };
+                    let start_row = self.pawn_start_row(color);
+                    let last_row = Self::promotion_row(color);
 
-                    // Single push
+                    // Single and double pushes
                     let push = geo.pawn_push(src_bb, is_white).andnot(occupied);
-                    let legal_push = push & move_mask;
-                    for pidx in legal_push.iter_ones() {
-                        let dst = Position::from_index(pidx, W);
-                        if usize::from(pos.row) == promo_row {
-                            for promo_pt in &PieceType::PROMOTABLE {
-                                if f(Move::from_position_with_promotion(
-                                    pos,
-                                    dst,
-                                    MoveFlags::PROMOTION,
-                                    *promo_pt,
-                                )) {
-                                    return true;
-                                }
-                            }
-                        } else if f(Move::from_position(pos, dst, MoveFlags::empty())) {
-                            return true;
-                        }
+                    let mut double = Bitboard::empty();
+                    if usize::from(pos.row) == start_row && !push.is_empty() && !captures_only {
+                        double = geo.pawn_push(push, is_white).andnot(occupied) & move_mask;
                     }
 
-                    // Double push
-                    if usize::from(pos.row) == start_row && !push.is_empty() {
-                        let double = geo.pawn_push(push, is_white).andnot(occupied) & move_mask;
-                        for pidx in double.iter_ones() {
-                            let dst = Position::from_index(pidx, W);
-                            if f(Move::from_position(pos, dst, MoveFlags::DOUBLE_PUSH)) {
-                                return true;
-                            }
+                    // Captures, and en passant (made and unmade to catch
+                    // discovered checks along the row)
+                    let attacks = geo.pawn_attacks(idx, is_white);
+                    let captures = attacks & capturable & move_mask;
+                    let mut en_passant = Bitboard::empty();
+                    if let Some(ep) = self.en_passant {
+                        let ep_bb = Bitboard::single(ep.to_index(W));
+                        if !(attacks & ep_bb).is_empty()
+                            && self.is_pseudo_legal_move_legal(
+                                &Move::from_position(
+                                    pos,
+                                    ep,
+                                    MoveFlags::CAPTURE | MoveFlags::EN_PASSANT,
+                                ),
+                                &piece,
+                            )
+                        {
+                            en_passant = ep_bb;
                         }
                     }
 
-                    // Captures
-                    let attacks = geo.pawn_attacks(idx, is_white);
-                    let captures = attacks & enemy & move_mask;
-                    for cidx in captures.iter_ones() {
-                        let dst = Position::from_index(cidx, W);
-                        if usize::from(pos.row) == promo_row {
-                            for promo_pt in &PieceType::PROMOTABLE {
-                                if f(Move::from_position_with_promotion(
-                                    pos,
-                                    dst,
-                                    MoveFlags::CAPTURE | MoveFlags::PROMOTION,
-                                    *promo_pt,
-                                )) {
+                    // The destination sets are disjoint, so walk them together
+                    // to emit the moves by ascending destination
+                    let targets = (push & move_mask) | double | captures | en_passant;
+                    for dst_idx in targets.iter_ones() {
+                        let dst = Position::from_index(dst_idx, W);
+                        let flags = if captures.get(dst_idx) {
+                            MoveFlags::CAPTURE
+                        } else if en_passant.get(dst_idx) {
+                            MoveFlags::CAPTURE | MoveFlags::EN_PASSANT
+                        } else if double.get(dst_idx) {
+                            MoveFlags::DOUBLE_PUSH
+                        } else {
+                            MoveFlags::empty()
+                        };
+                        if usize::from(dst.row) == last_row {
+                            for promo_pt in self.promotion_pieces() {
+                                if f(
+                                    Move::from_position_with_promotion(
+                                        pos,
+                                        dst,
+                                        flags | MoveFlags::PROMOTION,
+                                        *promo_pt,
+                                    ),
+                                    pt,
+                                ) {
                                     return true;
                                 }
                             }
-                        } else if f(Move::from_position(pos, dst, MoveFlags::CAPTURE)) {
+                        } else if (!captures_only || flags.contains(MoveFlags::CAPTURE))
+                            && f(Move::from_position(pos, dst, flags), pt)
+                        {
                             return true;
                         }
                     }
-
-                    // En passant (make/unmake fallback for discovered check)
-                    if let Some(ep) = self.en_passant {
-                        let ep_bb = Bitboard::single(ep.to_index(W));
-                        if !(attacks & ep_bb).is_empty() {
-                            let ep_move = Move::from_position(
-                                pos,
-                                ep,
-                                MoveFlags::CAPTURE | MoveFlags::EN_PASSANT,
-                            );
-                            if self.is_pseudo_legal_move_legal(&ep_move, &piece) && f(ep_move) {
-                                return true;
-                            }
-                        }
-                    }
                 }
                 PieceType::Bishop => {
                     let attacks = geo.diagonal_attacks(idx, occupied);
-                    let targets = attacks.andnot(own) & move_mask;
+                    let targets = attacks.andnot(blocked) & move_mask & target_mask;
                     for dst_idx in targets.iter_ones() {
                         let dst = Position::from_index(dst_idx, W);
                         let flags = if occupied.get(dst_idx) {
@@ -385,14 +528,14 @@ where
                         } else {
                             MoveFlags::empty()
                         };
-                        if f(Move::from_position(pos, dst, flags)) {
+                        if f(Move::from_position(pos, dst, flags), pt) {
                             return true;
                         }
                     }
                 }
                 PieceType::Rook => {
                     let attacks = geo.orthogonal_attacks(idx, occupied);
-                    let targets = attacks.andnot(own) & move_mask;
+                    let targets = attacks.andnot(blocked) & move_mask & target_mask;
                     for dst_idx in targets.iter_ones() {
                         let dst = Position::from_index(dst_idx, W);
                         let flags = if occupied.get(dst_idx) {
@@ -400,15 +543,15 @@ where
                         } else {
                             MoveFlags::empty()
                         };
-                        if f(Move::from_position(pos, dst, flags)) {
+                        if f(Move::from_position(pos, dst, flags), pt) {
                             return true;
                         }
                     }
                 }
-                PieceType::Queen => {
+                PieceType::Queen | PieceType::Archbishop | PieceType::Chancellor => {
                     let attacks =
-                        geo.orthogonal_attacks(idx, occupied) | geo.diagonal_attacks(idx, occupied);
-                    let targets = attacks.andnot(own) & move_mask;
+                        Board::<W, H>::piece_attacks(&pos, &Piece::new(pt, color), occupied);
+                    let targets = attacks.andnot(blocked) & move_mask & target_mask;
                     for dst_idx in targets.iter_ones() {
                         let dst = Position::from_index(dst_idx, W);
                         let flags = if occupied.get(dst_idx) {
@@ -416,7 +559,7 @@ where
                         } else {
                             MoveFlags::empty()
                         };
-                        if f(Move::from_position(pos, dst, flags)) {
+                        if f(Move::from_position(pos, dst, flags), pt) {
                             return true;
                         }
                     }
@@ -428,6 +571,21 @@ where
         false
     }
 
+    /// Squares holding pieces that `color` may capture: the opponent's pieces,
+    /// plus its own pieces other than the king when self-capture is enabled.
+    fn capturable(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        if self.self_capture {
+            let king_idx = match color {
+                Color::White => self.white_king_pos,
+                Color::Black => self.black_king_pos,
+            }
+            .to_index(W);
+            self.board.occupied().andnot(Bitboard::single(king_idx))
+        } else {
+            self.board.color_bb(color.opposite())
+        }
+    }
+
     pub fn pseudo_legal_moves(&self) -> MoveList {
         let mut moves = MoveList::new();
 
@@ -449,9 +607,14 @@ where
 
             self.generate_pseudo_legal_moves_for_piece_into(src, &piece, &mut pseudo_legal);
 
+            let checks = self.compute_check_squares();
             for mv in pseudo_legal.iter() {
                 if self.is_pseudo_legal_move_legal(mv, &piece) {
-                    moves.push(*mv);
+                    let mut mv = *mv;
+                    if Self::move_gives_check(&checks, &mv, piece.piece_type) {
+                        mv.flags |= MoveFlags::CHECK;
+                    }
+                    moves.push(mv);
                 }
             }
 
@@ -474,6 +637,9 @@ where
             PieceType::Rook => self.generate_pseudo_legal_rook_moves_into(src, piece, moves),
             PieceType::Queen => self.generate_pseudo_legal_queen_moves_into(src, piece, moves),
             PieceType::King => self.generate_pseudo_legal_king_moves_into(src, piece, moves),
+            PieceType::Archbishop | PieceType::Chancellor => {
+                self.generate_pseudo_legal_compound_moves_into(src, piece, moves)
+            }
         }
     }
 
@@ -484,12 +650,12 @@ where
         moves: &mut MoveList,
     ) {
         let occupied = self.board.occupied();
-        let enemy = self.board.color_bb(piece.color.opposite());
+        let capturable = self.capturable(piece.color);
         let is_white = piece.color == Color::White;
         let geo = Self::geo();
 
-        let start_row = if is_white { 1 } else { H - 2 };
-        let promo_row = if is_white { H - 2 } else { 1 };
+        let start_row = self.pawn_start_row(piece.color);
+        let last_row = Self::promotion_row(piece.color);
 
         let src_idx = src.to_index(W);
         let src_bb = Bitboard::single(src_idx);
@@ -498,8 +664,8 @@ where
         let push = geo.pawn_push(src_bb, is_white).andnot(occupied);
         for idx in push.iter_ones() {
             let dst = Position::from_index(idx, W);
-            if usize::from(src.row) == promo_row {
-                for pt in &PieceType::PROMOTABLE {
+            if usize::from(dst.row) == last_row {
+                for pt in self.promotion_pieces() {
                     moves.push(Move::from_position_with_promotion(
                         *src,
                         dst,
@@ -521,13 +687,13 @@ where
             }
         }
 
-        // Captures: diagonal attacks into enemy pieces
+        // Captures: diagonal attacks into capturable pieces
         let attacks = geo.pawn_attacks(src_idx, is_white);
-        let captures = attacks & enemy;
+        let captures = attacks & capturable;
         for idx in captures.iter_ones() {
             let dst = Position::from_index(idx, W);
-            if usize::from(src.row) == promo_row {
-                for pt in &PieceType::PROMOTABLE {
+            if usize::from(dst.row) == last_row {
+                for pt in self.promotion_pieces() {
                     moves.push(Move::from_position_with_promotion(
                         *src,
                         dst,
@@ -543,11 +709,12 @@ where
         // En passant
         if let Some(ep) = self.en_passant {
             debug_assert!(
-                ep.row == 2 || usize::from(ep.row) == H - 3,
-                "en passant square ({}, {}) is not on a valid row (expected row 2 or {})",
+                self.en_passant_victim(&ep)
+                    .and_then(|v| self.board.get_piece(&v))
+                    == Some(Piece::new(PieceType::Pawn, piece.color.opposite())),
+                "en passant square ({}, {}) has no capturable pawn behind it",
                 ep.col,
                 ep.row,
-                H - 3,
             );
             let ep_bb = Bitboard::single(ep.to_index(W));
             if !(attacks & ep_bb).is_empty() {
@@ -566,10 +733,10 @@ where
         piece: &Piece,
         moves: &mut MoveList,
     ) {
-        let own_color = self.board.color_bb(piece.color);
         let occupied = self.board.occupied();
+        let blocked = occupied.andnot(self.capturable(piece.color));
         let src_idx = src.to_index(W);
-        let attacks = Self::geo().knight_attacks(src_idx).andnot(own_color);
+        let attacks = Self::geo().knight_attacks(src_idx).andnot(blocked);
 
         for idx in attacks.iter_ones() {
             let to = Position::from_index(idx, W);
@@ -590,8 +757,8 @@ where
         moves: &mut MoveList,
     ) {
         let occupied = self.board.occupied();
-        let own_color = self.board.color_bb(piece.color);
-        let targets = attacks.andnot(own_color);
+        let blocked = occupied.andnot(self.capturable(piece.color));
+        let targets = attacks.andnot(blocked);
 
         for idx in targets.iter_ones() {
             let dst = Position::from_index(idx, W);
@@ -641,6 +808,17 @@ where
         self.generate_sliding_moves_from_attacks_into(src, piece, attacks, moves)
     }
 
+    /// Archbishop and chancellor moves: a slider's moves plus a knight's.
+    fn generate_pseudo_legal_compound_moves_into(
+        &self,
+        src: &Position,
+        piece: &Piece,
+        moves: &mut MoveList,
+    ) {
+        let attacks = Board::<W, H>::piece_attacks(src, piece, self.board.occupied());
+        self.generate_sliding_moves_from_attacks_into(src, piece, attacks, moves)
+    }
+
     fn generate_pseudo_legal_king_moves_into(
         &self,
         src: &Position,
@@ -655,12 +833,12 @@ where
             "king move generation src {:?} doesn't match tracked king pos",
             src,
         );
-        let own_color = self.board.color_bb(piece.color);
         let occupied = self.board.occupied();
+        let blocked = occupied.andnot(self.capturable(piece.color));
 
         // Regular moves
         let src_idx = src.to_index(W);
-        let attacks = Self::geo().king_attacks(src_idx).andnot(own_color);
+        let attacks = Self::geo().king_attacks(src_idx).andnot(blocked);
 
         for idx in attacks.iter_ones() {
             let to = Position::from_index(idx, W);
@@ -677,18 +855,17 @@ where
             let row = usize::from(src.row);
             let opponent = piece.color.opposite();
 
-            // Kingside: king to col king+2, rook from last col to king+1
-            if self.castling_rights.has_kingside(piece.color) {
-                let king_dst = usize::from(src.col) + 2;
-                let rook_col = W - 1;
-                if king_dst < rook_col {
-                    self.try_generate_castle(src, row, king_dst, rook_col, opponent, moves);
-                }
+            // Kingside: rook from the last col to the king's left
+            if self.castling_rights.has_kingside(piece.color)
+                && let Some(king_dst) = self.castling_king_dst(true, usize::from(src.col))
+            {
+                self.try_generate_castle(src, row, king_dst, W - 1, opponent, moves);
             }
 
-            // Queenside: king to col king-2, rook from col 0 to king-1
-            if self.castling_rights.has_queenside(piece.color) && src.col >= 2 {
-                let king_dst = usize::from(src.col) - 2;
+            // Queenside: rook from col 0 to the king's right
+            if self.castling_rights.has_queenside(piece.color)
+                && let Some(king_dst) = self.castling_king_dst(false, usize::from(src.col))
+            {
                 self.try_generate_castle(src, row, king_dst, 0, opponent, moves);
             }
         }
@@ -0,0 +1,30 @@
+use crate::r#move::Move;
+use crate::outcome::GameOutcome;
+
+/// Listener for state changes on a `Game`.
+///
+/// All methods have empty default implementations, so implementors only need to
+/// override the events they care about.
+pub trait GameObserver: Send + Sync {
+    /// Called after a move has been made on the board.
+    fn on_move(&mut self, _mv: &Move) {}
+
+    /// Called after a move has been taken back.
+    fn on_undo(&mut self, _mv: &Move) {}
+
+    /// Called after a move that ends the game.
+    fn on_game_over(&mut self, _outcome: GameOutcome) {}
+}
+
+/// Holds the observer attached to a game.
+///
+/// Cloning a game does not carry its observer over: clones are typically used as
+/// scratch copies for search or rollouts, whose moves shouldn't be reported.
+#[derive(Default)]
+pub(super) struct ObserverSlot(pub(super) Option<Box<dyn GameObserver>>);
+
+impl Clone for ObserverSlot {
+    fn clone(&self) -> Self {
+        ObserverSlot(None)
+    }
+}
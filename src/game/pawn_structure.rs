@@ -0,0 +1,159 @@
+use crate::bitboard::Bitboard;
+use crate::color::Color;
+use crate::pieces::PieceType;
+
+use super::Game;
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Every square on column `col`.
+    fn file_mask(col: usize) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let mut mask = Bitboard::empty();
+        for row in 0..H {
+            mask.set(row * W + col);
+        }
+        mask
+    }
+
+    /// Every square on the columns either side of `col`.
+    fn adjacent_files_mask(col: usize) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let mut mask = Bitboard::empty();
+        if col > 0 {
+            mask |= Self::file_mask(col - 1);
+        }
+        if col + 1 < W {
+            mask |= Self::file_mask(col + 1);
+        }
+        mask
+    }
+
+    /// Every square on the rows in front of `row`, from `color`'s side.
+    fn rows_ahead_mask(row: usize, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let rows = match color {
+            Color::White => row + 1..H,
+            Color::Black => 0..row,
+        };
+        let mut mask = Bitboard::empty();
+        for idx in rows.start * W..rows.end * W {
+            mask.set(idx);
+        }
+        mask
+    }
+
+    fn pawns_bb(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        self.board.piece_type_bb(PieceType::Pawn) & self.board.color_bb(color)
+    }
+
+    /// The pawns of `color` that match `predicate`, called with each pawn's
+    /// column and row.
+    fn pawns_where(
+        &self,
+        color: Color,
+        mut predicate: impl FnMut(usize, usize) -> bool,
+    ) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let mut matching = Bitboard::empty();
+        for idx in self.pawns_bb(color).iter_ones() {
+            if predicate(idx % W, idx / W) {
+                matching.set(idx);
+            }
+        }
+        matching
+    }
+
+    /// Columns holding a pawn of `color`, as a mask with bit `col` set for
+    /// each.
+    pub fn pawn_files(&self, color: Color) -> u32 {
+        self.pawns_bb(color)
+            .iter_ones()
+            .fold(0, |files, idx| files | (1 << (idx % W)))
+    }
+
+    /// Pawns of `color` with no enemy pawn in front of them on their own or
+    /// an adjacent column.
+    pub fn passed_pawns(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let enemy_pawns = self.pawns_bb(color.opposite());
+        self.pawns_where(color, |col, row| {
+            let span = (Self::file_mask(col) | Self::adjacent_files_mask(col))
+                & Self::rows_ahead_mask(row, color);
+            (span & enemy_pawns).is_empty()
+        })
+    }
+
+    /// Pawns of `color` sharing their column with another pawn of `color`.
+    pub fn doubled_pawns(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let pawns = self.pawns_bb(color);
+        self.pawns_where(color, |col, _| (Self::file_mask(col) & pawns).count() > 1)
+    }
+
+    /// Pawns of `color` with no pawn of `color` on an adjacent column.
+    pub fn isolated_pawns(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let pawns = self.pawns_bb(color);
+        self.pawns_where(color, |col, _| {
+            (Self::adjacent_files_mask(col) & pawns).is_empty()
+        })
+    }
+
+    /// Pawns of `color` that can't be defended by a pawn of `color` (none is
+    /// level with or behind them on an adjacent column) and whose square in
+    /// front is attacked by an enemy pawn, so they can't safely advance.
+    pub fn backward_pawns(&self, color: Color) -> Bitboard<{ (W * H).div_ceil(64) }> {
+        let geo = Self::geo();
+        let pawns = self.pawns_bb(color);
+        let enemy_pawns = self.pawns_bb(color.opposite());
+        self.pawns_where(color, |col, row| {
+            let supporters =
+                Self::adjacent_files_mask(col).andnot(Self::rows_ahead_mask(row, color));
+            if !(supporters & pawns).is_empty() {
+                return false;
+            }
+            let stop_row = match color {
+                Color::White if row + 1 < H => row + 1,
+                Color::Black if row > 0 => row - 1,
+                _ => return false,
+            };
+            let stop = stop_row * W + col;
+            // Enemy pawns attacking the stop square stand where a pawn of
+            // `color` on it would attack
+            !(geo.pawn_attacks(stop, color == Color::White) & enemy_pawns).is_empty()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::Color;
+    use crate::game::StandardGame;
+    use crate::position::Position;
+
+    fn squares(bb: crate::bitboard::Bitboard<1>) -> Vec<String> {
+        bb.iter_ones()
+            .map(|idx| Position::from_index(idx, 8).to_algebraic())
+            .collect()
+    }
+
+    #[test]
+    fn test_pawn_structure() {
+        // White: doubled c-pawns, isolated a- and h-pawns, and a d-pawn left
+        // backward by the e5 pawn, which is itself backward against d3
+        let game = StandardGame::new("4k3/8/8/2P1p3/2P5/3P3P/P7/4K3 w - - 0 1", true)
+            .expect("test_pawn_structure: valid FEN");
+
+        assert_eq!(game.pawn_files(Color::White), 0b1000_1101);
+        assert_eq!(game.pawn_files(Color::Black), 0b0001_0000);
+        assert_eq!(squares(game.doubled_pawns(Color::White)), ["c4", "c5"]);
+        assert_eq!(squares(game.isolated_pawns(Color::White)), ["a2", "h3"]);
+        assert_eq!(
+            squares(game.passed_pawns(Color::White)),
+            ["a2", "h3", "c4", "c5"]
+        );
+        assert_eq!(squares(game.backward_pawns(Color::White)), ["d3"]);
+
+        assert!(game.passed_pawns(Color::Black).is_empty());
+        assert_eq!(squares(game.isolated_pawns(Color::Black)), ["e5"]);
+        assert!(game.doubled_pawns(Color::Black).is_empty());
+        assert_eq!(squares(game.backward_pawns(Color::Black)), ["e5"]);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::r#move::Move;
+use crate::outcome::MoveList;
+
+use super::Game;
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Number of leaf nodes of the legal move tree `depth` plies deep, for
+    /// checking move generation against known perft values. Finished games
+    /// are not cut short: a node counts as long as it has legal moves.
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        match depth {
+            0 => 1,
+            // Bulk count the last ply
+            1 => {
+                let mut count = 0;
+                self.for_each_legal_move_unflagged(|_mv| {
+                    count += 1;
+                    false
+                });
+                count
+            }
+            _ => {
+                let mut moves = MoveList::new();
+                self.legal_moves_into(&mut moves);
+                let mut nodes = 0;
+                for mv in moves {
+                    self.make_move_silent(&mv);
+                    nodes += self.perft(depth - 1);
+                    self.unmake_move_silent();
+                }
+                nodes
+            }
+        }
+    }
+
+    /// `perft(depth - 1)` after each legal move, in `legal_moves()` order.
+    /// The counts sum to `perft(depth)`; comparing them with another move
+    /// generator's narrows a discrepancy down to one move.
+    pub fn perft_divide(&mut self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+        self.legal_moves()
+            .into_iter()
+            .map(|mv| {
+                self.make_move_silent(&mv);
+                let nodes = self.perft(depth - 1);
+                self.unmake_move_silent();
+                (mv, nodes)
+            })
+            .collect()
+    }
+}
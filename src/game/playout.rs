@@ -0,0 +1,88 @@
+use crate::outcome::{GameOutcome, MoveList};
+
+use super::Game;
+
+/// SplitMix64, enough to pick playout moves. Seeded from the caller so
+/// playouts are reproducible, and needs neither `rand` nor its feature flag.
+pub(crate) struct PlayoutRng(pub(crate) u64);
+
+impl PlayoutRng {
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub(crate) fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform index below `len`, which must be nonzero.
+    fn below(&mut self, len: usize) -> usize {
+        ((u128::from(self.next_u64()) * len as u128) >> 64) as usize
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Play uniformly random legal moves until the game ends, returning its
+    /// outcome, or until `max_plies` more plies have been played, returning
+    /// None. The moves are played on this game, so clone it first to keep the
+    /// position. The same seed always plays the same moves.
+    pub fn random_playout(&mut self, rng_seed: u64, max_plies: usize) -> Option<GameOutcome> {
+        if let Some(outcome) = self.declared_outcome() {
+            return Some(outcome);
+        }
+        let mut rng = PlayoutRng(rng_seed);
+        let mut moves = MoveList::new();
+        for _ in 0..max_plies {
+            if let Some(termination) = self.rule_draw() {
+                return Some(GameOutcome::draw(termination));
+            }
+            self.legal_moves_into(&mut moves);
+            if moves.is_empty() {
+                return Some(self.no_moves_outcome());
+            }
+            let mv = moves[rng.below(moves.len())];
+            self.make_move_unchecked(&mv);
+        }
+        self.outcome()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{Game, StandardGame};
+
+    #[test]
+    fn test_random_playout() {
+        let start = StandardGame::standard();
+
+        let mut game = start.clone();
+        let outcome = game.random_playout(7, 10_000);
+        assert!(outcome.is_some());
+        assert_eq!(game.outcome(), outcome);
+
+        // Seeded playouts repeat
+        let mut again = start.clone();
+        assert_eq!(again.random_playout(7, 10_000), outcome);
+        assert_eq!(again.to_fen(), game.to_fen());
+
+        let mut short = start.clone();
+        assert_eq!(short.random_playout(7, 4), None);
+        assert_eq!(short.move_count(), 4);
+
+        // A finished game plays no moves
+        let mut mated: Game<8, 8> =
+            Game::new("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1", true).expect("valid FEN");
+        assert!(mated.random_playout(1, 0).is_some());
+        assert!(mated.random_playout(1, 100).is_some());
+        assert_eq!(mated.move_count(), 0);
+    }
+}
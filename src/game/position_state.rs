@@ -0,0 +1,187 @@
+use smallvec::SmallVec;
+
+use crate::board::Board;
+use crate::color::Color;
+use crate::outcome::DrawRules;
+use crate::pieces::Piece;
+use crate::position::Position;
+
+use super::{CastlingRights, Game, ObserverSlot, PieceCounts};
+
+/// An immutable snapshot of a position: board, side to move, castling rights,
+/// en passant square and move clocks, without any move history.
+///
+/// `PositionState` is `Copy`, `Send` and `Sync`, so it can be stored in bulk
+/// (e.g. in search trees) or shared behind an `Arc`. Use
+/// `Game::position_state()` to take a snapshot and `Game::from_position_state()`
+/// to resume play from one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PositionState<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    pub(super) board: Board<W, H>,
+    pub(super) turn: Color,
+    pub(super) castling_rights: CastlingRights,
+    pub(super) castling_enabled: bool,
+    pub(super) self_capture: bool,
+    pub(super) fairy_promotions: bool,
+    pub(super) castling_king_cols: Option<(u8, u8)>,
+    pub(super) double_push_row: Option<u8>,
+    pub(super) draw_rules: DrawRules,
+    pub(super) en_passant: Option<Position>,
+    pub(super) halfmove_clock: u32,
+    pub(super) fullmove_number: u32,
+    pub(super) white_king_pos: Position,
+    pub(super) black_king_pos: Position,
+    pub(super) piece_counts: PieceCounts,
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> PositionState<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    pub fn width(&self) -> usize {
+        W
+    }
+
+    pub fn height(&self) -> usize {
+        H
+    }
+
+    pub fn get_piece(&self, pos: &Position) -> Option<Piece> {
+        self.board.get_piece(pos)
+    }
+
+    pub fn pieces(&self, color: Color) -> Vec<(Position, Piece)> {
+        self.board.pieces(color)
+    }
+
+    pub fn turn(&self) -> Color {
+        self.turn
+    }
+
+    pub fn castling_rights(&self) -> &CastlingRights {
+        &self.castling_rights
+    }
+
+    pub fn castling_enabled(&self) -> bool {
+        self.castling_enabled
+    }
+
+    pub fn self_capture_enabled(&self) -> bool {
+        self.self_capture
+    }
+
+    pub fn fairy_promotions_enabled(&self) -> bool {
+        self.fairy_promotions
+    }
+
+    pub fn draw_rules(&self) -> &DrawRules {
+        &self.draw_rules
+    }
+
+    /// The en passant target square, whether or not the capture is legal.
+    pub fn en_passant_square(&self) -> Option<Position> {
+        self.en_passant
+    }
+
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    pub fn piece_counts(&self) -> &PieceCounts {
+        &self.piece_counts
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Take a snapshot of the current position, without move history.
+    pub fn position_state(&self) -> PositionState<W, H> {
+        PositionState {
+            board: self.board,
+            turn: self.turn,
+            castling_rights: self.castling_rights,
+            castling_enabled: self.castling_enabled,
+            self_capture: self.self_capture,
+            fairy_promotions: self.fairy_promotions,
+            castling_king_cols: self.castling_king_cols,
+            double_push_row: self.double_push_row,
+            draw_rules: self.draw_rules,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+            white_king_pos: self.white_king_pos,
+            black_king_pos: self.black_king_pos,
+            piece_counts: self.piece_counts,
+        }
+    }
+
+    /// Create a game starting from a position snapshot, with empty move history.
+    pub fn from_position_state(state: &PositionState<W, H>) -> Self {
+        Game {
+            board: state.board,
+            turn: state.turn,
+            move_history: SmallVec::new(),
+            castling_rights: state.castling_rights,
+            castling_enabled: state.castling_enabled,
+            self_capture: state.self_capture,
+            fairy_promotions: state.fairy_promotions,
+            castling_king_cols: state.castling_king_cols,
+            double_push_row: state.double_push_row,
+            draw_rules: state.draw_rules,
+            en_passant: state.en_passant,
+            halfmove_clock: state.halfmove_clock,
+            fullmove_number: state.fullmove_number,
+            white_king_pos: state.white_king_pos,
+            black_king_pos: state.black_king_pos,
+            piece_counts: state.piece_counts,
+            legal_moves_cache: None,
+            declared_outcome: None,
+            observer: ObserverSlot::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_position_state_round_trip() {
+        let mut game = StandardGame::standard();
+        play(&mut game, &["e2e4", "c7c5", "e4e5", "d7d5"]);
+
+        let state = Arc::new(game.position_state());
+        assert_eq!(state.turn(), Color::White);
+        assert_eq!(state.en_passant_square(), Some(Position::new(3, 5)));
+
+        let mut resumed = StandardGame::from_position_state(&state);
+        assert_eq!(resumed.move_count(), 0);
+        assert_eq!(resumed.to_fen(), game.to_fen());
+        assert_eq!(resumed.legal_moves(), game.legal_moves());
+        assert_eq!(resumed.position_state(), *state);
+    }
+
+    #[test]
+    fn test_position_state_transpositions_equal() {
+        let mut a = StandardGame::standard();
+        let mut b = StandardGame::standard();
+        play(&mut a, &["g1f3", "g8f6", "b1c3"]);
+        play(&mut b, &["b1c3", "g8f6", "g1f3"]);
+        assert_eq!(a.position_state(), b.position_state());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::error::ChessError;
+use crate::r#move::Move;
+use crate::outcome::{DrawRules, GameOutcome};
+
+use super::Game;
+
+/// A game as its starting position and the moves played from it, enough to
+/// rebuild the whole game including its history. This is the form `Game` is
+/// serialized in with the `serde` feature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameRecord {
+    pub width: usize,
+    pub height: usize,
+    /// FEN of the position before the first move.
+    pub start_fen: String,
+    pub moves: Vec<Move>,
+    pub castling_enabled: bool,
+    pub self_capture: bool,
+    /// Whether pawns may promote to archbishops and chancellors, as in
+    /// `Game::fairy_promotions_enabled`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fairy_promotions: bool,
+    /// Columns the king lands on when castling, as in
+    /// `Game::castling_king_cols`.
+    pub castling_king_cols: Option<(u8, u8)>,
+    /// Row pawns double push from, as in `Game::double_push_row`.
+    pub double_push_row: Option<u8>,
+    pub draw_rules: DrawRules,
+    /// Outcome set by `resign`, `agree_draw`, `claim_draw` or `adjudicate`,
+    /// if the game was ended that way.
+    pub outcome: Option<GameOutcome>,
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// The starting position and move history of the game.
+    pub fn to_record(&self) -> GameRecord {
+        GameRecord {
+            width: W,
+            height: H,
+            start_fen: self.start_position().to_fen(),
+            moves: self.move_history.iter().map(|entry| entry.mv).collect(),
+            castling_enabled: self.castling_enabled,
+            self_capture: self.self_capture,
+            fairy_promotions: self.fairy_promotions,
+            castling_king_cols: self.castling_king_cols,
+            double_push_row: self.double_push_row,
+            draw_rules: self.draw_rules,
+            outcome: self.declared_outcome(),
+        }
+    }
+
+    /// Rebuild a game from its record by replaying the moves, then restore its
+    /// declared outcome. Fails if the
+    /// record is for another board size, its rule options are invalid, or a
+    /// move is illegal.
+    pub fn from_record(record: &GameRecord) -> Result<Self, ChessError> {
+        if record.width != W || record.height != H {
+            return Err(ChessError::InvalidDimensions(format!(
+                "record is for a {}x{} board, not {}x{}",
+                record.width, record.height, W, H
+            )));
+        }
+        let mut game = Self::parse_fen(
+            &record.start_fen,
+            record.castling_enabled,
+            record.double_push_row,
+        )?;
+        game.set_self_capture(record.self_capture);
+        game.set_fairy_promotions(record.fairy_promotions);
+        game.set_castling_king_cols(record.castling_king_cols)?;
+        game.set_draw_rules(record.draw_rules)?;
+        for mv in &record.moves {
+            let lan = mv.to_lan();
+            let legal = if mv.is_null() {
+                game.make_null_move()
+            } else {
+                let mv = game.move_from_lan(&lan)?;
+                game.make_move(&mv)
+            };
+            if !legal {
+                return Err(ChessError::IllegalMove(lan));
+            }
+        }
+        game.declared_outcome = record
+            .outcome
+            .map(|outcome| (game.move_history.len(), outcome));
+        Ok(game)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const W: usize, const H: usize> serde::Serialize for Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Serializes as a `GameRecord`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_record().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const W: usize, const H: usize> serde::Deserialize<'de> for Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Deserializes a `GameRecord` and replays its moves.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let record = GameRecord::deserialize(deserializer)?;
+        Self::from_record(&record).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Color;
+    use crate::game::{Game, StandardGame};
+    use crate::outcome::Termination;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_record_roundtrip() {
+        let mut game =
+            StandardGame::new("r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1", true)
+                .expect("valid FEN");
+        play(&mut game, &["e2e4", "d7d5", "e1c1"]);
+        assert!(game.make_null_move());
+
+        let record = game.to_record();
+        assert_eq!((record.width, record.height), (8, 8));
+        assert_eq!(
+            record.start_fen,
+            "r3k2r/pppppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1"
+        );
+        assert_eq!(record.moves.len(), 4);
+
+        let mut rebuilt = StandardGame::from_record(&record).expect("record replays");
+        assert_eq!(rebuilt.to_fen(), game.to_fen());
+        assert_eq!(rebuilt.to_record(), record);
+        assert!(rebuilt.castling_enabled());
+
+        let mut wrong_size = record.clone();
+        wrong_size.width = 10;
+        assert!(matches!(
+            StandardGame::from_record(&wrong_size),
+            Err(ChessError::InvalidDimensions(_))
+        ));
+        let mut illegal = record;
+        illegal.moves[1] = Move::from_lan("e7e4", 8, 8).expect("valid LAN");
+        assert!(matches!(
+            StandardGame::from_record(&illegal),
+            Err(ChessError::IllegalMove(_))
+        ));
+    }
+
+    #[test]
+    fn test_record_keeps_rule_options() {
+        let mut game = Game::<10, 8>::capablanca();
+        game.set_self_capture(true);
+        game.set_draw_rules(DrawRules {
+            halfmove_limit: 40,
+            repetition_limit: 3,
+            ply_limit: Some(200),
+        })
+        .expect("valid draw rules");
+        play(
+            &mut game,
+            &[
+                "i1j3", "i8j6", "h2h3", "h7h6", "g1h2", "g8h7", "h1i3", "h8i6",
+            ],
+        );
+        // Capablanca castling takes the king from the f-file to the i-file
+        play(&mut game, &["f1i1"]);
+
+        let record = game.to_record();
+        assert_eq!(record.castling_king_cols, Some((8, 2)));
+        let rebuilt = Game::<10, 8>::from_record(&record).expect("record replays");
+        assert_eq!(rebuilt.castling_king_cols(), Some((8, 2)));
+        assert!(rebuilt.self_capture_enabled());
+        assert!(rebuilt.fairy_promotions_enabled());
+        assert_eq!(rebuilt.draw_rules(), game.draw_rules());
+        assert_eq!(rebuilt.to_record(), record);
+        assert_eq!(rebuilt.position_state(), game.position_state());
+    }
+
+    #[test]
+    fn test_record_keeps_declared_outcome() {
+        let mut game = StandardGame::standard();
+        play(&mut game, &["e2e4", "e7e5"]);
+        assert!(game.resign(Color::White));
+
+        let record = game.to_record();
+        assert_eq!(
+            record.outcome,
+            Some(GameOutcome::win(Color::Black, Termination::Resignation))
+        );
+        let mut rebuilt = StandardGame::from_record(&record).expect("record replays");
+        assert_eq!(rebuilt.outcome(), game.outcome());
+        assert_eq!(rebuilt.to_record(), record);
+
+        // Taking back a move still reopens the game
+        assert!(rebuilt.unmake_move());
+        assert_eq!(rebuilt.outcome(), None);
+    }
+}
@@ -0,0 +1,110 @@
+use std::hash::{Hash, Hasher};
+
+use super::Game;
+
+/// Multiplier of the FxHash mixing step.
+const KEY_SEED: u64 = 0x517c_c1b7_2722_0a95;
+
+/// Cheap non-cryptographic hasher for position keys. Keys are computed on
+/// every move, where SipHash would dominate the cost of making the move. The
+/// board itself is keyed incrementally by `Board::key`.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl KeyHasher {
+    #[inline]
+    fn mix(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(KEY_SEED);
+    }
+}
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.mix(u64::from_le_bytes(
+                chunk
+                    .try_into()
+                    .expect("KeyHasher::write: chunk of 8 bytes"),
+            ));
+        }
+        for &byte in chunks.remainder() {
+            self.mix(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.mix(value);
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Key of the board, side to move and castling rights: the position
+    /// without its en passant square. Cheap enough to record on every move.
+    pub(super) fn repetition_key(&self) -> u64 {
+        let mut hasher = KeyHasher::default();
+        hasher.write_u64(self.board.key());
+        (self.turn as i8).hash(&mut hasher);
+        self.castling_rights.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Key identifying the current position for repetition: the board, the
+    /// side to move, castling rights and the en passant square if an en
+    /// passant capture is legal. Move clocks are not part of the key.
+    pub fn position_key(&mut self) -> u64 {
+        let mut hasher = KeyHasher(self.repetition_key());
+        if self.has_legal_en_passant() {
+            self.en_passant.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Number of times the current position has occurred, counting this
+    /// occurrence, since the last capture or pawn move.
+    pub fn repetition_count(&mut self) -> usize {
+        let plies = (self.halfmove_clock as usize).min(self.move_history.len());
+        // Positions only recur with the same side to move
+        if plies < 2 {
+            return 1;
+        }
+        let key = self.repetition_key();
+        let len = self.move_history.len();
+        let current_ep = self.en_passant.filter(|_| self.has_legal_en_passant());
+        let mut count = 1;
+        for back in (2..=plies).step_by(2) {
+            let entry = &self.move_history[len - back];
+            if entry.repetition_key != key {
+                continue;
+            }
+            // Same board, side to move and castling rights, so an earlier en
+            // passant square is only legal if it would be legal here
+            let earlier_ep = entry
+                .en_passant
+                .filter(|&ep_square| self.can_capture_en_passant(ep_square));
+            if earlier_ep == current_ep {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Whether the current position has occurred at least three times, so a
+    /// draw can be claimed. With the default `DrawRules` the game only ends
+    /// by itself at the fifth occurrence.
+    pub fn is_threefold_repetition(&mut self) -> bool {
+        self.repetition_count() >= 3
+    }
+
+    pub fn is_fivefold_repetition(&mut self) -> bool {
+        self.repetition_count() >= 5
+    }
+}
@@ -1,11 +1,18 @@
 use crate::color::Color;
+use crate::error::ChessError;
 use crate::r#move::{Move, MoveFlags};
-use crate::outcome::{GameOutcome, TurnState};
+use crate::outcome::{GameOutcome, RewardConfig, Termination, TurnState};
 use crate::pieces::{Piece, PieceType};
 use crate::position::Position;
 
 use super::Game;
 
+/// Halfmove clock from which a draw can be claimed under the fifty-move rule.
+const CLAIM_HALFMOVES: u32 = 100;
+
+/// Occurrences of a position from which a draw can be claimed by repetition.
+const CLAIM_REPETITIONS: usize = 3;
+
 #[hotpath::measure_all]
 impl<const W: usize, const H: usize> Game<W, H>
 where
@@ -29,8 +36,8 @@ where
             }
         }
 
-        // 2. Knight attacks
-        let knights = self.board.piece_type_bb(PieceType::Knight) & enemy;
+        // 2. Knight attacks (archbishops and chancellors jump as knights too)
+        let knights = self.board.knight_jumpers() & enemy;
         if !knights.is_empty() && !(geo.knight_attacks(square_idx) & knights).is_empty() {
             return true;
         }
@@ -41,9 +48,8 @@ where
             return true;
         }
 
-        // 4. Sliding attacks — rooks/queens along ranks and files
-        let queens = self.board.piece_type_bb(PieceType::Queen) & enemy;
-        let rooks_queens = (self.board.piece_type_bb(PieceType::Rook) & enemy) | queens;
+        // 4. Sliding attacks — rooks/queens/chancellors along ranks and files
+        let rooks_queens = self.board.orthogonal_sliders() & enemy;
         if !rooks_queens.is_empty() {
             let ortho = geo.orthogonal_attacks(square_idx, occupied);
             if !(ortho & rooks_queens).is_empty() {
@@ -51,8 +57,8 @@ where
             }
         }
 
-        // 5. Sliding attacks — bishops/queens along diagonals
-        let bishops_queens = (self.board.piece_type_bb(PieceType::Bishop) & enemy) | queens;
+        // 5. Sliding attacks — bishops/queens/archbishops along diagonals
+        let bishops_queens = self.board.diagonal_sliders() & enemy;
         if !bishops_queens.is_empty() {
             let diag = geo.diagonal_attacks(square_idx, occupied);
             if !(diag & bishops_queens).is_empty() {
@@ -79,8 +85,13 @@ where
         self.is_in_check(self.turn)
     }
 
-    fn has_any_legal_move(&mut self) -> bool {
-        self.for_each_legal_move(|_mv| true)
+    /// Whether the side to move has a legal move, stopping at the first one
+    /// found instead of generating them all.
+    pub fn has_any_legal_move(&mut self) -> bool {
+        if let Some(ref cached) = self.legal_moves_cache {
+            return !cached.is_empty();
+        }
+        self.for_each_legal_move_unflagged(|_mv| true)
     }
 
     pub fn is_checkmate(&mut self) -> bool {
@@ -92,7 +103,7 @@ where
     }
 
     pub fn is_over(&mut self) -> bool {
-        self.halfmove_clock >= 150 || self.is_insufficient_material() || !self.has_any_legal_move()
+        self.outcome().is_some()
     }
 
     pub fn en_passant_square(&self) -> Option<Position> {
@@ -100,40 +111,48 @@ where
     }
 
     pub fn has_legal_en_passant(&mut self) -> bool {
-        if let Some(ep_square) = self.en_passant {
-            let ep_idx = ep_square.to_index(W);
-            let is_white = self.turn == Color::White;
-            let geo = Self::geo();
-
-            // Find our pawns that can attack the ep square using reverse pawn attacks
-            let candidates = geo.pawn_attacks(ep_idx, !is_white)
-                & self.board.piece_type_bb(PieceType::Pawn)
-                & self.board.color_bb(self.turn);
-
-            if candidates.is_empty() {
-                return false;
-            }
+        self.en_passant
+            .is_some_and(|ep_square| self.can_capture_en_passant(ep_square))
+    }
 
-            let pawn = Piece::new(PieceType::Pawn, self.turn);
-            let captured_pawn = Piece::new(PieceType::Pawn, self.turn.opposite());
+    /// Whether the side to move could legally capture en passant on
+    /// `ep_square`, were it the en passant square.
+    pub(super) fn can_capture_en_passant(&mut self, ep_square: Position) -> bool {
+        let ep_idx = ep_square.to_index(W);
+        let is_white = self.turn == Color::White;
+        let geo = Self::geo();
 
-            for pawn_idx in candidates.iter_ones() {
-                let pawn_pos = Position::from_index(pawn_idx, W);
-                let captured_pawn_pos = Position::new(ep_square.col, pawn_pos.row);
+        // Find our pawns that can attack the ep square using reverse pawn attacks
+        let candidates = geo.pawn_attacks(ep_idx, !is_white)
+            & self.board.piece_type_bb(PieceType::Pawn)
+            & self.board.color_bb(self.turn);
 
-                self.board.remove_piece(&pawn_pos, &pawn);
-                self.board.remove_piece(&captured_pawn_pos, &captured_pawn);
-                self.board.place_piece(&ep_square, &pawn);
+        if candidates.is_empty() {
+            return false;
+        }
+
+        let pawn = Piece::new(PieceType::Pawn, self.turn);
+        let captured_pawn = Piece::new(PieceType::Pawn, self.turn.opposite());
 
-                let in_check = self.is_in_check(self.turn);
+        let Some(captured_pawn_pos) = self.en_passant_victim(&ep_square) else {
+            return false;
+        };
 
-                self.board.remove_piece(&ep_square, &pawn);
-                self.board.place_piece(&captured_pawn_pos, &captured_pawn);
-                self.board.place_piece(&pawn_pos, &pawn);
+        for pawn_idx in candidates.iter_ones() {
+            let pawn_pos = Position::from_index(pawn_idx, W);
 
-                if !in_check {
-                    return true;
-                }
+            self.board.remove_piece(&pawn_pos, &pawn);
+            self.board.remove_piece(&captured_pawn_pos, &captured_pawn);
+            self.board.place_piece(&ep_square, &pawn);
+
+            let in_check = self.is_in_check(self.turn);
+
+            self.board.remove_piece(&ep_square, &pawn);
+            self.board.place_piece(&captured_pawn_pos, &captured_pawn);
+            self.board.place_piece(&pawn_pos, &pawn);
+
+            if !in_check {
+                return true;
             }
         }
         false
@@ -147,7 +166,7 @@ where
             flags |= MoveFlags::CAPTURE;
         }
 
-        if piece.piece_type == PieceType::King && (dst.col as i32 - src.col as i32).abs() == 2 {
+        if self.is_castling_move(src, dst, piece) {
             flags |= MoveFlags::CASTLE;
         }
 
@@ -165,31 +184,87 @@ where
         flags
     }
 
+    /// `MoveFlags::CHECK` if `mv`, played by `piece`, would check the opposing
+    /// king, and no flags for a piece of the side not to move.
+    pub(super) fn check_flag(&self, mv: &Move, piece: &Piece) -> MoveFlags {
+        if piece.color == self.turn
+            && Self::move_gives_check(&self.compute_check_squares(), mv, piece.piece_type)
+        {
+            MoveFlags::CHECK
+        } else {
+            MoveFlags::empty()
+        }
+    }
+
+    /// Return a copy of `mv` with flags (capture, castle, en passant, double push,
+    /// promotion, check) derived from the current position rather than trusted
+    /// from the caller. The promotion piece is kept as given, so `make_move` can
+    /// reject a pawn move to the last row without one, or a promotion piece on
+    /// any other move. Moves without a piece on the source square are returned
+    /// unchanged.
+    pub fn annotate_move(&self, mv: &Move) -> Move {
+        let piece = match self.board.get_piece(&mv.src) {
+            Some(p) => p,
+            None => return *mv,
+        };
+
+        let mut flags = self.infer_move_flags(&mv.src, &mv.dst, &piece);
+
+        // Castling never captures, even if the king lands on its own rook's square
+        if flags.contains(MoveFlags::CASTLE) {
+            flags.remove(MoveFlags::CAPTURE);
+        }
+
+        if piece.piece_type == PieceType::Pawn
+            && usize::from(mv.dst.row) == Self::promotion_row(piece.color)
+        {
+            flags |= MoveFlags::PROMOTION;
+        }
+
+        let mut mv = Move {
+            src: mv.src,
+            dst: mv.dst,
+            flags,
+            promotion: mv.promotion,
+        };
+        mv.flags |= self.check_flag(&mv, &piece);
+        mv
+    }
+
     /// Parse a LAN move string, with game context to set proper flags (castling, en passant, etc.)
     /// The `from_lan()` method on Move itself lacks game context.
-    pub fn move_from_lan(&self, lan: &str) -> Result<Move, String> {
+    pub fn move_from_lan(&self, lan: &str) -> Result<Move, ChessError> {
         let base_move = Move::from_lan(lan, W, H)?;
 
-        let piece = self
-            .board
-            .get_piece(&base_move.src)
-            .ok_or_else(|| "No piece at source square".to_string())?;
+        let piece = self.board.get_piece(&base_move.src).ok_or_else(|| {
+            ChessError::IllegalMove(format!("no piece at source square: {}", lan))
+        })?;
 
         let flags = base_move.flags | self.infer_move_flags(&base_move.src, &base_move.dst, &piece);
 
-        Ok(Move {
+        let mut mv = Move {
             src: base_move.src,
             dst: base_move.dst,
             flags,
             promotion: base_move.promotion,
-        })
+        };
+        mv.flags |= self.check_flag(&mv, &piece);
+        Ok(mv)
     }
 
     pub fn move_to_lan(&self, mv: &Move) -> String {
         mv.to_lan()
     }
 
+    /// SAN of a move in the current position, with minimal disambiguation
+    /// and a `+` or `#` suffix. Flags are taken from the position rather than
+    /// from `mv`, so a bare source/destination move is written correctly. The
+    /// null move is written `--`, as in PGN.
     pub fn move_to_san(&mut self, mv: &Move) -> String {
+        if mv.is_null() {
+            return "--".to_string();
+        }
+        let mv = &self.annotate_move(mv);
         let mut san = String::new();
 
         // Castling
@@ -253,8 +328,10 @@ where
             }
         }
 
-        // Check/checkmate suffix
-        self.make_move_unchecked(mv);
+        // Check/checkmate suffix. The probe returns to this position, so keep its
+        // cached legal moves rather than letting make/unmake discard them.
+        let cached = self.legal_moves_cache.take();
+        self.make_move_silent(mv);
         if self.is_in_check(self.turn) {
             if !self.has_any_legal_move() {
                 san.push('#');
@@ -262,17 +339,38 @@ where
                 san.push('+');
             }
         }
-        self.unmake_move();
+        self.unmake_move_silent();
+        self.legal_moves_cache = cached;
 
         san
     }
 
-    pub fn move_from_san(&mut self, san: &str) -> Result<Move, String> {
-        // Strip check/mate suffixes
+    /// SAN of every legal move, in `legal_moves()` order.
+    pub fn legal_moves_san(&mut self) -> Vec<String> {
+        self.legal_moves()
+            .iter()
+            .map(|mv| self.move_to_san(mv))
+            .collect()
+    }
+
+    /// Parse a SAN move against the current position. Check and mate
+    /// suffixes, annotation glyphs (`!`, `?`) and a trailing `e.p.` are
+    /// accepted, as is a promotion without the `=` (`e8Q`).
+    pub fn move_from_san(&mut self, san: &str) -> Result<Move, ChessError> {
+        // Strip annotations, check/mate suffixes and the en passant marker
+        let san = san.trim().trim_end_matches(['!', '?']);
         let san = san.trim_end_matches(['+', '#']);
+        let (san, en_passant) = match san.strip_suffix("e.p.") {
+            Some(rest) => (rest.trim_end(), true),
+            None => (san, false),
+        };
+
+        if san == "--" {
+            return Ok(Move::null());
+        }
 
         if san.is_empty() {
-            return Err("Empty SAN string".to_string());
+            return Err(ChessError::InvalidMove("empty SAN string".to_string()));
         }
 
         let legal = self.legal_moves();
@@ -282,13 +380,17 @@ where
             return legal
                 .into_iter()
                 .find(|m| m.flags.contains(MoveFlags::CASTLE) && m.dst.col > m.src.col)
-                .ok_or_else(|| "Kingside castling not available".to_string());
+                .ok_or_else(|| {
+                    ChessError::IllegalMove("kingside castling not available".to_string())
+                });
         }
         if san == "O-O-O" || san == "0-0-0" {
             return legal
                 .into_iter()
                 .find(|m| m.flags.contains(MoveFlags::CASTLE) && m.dst.col < m.src.col)
-                .ok_or_else(|| "Queenside castling not available".to_string());
+                .ok_or_else(|| {
+                    ChessError::IllegalMove("queenside castling not available".to_string())
+                });
         }
 
         let bytes = san.as_bytes();
@@ -300,7 +402,10 @@ where
                 idx += 1;
                 pt
             } else {
-                return Err(format!("Invalid piece character: {}", bytes[idx] as char));
+                return Err(ChessError::InvalidMove(format!(
+                    "invalid piece character: {}",
+                    bytes[idx] as char
+                )));
             }
         } else {
             PieceType::Pawn
@@ -313,12 +418,22 @@ where
         if end >= 2 && bytes[end - 2] == b'=' {
             promo_type = PieceType::from_san_char(bytes[end - 1] as char);
             if promo_type.is_none() {
-                return Err(format!(
-                    "Invalid promotion piece: {}",
+                return Err(ChessError::InvalidMove(format!(
+                    "invalid promotion piece: {}",
                     bytes[end - 1] as char
-                ));
+                )));
             }
             end -= 2;
+        } else if end >= 3 && bytes[end - 1].is_ascii_uppercase() && bytes[end - 2].is_ascii_digit()
+        {
+            promo_type = PieceType::from_san_char(bytes[end - 1] as char);
+            if promo_type.is_none() {
+                return Err(ChessError::InvalidMove(format!(
+                    "invalid promotion piece: {}",
+                    bytes[end - 1] as char
+                )));
+            }
+            end -= 1;
         }
 
         // Destination square is the trailing file plus one-or-more-digit rank.
@@ -327,15 +442,18 @@ where
             rank_start -= 1;
         }
         if rank_start == end || rank_start == 0 || rank_start <= idx {
-            return Err("SAN too short to contain destination square".to_string());
+            return Err(ChessError::InvalidMove(format!(
+                "SAN too short to contain destination square: {}",
+                san
+            )));
         }
         let file_idx = rank_start - 1;
         let dst_file = bytes[file_idx] as char;
         if !dst_file.is_ascii_lowercase() {
-            return Err(format!(
-                "Invalid destination square: {}",
+            return Err(ChessError::InvalidMove(format!(
+                "invalid destination square: {}",
                 &san[file_idx..end]
-            ));
+            )));
         }
         let dst = Position::from_algebraic(&san[file_idx..end])?;
         let disambig = san[idx..file_idx].replace('x', "");
@@ -354,13 +472,19 @@ where
 
             if !rest.is_empty() {
                 if !rest.chars().all(|c| c.is_ascii_digit()) {
-                    return Err(format!("Invalid SAN disambiguation: {}", san));
+                    return Err(ChessError::InvalidMove(format!(
+                        "invalid SAN disambiguation: {}",
+                        san
+                    )));
                 }
-                let rank_num = rest
-                    .parse::<usize>()
-                    .map_err(|_| format!("Invalid SAN disambiguation: {}", san))?;
+                let rank_num = rest.parse::<usize>().map_err(|_| {
+                    ChessError::InvalidMove(format!("invalid SAN disambiguation: {}", san))
+                })?;
                 if rank_num == 0 {
-                    return Err(format!("Invalid SAN disambiguation: {}", san));
+                    return Err(ChessError::InvalidMove(format!(
+                        "invalid SAN disambiguation: {}",
+                        san
+                    )));
                 }
                 rank_hint = Some(rank_num - 1);
             }
@@ -402,69 +526,199 @@ where
             .collect();
 
         match candidates.len() {
-            0 => Err(format!("No legal move matches SAN: {}", san)),
-            1 => Ok(candidates
-                .into_iter()
-                .next()
-                .expect("move_from_san: candidates vec confirmed to have exactly one element")),
-            _ => Err(format!(
-                "Ambiguous SAN: {} matches {} moves",
+            0 => Err(ChessError::IllegalMove(format!(
+                "no legal move matches SAN: {}",
+                san
+            ))),
+            1 => {
+                let mv = candidates
+                    .into_iter()
+                    .next()
+                    .expect("move_from_san: candidates vec confirmed to have exactly one element");
+                if en_passant && !mv.flags.contains(MoveFlags::EN_PASSANT) {
+                    return Err(ChessError::IllegalMove(format!(
+                        "not an en passant capture: {}",
+                        san
+                    )));
+                }
+                Ok(mv)
+            }
+            _ => Err(ChessError::InvalidMove(format!(
+                "ambiguous SAN: {} matches {} moves ({})",
                 san,
-                candidates.len()
-            )),
+                candidates.len(),
+                candidates
+                    .iter()
+                    .map(|m| m.to_lan())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
         }
     }
 
+    /// The PGN result of the game: "1-0", "0-1", "1/2-1/2", or "*" while it
+    /// is still going.
+    pub fn pgn_result(&mut self) -> &'static str {
+        self.outcome()
+            .map_or("*", |outcome| outcome.to_pgn_result())
+    }
+
     pub fn outcome(&mut self) -> Option<GameOutcome> {
-        if self.halfmove_clock >= 150 {
-            return Some(GameOutcome::FiftyMoveRule);
+        if let Some(outcome) = self.declared_outcome() {
+            return Some(outcome);
         }
-
-        if self.is_insufficient_material() {
-            return Some(GameOutcome::InsufficientMaterial);
+        if let Some(termination) = self.rule_draw() {
+            return Some(GameOutcome::draw(termination));
         }
 
         if self.has_any_legal_move() {
             return None;
         }
+        Some(self.no_moves_outcome())
+    }
+
+    /// The rule that draws the game regardless of the legal moves, if any,
+    /// under the game's `DrawRules`. Checkmate takes precedence, so a mate
+    /// delivered on the move that reaches a limit still wins.
+    pub(super) fn rule_draw(&mut self) -> Option<Termination> {
+        if self.is_check() && !self.has_any_legal_move() {
+            return None;
+        }
+        let rules = self.draw_rules;
+        if self.halfmove_clock >= rules.halfmove_limit {
+            Some(Termination::SeventyFiveMoveRule)
+        } else if self.is_insufficient_material() {
+            Some(Termination::InsufficientMaterial)
+        } else if self.repetition_count() >= rules.repetition_limit {
+            Some(Termination::FivefoldRepetition)
+        } else if rules
+            .ply_limit
+            .is_some_and(|limit| self.move_history.len() >= limit)
+        {
+            Some(Termination::PlyLimit)
+        } else {
+            None
+        }
+    }
+
+    /// The outcome set by `claim_draw`, `resign`, `agree_draw` or
+    /// `adjudicate`, if it still stands.
+    pub(super) fn declared_outcome(&self) -> Option<GameOutcome> {
+        self.declared_outcome
+            .filter(|&(plies, _)| self.move_history.len() >= plies)
+            .map(|(_, outcome)| outcome)
+    }
+
+    /// Whether the side to move may claim a draw under the fifty-move rule:
+    /// 100 halfmoves have passed without a capture or pawn move. The game
+    /// only ends automatically at `DrawRules::halfmove_limit` (150 by
+    /// default).
+    pub fn can_claim_fifty_moves(&self) -> bool {
+        self.halfmove_clock >= CLAIM_HALFMOVES
+    }
+
+    /// Whether the side to move may claim a draw by repetition: the current
+    /// position has occurred at least three times. The game only ends
+    /// automatically at `DrawRules::repetition_limit` occurrences.
+    pub fn can_claim_threefold(&mut self) -> bool {
+        self.repetition_count() >= CLAIM_REPETITIONS
+    }
+
+    /// Whether the game is still going and a draw can be claimed.
+    pub fn can_claim_draw(&mut self) -> bool {
+        (self.can_claim_fifty_moves() || self.can_claim_threefold()) && !self.is_over()
+    }
+
+    /// Claim a draw for the side to move, ending the game if the claim is
+    /// valid (see `can_claim_draw`). The outcome stands until one of the
+    /// moves played so far is taken back with `unmake_move`.
+    ///
+    /// Returns the outcome, or None if no draw can be claimed.
+    pub fn claim_draw(&mut self) -> Option<GameOutcome> {
+        if !self.can_claim_draw() {
+            return None;
+        }
+        let termination = if self.can_claim_threefold() {
+            Termination::ThreefoldRepetition
+        } else {
+            Termination::FiftyMoveRule
+        };
+        let outcome = GameOutcome::draw(termination);
+        self.declare_outcome(outcome);
+        Some(outcome)
+    }
 
-        // No legal moves: checkmate or stalemate
+    /// `color` resigns, ending the game as a win for the other side.
+    ///
+    /// Returns false, leaving the game unchanged, if it is already over.
+    pub fn resign(&mut self, color: Color) -> bool {
+        self.end_with(GameOutcome::win(color.opposite(), Termination::Resignation))
+    }
+
+    /// End the game as a draw agreed by both players.
+    ///
+    /// Returns false, leaving the game unchanged, if it is already over.
+    pub fn agree_draw(&mut self) -> bool {
+        self.end_with(GameOutcome::draw(Termination::Agreement))
+    }
+
+    /// End the game with an outcome decided outside it, e.g. by an arena's
+    /// adjudication rules or a clock (`Termination::Timeout`).
+    ///
+    /// Returns false, leaving the game unchanged, if it is already over.
+    pub fn adjudicate(&mut self, outcome: GameOutcome) -> bool {
+        self.end_with(outcome)
+    }
+
+    /// Declare `outcome` if the game isn't over yet.
+    fn end_with(&mut self, outcome: GameOutcome) -> bool {
+        if self.is_over() {
+            return false;
+        }
+        self.declare_outcome(outcome);
+        true
+    }
+
+    /// End the game with `outcome` until one of the moves played so far is
+    /// taken back with `unmake_move`.
+    fn declare_outcome(&mut self, outcome: GameOutcome) {
+        self.declared_outcome = Some((self.move_history.len(), outcome));
+        if let Some(observer) = self.observer.0.as_mut() {
+            observer.on_game_over(outcome);
+        }
+    }
+
+    /// The result when the side to move has no legal moves: checkmate or
+    /// stalemate.
+    pub(super) fn no_moves_outcome(&self) -> GameOutcome {
         if self.is_check() {
-            if self.turn == Color::White {
-                Some(GameOutcome::BlackWin)
-            } else {
-                Some(GameOutcome::WhiteWin)
-            }
+            GameOutcome::win(self.turn.opposite(), Termination::Checkmate)
         } else {
-            Some(GameOutcome::Stalemate)
+            GameOutcome::draw(Termination::Stalemate)
         }
     }
 
+    /// Reward for `perspective` under `config`, with the moves played as the
+    /// game length, or `None` if the game isn't over.
+    pub fn reward(&mut self, perspective: Color, config: &RewardConfig) -> Option<f32> {
+        let plies = self.move_count();
+        self.outcome()
+            .map(|outcome| outcome.reward(perspective, plies, config))
+    }
+
     pub fn turn_state(&mut self) -> TurnState {
-        if self.halfmove_clock >= 150 {
-            return TurnState::Over(GameOutcome::FiftyMoveRule);
+        if let Some(outcome) = self.declared_outcome() {
+            return TurnState::Over(outcome);
         }
-
-        if self.is_insufficient_material() {
-            return TurnState::Over(GameOutcome::InsufficientMaterial);
+        if let Some(termination) = self.rule_draw() {
+            return TurnState::Over(GameOutcome::draw(termination));
         }
 
         let moves = self.legal_moves();
         if !moves.is_empty() {
             return TurnState::Ongoing(moves);
         }
-
-        let outcome = if self.is_check() {
-            if self.turn == Color::White {
-                GameOutcome::BlackWin
-            } else {
-                GameOutcome::WhiteWin
-            }
-        } else {
-            GameOutcome::Stalemate
-        };
-
-        TurnState::Over(outcome)
+        TurnState::Over(self.no_moves_outcome())
     }
 
     pub fn is_insufficient_material(&self) -> bool {
@@ -475,13 +729,17 @@ where
         );
         let pc = &self.piece_counts;
 
-        // If either side has pawns, queens, or rooks, there's sufficient material
-        if pc.get(PieceType::Pawn, Color::White) > 0
-            || pc.get(PieceType::Pawn, Color::Black) > 0
-            || pc.get(PieceType::Rook, Color::White) > 0
-            || pc.get(PieceType::Rook, Color::Black) > 0
-            || pc.get(PieceType::Queen, Color::White) > 0
-            || pc.get(PieceType::Queen, Color::Black) > 0
+        // If either side has pawns, queens, rooks, or fairy pieces, there's
+        // sufficient material
+        if [
+            PieceType::Pawn,
+            PieceType::Rook,
+            PieceType::Queen,
+            PieceType::Archbishop,
+            PieceType::Chancellor,
+        ]
+        .into_iter()
+        .any(|pt| pc.get(pt, Color::White) > 0 || pc.get(pt, Color::Black) > 0)
         {
             return false;
         }
@@ -538,7 +796,7 @@ where
     }
 
     pub fn to_fen(&mut self) -> String {
-        let mut fen = self.board.to_fen();
+        let mut fen = self.board.fen();
 
         // Turn
         fen.push(' ');
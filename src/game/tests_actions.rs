@@ -1,5 +1,6 @@
 use super::*;
 use crate::color::Color;
+use crate::encode::EncoderConfig;
 use crate::position::Position;
 
 use rand::SeedableRng;
@@ -58,6 +59,27 @@ fn encode_decode_action_roundtrip() {
     }
 }
 
+#[test]
+fn legal_actions_are_distinct() {
+    // Every action is plane * W * H + src index, so the same move pattern from
+    // different squares (here the many one-square pushes) never collides
+    let mut game = Game8x8::new(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        true,
+    )
+    .expect("legal_actions_are_distinct: valid FEN");
+    let total = crate::encode::get_total_actions(8, 8);
+    let mut actions: Vec<usize> = game
+        .legal_moves()
+        .iter()
+        .map(|mv| game.encode_action(mv).expect("encodable"))
+        .collect();
+    assert!(actions.iter().all(|&action| action < total));
+    actions.sort_unstable();
+    actions.dedup();
+    assert_eq!(actions.len(), game.legal_moves().len());
+}
+
 #[test]
 fn apply_action_roundtrip() {
     let mut game = Game8x8::standard();
@@ -76,3 +98,138 @@ fn apply_action_roundtrip() {
     assert!(game.get_piece(&Position::new(4, 1)).is_none());
     assert!(game.get_piece(&Position::new(4, 3)).is_some());
 }
+
+#[test]
+fn apply_action_rejects_illegal_actions() {
+    let mut game = Game8x8::standard();
+    let fen = game.to_fen();
+    // A blocked rook move, a move of the opponent's pawn and an empty source
+    for lan in ["a1a3", "e7e5", "e4e5"] {
+        let mv = Move::from_lan(lan, 8, 8).expect("valid LAN");
+        let action = game.encode_action(&mv).expect("encodable");
+        assert!(!game.is_legal_action_with_config(action, &EncoderConfig::default()));
+        assert!(!game.apply_action(action), "{} was applied", lan);
+    }
+    assert_eq!(game.to_fen(), fen);
+
+    // Castling decodes from the king's two-square move
+    let mut game = Game8x8::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", true)
+        .expect("apply_action_rejects_illegal_actions: valid FEN");
+    let castle = Move::from_lan("e1g1", 8, 8).expect("valid LAN");
+    let action = game.encode_action(&castle).expect("encodable");
+    assert!(game.is_legal_action_with_config(action, &EncoderConfig::default()));
+    assert!(game.apply_action(action));
+    assert_eq!(game.to_fen(), "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 1");
+}
+
+#[test]
+fn filter_legal_matches_per_move_checks() {
+    let mut rng = SmallRng::seed_from_u64(7);
+    let total_actions = crate::encode::get_total_actions(8, 8);
+    let all_actions: Vec<usize> = (0..total_actions).collect();
+
+    for _game_num in 0..20 {
+        let mut game = Game8x8::standard();
+        for _move_num in 0..60 {
+            if game.is_over() {
+                break;
+            }
+
+            // Every action that decodes to a move is a candidate, legal or not
+            let candidates: Vec<Move> = all_actions
+                .iter()
+                .filter_map(|&action| game.decode_action(action))
+                .collect();
+            let filtered = game.filter_legal(&candidates);
+            for (mv, &legal) in candidates.iter().zip(&filtered) {
+                assert_eq!(legal, game.is_legal_move(mv), "move {}", mv.to_lan());
+            }
+
+            let legal_actions = game.filter_legal_actions(&all_actions);
+            let expected: Vec<usize> = game
+                .legal_moves()
+                .iter()
+                .filter_map(|m| game.encode_action(m))
+                .collect();
+            let mut found: Vec<usize> = all_actions
+                .iter()
+                .zip(&legal_actions)
+                .filter(|&(_, &legal)| legal)
+                .map(|(&action, _)| action)
+                .collect();
+            let mut expected_sorted = expected.clone();
+            expected_sorted.sort_unstable();
+            found.sort_unstable();
+            assert_eq!(found, expected_sorted);
+
+            let legal_moves = game.legal_moves();
+            let chosen = legal_moves
+                .choose(&mut rng)
+                .expect("filter_legal_matches_per_move_checks: game is not over");
+            game.make_move_unchecked(chosen);
+        }
+    }
+
+    // Off-board candidates are never legal
+    let mut game = Game8x8::standard();
+    let off_board =
+        Move::from_position(Position::new(4, 1), Position::new(4, 9), MoveFlags::empty());
+    assert_eq!(game.filter_legal(&[off_board]), vec![false]);
+}
+
+#[test]
+fn pass_action_makes_null_move() {
+    let config = EncoderConfig {
+        pass_action: true,
+        ..EncoderConfig::default()
+    };
+    let base_actions = crate::encode::get_total_actions(8, 8);
+    assert_eq!(config.total_actions(8, 8), base_actions + 1);
+    assert_eq!(config.pass_action_index(8, 8), Some(base_actions));
+    assert_eq!(EncoderConfig::default().pass_action_index(8, 8), None);
+
+    let mut game = Game8x8::standard();
+    let e4 = game
+        .move_from_lan("e2e4")
+        .expect("pass_action_makes_null_move: e2e4 is valid");
+    assert!(game.make_move(&e4));
+    let before = game.to_fen();
+
+    // Without the pass action the index is out of range
+    assert_eq!(game.decode_action(base_actions), None);
+    assert!(!game.apply_action(base_actions));
+
+    let pass = game
+        .decode_action_with_config(base_actions, &config)
+        .expect("pass_action_makes_null_move: pass action decodes");
+    assert!(pass.is_null());
+    assert_eq!(
+        crate::encode::encode_action_with_config(&pass, 8, 8, &config),
+        Some(base_actions)
+    );
+
+    // Passing clears the en passant square and hands the move back to white
+    assert!(game.apply_action_with_config(base_actions, &config));
+    assert_eq!(game.turn(), Color::White);
+    assert_eq!(
+        game.to_fen(),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 1 2"
+    );
+    assert_eq!(game.move_count(), 2);
+    assert!(game.last_move().is_some_and(|m| m.is_null()));
+
+    // Encoding replays history through the null move
+    let (data, planes, _, _) = crate::encode::encode_game_planes(&mut game);
+    assert_eq!(data.len(), planes * 64);
+    assert_eq!(game.move_count(), 2);
+
+    assert!(game.unmake_move());
+    assert_eq!(game.to_fen(), before);
+
+    // The side to move can't pass out of check
+    let mut checked = Game8x8::new("4k3/8/8/8/8/8/8/4K2r w - - 0 1", true)
+        .expect("pass_action_makes_null_move: valid FEN");
+    assert!(!checked.apply_action_with_config(base_actions, &config));
+    assert!(!checked.make_null_move());
+    assert_eq!(checked.move_count(), 0);
+}
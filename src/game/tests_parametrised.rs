@@ -209,7 +209,7 @@ macro_rules! board_size_tests {
 
                     game.halfmove_clock = 150;
                     assert!(game.is_over());
-                    assert_eq!(game.outcome(), Some(GameOutcome::FiftyMoveRule));
+                    assert_eq!(game.outcome(), Some(GameOutcome::SeventyFiveMoveRule));
                 }
 
                 // -------------------------------------------------------------
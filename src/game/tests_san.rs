@@ -2,6 +2,7 @@ use super::*;
 use crate::r#move::MoveFlags;
 use crate::pieces::PieceType;
 use crate::position::Position;
+use crate::test_support::play;
 use rand::SeedableRng;
 use rand::prelude::IndexedRandom;
 
@@ -242,6 +243,20 @@ fn san_en_passant() {
     assert_eq!(game.move_to_san(&mv), "exd6");
 }
 
+#[test]
+fn san_flags_from_position_and_null_move() {
+    // 1. e4 d5, then exd5 given without its capture flag
+    let mut game = Game8x8::standard();
+    play(&mut game, &["e2e4", "d7d5"]);
+    let bare = Move::from_lan("e4d5", 8, 8)
+        .expect("san_flags_from_position_and_null_move: failed to parse e4d5");
+    assert!(!bare.flags.contains(MoveFlags::CAPTURE));
+    assert_eq!(game.move_to_san(&bare), "exd5");
+
+    assert_eq!(game.move_to_san(&Move::null()), "--");
+    assert_eq!(game.move_from_san("--"), Ok(Move::null()));
+}
+
 #[test]
 fn san_from_basic() {
     let mut game = Game8x8::standard();
@@ -325,6 +340,40 @@ fn san_from_disambiguation() {
     assert_eq!(mv.dst, Position::new(3, 0)); // d1
 }
 
+#[test]
+fn san_from_en_passant_and_annotations() {
+    let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+    let mut game = Game8x8::new(fen, false)
+        .expect("san_from_en_passant_and_annotations: failed to create game from FEN");
+    for san in ["exd6", "exd6 e.p.", "exd6e.p.", "exd6!?"] {
+        let mv = game
+            .move_from_san(san)
+            .expect("san_from_en_passant_and_annotations: failed to parse SAN");
+        assert!(mv.flags.contains(MoveFlags::EN_PASSANT), "{}", san);
+    }
+    assert!(game.move_from_san("Kd2 e.p.").is_err());
+
+    let fen = "k7/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    let mut game = Game8x8::new(fen, false)
+        .expect("san_from_en_passant_and_annotations: failed to create game from FEN");
+    let mv = game
+        .move_from_san("e8N+")
+        .expect("san_from_en_passant_and_annotations: failed to parse SAN e8N+");
+    assert_eq!(mv.promotion, Some(PieceType::Knight));
+}
+
+#[test]
+fn san_from_ambiguous() {
+    let fen = "4k3/8/8/8/8/8/4K3/R6R w - - 0 1";
+    let mut game =
+        Game8x8::new(fen, false).expect("san_from_ambiguous: failed to create game from FEN");
+    let err = game
+        .move_from_san("Rd1")
+        .expect_err("san_from_ambiguous: Rd1 should be ambiguous")
+        .to_string();
+    assert!(err.contains("a1d1") && err.contains("h1d1"), "{}", err);
+}
+
 #[test]
 fn san_from_error_invalid() {
     let mut game = Game8x8::standard();
@@ -417,3 +466,20 @@ fn san_roundtrip_random_games() {
         }
     }
 }
+
+#[test]
+fn legal_moves_san_matches_move_to_san() {
+    // Two knights can reach d2, one move mates
+    let mut game = Game8x8::new("6k1/5ppp/8/8/8/8/8/RN2KN2 w - - 0 1", true)
+        .expect("legal_moves_san_matches_move_to_san: valid FEN");
+    let sans = game.legal_moves_san();
+    let legal = game.legal_moves();
+    assert_eq!(sans.len(), legal.len());
+    for (san, mv) in sans.iter().zip(&legal) {
+        assert_eq!(*san, game.move_to_san(mv));
+    }
+    assert!(sans.contains(&"Nbd2".to_string()));
+    assert!(sans.contains(&"Nfd2".to_string()));
+    assert!(sans.contains(&"Ra8#".to_string()));
+    assert_eq!(game.legal_moves(), legal);
+}
@@ -1,9 +1,10 @@
 use super::*;
 use crate::color::Color;
 use crate::r#move::{Move, MoveFlags};
-use crate::outcome::{GameOutcome, TurnState};
+use crate::outcome::{DrawRules, GameOutcome, MoveList, Termination, TurnState};
 use crate::pieces::{Piece, PieceType};
 use crate::position::Position;
+use crate::test_support::play;
 use rstest::rstest;
 
 type Game8x8 = Game<8, 8>;
@@ -36,6 +37,47 @@ fn fen_parsing_valid_en_passant() {
     assert_eq!(game.halfmove_clock(), 0);
 }
 
+#[rstest]
+#[case::standard("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", vec![])]
+#[case::two_kings(
+    "4k3/8/8/8/8/8/8/K3K3 w - - 0 1",
+    vec![Violation::KingCount { color: Color::White, count: 2 }]
+)]
+#[case::pawn_on_back_rank(
+    "4k2P/8/8/8/8/8/8/p3K3 w - - 0 1",
+    vec![
+        Violation::PawnOnBackRank(Position::new(0, 0)),
+        Violation::PawnOnBackRank(Position::new(7, 7)),
+    ]
+)]
+#[case::opponent_in_check("4k3/8/8/8/8/8/8/4RK2 w - - 0 1", vec![Violation::OpponentInCheck])]
+#[case::castling_without_rook(
+    "r3k3/8/8/8/8/8/8/4K2R w KQkq - 0 1",
+    vec![
+        Violation::InvalidCastlingRights { color: Color::White, kingside: false },
+        Violation::InvalidCastlingRights { color: Color::Black, kingside: true },
+    ]
+)]
+fn validate_reports_violations(#[case] fen: &str, #[case] expected: Vec<Violation>) {
+    let game = Game8x8::new(fen, true).expect("FEN should parse");
+    assert_eq!(game.validate(), expected);
+}
+
+#[test]
+fn validate_reports_stale_en_passant() {
+    let mut game = Game8x8::new(
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        true,
+    )
+    .expect("Failed to parse FEN");
+    assert!(game.validate().is_empty());
+    game.set_piece(&Position::new(3, 4), None);
+    assert_eq!(
+        game.validate(),
+        vec![Violation::InvalidEnPassant(Position::new(3, 5))]
+    );
+}
+
 #[test]
 fn fen_parsing_invalid_en_passant() {
     let invalid_ep_fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1";
@@ -137,6 +179,48 @@ fn outcome_stalemate() {
     assert_eq!(game.outcome(), Some(GameOutcome::Stalemate));
 }
 
+#[test]
+fn has_any_legal_move_agrees_with_outcome() {
+    let mut game = Game8x8::new("K7/8/1q6/8/8/8/8/2k5 w - - 0 1", false).expect("valid FEN");
+    assert!(!game.has_any_legal_move());
+    assert!(game.is_over());
+
+    // Mate in one: the position after it has no legal move and is checkmate
+    let mut game = Game8x8::new("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", true).expect("valid FEN");
+    assert!(game.has_any_legal_move());
+    assert!(!game.is_over());
+    let mate = game.move_from_lan("a1a8").expect("valid LAN");
+    assert!(game.make_move(&mate));
+    assert!(!game.has_any_legal_move());
+    assert_eq!(
+        game.outcome(),
+        Some(GameOutcome::win(Color::White, Termination::Checkmate))
+    );
+    assert_eq!(game.is_over(), game.outcome().is_some());
+}
+
+#[test]
+fn outcome_stalemate_from_ascii() {
+    let mut game = Game8x8::from_ascii(
+        "
+        8 K . . . . . . .
+        7 . . . . . . . .
+        6 . q . . . . . .
+        5 . . . . . . . .
+        4 . . . . . . . .
+        3 . . . . . . . .
+        2 . . . . . . . .
+        1 . . k . . . . .
+          a b c d e f g h
+        ",
+        Color::White,
+    )
+    .expect("Failed to parse stalemate diagram");
+
+    assert_eq!(game.to_fen(), "K7/8/1q6/8/8/8/8/2k5 w - - 0 1");
+    assert_eq!(game.outcome(), Some(GameOutcome::Stalemate));
+}
+
 #[test]
 fn turn_state_ongoing_returns_legal_moves() {
     let mut game = Game8x8::standard();
@@ -265,6 +349,57 @@ fn castling_rights_methods() {
     assert!(game.castling_rights().has_queenside(Color::Black));
 }
 
+#[test]
+fn castling_king_cols() {
+    // Capablanca chess: the king castles from the f-file to the i- or c-file
+    let fen = "r4k3r/10/10/10/10/10/10/R4K3R w KQkq - 0 1";
+    let mut game: Game<10, 8> = Game::new(fen, true).expect("castling_king_cols: valid FEN");
+    assert_eq!(game.castling_king_cols(), None);
+    assert!(game.is_legal_move(&game.move_from_lan("f1h1").expect("valid LAN")));
+
+    game.set_castling_king_cols(Some((8, 2)))
+        .expect("castling_king_cols: columns fit the board");
+    assert_eq!(game.castling_king_cols(), Some((8, 2)));
+    let kingside = game.move_from_lan("f1i1").expect("valid LAN");
+    let queenside = game.move_from_lan("f1c1").expect("valid LAN");
+    assert!(kingside.flags.contains(MoveFlags::CASTLE));
+    assert!(queenside.flags.contains(MoveFlags::CASTLE));
+    assert!(game.is_legal_move(&kingside));
+    assert!(game.is_legal_move(&queenside));
+    assert!(!game.is_legal_move(&Move::from_lan("f1h1", 10, 8).expect("valid LAN")));
+
+    // Without generating moves, is_legal_move tells castles from king steps
+    // the same way move generation does
+    let mut fresh: Game<10, 8> = Game::new(fen, true).expect("castling_king_cols: valid FEN");
+    fresh
+        .set_castling_king_cols(Some((8, 2)))
+        .expect("castling_king_cols: columns fit the board");
+    let king = Position::from_algebraic("f1").expect("valid square");
+    let legal = game.legal_moves();
+    for col in 0..10 {
+        for row in 0..2 {
+            let dst = Position::new(col, row);
+            let mv = Move::from_position(king, dst, MoveFlags::empty());
+            let generated = legal.iter().any(|m| m.src == king && m.dst == dst);
+            assert_eq!(fresh.is_legal_move(&mv), generated, "{}", mv.to_lan());
+        }
+    }
+    assert_eq!(game.move_to_san(&kingside), "O-O");
+    assert_eq!(game.move_from_san("O-O-O"), Ok(queenside));
+
+    assert!(game.make_move(&kingside));
+    assert_eq!(game.to_fen(), "r4k3r/10/10/10/10/10/10/R6RK1 b kq - 1 1");
+    let black_queenside = game.move_from_san("O-O-O").expect("legal castle");
+    assert!(game.make_move(&black_queenside));
+    assert_eq!(game.to_fen(), "2kr5r/10/10/10/10/10/10/R6RK1 w - - 2 2");
+    game.unmake_move();
+    game.unmake_move();
+    assert_eq!(game.to_fen(), fen);
+
+    assert!(game.set_castling_king_cols(Some((9, 2))).is_err());
+    assert!(game.set_castling_king_cols(Some((3, 3))).is_err());
+}
+
 #[test]
 fn castling_rights_rook_move() {
     let mut game = Game8x8::standard();
@@ -348,7 +483,184 @@ fn fifty_move_rule() {
     game.halfmove_clock = 150;
 
     assert!(game.is_over());
-    assert_eq!(game.outcome(), Some(GameOutcome::FiftyMoveRule));
+    assert_eq!(game.outcome(), Some(GameOutcome::SeventyFiveMoveRule));
+}
+
+#[test]
+fn checkmate_takes_precedence_over_draw_limits() {
+    let mut game = Game8x8::new("6k1/5ppp/8/8/8/8/8/R5K1 w - - 149 80", true).expect("valid FEN");
+    game.set_draw_rules(DrawRules {
+        ply_limit: Some(1),
+        ..*game.draw_rules()
+    })
+    .expect("valid draw rules");
+    let mate = game.move_from_lan("a1a8").expect("valid LAN");
+    assert!(game.make_move(&mate));
+
+    // The mate reaches both the halfmove limit and the ply limit
+    assert_eq!(game.halfmove_clock(), 150);
+    let checkmate = GameOutcome::win(Color::White, Termination::Checkmate);
+    assert_eq!(game.outcome(), Some(checkmate));
+    assert!(matches!(game.turn_state(), TurnState::Over(outcome) if outcome == checkmate));
+    assert_eq!(game.random_playout(0, 10), Some(checkmate));
+
+    // A check that can be answered is still drawn by the limit
+    let mut game = Game8x8::new("6k1/6pp/8/8/8/8/8/R5K1 w - - 149 80", true).expect("valid FEN");
+    let check = game.move_from_lan("a1a8").expect("valid LAN");
+    assert!(game.make_move(&check));
+    assert_eq!(game.outcome(), Some(GameOutcome::SeventyFiveMoveRule));
+}
+
+#[test]
+fn configurable_draw_rules() {
+    let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+    let mut game = Game8x8::standard();
+    assert_eq!(*game.draw_rules(), DrawRules::default());
+    game.set_draw_rules(DrawRules {
+        halfmove_limit: 100,
+        repetition_limit: 4,
+        ply_limit: Some(9),
+    })
+    .expect("valid draw rules");
+
+    // The third occurrence no longer draws
+    for lan in shuffle.iter().chain(&shuffle) {
+        assert!(!game.is_over());
+        play(&mut game, &[*lan]);
+    }
+    assert_eq!(game.repetition_count(), 3);
+    assert_eq!(game.outcome(), None);
+
+    game.set_draw_rules(DrawRules {
+        halfmove_limit: 8,
+        ..*game.draw_rules()
+    })
+    .expect("valid draw rules");
+    assert_eq!(game.outcome(), Some(GameOutcome::SeventyFiveMoveRule));
+
+    game.set_draw_rules(DrawRules {
+        halfmove_limit: 100,
+        repetition_limit: 3,
+        ply_limit: Some(9),
+    })
+    .expect("valid draw rules");
+    assert_eq!(game.outcome(), Some(GameOutcome::FivefoldRepetition));
+
+    game.set_draw_rules(DrawRules {
+        repetition_limit: 5,
+        ..*game.draw_rules()
+    })
+    .expect("valid draw rules");
+    assert_eq!(game.outcome(), None);
+    let mv = game.move_from_lan("e2e4").expect("valid LAN");
+    assert!(game.make_move(&mv));
+    assert_eq!(
+        game.outcome(),
+        Some(GameOutcome::draw(Termination::PlyLimit))
+    );
+    // Snapshots keep the rules
+    let mut resumed = Game8x8::from_position_state(&game.position_state());
+    assert_eq!(resumed.draw_rules().ply_limit, Some(9));
+    assert_eq!(resumed.outcome(), None);
+
+    assert!(
+        game.set_draw_rules(DrawRules {
+            repetition_limit: 1,
+            ..DrawRules::default()
+        })
+        .is_err()
+    );
+}
+
+#[test]
+fn claimable_draws() {
+    let mut game = Game8x8::standard();
+    assert!(!game.can_claim_draw());
+    assert_eq!(game.claim_draw(), None);
+
+    // A threefold repetition doesn't end the game by default, but can be
+    // claimed
+    play(&mut game, &["g1f3", "g8f6", "f3g1", "f6g8"].repeat(2));
+    assert!(game.can_claim_threefold());
+    assert!(!game.can_claim_fifty_moves());
+    assert!(game.can_claim_draw());
+    assert_eq!(game.outcome(), None);
+
+    let claimed = Some(GameOutcome::ThreefoldRepetition);
+    assert_eq!(game.claim_draw(), claimed);
+    assert_eq!(game.outcome(), claimed);
+    assert!(matches!(game.turn_state(), TurnState::Over(outcome) if Some(outcome) == claimed));
+    assert!(!game.can_claim_draw());
+
+    // Taking back a move withdraws the claim
+    assert!(game.unmake_move());
+    assert_eq!(game.outcome(), None);
+    assert!(!game.can_claim_threefold());
+
+    let mut game = Game8x8::new("4k3/8/8/8/8/8/8/R3K3 w - - 100 80", true).expect("valid FEN");
+    assert!(game.can_claim_fifty_moves());
+    assert!(!game.is_over());
+    assert_eq!(game.claim_draw(), Some(GameOutcome::FiftyMoveRule));
+    let mut clone = game.clone();
+    assert_eq!(clone.outcome(), Some(GameOutcome::FiftyMoveRule));
+    let mut resumed = Game8x8::from_position_state(&game.position_state());
+    assert_eq!(resumed.outcome(), None);
+
+    // A claimed repetition and one that ends the game automatically are
+    // told apart
+    let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+    let mut claimed = Game8x8::standard();
+    play(&mut claimed, &shuffle.repeat(2));
+    let mut automatic = claimed.clone();
+    play(&mut automatic, &shuffle.repeat(2));
+    assert_eq!(
+        claimed.claim_draw().map(|outcome| outcome.termination),
+        Some(Termination::ThreefoldRepetition)
+    );
+    assert_eq!(
+        automatic.outcome().map(|outcome| outcome.termination),
+        Some(Termination::FivefoldRepetition)
+    );
+    assert_eq!(automatic.claim_draw(), None);
+}
+
+#[test]
+fn resignation_agreement_and_adjudication() {
+    let mut game = Game8x8::standard();
+    let mv = game.move_from_lan("e2e4").expect("valid LAN");
+    assert!(game.make_move(&mv));
+
+    assert!(game.resign(Color::Black));
+    let resigned = GameOutcome::win(Color::White, Termination::Resignation);
+    assert_eq!(game.outcome(), Some(resigned));
+    assert_eq!(resigned.to_string(), "white_win_resignation");
+    // The first ending stands
+    assert!(!game.agree_draw());
+    assert!(!game.resign(Color::White));
+    assert_eq!(game.outcome(), Some(resigned));
+    // Moves played after it don't change it
+    let mv = game.move_from_lan("e7e5").expect("valid LAN");
+    assert!(game.make_move(&mv));
+    assert!(game.unmake_move());
+    assert_eq!(game.outcome(), Some(resigned));
+
+    assert!(game.unmake_move());
+    assert_eq!(game.outcome(), None);
+    assert!(game.agree_draw());
+    assert_eq!(
+        game.outcome(),
+        Some(GameOutcome::draw(Termination::Agreement))
+    );
+
+    let mut game = Game8x8::standard();
+    let timeout = GameOutcome::win(Color::Black, Termination::Timeout);
+    assert!(game.adjudicate(timeout));
+    assert_eq!(game.turn_state(), TurnState::Over(timeout));
+
+    // A finished game can't be adjudicated
+    let mut mated = Game8x8::new("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", true).expect("valid FEN");
+    assert!(!mated.adjudicate(GameOutcome::draw(Termination::Adjudication)));
+    assert_eq!(mated.outcome(), Some(GameOutcome::WhiteWin));
 }
 
 #[test]
@@ -359,3 +671,873 @@ fn total_actions_standard() {
         5248
     );
 }
+
+#[derive(Default)]
+struct RecordingObserver {
+    events: std::sync::Arc<std::sync::Mutex<Vec<String>>>,
+}
+
+impl GameObserver for RecordingObserver {
+    fn on_move(&mut self, mv: &Move) {
+        self.events
+            .lock()
+            .expect("observer lock")
+            .push(format!("move {}", mv.to_lan()));
+    }
+
+    fn on_undo(&mut self, mv: &Move) {
+        self.events
+            .lock()
+            .expect("observer lock")
+            .push(format!("undo {}", mv.to_lan()));
+    }
+
+    fn on_game_over(&mut self, outcome: GameOutcome) {
+        self.events
+            .lock()
+            .expect("observer lock")
+            .push(format!("over {}", outcome));
+    }
+}
+
+#[test]
+fn observer_receives_move_undo_and_game_over() {
+    let observer = RecordingObserver::default();
+    let events = observer.events.clone();
+
+    let mut game = Game8x8::standard();
+    game.set_observer(Box::new(observer));
+
+    play(&mut game, &["f2f3", "e7e5", "g2g4"]);
+    assert!(game.unmake_move());
+    let mv = game.move_from_lan("g2g4").expect("valid LAN");
+    assert!(game.make_move(&mv));
+
+    // SAN conversion probes the move internally; this must not be reported
+    let mate = game.move_from_lan("d8h4").expect("valid LAN");
+    assert_eq!(game.move_to_san(&mate), "Qh4#");
+    // Clones are scratch copies and don't inherit the observer
+    let mut scratch = game.clone();
+    assert!(!scratch.has_observer());
+    assert!(scratch.make_move(&mate));
+
+    assert!(game.make_move(&mate));
+
+    assert_eq!(
+        *events.lock().expect("observer lock"),
+        vec![
+            "move f2f3",
+            "move e7e5",
+            "move g2g4",
+            "undo g2g4",
+            "move g2g4",
+            "move d8h4",
+            "over black_win",
+        ]
+    );
+    assert!(game.take_observer().is_some());
+    assert!(!game.has_observer());
+}
+
+#[test]
+fn legal_moves_cache_invalidated_on_position_change() {
+    let mut game = Game8x8::standard();
+    assert_eq!(game.legal_moves().len(), 20);
+    assert!(game.legal_moves_cache.is_some());
+
+    let e4 = game.move_from_lan("e2e4").expect("valid LAN");
+    assert_eq!(game.move_to_san(&e4), "e4");
+    assert!(
+        game.legal_moves_cache.is_some(),
+        "SAN probe should keep the cache"
+    );
+
+    assert!(game.make_move(&e4));
+    assert!(game.legal_moves_cache.is_none());
+    assert_eq!(game.legal_moves().len(), 20);
+
+    assert!(game.unmake_move());
+    assert!(game.legal_moves_cache.is_none());
+    assert_eq!(game.legal_moves().len(), 20);
+
+    // Removing the g1 knight frees h1 rook moves and drops the knight's two moves
+    game.set_piece(&Position::new(6, 0), None);
+    assert_eq!(game.legal_moves().len(), 19);
+    assert!(!game.is_legal_move(&Move::from_lan("g1f3", 8, 8).expect("valid LAN")));
+}
+
+#[test]
+fn make_move_infers_flags_for_raw_moves() {
+    let fen = "r3k2r/8/8/3pP3/8/8/1p6/R3K2R w KQkq d6 0 1";
+
+    // Castling from a flag-less king move must also move the rook
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    let raw = Move::from_position(Position::new(4, 0), Position::new(6, 0), MoveFlags::empty());
+    assert_eq!(
+        game.annotate_move(&raw).flags,
+        MoveFlags::CASTLE,
+        "castle should be flagged"
+    );
+    assert!(game.make_move(&raw));
+    assert_eq!(
+        game.get_piece(&Position::new(5, 0)),
+        Some(Piece::new(PieceType::Rook, Color::White))
+    );
+    assert_eq!(game.get_piece(&Position::new(7, 0)), None);
+
+    // En passant from a flag-less pawn capture must remove the captured pawn
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    let raw = Move::from_lan("e5d6", 8, 8).expect("valid LAN");
+    assert!(game.make_move(&raw));
+    assert_eq!(game.get_piece(&Position::new(3, 4)), None);
+    let last = game.move_history().last().expect("move recorded").mv;
+    assert!(
+        last.flags
+            .contains(MoveFlags::EN_PASSANT | MoveFlags::CAPTURE)
+    );
+
+    // Promotion is flagged from the destination row; explicit pieces are kept
+    let mut game = Game8x8::new("4k3/8/8/8/8/8/1p6/R3K3 b - - 0 1", true).expect("valid FEN");
+    let under = Move::from_lan("b2b1n", 8, 8).expect("valid LAN");
+    assert!(game.make_move(&under));
+    assert_eq!(
+        game.get_piece(&Position::new(1, 0)),
+        Some(Piece::new(PieceType::Knight, Color::Black))
+    );
+}
+
+#[test]
+fn legal_moves_order_is_deterministic() {
+    let fen = "r3k2r/pPppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+    let mut a = Game8x8::new(fen, true).expect("valid FEN");
+    let mut b = Game8x8::standard();
+    play(&mut b, &["e2e4", "e7e5"]);
+    let mut c = Game8x8::new(&b.to_fen(), true).expect("valid FEN");
+
+    // Same position reached by different routes gives the same order
+    assert_eq!(b.legal_moves(), c.legal_moves());
+
+    // King moves and castling come first, then pieces by ascending square index
+    let moves = a.legal_moves();
+    assert_eq!(moves[0].src, Position::new(4, 0));
+    let first_castle = moves
+        .iter()
+        .position(|m| m.flags.contains(MoveFlags::CASTLE))
+        .expect("castling available");
+    assert!(
+        moves[..first_castle]
+            .iter()
+            .all(|m| m.src == Position::new(4, 0))
+    );
+    let rest: Vec<usize> = moves[first_castle + 2..]
+        .iter()
+        .map(|m| m.src.to_index(8))
+        .collect();
+    assert!(rest.windows(2).all(|w| w[0] <= w[1]));
+
+    // The sorted order is strictly increasing in the canonical key
+    let sorted = a.legal_moves_sorted();
+    assert_eq!(sorted.len(), moves.len());
+    assert!(
+        sorted
+            .windows(2)
+            .all(|w| w[0].canonical_key(8) < w[1].canonical_key(8))
+    );
+    let promotions: Vec<String> = sorted
+        .iter()
+        .filter(|m| m.src == Position::new(1, 6) && m.dst == Position::new(1, 7))
+        .map(|m| m.to_lan())
+        .collect();
+    assert_eq!(promotions, vec!["b7b8q", "b7b8n", "b7b8b", "b7b8r"]);
+}
+
+#[rstest]
+#[case::capture_before_pushes("4k3/8/8/8/8/3p4/4P3/4K3 w - - 0 1", "e2", &["e2d3", "e2e3", "e2e4"])]
+#[case::en_passant_after_push("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1", "d4", &["d4d3", "d4e3"])]
+#[case::promotions(
+    "n1n1k3/1P6/8/8/8/8/8/4K3 w - - 0 1",
+    "b7",
+    &["b7a8q", "b7a8n", "b7a8b", "b7a8r", "b7b8q", "b7b8n", "b7b8b", "b7b8r", "b7c8q", "b7c8n", "b7c8b", "b7c8r"],
+)]
+fn legal_moves_orders_pawn_moves_by_destination(
+    #[case] fen: &str,
+    #[case] square: &str,
+    #[case] expected: &[&str],
+) {
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    let src = Position::from_algebraic(square).expect("valid square");
+    let moves: Vec<String> = game
+        .legal_moves()
+        .iter()
+        .filter(|m| m.src == src)
+        .map(|m| m.to_lan())
+        .collect();
+    assert_eq!(moves, expected);
+}
+
+#[rstest]
+#[case::no_pawn("4k3/8/8/8/8/8/8/4K3 w - e6 0 1")]
+#[case::occupied("4k3/8/8/3pP3/8/8/8/4K3 w - d5 0 1")]
+#[case::wrong_side("4k3/8/8/3pP3/8/8/8/4K3 b - d6 0 1")]
+#[case::origin_occupied("4k3/3p4/8/3pP3/8/8/8/4K3 w - d6 0 1")]
+#[case::off_board("4k3/8/8/8/8/8/8/4K3 w - i6 0 1")]
+fn new_rejects_invalid_en_passant_square(#[case] fen: &str) {
+    assert!(Game8x8::new(fen, true).is_err());
+}
+
+#[test]
+fn en_passant_on_tall_board() {
+    // Black double pushes from row 9 to row 7 on a 10-row board
+    let mut game: Game<8, 10> =
+        Game::new("4k3/3p4/8/4P3/8/8/8/8/8/4K3 b - - 0 1", true).expect("valid FEN");
+    for lan in ["d9d7", "e7d8"] {
+        let mv = game.move_from_lan(lan).expect("valid LAN");
+        if lan == "e7d8" {
+            assert_eq!(game.en_passant_square(), Some(Position::new(3, 7)));
+            assert!(game.has_legal_en_passant());
+            assert!(mv.flags.contains(MoveFlags::EN_PASSANT));
+        }
+        assert!(game.make_move(&mv));
+    }
+    assert_eq!(game.get_piece(&Position::new(3, 6)), None);
+    assert_eq!(game.to_fen(), "4k3/8/3P4/8/8/8/8/8/8/4K3 b - - 0 2");
+}
+
+#[test]
+fn double_push_from_custom_row() {
+    let mut game = Game8x8::new("4k3/8/8/3p4/8/4P3/P7/4K3 w - - 0 1", true).expect("valid FEN");
+    let has_move =
+        |game: &mut Game8x8, lan: &str| game.legal_moves().iter().any(|mv| mv.to_lan() == lan);
+    assert!(has_move(&mut game, "a2a4"));
+    assert!(!has_move(&mut game, "e3e5"));
+
+    assert!(game.set_double_push_row(Some(5)).is_err());
+    game.set_double_push_row(Some(2)).expect("valid row");
+    assert_eq!(game.double_push_row(), Some(2));
+    assert!(!has_move(&mut game, "a2a4"));
+    play(&mut game, &["e3e5"]);
+    assert_eq!(game.en_passant_square(), Some(Position::new(4, 3)));
+    assert!(game.validate().is_empty());
+
+    // The en passant square only fits a double push from the third row
+    let fen = game.to_fen();
+    assert!(Game8x8::new(&fen, true).is_err());
+    assert!(game.set_double_push_row(None).is_err());
+    assert_eq!(game.double_push_row(), Some(2));
+    let mut rebuilt = Game8x8::from_record(&game.to_record()).expect("record replays");
+    assert_eq!(rebuilt.to_fen(), fen);
+
+    let ep = game.move_from_lan("d5e4").expect("valid LAN");
+    assert!(ep.flags.contains(MoveFlags::EN_PASSANT));
+    assert!(game.make_move(&ep));
+    assert_eq!(game.to_fen(), "4k3/8/8/8/4p3/8/P7/4K3 w - - 0 2");
+}
+
+#[rstest]
+#[case::missing_piece("b2b1")]
+#[case::king("b2b1k")]
+#[case::pawn("b2b1p")]
+#[case::not_last_row("e8e7q")]
+fn make_move_rejects_invalid_promotions(#[case] lan: &str) {
+    let mut game = Game8x8::new("4k3/8/8/8/8/8/1p6/R3K3 b - - 0 1", true).expect("valid FEN");
+    let mv = Move::from_lan(lan, 8, 8).expect("valid LAN");
+    assert!(!game.is_legal_move(&mv));
+    assert!(!game.make_move(&mv));
+    assert_eq!(game.move_count(), 0);
+}
+
+#[test]
+fn promotion_generated_from_destination_row() {
+    // On a 6-row board white promotes on row 6, one row earlier than on 8x8
+    let mut game: Game<8, 6> = Game::new("4k3/P7/8/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+    let promotions: Vec<Move> = game
+        .legal_moves()
+        .into_iter()
+        .filter(|m| m.src == Position::new(0, 4))
+        .collect();
+    assert_eq!(promotions.len(), 4);
+    assert!(promotions.iter().all(|m| m.dst.row == 5
+        && m.flags.contains(MoveFlags::PROMOTION)
+        && m.promotion.is_some()));
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "make_move_unchecked: illegal move e2e5")]
+fn make_move_unchecked_asserts_legality_in_debug() {
+    let mut game = Game8x8::standard();
+    let mv = Move::from_lan("e2e5", 8, 8).expect("valid LAN");
+    game.make_move_unchecked(&mv);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "wrong castle/en passant/promotion flags")]
+fn make_move_unchecked_asserts_flags_in_debug() {
+    let mut game = Game8x8::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", true).expect("valid FEN");
+    let mv = Move::from_position(Position::new(4, 0), Position::new(6, 0), MoveFlags::empty());
+    game.make_move_unchecked(&mv);
+}
+
+#[rstest]
+#[case("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1")]
+#[case("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3")]
+#[case("r3k2r/8/8/8/8/8/8/R3K2R b Kq - 12 40")]
+fn from_str_and_fen_display_round_trip(#[case] fen: &str) {
+    let game: Game8x8 = fen.parse().expect("valid FEN");
+    assert_eq!(format!("{:#}", game), fen);
+    assert!(format!("{}", game).starts_with("Game(current_player: "));
+}
+
+#[test]
+fn fen_display_omits_unusable_en_passant() {
+    let game: Game8x8 = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        .parse()
+        .expect("valid FEN");
+    assert_eq!(
+        format!("{:#}", game),
+        "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+    );
+    assert!("not a fen".parse::<Game8x8>().is_err());
+}
+
+#[test]
+fn ply_last_move_and_last_capture() {
+    let mut game = Game8x8::standard();
+    assert_eq!(game.ply(), 0);
+    assert_eq!(game.last_move(), None);
+    assert_eq!(game.last_capture(), None);
+
+    play(&mut game, &["e2e4", "d7d5", "e4d5"]);
+    assert_eq!(game.ply(), 3);
+    assert_eq!(
+        game.last_move().map(|m| m.to_lan()),
+        Some("e4d5".to_string())
+    );
+    assert_eq!(
+        game.last_capture(),
+        Some(Piece::new(PieceType::Pawn, Color::Black))
+    );
+
+    // En passant captures report the pawn taken off its own square
+    let mut game = Game8x8::new(
+        "rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+        true,
+    )
+    .expect("valid FEN");
+    assert_eq!(game.ply(), 0);
+    assert_eq!(game.absolute_ply(), 4);
+    let ep = game.move_from_lan("e5d6").expect("valid LAN");
+    assert!(game.make_move(&ep));
+    assert_eq!(game.ply(), 1);
+    assert_eq!(game.absolute_ply(), 5);
+    assert_eq!(
+        game.last_capture(),
+        Some(Piece::new(PieceType::Pawn, Color::Black))
+    );
+}
+
+#[test]
+fn bulk_board_edits() {
+    let mut game = Game8x8::new("4k3/pppp4/8/8/8/8/4PP2/4K2R w Kk - 0 1", true).expect("valid FEN");
+
+    assert_eq!(game.remove_all(PieceType::Pawn, Color::Black), Ok(4));
+    assert!(game.remove_all(PieceType::King, Color::White).is_err());
+    // Black had no rook to castle with, so the stale right is dropped
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/4PP2/4K2R w K - 0 1");
+
+    // The white king is on the bottom row
+    assert!(game.shift_board(0, -1).is_err());
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/4PP2/4K2R w K - 0 1");
+
+    // The rook on the h-file blocks shifting right, but not left
+    assert!(game.shift_board(1, 0).is_err());
+    game.remove_all(PieceType::Rook, Color::White)
+        .expect("rooks can be removed");
+    game.shift_board(-1, 0).expect("shift stays on the board");
+    assert_eq!(game.to_fen(), "3k4/8/8/8/8/8/3PP3/3K4 w - - 0 1");
+    assert_eq!(game.legal_moves().len(), 7);
+}
+
+#[test]
+fn bulk_edits_revoke_stale_rights() {
+    let mut game = Game8x8::new("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1", true).expect("valid FEN");
+    assert_eq!(game.remove_all(PieceType::Rook, Color::White), Ok(2));
+    assert_eq!(game.to_fen(), "r3k2r/8/8/8/8/8/8/4K3 w kq - 0 1");
+    assert!(
+        game.legal_moves()
+            .iter()
+            .all(|mv| !mv.flags.contains(MoveFlags::CASTLE))
+    );
+    assert!(game.validate().is_empty());
+
+    // Removing the pawn that just double pushed clears the en passant square
+    let mut game = Game8x8::new("4k3/8/8/3Pp3/8/8/8/4K3 w - e6 0 1", true).expect("valid FEN");
+    assert_eq!(game.remove_all(PieceType::Pawn, Color::Black), Ok(1));
+    assert_eq!(game.to_fen(), "4k3/8/8/3P4/8/8/8/4K3 w - - 0 1");
+    assert!(game.validate().is_empty());
+}
+
+#[test]
+fn truncate_to_ply_restores_earlier_position() {
+    let mut game = Game8x8::standard();
+    let mut fens = vec![game.to_fen()];
+    for lan in ["e2e4", "e7e5", "g1f3", "b8c6", "f1b5"] {
+        play(&mut game, &[lan]);
+        fens.push(game.to_fen());
+    }
+
+    assert!(!game.truncate_to_ply(6));
+    assert_eq!(game.move_count(), 5);
+
+    assert!(game.truncate_to_ply(2));
+    assert_eq!(game.move_count(), 2);
+    assert_eq!(game.to_fen(), fens[2]);
+    assert_eq!(game.legal_moves().len(), 29);
+
+    assert!(game.truncate_to_ply(2));
+    assert!(game.truncate_to_ply(0));
+    assert_eq!(game.to_fen(), fens[0]);
+
+    // Counts moves played since the FEN, like `ply()`
+    let start = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+    let mut game = Game8x8::new(start, true).expect("valid FEN");
+    play(&mut game, &["f1b5", "a7a6"]);
+    let after = game.to_fen();
+    assert!(game.truncate_to_ply(game.ply()));
+    assert_eq!(game.to_fen(), after);
+    assert!(!game.truncate_to_ply(game.absolute_ply()));
+    assert!(game.truncate_to_ply(0));
+    assert_eq!(game.to_fen(), start);
+}
+
+#[rstest]
+#[case::kiwipete("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")]
+#[case::pinned_and_checked("4k3/8/8/8/q7/4n3/2B5/r2K4 w - - 0 1")]
+#[case::en_passant_and_promotion("3k4/1P6/8/3pP3/8/8/8/4K3 w - d6 0 1")]
+fn is_legal_move_matches_generation(#[case] fen: &str) {
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    let legal = game.clone().legal_moves();
+    let promotions = [None, Some(PieceType::Queen), Some(PieceType::Knight)];
+    for src in 0..64 {
+        for dst in 0..64 {
+            for promotion in promotions {
+                let mut mv = Move::from_position(
+                    Position::from_index(src, 8),
+                    Position::from_index(dst, 8),
+                    MoveFlags::empty(),
+                );
+                mv.promotion = promotion;
+                let expected = legal
+                    .iter()
+                    .any(|m| m.src == mv.src && m.dst == mv.dst && m.promotion == promotion);
+                assert_eq!(
+                    game.is_legal_move(&mv),
+                    expected,
+                    "{} in {}",
+                    mv.to_lan(),
+                    fen
+                );
+            }
+        }
+    }
+}
+
+#[rstest]
+#[case::kiwipete("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")]
+#[case::pinned_and_checked("4k3/8/8/8/q7/4n3/2B5/r2K4 w - - 0 1")]
+#[case::en_passant_and_promotion("3k4/1P6/8/3pP3/8/8/8/4K3 w - d6 0 1")]
+#[case::promotions("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")]
+#[case::double_check("4r1k1/8/8/8/8/3n4/3P4/4K3 w - - 0 1")]
+fn capture_and_evasion_moves_match_generation(#[case] fen: &str) {
+    for self_capture in [false, true] {
+        let mut game = Game8x8::new(fen, true).expect("valid FEN");
+        game.set_self_capture(self_capture);
+        let captures = game.capture_moves();
+        let evasions = game.evasion_moves();
+
+        let legal = game.legal_moves();
+        let expected: MoveList = legal
+            .iter()
+            .copied()
+            .filter(|mv| {
+                mv.flags
+                    .intersects(MoveFlags::CAPTURE | MoveFlags::PROMOTION)
+            })
+            .collect();
+        assert_eq!(captures, expected, "{}", fen);
+        // Filtered from the cache once it's filled
+        assert_eq!(game.capture_moves(), expected, "{}", fen);
+
+        if game.is_check() {
+            assert_eq!(evasions, legal, "{}", fen);
+        } else {
+            assert!(evasions.is_empty(), "{}", fen);
+        }
+    }
+}
+
+#[test]
+fn legal_moves_flag_checks() {
+    let mut game = Game8x8::standard();
+    play(&mut game, &["e2e4", "d7d6"]);
+    let bb5 = game
+        .legal_moves()
+        .into_iter()
+        .find(|mv| mv.to_lan() == "f1b5")
+        .expect("Bb5 is legal");
+    assert!(bb5.flags.contains(MoveFlags::CHECK));
+    assert!(
+        game.legal_moves()
+            .iter()
+            .all(|mv| { mv.flags.contains(MoveFlags::CHECK) == (mv.to_lan() == "f1b5") })
+    );
+
+    let mut game = Game8x8::standard();
+    play(&mut game, &["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6"]);
+    let qxf7 = game.move_from_lan("h5f7").expect("valid LAN");
+    assert_eq!(qxf7.flags, MoveFlags::CAPTURE | MoveFlags::CHECK);
+    assert!(game.legal_moves().contains(&qxf7));
+    assert!(game.capture_moves().contains(&qxf7));
+    assert!(game.make_move(&qxf7));
+    assert!(game.is_checkmate());
+}
+
+#[rstest]
+#[case::kiwipete("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")]
+#[case::promotions("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1")]
+#[case::discovered_checks("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8")]
+#[case::castling_and_discovered("5k2/8/8/2N5/8/Q7/8/4K2R w K - 0 1")]
+#[case::en_passant_along_row("8/8/8/R2pP2k/8/8/8/4K3 w - d6 0 1")]
+#[case::promotion_off_the_line("2n5/1P6/k7/8/8/8/8/4K3 w - - 0 1")]
+#[case::fairy_pieces("4k3/8/8/8/3A4/8/8/K1C5 w - - 0 1")]
+fn check_flags_match_making_the_move(#[case] fen: &str) {
+    fn assert_flags(game: &mut Game8x8) {
+        for mv in game.legal_moves() {
+            let mut after = game.clone();
+            assert!(after.make_move(&mv));
+            assert_eq!(
+                mv.flags.contains(MoveFlags::CHECK),
+                after.is_check(),
+                "{} in {}",
+                mv.to_lan(),
+                game.to_fen()
+            );
+            assert_eq!(game.annotate_move(&mv), mv);
+            assert!(game.legal_moves_for_position(&mv.src).contains(&mv));
+        }
+    }
+
+    // The position and every position one move in
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    assert_flags(&mut game);
+    for mv in game.legal_moves() {
+        assert!(game.make_move(&mv));
+        assert_flags(&mut game);
+        assert!(game.unmake_move());
+    }
+}
+
+#[test]
+fn self_capture_rule() {
+    let fen = "4k3/8/8/8/8/8/P3P3/R2QK3 w - - 0 1";
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    let normal = game.legal_moves().len();
+    let rxa2 = game.move_from_lan("a1a2").expect("valid LAN");
+    assert!(!game.is_legal_move(&rxa2));
+
+    game.set_self_capture(true);
+    assert!(game.self_capture_enabled());
+    // Rxa2, Rxd1, Qxa1, Qxe2, Kxd1 and Kxe2, but never the king itself
+    let mut self_captures: Vec<String> = game
+        .legal_moves()
+        .iter()
+        .filter(|mv| {
+            game.get_piece(&mv.dst)
+                .is_some_and(|p| p.color == Color::White)
+        })
+        .map(|mv| mv.to_lan())
+        .collect();
+    self_captures.sort();
+    assert_eq!(
+        self_captures,
+        vec!["a1a2", "a1d1", "d1a1", "d1e2", "e1d1", "e1e2"]
+    );
+    assert_eq!(game.legal_moves().len(), normal + 6);
+
+    // The legal-move fast path agrees with per-square generation
+    for mv in game.legal_moves() {
+        assert!(game.legal_moves_for_position(&mv.src).contains(&mv));
+    }
+
+    assert!(game.is_legal_move(&rxa2));
+    assert_eq!(game.move_to_san(&rxa2), "Rxa2");
+    assert!(game.make_move(&rxa2));
+    assert_eq!(game.to_fen(), "4k3/8/8/8/8/8/R3P3/3QK3 b - - 0 1");
+    assert_eq!(game.piece_counts().get(PieceType::Pawn, Color::White), 1);
+    assert!(game.unmake_move());
+    assert_eq!(game.to_fen(), fen);
+
+    // The king can't take its own piece on a square the opponent attacks
+    let mut game = Game8x8::new("4k3/8/8/1b6/8/8/4P3/4K3 w - - 0 1", true).expect("valid FEN");
+    game.set_self_capture(true);
+    let kxe2 = game.move_from_lan("e1e2").expect("valid LAN");
+    assert!(!game.is_legal_move(&kxe2));
+    assert!(!game.legal_moves().contains(&kxe2));
+}
+
+#[test]
+fn threefold_repetition() {
+    let mut game = Game8x8::standard();
+    let shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"];
+    assert_eq!(game.repetition_count(), 1);
+
+    for (i, lan) in shuffle.iter().chain(shuffle.iter()).enumerate() {
+        assert!(!game.is_over(), "over before move {}", i);
+        play(&mut game, &[*lan]);
+    }
+    assert_eq!(game.repetition_count(), 3);
+    assert!(game.is_threefold_repetition());
+    assert!(!game.is_fivefold_repetition());
+    assert!(game.can_claim_threefold());
+    assert!(!game.is_over());
+
+    // The default rules only draw automatically at the fifth occurrence
+    for lan in shuffle.iter().chain(shuffle.iter()) {
+        assert!(!game.is_over());
+        play(&mut game, &[*lan]);
+    }
+    assert_eq!(game.repetition_count(), 5);
+    assert!(game.is_fivefold_repetition());
+    assert!(game.is_over());
+    assert_eq!(game.outcome(), Some(GameOutcome::FivefoldRepetition));
+
+    // Taking a move back returns to a position seen four times
+    assert!(game.unmake_move());
+    assert_eq!(game.repetition_count(), 4);
+    assert_eq!(game.outcome(), None);
+
+    // Keys ignore the move clocks, and positions before a pawn move don't count
+    let mut a = Game8x8::new("4k3/8/8/8/8/8/4P3/4K3 w - - 7 20", true).expect("valid FEN");
+    let mut b = Game8x8::new("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", true).expect("valid FEN");
+    assert_eq!(a.position_key(), b.position_key());
+    play(&mut a, &["e2e3", "e8d8", "e1d1", "d8e8", "d1e1"]);
+    assert_eq!(a.repetition_count(), 2);
+
+    // An en passant square without a legal capture doesn't change the key
+    let mut ep = Game8x8::new("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1", true).expect("valid FEN");
+    let mut no_ep = Game8x8::new("4k3/8/8/8/4P3/8/8/4K3 b - - 0 1", true).expect("valid FEN");
+    assert_eq!(ep.position_key(), no_ep.position_key());
+
+    // ...so the position after the double push is repeated once the kings
+    // shuffle back, unless the en passant capture was legal there
+    for (fen, repetitions) in [
+        ("4k3/8/8/8/4P3/8/8/4K3 b - e3 0 1", 2),
+        ("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1", 1),
+    ] {
+        let mut game = Game8x8::new(fen, true).expect("valid FEN");
+        play(&mut game, &["e8d8", "e1d1", "d8e8", "d1e1"]);
+        assert_eq!(game.repetition_count(), repetitions, "{}", fen);
+    }
+}
+
+#[test]
+fn legal_moves_into_reuses_buffer() {
+    let mut game = Game8x8::new(
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        true,
+    )
+    .expect("legal_moves_into_reuses_buffer: valid FEN");
+    let mut moves = MoveList::new();
+    moves.push(Move::null());
+    game.legal_moves_into(&mut moves);
+    assert_eq!(moves.len(), 48);
+
+    // Filled from the cache once legal_moves() has run
+    let expected = game.legal_moves();
+    game.legal_moves_into(&mut moves);
+    assert_eq!(moves, expected);
+}
+
+#[rstest]
+#[case::start("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902])]
+#[case::kiwipete(
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    &[48, 2039, 97862]
+)]
+#[case::endgame("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812, 43238])]
+#[case::promotions(
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    &[6, 264, 9467]
+)]
+#[case::discovered_checks(
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    &[44, 1486, 62379]
+)]
+fn perft_known_positions(#[case] fen: &str, #[case] expected: &[u64]) {
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    for (depth, &nodes) in expected.iter().enumerate() {
+        assert_eq!(game.perft(depth as u32 + 1), nodes, "depth {}", depth + 1);
+    }
+    assert_eq!(game.to_fen(), fen);
+
+    let divide = game.perft_divide(2);
+    assert_eq!(divide.len() as u64, expected[0]);
+    assert_eq!(divide.iter().map(|&(_, n)| n).sum::<u64>(), expected[1]);
+}
+
+#[rstest]
+#[case::archbishop("8/7k/8/8/3A4/8/8/K7 w - - 0 1", 23)]
+#[case::chancellor("8/7k/8/8/3C4/8/8/K7 w - - 0 1", 25)]
+fn fairy_piece_moves(#[case] fen: &str, #[case] expected: usize) {
+    let mut game = Game8x8::new(fen, true).expect("valid FEN");
+    assert_eq!(game.legal_moves().len(), expected);
+    assert!(!game.is_insufficient_material());
+    assert_eq!(game.to_fen(), fen);
+}
+
+#[test]
+fn capablanca_start() {
+    let mut game = Game::capablanca();
+    assert_eq!(
+        game.to_fen(),
+        "rnabqkbcnr/pppppppppp/10/10/10/10/PPPPPPPPPP/RNABQKBCNR w KQkq - 0 1"
+    );
+    assert!(game.fairy_promotions_enabled());
+    for (depth, nodes) in [28, 784, 25228].into_iter().enumerate() {
+        assert_eq!(game.perft(depth as u32 + 1), nodes, "depth {}", depth + 1);
+    }
+
+    let mv = game.move_from_san("Ad3").expect("legal archbishop move");
+    assert_eq!(mv.to_lan(), "c1d3");
+    assert!(game.make_move(&mv));
+    let mv = game.move_from_san("Cg6").expect("legal chancellor move");
+    assert_eq!(game.move_to_san(&mv), "Cg6");
+}
+
+#[test]
+fn capablanca_promotions() {
+    let mut game: Game<10, 8> =
+        Game::new("4k5/P9/10/10/10/10/10/4K5 w - - 0 1", true).expect("valid FEN");
+    let promotions = |game: &mut Game<10, 8>| -> Vec<String> {
+        game.legal_moves()
+            .iter()
+            .filter(|m| m.promotion.is_some())
+            .map(|m| m.to_lan())
+            .collect()
+    };
+    assert_eq!(promotions(&mut game), ["a7a8q", "a7a8n", "a7a8b", "a7a8r"]);
+    let archbishop = Move::from_lan("a7a8a", 10, 8).expect("valid LAN");
+    assert!(!game.is_legal_move(&archbishop));
+
+    game.set_fairy_promotions(true);
+    assert_eq!(
+        promotions(&mut game),
+        ["a7a8q", "a7a8n", "a7a8b", "a7a8r", "a7a8a", "a7a8c"]
+    );
+    assert_eq!(game.move_to_san(&archbishop), "a8=A");
+    assert_eq!(
+        game.move_from_san("a8=C")
+            .expect("legal promotion")
+            .to_lan(),
+        "a7a8c"
+    );
+    assert!(game.make_move(&archbishop));
+    assert_eq!(
+        game.get_piece(&Position::from_algebraic("a8").expect("valid square")),
+        Some(Piece::new(PieceType::Archbishop, Color::White))
+    );
+
+    // Promotions by push and capture on both sides, with fairy pieces to
+    // capture; the same position has six promotions fewer without them
+    let fen = "r1c1k4r/1P8/10/10/10/10/7p2/R3K4R w KQkq - 0 1";
+    let mut game: Game<10, 8> = Game::new(fen, true).expect("valid FEN");
+    game.set_castling_king_cols(Some((8, 2)))
+        .expect("Capablanca castling columns");
+    game.set_fairy_promotions(true);
+    for (depth, nodes) in [44, 1558, 51072].into_iter().enumerate() {
+        assert_eq!(game.perft(depth as u32 + 1), nodes, "depth {}", depth + 1);
+    }
+    game.set_fairy_promotions(false);
+    assert_eq!(game.perft(1), 44 - 6);
+}
+
+#[test]
+fn piece_count_and_material() {
+    let mut game = Game8x8::standard();
+    assert_eq!(game.piece_count(Color::White, PieceType::Knight), 2);
+    assert_eq!(game.piece_count(Color::Black, PieceType::Pawn), 8);
+    assert_eq!(
+        game.material(Color::White, PieceType::value),
+        game.material(Color::Black, PieceType::value)
+    );
+    assert_eq!(game.material(Color::White, |_| 1), 16);
+
+    play(&mut game, &["e2e4", "d7d5", "e4d5"]);
+    assert_eq!(game.piece_count(Color::Black, PieceType::Pawn), 7);
+    assert_eq!(
+        game.material(Color::White, PieceType::value)
+            - game.material(Color::Black, PieceType::value),
+        PieceType::Pawn.value()
+    );
+    for piece_type in PieceType::ALL {
+        for color in [Color::White, Color::Black] {
+            assert_eq!(
+                game.piece_count(color, piece_type),
+                u32::from(game.piece_counts().get(piece_type, color))
+            );
+        }
+    }
+}
+
+#[test]
+fn setup_mutators() {
+    let square = |name: &str| Position::from_algebraic(name).expect("valid square");
+    let mut game = Game8x8::new("4k3/8/8/8/4P3/8/8/R3K2R w - - 0 1", true).expect("valid FEN");
+
+    // Moving a king keeps its cached square in step
+    game.set_piece(&square("e1"), None);
+    game.set_piece(
+        &square("d2"),
+        Some(Piece::new(PieceType::King, Color::White)),
+    );
+    game.set_piece(
+        &square("d8"),
+        Some(Piece::new(PieceType::Rook, Color::Black)),
+    );
+    assert!(game.is_check());
+    assert!(game.set_turn(Color::Black).is_err());
+    game.set_piece(&square("d8"), None);
+
+    assert!(game.set_castling_rights(Color::White, true, false).is_err());
+    game.set_piece(&square("d2"), None);
+    game.set_piece(
+        &square("e1"),
+        Some(Piece::new(PieceType::King, Color::White)),
+    );
+    game.set_castling_rights(Color::White, true, true)
+        .expect("king and rooks are in place");
+    assert!(game.set_castling_rights(Color::Black, false, true).is_err());
+
+    // The pawn on e4 could just have double pushed with black to move
+    assert!(game.set_en_passant(Some(square("e3"))).is_err());
+    game.set_turn(Color::Black).expect("white isn't in check");
+    game.set_en_passant(Some(square("e3")))
+        .expect("e4 could have double pushed");
+    assert_eq!(game.to_fen(), "4k3/8/8/8/4P3/8/8/R3K2R b KQ - 0 1");
+    assert!(game.set_en_passant(Some(square("d3"))).is_err());
+    assert!(
+        game.legal_moves()
+            .iter()
+            .all(|mv| !mv.flags.contains(MoveFlags::EN_PASSANT))
+    );
+    assert!(game.validate().is_empty());
+
+    let mut no_castling =
+        Game8x8::new("4k3/8/8/8/8/8/8/R3K2R w - - 0 1", false).expect("valid FEN");
+    assert!(
+        no_castling
+            .set_castling_rights(Color::White, true, false)
+            .is_err()
+    );
+}
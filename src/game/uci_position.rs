@@ -0,0 +1,122 @@
+use super::Game;
+use crate::error::ChessError;
+
+/// FEN of the standard starting position, which UCI calls `startpos`.
+const STARTPOS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// The game as it was before any of its moves were played.
+    pub fn start_position(&self) -> Self {
+        let mut start = self.clone();
+        while start.unmake_move_silent().is_some() {}
+        start
+    }
+
+    /// Build the UCI `position` command reproducing this game: the starting
+    /// position (as `startpos` or `fen ...`) followed by the moves played.
+    pub fn to_uci_position_string(&self) -> String {
+        let start_fen = self.start_position().to_fen();
+
+        let mut cmd = String::from("position ");
+        if W == 8 && H == 8 && start_fen == STARTPOS_FEN {
+            cmd.push_str("startpos");
+        } else {
+            cmd.push_str("fen ");
+            cmd.push_str(&start_fen);
+        }
+
+        if !self.move_history.is_empty() {
+            cmd.push_str(" moves");
+            for entry in &self.move_history {
+                cmd.push(' ');
+                cmd.push_str(&entry.mv.to_lan());
+            }
+        }
+        cmd
+    }
+
+    /// Parse a UCI `position` command (the leading `position` keyword is
+    /// optional) and replay its moves. Castling is enabled, and every move must
+    /// be legal.
+    pub fn from_uci_position_string(s: &str) -> Result<Self, ChessError> {
+        let s = s.trim();
+        let s = s.strip_prefix("position").unwrap_or(s).trim_start();
+
+        let (setup, moves) = match s.split_once(" moves") {
+            Some((setup, moves)) => (setup.trim(), moves),
+            None if s.ends_with("moves") => (s.trim_end_matches("moves").trim(), ""),
+            None => (s, ""),
+        };
+
+        let mut game = if setup == "startpos" {
+            if W != 8 || H != 8 {
+                return Err(ChessError::InvalidDimensions(format!(
+                    "startpos is only defined for 8x8 boards, not {}x{}",
+                    W, H
+                )));
+            }
+            Self::new(STARTPOS_FEN, true)?
+        } else if let Some(fen) = setup.strip_prefix("fen ") {
+            Self::new(fen.trim(), true)?
+        } else {
+            return Err(ChessError::fen(format!("invalid UCI position {}", s)));
+        };
+
+        for lan in moves.split_whitespace() {
+            let mv = game.move_from_lan(lan)?;
+            if !game.make_move(&mv) {
+                return Err(ChessError::IllegalMove(lan.to_string()));
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{Game, StandardGame};
+    use crate::test_support::play;
+
+    #[test]
+    fn test_uci_position_round_trip_startpos() {
+        let mut game = StandardGame::standard();
+        assert_eq!(game.to_uci_position_string(), "position startpos");
+
+        play(&mut game, &["e2e4", "e7e5", "g1f3"]);
+        let cmd = game.to_uci_position_string();
+        assert_eq!(cmd, "position startpos moves e2e4 e7e5 g1f3");
+
+        let mut parsed = StandardGame::from_uci_position_string(&cmd).expect("valid command");
+        assert_eq!(parsed.to_fen(), game.to_fen());
+        assert_eq!(parsed.move_count(), 3);
+    }
+
+    #[test]
+    fn test_uci_position_round_trip_fen() {
+        let fen = "4k5/10/10/10/10/10/PPPPPPPPPP/4K5 w - - 0 1";
+        let mut game: Game<10, 8> = Game::new(fen, true).expect("valid FEN");
+        let mv = game.move_from_lan("j2j4").expect("valid LAN");
+        assert!(game.make_move(&mv));
+
+        let cmd = game.to_uci_position_string();
+        assert_eq!(cmd, format!("position fen {} moves j2j4", fen));
+
+        let mut parsed: Game<10, 8> = Game::from_uci_position_string(&cmd).expect("valid command");
+        assert_eq!(parsed.to_fen(), game.to_fen());
+        assert_eq!(game.start_position().to_fen(), fen);
+        assert_eq!(game.move_count(), 1);
+    }
+
+    #[test]
+    fn test_uci_position_errors() {
+        assert!(StandardGame::from_uci_position_string("position startpos moves e2e5").is_err());
+        assert!(StandardGame::from_uci_position_string("position nonsense").is_err());
+        assert!(Game::<10, 8>::from_uci_position_string("position startpos").is_err());
+        assert!(StandardGame::from_uci_position_string("startpos moves").is_ok());
+    }
+}
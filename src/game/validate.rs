@@ -0,0 +1,112 @@
+use std::fmt;
+
+use crate::color::Color;
+use crate::pieces::{Piece, PieceType};
+use crate::position::Position;
+
+use super::Game;
+
+/// A way a position breaks the rules of chess, found by `Game::validate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Violation {
+    /// The side doesn't have exactly one king.
+    KingCount { color: Color, count: u32 },
+    /// A pawn on the first or last row, where it can never stand.
+    PawnOnBackRank(Position),
+    /// The side that just moved left its own king in check.
+    OpponentInCheck,
+    /// The en passant square isn't just behind a pawn that could have double
+    /// pushed past it.
+    InvalidEnPassant(Position),
+    /// A castling right without the king on its home row, or without a rook
+    /// of the same color in the matching corner.
+    InvalidCastlingRights { color: Color, kingside: bool },
+}
+
+#[hotpath::measure_all]
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::KingCount { color, count } => {
+                write!(f, "{} has {} kings", color, count)
+            }
+            Violation::PawnOnBackRank(pos) => write!(f, "pawn on back rank at {}", pos),
+            Violation::OpponentInCheck => write!(f, "side not to move is in check"),
+            Violation::InvalidEnPassant(pos) => write!(f, "invalid en passant square {}", pos),
+            Violation::InvalidCastlingRights { color, kingside } => write!(
+                f,
+                "{} {} castling right without king and rook in place",
+                color,
+                if *kingside { "kingside" } else { "queenside" }
+            ),
+        }
+    }
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Game<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Every rule the position breaks, or an empty list if it's legal: each
+    /// side has exactly one king, no pawns stand on the first or last row,
+    /// the side not to move isn't in check, and the en passant square and
+    /// castling rights match the board.
+    ///
+    /// FEN parsing accepts positions that break these rules, so check
+    /// user-supplied FENs with this before playing from them.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations = self.board.validate();
+
+        let kings_ok = !violations
+            .iter()
+            .any(|v| matches!(v, Violation::KingCount { .. }));
+        if kings_ok {
+            let opponent = self.turn.opposite();
+            let king = self
+                .board
+                .find_king(opponent)
+                .expect("validate: king count already checked");
+            if self.is_square_attacked(&king, self.turn) {
+                violations.push(Violation::OpponentInCheck);
+            }
+        }
+
+        if let Some(ep) = self.en_passant
+            && !self.is_valid_en_passant_square(&ep)
+        {
+            violations.push(Violation::InvalidEnPassant(ep));
+        }
+
+        for color in [Color::White, Color::Black] {
+            for kingside in [true, false] {
+                let has_right = if kingside {
+                    self.castling_rights.has_kingside(color)
+                } else {
+                    self.castling_rights.has_queenside(color)
+                };
+                if has_right && !self.castling_pieces_in_place(color, kingside) {
+                    violations.push(Violation::InvalidCastlingRights { color, kingside });
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Whether `color`'s king is on its home row, between the corners, with
+    /// a rook of its color in the corner castled towards.
+    pub(super) fn castling_pieces_in_place(&self, color: Color, kingside: bool) -> bool {
+        let row = match color {
+            Color::White => 0,
+            Color::Black => H - 1,
+        };
+        let rook_col = if kingside { W - 1 } else { 0 };
+        let rook = self.board.get_piece(&Position::from_usize(rook_col, row));
+        let king_in_place = (1..W - 1).any(|col| {
+            self.board.get_piece(&Position::from_usize(col, row))
+                == Some(Piece::new(PieceType::King, color))
+        });
+        king_in_place && rook == Some(Piece::new(PieceType::Rook, color))
+    }
+}
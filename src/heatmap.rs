@@ -0,0 +1,261 @@
+//! Per-square activity tallies over one or many games.
+//!
+//! A `Heatmap` counts, for every square, how often it was occupied by each
+//! side, how often it was a move's destination, and how often it was attacked
+//! by each side. Its dimensions are chosen at runtime, so one type serves every
+//! board size; games of a different size are rejected.
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::r#move::Move;
+
+/// Number of planes produced by `Heatmap::to_planes`.
+pub const HEATMAP_PLANES: usize = 5;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heatmap {
+    width: usize,
+    height: usize,
+    games: u64,
+    positions: u64,
+    occupancy: [Vec<u64>; 2],
+    destinations: Vec<u64>,
+    attacks: [Vec<u64>; 2],
+}
+
+#[inline]
+fn side(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+#[hotpath::measure_all]
+impl Heatmap {
+    pub fn new(width: usize, height: usize) -> Self {
+        let squares = width * height;
+        Heatmap {
+            width,
+            height,
+            games: 0,
+            positions: 0,
+            occupancy: [vec![0; squares], vec![0; squares]],
+            destinations: vec![0; squares],
+            attacks: [vec![0; squares], vec![0; squares]],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of games added with `add_game`.
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    /// Number of positions tallied.
+    pub fn positions(&self) -> u64 {
+        self.positions
+    }
+
+    /// How often each square held a piece of `color`, indexed by
+    /// `Position::to_index`.
+    pub fn occupancy(&self, color: Color) -> &[u64] {
+        &self.occupancy[side(color)]
+    }
+
+    /// How often each square was a move's destination.
+    pub fn destinations(&self) -> &[u64] {
+        &self.destinations
+    }
+
+    /// How often each square was attacked by `color`, counting each attacker.
+    pub fn attacks(&self, color: Color) -> &[u64] {
+        &self.attacks[side(color)]
+    }
+
+    fn check_dimensions(&self, width: usize, height: usize) -> Result<(), String> {
+        if (width, height) != (self.width, self.height) {
+            return Err(format!(
+                "Heatmap is {}x{}, but the board is {}x{}",
+                self.width, self.height, width, height
+            ));
+        }
+        Ok(())
+    }
+
+    /// Tally the occupancy and attacks of the current position of `game`.
+    pub fn add_position<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), String>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_dimensions(W, H)?;
+        for color in [Color::White, Color::Black] {
+            let s = side(color);
+            for (pos, _) in game.pieces_iter(color) {
+                self.occupancy[s][pos.to_index(W)] += 1;
+            }
+            for (idx, count) in game.attack_counts(color).into_iter().enumerate() {
+                self.attacks[s][idx] += u64::from(count);
+            }
+        }
+        self.positions += 1;
+        Ok(())
+    }
+
+    /// Tally the destination square of `mv`. A null move tallies nothing.
+    pub fn add_move(&mut self, mv: &Move) -> Result<(), String> {
+        if mv.is_null() {
+            return Ok(());
+        }
+        if !mv.dst.is_valid(self.width, self.height) {
+            return Err(format!(
+                "Move {} is off a {}x{} board",
+                mv.to_lan(),
+                self.width,
+                self.height
+            ));
+        }
+        self.destinations[mv.dst.to_index(self.width)] += 1;
+        Ok(())
+    }
+
+    /// Tally every position of `game`, from its starting position to the
+    /// current one, and every move played.
+    pub fn add_game<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), String>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.check_dimensions(W, H)?;
+
+        let mut replay = game.clone();
+        while replay.unmake_move_silent().is_some() {}
+
+        self.add_position(&replay)?;
+        for entry in game.move_history() {
+            self.add_move(&entry.mv)?;
+            replay.make_move_silent(&entry.mv);
+            self.add_position(&replay)?;
+        }
+        self.games += 1;
+        Ok(())
+    }
+
+    /// Add the tallies of another heatmap of the same size.
+    pub fn merge(&mut self, other: &Heatmap) -> Result<(), String> {
+        self.check_dimensions(other.width, other.height)?;
+        let pairs = self
+            .occupancy
+            .iter_mut()
+            .chain(self.attacks.iter_mut())
+            .chain(std::iter::once(&mut self.destinations))
+            .zip(
+                other
+                    .occupancy
+                    .iter()
+                    .chain(other.attacks.iter())
+                    .chain(std::iter::once(&other.destinations)),
+            );
+        for (ours, theirs) in pairs {
+            for (a, b) in ours.iter_mut().zip(theirs) {
+                *a += b;
+            }
+        }
+        self.games += other.games;
+        self.positions += other.positions;
+        Ok(())
+    }
+
+    /// Export the tallies as `HEATMAP_PLANES` planes for transfer to
+    /// Python/numpy: white occupancy, black occupancy, move destinations, white
+    /// attacks and black attacks.
+    ///
+    /// Returns (flat_data, num_planes, height, width), where flat_data is in
+    /// row-major order with row 0 first.
+    pub fn to_planes(&self) -> (Vec<f32>, usize, usize, usize) {
+        let planes = [
+            &self.occupancy[0],
+            &self.occupancy[1],
+            &self.destinations,
+            &self.attacks[0],
+            &self.attacks[1],
+        ];
+        let data = planes
+            .iter()
+            .flat_map(|plane| plane.iter().map(|&count| count as f32))
+            .collect();
+        (data, HEATMAP_PLANES, self.height, self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Game, StandardGame};
+    use crate::position::Position;
+    use crate::test_support::play;
+
+    fn idx(s: &str) -> usize {
+        Position::from_algebraic(s)
+            .expect("valid square")
+            .to_index(8)
+    }
+
+    #[test]
+    fn test_heatmap_add_game() {
+        let mut game = StandardGame::standard();
+        play(&mut game, &["e2e4", "e7e5", "g1f3"]);
+
+        let mut heatmap = Heatmap::new(8, 8);
+        heatmap.add_game(&game).expect("same size");
+        assert_eq!(heatmap.games(), 1);
+        assert_eq!(heatmap.positions(), 4);
+
+        let white = heatmap.occupancy(Color::White);
+        assert_eq!(white[idx("e2")], 1);
+        assert_eq!(white[idx("e4")], 3);
+        assert_eq!(white[idx("a1")], 4);
+        assert_eq!(heatmap.occupancy(Color::Black)[idx("e5")], 2);
+
+        assert_eq!(heatmap.destinations()[idx("f3")], 1);
+        assert_eq!(heatmap.destinations().iter().sum::<u64>(), 3);
+
+        // f3 is covered by the e2 and g2 pawns and the g1 knight; once the
+        // e-pawn moves the queen covers it instead, and the knight stops
+        // covering it by moving there
+        assert_eq!(heatmap.attacks(Color::White)[idx("f3")], 3 + 3 + 3 + 2);
+
+        let (data, planes, height, width) = heatmap.to_planes();
+        assert_eq!((planes, height, width), (HEATMAP_PLANES, 8, 8));
+        assert_eq!(data.len(), HEATMAP_PLANES * 64);
+        assert_eq!(data[2 * 64 + idx("f3")], 1.0);
+    }
+
+    #[test]
+    fn test_heatmap_merge_and_size_checks() {
+        let game = StandardGame::standard();
+        let mut a = Heatmap::new(8, 8);
+        a.add_game(&game).expect("same size");
+        let mut b = a.clone();
+        b.merge(&a).expect("same size");
+        assert_eq!(b.games(), 2);
+        assert_eq!(b.occupancy(Color::White)[idx("e1")], 2);
+
+        let small: Game<6, 6> = Game::new("k5/6/6/6/6/5K w - - 0 1", true).expect("valid FEN");
+        assert!(a.add_game(&small).is_err());
+        assert!(a.merge(&Heatmap::new(6, 6)).is_err());
+        assert_eq!(a.games(), 1);
+    }
+}
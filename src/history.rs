@@ -0,0 +1,181 @@
+//! Rolling window of encoded positions, kept outside of `Game`.
+//!
+//! `encode_game_planes` builds its history planes by unmaking and replaying
+//! the game's own moves. Environments that reset, or search trees that branch,
+//! often don't have that history; a `HistoryStacker` is pushed one position at
+//! a time instead and produces the same history planes. Clone it to branch.
+
+use std::collections::VecDeque;
+
+use crate::color::Color;
+use crate::encode::{PIECE_PLANES, fill_chess_planes};
+use crate::game::Game;
+
+/// Most recent positions encoded as piece planes, from white's perspective.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryStacker {
+    width: usize,
+    height: usize,
+    length: usize,
+    /// Newest first.
+    frames: VecDeque<Vec<f32>>,
+}
+
+#[hotpath::measure_all]
+impl HistoryStacker {
+    /// A stacker for `width` x `height` boards keeping the last `length`
+    /// positions. With `length = HISTORY_LENGTH` its output matches the history
+    /// planes of `encode_game_planes`.
+    pub fn new(width: usize, height: usize, length: usize) -> Self {
+        HistoryStacker {
+            width,
+            height,
+            length,
+            frames: VecDeque::with_capacity(length + 1),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Maximum number of positions kept.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Number of positions currently kept.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Forget all positions, e.g. when the environment resets.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Push the current position of `game` as the newest frame, dropping the
+    /// oldest once `length` positions are kept.
+    pub fn push_position<const W: usize, const H: usize>(
+        &mut self,
+        game: &Game<W, H>,
+    ) -> Result<(), String>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        if (W, H) != (self.width, self.height) {
+            return Err(format!(
+                "HistoryStacker is {}x{}, but the board is {}x{}",
+                self.width, self.height, W, H
+            ));
+        }
+        if self.length == 0 {
+            return Ok(());
+        }
+
+        let mut frame = if self.frames.len() == self.length {
+            let mut reused = self
+                .frames
+                .pop_back()
+                .expect("push_position: frames is non-empty");
+            reused.fill(0.0);
+            reused
+        } else {
+            vec![0.0f32; PIECE_PLANES * W * H]
+        };
+        fill_chess_planes::<W, H>(&mut frame, game, Color::White, 0);
+        self.frames.push_front(frame);
+        Ok(())
+    }
+
+    /// The kept positions stacked newest first, `PIECE_PLANES` planes each,
+    /// with `perspective`'s pieces in the first half of every frame. Missing
+    /// history is left as zeros, so there are always `length` frames.
+    ///
+    /// Returns (flat_data, num_planes, height, width), where flat_data is in
+    /// row-major order with row 0 first.
+    pub fn get_stacked(&self, perspective: Color) -> (Vec<f32>, usize, usize, usize) {
+        let num_planes = self.length * PIECE_PLANES;
+        let board_size = self.width * self.height;
+        let half = (PIECE_PLANES / 2) * board_size;
+
+        let mut data = vec![0.0f32; num_planes * board_size];
+        for (frame, out) in self
+            .frames
+            .iter()
+            .zip(data.chunks_exact_mut(PIECE_PLANES * board_size))
+        {
+            match perspective {
+                Color::White => out.copy_from_slice(frame),
+                Color::Black => {
+                    out[..half].copy_from_slice(&frame[half..]);
+                    out[half..].copy_from_slice(&frame[..half]);
+                }
+            }
+        }
+        (data, num_planes, self.height, self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encode::{HISTORY_LENGTH, encode_game_planes};
+    use crate::game::StandardGame;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_history_stacker_matches_encoder() {
+        let mut game = StandardGame::standard();
+        let mut stacker = HistoryStacker::new(8, 8, HISTORY_LENGTH);
+        stacker.push_position(&game).expect("same size");
+
+        let lans = [
+            "e2e4", "e7e5", "g1f3", "b8c6", "f1b5", "a7a6", "b5a4", "g8f6", "e1g1", "f8e7",
+        ];
+        for lan in lans {
+            play(&mut game, &[lan]);
+            stacker.push_position(&game).expect("same size");
+
+            let (data, num_planes, height, width) = stacker.get_stacked(game.turn());
+            assert_eq!(
+                (num_planes, height, width),
+                (HISTORY_LENGTH * PIECE_PLANES, 8, 8)
+            );
+            let (encoded, _, _, _) = encode_game_planes(&mut game);
+            assert_eq!(&data[..], &encoded[..data.len()], "after {}", lan);
+        }
+        assert_eq!(stacker.len(), HISTORY_LENGTH);
+    }
+
+    #[test]
+    fn test_history_stacker_branch_and_reset() {
+        let mut game = StandardGame::standard();
+        let mut stacker = HistoryStacker::new(8, 8, 2);
+        stacker.push_position(&game).expect("same size");
+
+        let mut branch = stacker.clone();
+        play(&mut game, &["e2e4"]);
+        branch.push_position(&game).expect("same size");
+        assert_eq!(stacker.len(), 1);
+        assert_eq!(branch.len(), 2);
+
+        // The second frame of a single-position stack is empty
+        let (data, _, _, _) = stacker.get_stacked(Color::White);
+        assert!(data[PIECE_PLANES * 64..].iter().all(|&v| v == 0.0));
+
+        branch.clear();
+        assert!(branch.is_empty());
+
+        let small: Game<6, 6> = Game::new("k5/6/6/6/6/5K w - - 0 1", true).expect("valid FEN");
+        assert!(stacker.push_position(&small).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Conversions between spooky_chess 8x8 types and `shakmaty`.
+//!
+//! Enabled with the `shakmaty` feature. Positions convert through FEN, so the
+//! conversion is exact for everything FEN records (board, side to move,
+//! castling rights, en passant square and clocks) but drops move history.
+
+use shakmaty::fen::Fen;
+use shakmaty::uci::UciMove;
+use shakmaty::{CastlingMode, Chess, EnPassantMode, File, Rank, Role, Square};
+
+use crate::color::Color;
+use crate::error::ChessError;
+use crate::game::StandardGame;
+use crate::r#move::{Move, MoveFlags};
+use crate::pieces::{Piece, PieceType};
+use crate::position::Position;
+
+impl From<Color> for shakmaty::Color {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::White => shakmaty::Color::White,
+            Color::Black => shakmaty::Color::Black,
+        }
+    }
+}
+
+impl From<shakmaty::Color> for Color {
+    fn from(color: shakmaty::Color) -> Self {
+        match color {
+            shakmaty::Color::White => Color::White,
+            shakmaty::Color::Black => Color::Black,
+        }
+    }
+}
+
+impl TryFrom<PieceType> for Role {
+    type Error = ChessError;
+
+    /// Fails for fairy pieces, which `shakmaty` doesn't have.
+    fn try_from(piece_type: PieceType) -> Result<Self, Self::Error> {
+        Ok(match piece_type {
+            PieceType::Pawn => Role::Pawn,
+            PieceType::Knight => Role::Knight,
+            PieceType::Bishop => Role::Bishop,
+            PieceType::Rook => Role::Rook,
+            PieceType::Queen => Role::Queen,
+            PieceType::King => Role::King,
+            PieceType::Archbishop | PieceType::Chancellor => {
+                return Err(ChessError::Unsupported(format!(
+                    "No shakmaty role for {}",
+                    piece_type.name()
+                )));
+            }
+        })
+    }
+}
+
+impl From<Role> for PieceType {
+    fn from(role: Role) -> Self {
+        match role {
+            Role::Pawn => PieceType::Pawn,
+            Role::Knight => PieceType::Knight,
+            Role::Bishop => PieceType::Bishop,
+            Role::Rook => PieceType::Rook,
+            Role::Queen => PieceType::Queen,
+            Role::King => PieceType::King,
+        }
+    }
+}
+
+impl TryFrom<Piece> for shakmaty::Piece {
+    type Error = ChessError;
+
+    fn try_from(piece: Piece) -> Result<Self, Self::Error> {
+        Ok(shakmaty::Piece {
+            color: piece.color.into(),
+            role: piece.piece_type.try_into()?,
+        })
+    }
+}
+
+impl From<shakmaty::Piece> for Piece {
+    fn from(piece: shakmaty::Piece) -> Self {
+        Piece::new(piece.role.into(), piece.color.into())
+    }
+}
+
+impl TryFrom<Position> for Square {
+    type Error = ChessError;
+
+    fn try_from(pos: Position) -> Result<Self, Self::Error> {
+        if pos.col >= 8 || pos.row >= 8 {
+            return Err(ChessError::Unsupported(format!(
+                "Square {} is outside an 8x8 board",
+                pos.to_algebraic()
+            )));
+        }
+        Ok(Square::from_coords(
+            File::new(u32::from(pos.col)),
+            Rank::new(u32::from(pos.row)),
+        ))
+    }
+}
+
+impl From<Square> for Position {
+    fn from(square: Square) -> Self {
+        Position::new(square.file().to_u32() as u8, square.rank().to_u32() as u8)
+    }
+}
+
+impl TryFrom<&Move> for UciMove {
+    type Error = ChessError;
+
+    fn try_from(mv: &Move) -> Result<Self, Self::Error> {
+        let lan = mv.to_lan();
+        UciMove::from_ascii(lan.as_bytes())
+            .map_err(|e| ChessError::InvalidMove(format!("{}: {}", lan, e)))
+    }
+}
+
+impl TryFrom<&UciMove> for Move {
+    type Error = ChessError;
+
+    /// Converts coordinates and promotion only; flags are left empty, as with
+    /// `Move::from_lan`.
+    fn try_from(uci: &UciMove) -> Result<Self, Self::Error> {
+        match uci {
+            UciMove::Normal { .. } => Move::from_lan(&uci.to_string(), 8, 8),
+            _ => Err(ChessError::Unsupported(format!(
+                "Unsupported UCI move: {}",
+                uci
+            ))),
+        }
+    }
+}
+
+impl TryFrom<&shakmaty::Move> for Move {
+    type Error = ChessError;
+
+    /// Converts a fully specified `shakmaty` move, including flags.
+    fn try_from(mv: &shakmaty::Move) -> Result<Self, Self::Error> {
+        match *mv {
+            shakmaty::Move::Normal {
+                role,
+                from,
+                capture,
+                to,
+                promotion,
+            } => {
+                let mut flags = MoveFlags::empty();
+                if capture.is_some() {
+                    flags |= MoveFlags::CAPTURE;
+                }
+                if role == Role::Pawn && from.rank().to_u32().abs_diff(to.rank().to_u32()) == 2 {
+                    flags |= MoveFlags::DOUBLE_PUSH;
+                }
+                Ok(match promotion {
+                    Some(promo) => Move::from_position_with_promotion(
+                        from.into(),
+                        to.into(),
+                        flags,
+                        promo.into(),
+                    ),
+                    None => Move::from_position(from.into(), to.into(), flags),
+                })
+            }
+            shakmaty::Move::EnPassant { from, to } => Ok(Move::from_position(
+                from.into(),
+                to.into(),
+                MoveFlags::CAPTURE | MoveFlags::EN_PASSANT,
+            )),
+            shakmaty::Move::Castle { king, rook } => {
+                // shakmaty encodes castling as king-takes-rook; we use the
+                // king's destination square
+                let king_file = if rook.file() > king.file() {
+                    File::G
+                } else {
+                    File::C
+                };
+                let dst = Square::from_coords(king_file, king.rank());
+                Ok(Move::from_position(
+                    king.into(),
+                    dst.into(),
+                    MoveFlags::CASTLE,
+                ))
+            }
+            shakmaty::Move::Put { .. } => {
+                Err(ChessError::Unsupported(format!("Unsupported move: {}", mv)))
+            }
+        }
+    }
+}
+
+impl TryFrom<&StandardGame> for Chess {
+    type Error = ChessError;
+
+    fn try_from(game: &StandardGame) -> Result<Self, Self::Error> {
+        let fen = format!("{:#}", game);
+        Fen::from_ascii(fen.as_bytes())
+            .map_err(|e| ChessError::fen(format!("{}: {}", fen, e)))?
+            .into_position(CastlingMode::Standard)
+            .map_err(|e| ChessError::InvalidPosition(format!("Invalid position {}: {}", fen, e)))
+    }
+}
+
+impl TryFrom<&Chess> for StandardGame {
+    type Error = ChessError;
+
+    fn try_from(pos: &Chess) -> Result<Self, Self::Error> {
+        let fen = Fen::from_position(pos, EnPassantMode::Legal).to_string();
+        StandardGame::new(&fen, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shakmaty::Position as _;
+
+    use super::*;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_shakmaty_position_round_trip() {
+        let mut game = StandardGame::standard();
+        play(&mut game, &["e2e4", "c7c5", "e4e5", "d7d5"]);
+
+        let chess = Chess::try_from(&game).expect("valid position");
+        let mut back = StandardGame::try_from(&chess).expect("valid position");
+        assert_eq!(back.to_fen(), game.to_fen());
+    }
+
+    #[test]
+    fn test_shakmaty_legal_moves_match() {
+        let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1";
+        let mut game = StandardGame::new(fen, true).expect("valid FEN");
+        let chess = Chess::try_from(&game).expect("valid position");
+
+        let mut ours: Vec<Move> = game.legal_moves().into_iter().collect();
+        let mut theirs: Vec<Move> = chess
+            .legal_moves()
+            .iter()
+            .map(|m| Move::try_from(m).expect("standard move"))
+            .collect();
+        let key = |m: &Move| m.to_lan();
+        ours.sort_by_key(key);
+        theirs.sort_by_key(key);
+        assert_eq!(ours, theirs);
+    }
+
+    #[test]
+    fn test_shakmaty_square_conversion() {
+        let pos = Position::new(4, 3);
+        let square = Square::try_from(pos).expect("on board");
+        assert_eq!(square, Square::E4);
+        assert_eq!(Position::from(square), pos);
+        assert!(Square::try_from(Position::new(8, 0)).is_err());
+    }
+
+    #[test]
+    fn test_shakmaty_role_conversion() {
+        for piece_type in PieceType::STANDARD {
+            let role = Role::try_from(piece_type).expect("standard piece");
+            assert_eq!(PieceType::from(role), piece_type);
+        }
+        assert!(Role::try_from(PieceType::Archbishop).is_err());
+        assert!(
+            shakmaty::Piece::try_from(Piece::new(PieceType::Chancellor, Color::White)).is_err()
+        );
+    }
+}
@@ -1,19 +1,43 @@
 #![feature(generic_const_exprs)]
 #![allow(incomplete_features)]
 
+pub mod action_space;
+pub mod adjudication;
+pub mod analysis;
+pub mod arena;
 pub mod bitboard;
 pub(crate) mod board;
 pub mod color;
+pub mod dataset;
 pub mod directions;
+#[cfg(feature = "dyn-game")]
+#[macro_use]
+pub mod dyn_game;
 pub mod encode;
+pub mod epd;
+pub mod error;
 pub mod game;
+pub mod heatmap;
+pub mod history;
+#[cfg(feature = "shakmaty")]
+pub mod interop;
 pub(crate) mod limits;
+pub mod mcts;
 pub mod r#move;
+pub mod move_list;
+pub mod opening_tree;
 pub mod outcome;
 pub mod pgn;
 pub mod pieces;
 pub mod position;
+pub mod render;
+pub mod search;
+pub mod self_play;
+pub mod stats;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod uci;
+pub mod wdl;
 
 #[cfg(feature = "python")]
 extern crate pyo3;
@@ -31,15 +55,36 @@ fn spooky_chess(m: &Bound<'_, PyModule>) -> PyResult<()> {
     use color::Color;
     use python::*;
     m.add_class::<PyGame>()?;
+    m.add_class::<PyGameBatch>()?;
     m.add_class::<PyMove>()?;
     m.add_class::<PyPiece>()?;
     m.add_class::<PyPosition>()?;
     m.add_class::<PyGameOutcome>()?;
     m.add_class::<PyTurnState>()?;
     m.add_class::<PyPgnGame>()?;
+    m.add_class::<PyPgnReader>()?;
     m.add_class::<PyUciEngine>()?;
     m.add_class::<PySearchResult>()?;
+    m.add_class::<PyEncoderConfig>()?;
+    m.add_class::<PyRewardConfig>()?;
+    m.add_class::<PyWdlModel>()?;
+    m.add_class::<PyGameStats>()?;
+    m.add_class::<PyMoveGenStats>()?;
+    m.add_class::<PyHeatmap>()?;
+    m.add_class::<PyHistoryStacker>()?;
+    m.add_class::<PyAecEnv>()?;
+    m.add_class::<PyAecAgentIter>()?;
+    m.add_class::<PyChessEnv>()?;
+    m.add_class::<PyVecEnv>()?;
+    m.add_class::<PySampleWriter>()?;
+    m.add_class::<PySampleReader>()?;
+    register_exceptions(m)?;
+    m.add("HEATMAP_PLANES", heatmap::HEATMAP_PLANES)?;
     m.add_function(wrap_pyfunction!(py_parse_pgn, m)?)?;
+    m.add_function(wrap_pyfunction!(py_label_with_engine, m)?)?;
+    m.add_function(wrap_pyfunction!(py_self_play, m)?)?;
+    m.add_function(wrap_pyfunction!(py_play_match, m)?)?;
+    m.add_function(wrap_pyfunction!(py_pgn_to_samples, m)?)?;
     m.add("WHITE", Color::White as i8)?;
     m.add("BLACK", Color::Black as i8)?;
     m.add("TOTAL_INPUT_PLANES", encode::TOTAL_INPUT_PLANES)?;
@@ -57,5 +102,7 @@ fn spooky_chess(m: &Bound<'_, PyModule>) -> PyResult<()> {
         "NUM_PROMOTION_ORIENTATIONS",
         encode::NUM_PROMOTION_ORIENTATIONS,
     )?;
+    m.add("NUM_FAIRY_PROMO_PIECES", encode::NUM_FAIRY_PROMO_PIECES)?;
+    m.add("FAIRY_PROMOTION_PLANES", encode::FAIRY_PROMOTION_PLANES)?;
     Ok(())
 }
@@ -1,3 +1,5 @@
+use crate::error::ChessError;
+
 pub const MIN_BOARD_DIM: usize = 6;
 pub const MAX_BOARD_DIM: usize = 16;
 
@@ -6,18 +8,18 @@ pub const fn board_dimension_is_valid(dimension: usize) -> bool {
     dimension >= MIN_BOARD_DIM && dimension <= MAX_BOARD_DIM
 }
 
-pub fn validate_board_dimensions(width: usize, height: usize) -> Result<(), String> {
+pub fn validate_board_dimensions(width: usize, height: usize) -> Result<(), ChessError> {
     if !board_dimension_is_valid(width) {
-        return Err(format!(
+        return Err(ChessError::InvalidDimensions(format!(
             "Board width must be between {} and {}",
             MIN_BOARD_DIM, MAX_BOARD_DIM
-        ));
+        )));
     }
     if !board_dimension_is_valid(height) {
-        return Err(format!(
+        return Err(ChessError::InvalidDimensions(format!(
             "Board height must be between {} and {}",
             MIN_BOARD_DIM, MAX_BOARD_DIM
-        ));
+        )));
     }
     Ok(())
 }
@@ -0,0 +1,702 @@
+//! Monte Carlo tree search with PUCT selection, as in AlphaZero.
+//!
+//! Leaves are scored by an `Evaluator`, which supplies a prior for each legal
+//! move and a value for the side to move, typically from a network fed with
+//! the planes of `encode`. Leaves are handed to the evaluator in batches: a
+//! virtual loss is placed on each path already taken, so the simulations of
+//! one batch spread over different lines. Dirichlet noise can be mixed into
+//! the root priors to vary self-play games.
+
+use std::collections::VecDeque;
+
+use crate::encode::EncoderConfig;
+use crate::game::{Game, PlayoutRng};
+use crate::r#move::Move;
+use crate::outcome::MoveList;
+
+/// What an `Evaluator` reports for one position.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluation {
+    /// One prior per legal move, in `legal_moves()` order. They are
+    /// normalized when the node is expanded, so any non-negative weights do.
+    pub priors: Vec<f32>,
+    /// Expected result for the side to move, from -1 (loss) to 1 (win).
+    pub value: f32,
+}
+
+impl Evaluation {
+    /// The priors of `game`'s legal moves picked out of `policy`, a score per
+    /// action of `config`'s action space (e.g. a network's softmaxed output).
+    pub fn from_action_policy<const W: usize, const H: usize>(
+        game: &mut Game<W, H>,
+        policy: &[f32],
+        value: f32,
+        config: &EncoderConfig,
+    ) -> Self
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        let priors = game
+            .legal_moves()
+            .iter()
+            .map(|mv| {
+                game.encode_action_with_config(mv, config)
+                    .and_then(|action| policy.get(action).copied())
+                    .unwrap_or(0.0)
+            })
+            .collect();
+        Evaluation { priors, value }
+    }
+}
+
+/// Supplies priors and values for the leaves of an `Mcts` search.
+///
+/// Closures taking `&mut [Game<W, H>]` and returning
+/// `Result<Vec<Evaluation>, String>` are evaluators.
+pub trait Evaluator<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// One `Evaluation` per game, in order. Every game is unfinished.
+    fn evaluate(&mut self, games: &mut [Game<W, H>]) -> Result<Vec<Evaluation>, String>;
+}
+
+impl<F, const W: usize, const H: usize> Evaluator<W, H> for F
+where
+    F: FnMut(&mut [Game<W, H>]) -> Result<Vec<Evaluation>, String>,
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn evaluate(&mut self, games: &mut [Game<W, H>]) -> Result<Vec<Evaluation>, String> {
+        self(games)
+    }
+}
+
+/// Uniform priors, and the result of a random playout as the value: plain
+/// MCTS, without a network.
+pub struct RolloutEvaluator {
+    rng: PlayoutRng,
+    /// Plies after which a playout is scored as a draw.
+    pub max_plies: usize,
+}
+
+impl RolloutEvaluator {
+    pub fn new(seed: u64, max_plies: usize) -> Self {
+        RolloutEvaluator {
+            rng: PlayoutRng(seed),
+            max_plies,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Evaluator<W, H> for RolloutEvaluator
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn evaluate(&mut self, games: &mut [Game<W, H>]) -> Result<Vec<Evaluation>, String> {
+        Ok(games
+            .iter_mut()
+            .map(|game| {
+                let turn = game.turn();
+                let priors = vec![1.0; game.legal_moves().len()];
+                let value = game
+                    .clone()
+                    .random_playout(self.rng.next_u64(), self.max_plies)
+                    .map_or(0.0, |outcome| outcome.encode_winner_from_perspective(turn));
+                Evaluation { priors, value }
+            })
+            .collect())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MctsConfig {
+    /// Simulations added by each `run`.
+    pub simulations: u32,
+    /// Most leaves handed to the evaluator at once.
+    pub batch_size: usize,
+    /// Weight of the prior-driven exploration term against the mean value.
+    pub c_puct: f32,
+    pub dirichlet_alpha: f32,
+    /// Share of Dirichlet noise in the root priors; 0 disables the noise.
+    pub dirichlet_epsilon: f32,
+    /// Seed of the Dirichlet noise.
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            simulations: 800,
+            batch_size: 8,
+            c_puct: 1.5,
+            dirichlet_alpha: 0.3,
+            dirichlet_epsilon: 0.0,
+            seed: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Expansion {
+    Unexpanded,
+    /// Waiting for the evaluator, in the current batch.
+    Pending,
+    Expanded,
+    /// The game is over, with this value for the side to move.
+    Terminal(f32),
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    /// Move leading to the node; null for the root.
+    mv: Move,
+    prior: f32,
+    visits: u32,
+    /// Sum of the values backed up through the node, for the side that
+    /// played `mv`.
+    value_sum: f32,
+    virtual_loss: u32,
+    /// Children are stored contiguously.
+    first_child: usize,
+    num_children: usize,
+    expansion: Expansion,
+}
+
+impl Node {
+    fn new(mv: Move, prior: f32) -> Self {
+        Node {
+            mv,
+            prior,
+            visits: 0,
+            value_sum: 0.0,
+            virtual_loss: 0,
+            first_child: 0,
+            num_children: 0,
+            expansion: Expansion::Unexpanded,
+        }
+    }
+
+    fn children(&self) -> std::ops::Range<usize> {
+        self.first_child..self.first_child + self.num_children
+    }
+}
+
+/// A search tree rooted at a position. Each `run` adds simulations to it;
+/// `advance` moves the root down a move, keeping the subtree below it.
+pub struct Mcts<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    game: Game<W, H>,
+    nodes: Vec<Node>,
+    config: MctsConfig,
+    rng: PlayoutRng,
+    root_noised: bool,
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Mcts<W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    pub fn new(game: Game<W, H>, config: MctsConfig) -> Self {
+        Mcts {
+            game,
+            nodes: vec![Node::new(Move::null(), 1.0)],
+            rng: PlayoutRng(config.seed),
+            config,
+            root_noised: false,
+        }
+    }
+
+    /// The root position.
+    pub fn game(&self) -> &Game<W, H> {
+        &self.game
+    }
+
+    pub fn config(&self) -> &MctsConfig {
+        &self.config
+    }
+
+    /// Simulations through the root so far.
+    pub fn visits(&self) -> u32 {
+        self.nodes[0].visits
+    }
+
+    /// Mean value of the root for its side to move, or 0 before any
+    /// simulation.
+    pub fn root_value(&self) -> f32 {
+        let root = &self.nodes[0];
+        if root.visits == 0 {
+            return 0.0;
+        }
+        -root.value_sum / root.visits as f32
+    }
+
+    /// Visits of each legal move of the root, in `legal_moves()` order.
+    /// Empty until the root has been expanded.
+    pub fn visit_counts(&self) -> Vec<(Move, u32)> {
+        self.nodes[0]
+            .children()
+            .map(|child| (self.nodes[child].mv, self.nodes[child].visits))
+            .collect()
+    }
+
+    /// The most visited root move, the first on ties.
+    pub fn best_move(&self) -> Option<Move> {
+        self.visit_counts()
+            .into_iter()
+            .rev()
+            .max_by_key(|&(_, visits)| visits)
+            .map(|(mv, _)| mv)
+    }
+
+    /// Visit counts raised to `1 / temperature` and normalized, the usual
+    /// policy training target. A temperature of 0 puts everything on
+    /// `best_move`.
+    pub fn policy(&self, temperature: f32) -> Vec<(Move, f32)> {
+        let counts = self.visit_counts();
+        if temperature <= 0.0 {
+            let best = self.best_move();
+            return counts
+                .into_iter()
+                .map(|(mv, _)| (mv, if Some(mv) == best { 1.0 } else { 0.0 }))
+                .collect();
+        }
+        let weights: Vec<f32> = counts
+            .iter()
+            .map(|&(_, visits)| (visits as f32).powf(1.0 / temperature))
+            .collect();
+        let total: f32 = weights.iter().sum();
+        counts
+            .iter()
+            .zip(weights)
+            .map(|(&(mv, _), weight)| {
+                let share = if total > 0.0 {
+                    weight / total
+                } else {
+                    1.0 / counts.len() as f32
+                };
+                (mv, share)
+            })
+            .collect()
+    }
+
+    /// Add `config.simulations` simulations to the tree, asking `evaluator`
+    /// for batches of up to `config.batch_size` leaves. An evaluator error is
+    /// returned with the tree as it was before that batch.
+    pub fn run<E: Evaluator<W, H> + ?Sized>(&mut self, evaluator: &mut E) -> Result<(), String> {
+        let target = self.nodes[0].visits + self.config.simulations;
+        while self.nodes[0].visits < target {
+            if self.nodes[0].expansion == Expansion::Expanded && !self.root_noised {
+                self.add_root_noise();
+            }
+            let batch = self.config.batch_size.max(1);
+            let batch = batch.min((target - self.nodes[0].visits) as usize);
+            self.run_batch(batch, evaluator)?;
+        }
+        Ok(())
+    }
+
+    /// Play `mv` at the root, keeping its subtree. Returns false, changing
+    /// nothing, if the move is illegal.
+    pub fn advance(&mut self, mv: &Move) -> bool {
+        let same_move = |other: &Move| {
+            (other.src, other.dst, other.promotion) == (mv.src, mv.dst, mv.promotion)
+        };
+        let Some(legal) = self.game.legal_moves().into_iter().find(same_move) else {
+            return false;
+        };
+        let child = self.nodes[0]
+            .children()
+            .find(|&child| self.nodes[child].mv == legal);
+        self.game.make_move_unchecked(&legal);
+        self.root_noised = false;
+
+        let Some(child) = child else {
+            self.nodes = vec![Node::new(Move::null(), 1.0)];
+            return true;
+        };
+        let mut nodes = vec![self.nodes[child].clone()];
+        let mut queue = VecDeque::from([(child, 0)]);
+        while let Some((old, new)) = queue.pop_front() {
+            let first_child = nodes.len();
+            for old_child in self.nodes[old].children() {
+                queue.push_back((old_child, nodes.len()));
+                nodes.push(self.nodes[old_child].clone());
+            }
+            nodes[new].first_child = first_child;
+        }
+        self.nodes = nodes;
+        true
+    }
+
+    fn run_batch<E: Evaluator<W, H> + ?Sized>(
+        &mut self,
+        batch: usize,
+        evaluator: &mut E,
+    ) -> Result<(), String> {
+        let mut paths = Vec::new();
+        let mut games = Vec::new();
+        for _ in 0..batch {
+            let (path, mut game) = self.select();
+            let leaf = *path.last().expect("run_batch: path holds the root");
+            match self.nodes[leaf].expansion {
+                Expansion::Terminal(value) => self.backup(&path, value),
+                // Every line left leads to a leaf already in the batch
+                Expansion::Pending => break,
+                Expansion::Unexpanded => {
+                    if let Some(outcome) = game.outcome() {
+                        let value = outcome.encode_winner_from_perspective(game.turn());
+                        self.nodes[leaf].expansion = Expansion::Terminal(value);
+                        self.backup(&path, value);
+                        continue;
+                    }
+                    self.nodes[leaf].expansion = Expansion::Pending;
+                    self.add_virtual_loss(&path, 1);
+                    paths.push(path);
+                    games.push(game);
+                }
+                Expansion::Expanded => unreachable!("run_batch: selection stops at a leaf"),
+            }
+        }
+        if games.is_empty() {
+            return Ok(());
+        }
+
+        let evaluations = evaluator.evaluate(&mut games).and_then(|evaluations| {
+            check_evaluations(&mut games, &evaluations)?;
+            Ok(evaluations)
+        });
+        let evaluations = match evaluations {
+            Ok(evaluations) => evaluations,
+            Err(e) => {
+                for path in &paths {
+                    let leaf = *path.last().expect("run_batch: path holds the root");
+                    self.nodes[leaf].expansion = Expansion::Unexpanded;
+                    self.add_virtual_loss(path, -1);
+                }
+                return Err(e);
+            }
+        };
+        for ((path, game), evaluation) in paths.iter().zip(&mut games).zip(evaluations) {
+            let leaf = *path.last().expect("run_batch: path holds the root");
+            self.expand(leaf, game, &evaluation.priors);
+            self.add_virtual_loss(path, -1);
+            self.backup(path, evaluation.value);
+        }
+        Ok(())
+    }
+
+    /// Walk from the root to a leaf, choosing the child with the best PUCT
+    /// score at each node. Returns the nodes visited and the leaf's position.
+    fn select(&self) -> (Vec<usize>, Game<W, H>) {
+        let mut game = self.game.clone();
+        let mut path = vec![0];
+        let mut node = 0;
+        while self.nodes[node].expansion == Expansion::Expanded {
+            let parent = &self.nodes[node];
+            let parent_visits = (parent.visits + parent.virtual_loss) as f32;
+            let exploration = self.config.c_puct * parent_visits.max(1.0).sqrt();
+            let mut best = (f32::NEG_INFINITY, parent.first_child);
+            for child in parent.children() {
+                let child_node = &self.nodes[child];
+                let visits = (child_node.visits + child_node.virtual_loss) as f32;
+                // Virtual visits count as losses
+                let q = if visits > 0.0 {
+                    (child_node.value_sum - child_node.virtual_loss as f32) / visits
+                } else {
+                    0.0
+                };
+                let score = q + exploration * child_node.prior / (1.0 + visits);
+                if score > best.0 {
+                    best = (score, child);
+                }
+            }
+            node = best.1;
+            game.make_move_silent(&self.nodes[node].mv);
+            path.push(node);
+        }
+        (path, game)
+    }
+
+    /// Add a child for each legal move of `game`, the position at `leaf`,
+    /// with its prior from the matching entry of `priors`.
+    fn expand(&mut self, leaf: usize, game: &mut Game<W, H>, priors: &[f32]) {
+        let mut moves = MoveList::new();
+        game.legal_moves_into(&mut moves);
+        let weights: Vec<f32> = priors
+            .iter()
+            .map(|&p| if p.is_finite() { p.max(0.0) } else { 0.0 })
+            .collect();
+        let total: f32 = weights.iter().sum();
+
+        let first_child = self.nodes.len();
+        for (mv, weight) in moves.iter().zip(weights) {
+            let prior = if total > 0.0 {
+                weight / total
+            } else {
+                1.0 / moves.len() as f32
+            };
+            self.nodes.push(Node::new(*mv, prior));
+        }
+        let node = &mut self.nodes[leaf];
+        node.first_child = first_child;
+        node.num_children = moves.len();
+        node.expansion = Expansion::Expanded;
+    }
+
+    /// Add a visit with `value`, for the side to move at the end of `path`,
+    /// to every node on it.
+    fn backup(&mut self, path: &[usize], value: f32) {
+        let mut value = value;
+        for &node in path.iter().rev() {
+            // Each node keeps values for the side that moved into it
+            value = -value;
+            self.nodes[node].visits += 1;
+            self.nodes[node].value_sum += value;
+        }
+    }
+
+    fn add_virtual_loss(&mut self, path: &[usize], delta: i32) {
+        for &node in path {
+            let virtual_loss = &mut self.nodes[node].virtual_loss;
+            *virtual_loss = virtual_loss.saturating_add_signed(delta);
+        }
+    }
+
+    fn add_root_noise(&mut self) {
+        self.root_noised = true;
+        let epsilon = self.config.dirichlet_epsilon;
+        let children = self.nodes[0].children();
+        if epsilon <= 0.0 || children.is_empty() {
+            return;
+        }
+        let alpha = f64::from(self.config.dirichlet_alpha);
+        let samples: Vec<f64> = children
+            .clone()
+            .map(|_| sample_gamma(&mut self.rng, alpha))
+            .collect();
+        let total: f64 = samples.iter().sum();
+        if total <= 0.0 {
+            return;
+        }
+        for (child, sample) in children.zip(samples) {
+            let prior = &mut self.nodes[child].prior;
+            *prior = (1.0 - epsilon) * *prior + epsilon * (sample / total) as f32;
+        }
+    }
+}
+
+/// Check that `evaluations` has one evaluation per game, each with one prior
+/// per legal move.
+fn check_evaluations<const W: usize, const H: usize>(
+    games: &mut [Game<W, H>],
+    evaluations: &[Evaluation],
+) -> Result<(), String>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    if evaluations.len() != games.len() {
+        return Err(format!(
+            "evaluator returned {} evaluations for {} positions",
+            evaluations.len(),
+            games.len()
+        ));
+    }
+    for (game, evaluation) in games.iter_mut().zip(evaluations) {
+        let moves = game.legal_moves().len();
+        if evaluation.priors.len() != moves {
+            return Err(format!(
+                "evaluator returned {} priors for {} legal moves",
+                evaluation.priors.len(),
+                moves
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A standard normal sample, by the Box-Muller transform.
+fn sample_normal(rng: &mut PlayoutRng) -> f64 {
+    let u1 = 1.0 - rng.next_f64();
+    let u2 = rng.next_f64();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
+
+/// A Gamma(`alpha`, 1) sample, by Marsaglia and Tsang's method. Normalized
+/// gamma samples are a Dirichlet sample.
+fn sample_gamma(rng: &mut PlayoutRng, alpha: f64) -> f64 {
+    if alpha < 1.0 {
+        let u = rng.next_f64();
+        return sample_gamma(rng, alpha + 1.0) * u.powf(1.0 / alpha);
+    }
+    let d = alpha - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let x = sample_normal(rng);
+        let v = (1.0 + c * x).powi(3);
+        if v <= 0.0 {
+            continue;
+        }
+        let u = rng.next_f64();
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    fn uniform(games: &mut [StandardGame]) -> Result<Vec<Evaluation>, String> {
+        Ok(games
+            .iter_mut()
+            .map(|game| Evaluation {
+                priors: vec![1.0; game.legal_moves().len()],
+                value: 0.0,
+            })
+            .collect())
+    }
+
+    fn root_priors(mcts: &Mcts<8, 8>) -> Vec<f32> {
+        mcts.nodes[0]
+            .children()
+            .map(|child| mcts.nodes[child].prior)
+            .collect()
+    }
+
+    #[test]
+    fn test_mcts_finds_mate() {
+        let game = StandardGame::new("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", true).expect("valid FEN");
+        let config = MctsConfig {
+            simulations: 400,
+            ..MctsConfig::default()
+        };
+        let mut mcts = Mcts::new(game, config);
+        mcts.run(&mut uniform).expect("uniform evaluator");
+
+        assert_eq!(mcts.visits(), 400);
+        assert_eq!(
+            mcts.best_move().map(|mv| mv.to_lan()).as_deref(),
+            Some("a1a8")
+        );
+        assert!(mcts.root_value() > 0.5);
+        // The root's own evaluation is the one visit not below a child
+        let counts = mcts.visit_counts();
+        assert_eq!(counts.iter().map(|&(_, v)| v).sum::<u32>(), 399);
+
+        let policy = mcts.policy(1.0);
+        assert!((policy.iter().map(|&(_, p)| p).sum::<f32>() - 1.0).abs() < 1e-5);
+        let greedy = mcts.policy(0.0);
+        assert_eq!(greedy.iter().filter(|&&(_, p)| p == 1.0).count(), 1);
+
+        // Another run adds to the tree
+        mcts.run(&mut uniform).expect("uniform evaluator");
+        assert_eq!(mcts.visits(), 800);
+    }
+
+    #[test]
+    fn test_mcts_batches_distinct_leaves() {
+        let mut batch_sizes = Vec::new();
+        let mut evaluator = |games: &mut [StandardGame]| {
+            let mut fens: Vec<String> = games.iter_mut().map(|g| g.to_fen()).collect();
+            fens.sort();
+            fens.dedup();
+            assert_eq!(fens.len(), games.len());
+            batch_sizes.push(games.len());
+            uniform(games)
+        };
+        let config = MctsConfig {
+            simulations: 100,
+            batch_size: 8,
+            ..MctsConfig::default()
+        };
+        let mut mcts = Mcts::new(StandardGame::standard(), config);
+        mcts.run(&mut evaluator).expect("uniform evaluator");
+
+        assert_eq!(mcts.visits(), 100);
+        assert_eq!(batch_sizes[0], 1);
+        assert!(batch_sizes.iter().all(|&size| size <= 8));
+        assert!(batch_sizes.contains(&8));
+        assert!(mcts.nodes.iter().all(|node| node.virtual_loss == 0));
+    }
+
+    #[test]
+    fn test_mcts_evaluator_errors() {
+        let config = MctsConfig {
+            simulations: 50,
+            ..MctsConfig::default()
+        };
+        let mut mcts = Mcts::new(StandardGame::standard(), config);
+        mcts.run(&mut uniform).expect("uniform evaluator");
+        let visits = mcts.visits();
+        let nodes = mcts.nodes.len();
+
+        let mut wrong_priors = |games: &mut [StandardGame]| -> Result<Vec<Evaluation>, String> {
+            Ok(games
+                .iter()
+                .map(|_| Evaluation {
+                    priors: vec![1.0],
+                    value: 0.0,
+                })
+                .collect())
+        };
+        let err = mcts.run(&mut wrong_priors).expect_err("too few priors");
+        assert!(err.contains("priors"));
+        let mut failing =
+            |_: &mut [StandardGame]| -> Result<Vec<Evaluation>, String> { Err("offline".into()) };
+        assert_eq!(mcts.run(&mut failing), Err("offline".to_string()));
+
+        // The tree is as it was, and can keep searching
+        assert_eq!(mcts.visits(), visits);
+        assert_eq!(mcts.nodes.len(), nodes);
+        assert!(mcts.nodes.iter().all(|node| node.virtual_loss == 0));
+        assert!(
+            mcts.nodes
+                .iter()
+                .all(|node| node.expansion != Expansion::Pending)
+        );
+        mcts.run(&mut uniform).expect("uniform evaluator");
+        assert_eq!(mcts.visits(), visits + 50);
+    }
+
+    #[test]
+    fn test_mcts_root_noise_and_advance() {
+        let config = MctsConfig {
+            simulations: 64,
+            dirichlet_epsilon: 0.25,
+            seed: 3,
+            ..MctsConfig::default()
+        };
+        let mut noisy = Mcts::new(StandardGame::standard(), config);
+        noisy.run(&mut uniform).expect("uniform evaluator");
+        let priors = root_priors(&noisy);
+        assert!((priors.iter().sum::<f32>() - 1.0).abs() < 1e-5);
+        assert!(priors.iter().any(|&p| (p - 1.0 / 20.0).abs() > 1e-4));
+
+        // The noise is seeded
+        let mut again = Mcts::new(StandardGame::standard(), config);
+        again.run(&mut uniform).expect("uniform evaluator");
+        assert_eq!(root_priors(&again), priors);
+        assert_eq!(again.visit_counts(), noisy.visit_counts());
+
+        // Advancing keeps the subtree below the move
+        let mv = noisy.best_move().expect("a move");
+        let child_visits = noisy
+            .visit_counts()
+            .into_iter()
+            .find(|&(m, _)| m == mv)
+            .map(|(_, v)| v);
+        assert!(noisy.advance(&mv));
+        assert_eq!(Some(noisy.visits()), child_visits);
+        assert_eq!(noisy.game().move_count(), 1);
+        assert!(!noisy.advance(&mv));
+
+        let mut rollouts = RolloutEvaluator::new(1, 200);
+        noisy.run(&mut rollouts).expect("rollout evaluator");
+        assert_eq!(noisy.visits(), child_visits.unwrap_or(0) + 64);
+    }
+}
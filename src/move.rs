@@ -1,16 +1,15 @@
+use crate::error::ChessError;
 use crate::pieces::PieceType;
 use crate::position::Position;
 use bitflags::bitflags;
 
-fn parse_square_prefix(s: &str, start: usize) -> Result<(Position, usize), String> {
-    let bytes = s.as_bytes();
-    if start >= bytes.len() {
-        return Err("Invalid LAN move".to_string());
-    }
+#[cfg(feature = "serde")]
+use serde::Deserialize;
 
-    let file = bytes[start] as char;
-    if !file.is_ascii_lowercase() {
-        return Err("Invalid file character".to_string());
+fn parse_square_prefix(lan: &str, start: usize) -> Result<(Position, usize), ChessError> {
+    let bytes = lan.as_bytes();
+    if start >= bytes.len() || !bytes[start].is_ascii_lowercase() {
+        return Err(ChessError::InvalidMove(lan.to_string()));
     }
 
     let mut end = start + 1;
@@ -19,10 +18,12 @@ fn parse_square_prefix(s: &str, start: usize) -> Result<(Position, usize), Strin
     }
 
     if end == start + 1 {
-        return Err("Invalid row number".to_string());
+        return Err(ChessError::InvalidMove(lan.to_string()));
     }
 
-    Ok((Position::from_algebraic(&s[start..end])?, end))
+    let pos = Position::from_algebraic(&lan[start..end])
+        .map_err(|_| ChessError::InvalidMove(lan.to_string()))?;
+    Ok((pos, end))
 }
 
 bitflags! {
@@ -70,23 +71,52 @@ impl Move {
         }
     }
 
-    pub fn from_lan(lan: &str, board_width: usize, board_height: usize) -> Result<Self, String> {
+    /// The null move, which passes the turn without moving a piece. Written
+    /// `0000` in LAN, as in UCI.
+    pub fn null() -> Self {
+        Move::from_position(Position::new(0, 0), Position::new(0, 0), MoveFlags::empty())
+    }
+
+    /// Whether this is the null move.
+    pub fn is_null(&self) -> bool {
+        *self == Move::null()
+    }
+
+    pub fn from_lan(
+        lan: &str,
+        board_width: usize,
+        board_height: usize,
+    ) -> Result<Self, ChessError> {
+        let move_ = Self::parse_lan(lan)?;
+        if !move_.is_valid(board_width, board_height) {
+            return Err(ChessError::InvalidMove(lan.to_string()));
+        }
+        Ok(move_)
+    }
+
+    /// Whether both squares of the move are on a `width` x `height` board.
+    pub fn is_valid(&self, width: usize, height: usize) -> bool {
+        self.src.is_valid(width, height) && self.dst.is_valid(width, height)
+    }
+
+    /// Parse a LAN move without checking it against a board size.
+    fn parse_lan(lan: &str) -> Result<Self, ChessError> {
+        if lan == "0000" {
+            return Ok(Move::null());
+        }
+        let invalid = || ChessError::InvalidMove(lan.to_string());
         if lan.len() < 4 {
-            return Err("Invalid LAN move".to_string());
+            return Err(invalid());
         }
 
         let (src, next) = parse_square_prefix(lan, 0)?;
         let (dst, next) = parse_square_prefix(lan, next)?;
 
-        if !src.is_valid(board_width, board_height) || !dst.is_valid(board_width, board_height) {
-            return Err("Move positions out of bounds".to_string());
-        }
-
         let mut move_ = Move::from_position(src, dst, MoveFlags::empty());
 
         if next < lan.len() {
             if next + 1 != lan.len() {
-                return Err("Invalid LAN move".to_string());
+                return Err(invalid());
             }
 
             let promo_char = lan[next..]
@@ -94,8 +124,7 @@ impl Move {
                 .next()
                 .expect("Failed to get promotion character from LAN string");
 
-            let promotion = PieceType::from_char(promo_char)
-                .ok_or_else(|| "Invalid promotion piece".to_string())?;
+            let promotion = PieceType::from_char(promo_char).ok_or_else(invalid)?;
 
             move_.promotion = Some(promotion);
             move_.flags |= MoveFlags::PROMOTION;
@@ -124,7 +153,30 @@ impl Move {
         }
     }
 
+    /// Sort key for the canonical move order: source square index, then
+    /// destination square index (both row-major from a1), then promotion piece
+    /// (none first, then `PieceType::PROMOTABLE_WITH_FAIRY` order).
+    pub fn canonical_key(&self, board_width: usize) -> (usize, usize, usize) {
+        let promotion = match self.promotion {
+            None => 0,
+            Some(pt) => {
+                1 + PieceType::PROMOTABLE_WITH_FAIRY
+                    .iter()
+                    .position(|&p| p == pt)
+                    .unwrap_or(PieceType::PROMOTABLE_WITH_FAIRY.len())
+            }
+        };
+        (
+            self.src.to_index(board_width),
+            self.dst.to_index(board_width),
+            promotion,
+        )
+    }
+
     pub fn to_lan(&self) -> String {
+        if self.is_null() {
+            return "0000".to_string();
+        }
         let mut lan = format!("{}{}", self.src.to_algebraic(), self.dst.to_algebraic());
 
         if let Some(promo) = self.promotion {
@@ -133,6 +185,90 @@ impl Move {
 
         lan
     }
+
+    /// Pack the move into 16 bits, for transposition and killer tables and
+    /// compact game records: bits 0-5 hold the source square index, bits
+    /// 6-11 the destination (both row-major from a1, as `Position::to_index`),
+    /// and bits 12-15 the promotion piece (0 for none, otherwise one more than
+    /// its index in `PieceType::ALL`). The null move packs to 0.
+    ///
+    /// Flags aren't stored. Returns None if a square index doesn't fit in 6
+    /// bits, i.e. on boards with more than 64 squares; use `to_u32` there.
+    pub fn to_u16(&self, board_width: usize) -> Option<u16> {
+        let packed = self.to_u32(board_width);
+        let src = packed & 0xff;
+        let dst = (packed >> 8) & 0xff;
+        if src >= 64 || dst >= 64 {
+            return None;
+        }
+        let promotion = packed >> 16;
+        u16::try_from(src | (dst << 6) | (promotion << 12)).ok()
+    }
+
+    /// Unpack a move packed by `to_u16` on a `board_width` x `board_height`
+    /// board. Returns None if a square is off the board or the promotion piece
+    /// is invalid. The flags are empty apart from `PROMOTION`; `Game::make_move`
+    /// re-derives the rest.
+    pub fn from_u16(packed: u16, board_width: usize, board_height: usize) -> Option<Self> {
+        let packed = u32::from(packed);
+        let src = packed & 0x3f;
+        let dst = (packed >> 6) & 0x3f;
+        let promotion = packed >> 12;
+        Move::from_u32(
+            src | (dst << 8) | (promotion << 16),
+            board_width,
+            board_height,
+        )
+    }
+
+    /// Pack the move into 32 bits, for boards of any supported size (up to
+    /// 16x16): bits 0-7 hold the source square index, bits 8-15 the
+    /// destination, and bits 16-19 the promotion piece, encoded as in
+    /// `to_u16`. The null move packs to 0.
+    pub fn to_u32(&self, board_width: usize) -> u32 {
+        let square = |pos: &Position| {
+            u32::try_from(pos.to_index(board_width)).expect("to_u32: square index exceeds u32")
+        };
+        let promotion = self.promotion.map_or(0, |pt| {
+            let index = PieceType::ALL
+                .iter()
+                .position(|&p| p == pt)
+                .expect("to_u32: every piece type is in PieceType::ALL");
+            u32::try_from(index + 1).expect("to_u32: piece type index exceeds u32")
+        });
+        debug_assert!(
+            self.src.to_index(board_width) < 256 && self.dst.to_index(board_width) < 256,
+            "to_u32: square index of {} exceeds 8 bits",
+            self.to_lan(),
+        );
+        square(&self.src) | (square(&self.dst) << 8) | (promotion << 16)
+    }
+
+    /// Unpack a move packed by `to_u32`, as `from_u16`.
+    pub fn from_u32(packed: u32, board_width: usize, board_height: usize) -> Option<Self> {
+        if packed == 0 {
+            return Some(Move::null());
+        }
+        let square = |index: u32| {
+            let pos = Position::from_index(usize::try_from(index).ok()?, board_width);
+            pos.is_valid(board_width, board_height).then_some(pos)
+        };
+        let src = square(packed & 0xff)?;
+        let dst = square((packed >> 8) & 0xff)?;
+        match packed >> 16 {
+            0 => Some(Move::from_position(src, dst, MoveFlags::empty())),
+            promotion => {
+                let index = usize::try_from(promotion - 1).ok()?;
+                let promotion = *PieceType::ALL.get(index)?;
+                Some(Move::from_position_with_promotion(
+                    src,
+                    dst,
+                    MoveFlags::empty(),
+                    promotion,
+                ))
+            }
+        }
+    }
 }
 
 #[hotpath::measure_all]
@@ -142,6 +278,73 @@ impl std::fmt::Display for Move {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Move {
+    /// Serializes as a LAN string. Flags are not stored; `Game::make_move`
+    /// re-derives them from the position.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_lan())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Move {
+    /// Deserializes a LAN string, keeping the squares exactly as written. No
+    /// board size is checked here: check `is_valid` against the board the
+    /// move is for, or deserialize with `MoveSeed`.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LanVisitor;
+
+        impl serde::de::Visitor<'_> for LanVisitor {
+            type Value = Move;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "a LAN move")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, lan: &str) -> Result<Move, E> {
+                Move::parse_lan(lan).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(LanVisitor)
+    }
+}
+
+/// `DeserializeSeed` that parses a LAN move and checks it against the given
+/// board dimensions.
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MoveSeed {
+    pub width: usize,
+    pub height: usize,
+}
+
+#[cfg(feature = "serde")]
+impl MoveSeed {
+    pub fn new(width: usize, height: usize) -> Self {
+        MoveSeed { width, height }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::DeserializeSeed<'de> for MoveSeed {
+    type Value = Move;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Move, D::Error> {
+        let mv = Move::deserialize(deserializer)?;
+        if !mv.is_valid(self.width, self.height) {
+            return Err(serde::de::Error::custom(ChessError::InvalidMove(format!(
+                "{} is off a {}x{} board",
+                mv.to_lan(),
+                self.width,
+                self.height
+            ))));
+        }
+        Ok(mv)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +368,65 @@ mod tests {
         assert_eq!(parsed.dst, Position::new(0, 15));
         assert_eq!(parsed.promotion, Some(PieceType::Queen));
     }
+
+    #[test]
+    fn lan_roundtrips_null_move() {
+        assert_eq!(Move::null().to_lan(), "0000");
+        let parsed =
+            Move::from_lan("0000", 8, 8).expect("lan_roundtrips_null_move: failed to parse 0000");
+        assert!(parsed.is_null());
+        assert!(
+            !Move::from_lan("e2e4", 8, 8)
+                .expect("lan_roundtrips_null_move: failed to parse e2e4")
+                .is_null()
+        );
+    }
+
+    #[test]
+    fn packed_moves_roundtrip() {
+        let mv = Move::from_lan("e7e8n", 8, 8).expect("packed_moves_roundtrip: e7e8n parses");
+        let packed = mv
+            .to_u16(8)
+            .expect("packed_moves_roundtrip: 8x8 move fits in 16 bits");
+        assert_eq!(packed, 52 | (60 << 6) | (2 << 12));
+        assert_eq!(Move::from_u16(packed, 8, 8), Some(mv));
+        assert_eq!(Move::null().to_u16(8), Some(0));
+        assert_eq!(Move::from_u16(0, 8, 8), Some(Move::null()));
+
+        // Squares past index 63 need the 32-bit form
+        let mv = Move::from_lan("j9j10", 10, 10).expect("packed_moves_roundtrip: j9j10 parses");
+        assert_eq!(mv.to_u16(10), None);
+        assert_eq!(Move::from_u32(mv.to_u32(10), 10, 10), Some(mv));
+        let mv = Move::from_lan("p15p16q", 16, 16).expect("packed_moves_roundtrip: p15p16q parses");
+        assert_eq!(Move::from_u32(mv.to_u32(16), 16, 16), Some(mv));
+
+        // Off-board squares and unknown promotion pieces are rejected
+        assert_eq!(Move::from_u16(packed, 6, 6), None);
+        assert_eq!(Move::from_u16(52 | (60 << 6) | (15 << 12), 8, 8), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_deserializes_moves_on_large_boards() {
+        use serde::de::value::{Error, StrDeserializer};
+        use serde::de::{DeserializeSeed, IntoDeserializer};
+
+        let de: StrDeserializer<'_, Error> = "a15a16q".into_deserializer();
+        let parsed = Move::deserialize(de).expect("a15a16q is on a 16x16 board");
+        assert_eq!(parsed.dst, Position::new(0, 15));
+        assert_eq!(parsed.promotion, Some(PieceType::Queen));
+
+        // Squares are kept as written; bounds are up to the caller
+        let de: StrDeserializer<'_, Error> = "c20c21".into_deserializer();
+        let parsed = Move::deserialize(de).expect("c20c21 is well-formed LAN");
+        assert_eq!(parsed.dst, Position::new(2, 20));
+        assert!(!parsed.is_valid(16, 16));
+        let de: StrDeserializer<'_, Error> = "c2".into_deserializer();
+        assert!(Move::deserialize(de).is_err());
+
+        let de: StrDeserializer<'_, Error> = "j9j10".into_deserializer();
+        assert!(MoveSeed::new(10, 10).deserialize(de).is_ok());
+        let de: StrDeserializer<'_, Error> = "j9j10".into_deserializer();
+        assert!(MoveSeed::new(8, 8).deserialize(de).is_err());
+    }
 }
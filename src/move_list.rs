@@ -0,0 +1,189 @@
+use smallvec::SmallVec;
+
+use crate::r#move::Move;
+use crate::outcome::MoveList;
+
+/// A move paired with an optional ordering score (higher is better).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ScoredMove {
+    pub mv: Move,
+    pub score: Option<i32>,
+}
+
+/// Stack-allocated list of moves with optional scores, used for move ordering.
+///
+/// Unscored moves always sort after scored ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScoredMoveList {
+    entries: SmallVec<[ScoredMove; 256]>,
+}
+
+#[inline]
+fn sort_key(entry: &ScoredMove) -> i64 {
+    // Unscored moves rank below any scored move
+    entry.score.map_or(i64::MIN, i64::from)
+}
+
+#[hotpath::measure_all]
+impl ScoredMoveList {
+    pub fn new() -> Self {
+        ScoredMoveList {
+            entries: SmallVec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Append an unscored move.
+    pub fn push(&mut self, mv: Move) {
+        self.entries.push(ScoredMove { mv, score: None });
+    }
+
+    pub fn push_scored(&mut self, mv: Move, score: i32) {
+        self.entries.push(ScoredMove {
+            mv,
+            score: Some(score),
+        });
+    }
+
+    pub fn get(&self, index: usize) -> Option<&ScoredMove> {
+        self.entries.get(index)
+    }
+
+    pub fn set_score(&mut self, index: usize, score: i32) {
+        self.entries[index].score = Some(score);
+    }
+
+    /// Assign a score to every move using `f`.
+    pub fn score_with(&mut self, mut f: impl FnMut(&Move) -> i32) {
+        for entry in self.entries.iter_mut() {
+            entry.score = Some(f(&entry.mv));
+        }
+    }
+
+    /// Sort by descending score. Order among equal scores is unspecified.
+    pub fn sort_unstable_by_score(&mut self) {
+        self.entries
+            .sort_unstable_by_key(|entry| std::cmp::Reverse(sort_key(entry)));
+    }
+
+    /// Swap the best-scoring move at or after `start` into position `start` and
+    /// return it.
+    ///
+    /// Calling this with `start = 0, 1, 2, ...` yields moves in descending score
+    /// order, doing only as much sorting work as the caller consumes, which is
+    /// cheaper than a full sort when a search cuts off early.
+    pub fn pick_best(&mut self, start: usize) -> Option<ScoredMove> {
+        if start >= self.entries.len() {
+            return None;
+        }
+        let mut best = start;
+        for i in (start + 1)..self.entries.len() {
+            if sort_key(&self.entries[i]) > sort_key(&self.entries[best]) {
+                best = i;
+            }
+        }
+        self.entries.swap(start, best);
+        Some(self.entries[start])
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, ScoredMove> {
+        self.entries.iter()
+    }
+
+    /// Iterate over the moves without their scores.
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.entries.iter().map(|entry| entry.mv)
+    }
+
+    pub fn as_slice(&self) -> &[ScoredMove] {
+        &self.entries
+    }
+}
+
+impl From<MoveList> for ScoredMoveList {
+    fn from(moves: MoveList) -> Self {
+        moves.into_iter().collect()
+    }
+}
+
+impl FromIterator<Move> for ScoredMoveList {
+    fn from_iter<I: IntoIterator<Item = Move>>(iter: I) -> Self {
+        ScoredMoveList {
+            entries: iter
+                .into_iter()
+                .map(|mv| ScoredMove { mv, score: None })
+                .collect(),
+        }
+    }
+}
+
+impl IntoIterator for ScoredMoveList {
+    type Item = ScoredMove;
+    type IntoIter = smallvec::IntoIter<[ScoredMove; 256]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ScoredMoveList {
+    type Item = &'a ScoredMove;
+    type IntoIter = std::slice::Iter<'a, ScoredMove>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    fn lan(s: &str) -> Move {
+        Move::from_lan(s, 8, 8).expect("valid LAN")
+    }
+
+    #[test]
+    fn test_sort_unstable_by_score() {
+        let mut list = ScoredMoveList::new();
+        list.push_scored(lan("e2e4"), 10);
+        list.push(lan("d2d4"));
+        list.push_scored(lan("g1f3"), 30);
+        list.push_scored(lan("b1c3"), -5);
+
+        list.sort_unstable_by_score();
+        let order: Vec<String> = list.moves().map(|m| m.to_lan()).collect();
+        assert_eq!(order, vec!["g1f3", "e2e4", "b1c3", "d2d4"]);
+    }
+
+    #[test]
+    fn test_pick_best_yields_descending_scores() {
+        let mut game = StandardGame::standard();
+        let mut list = ScoredMoveList::from(game.legal_moves());
+        assert_eq!(list.len(), 20);
+        list.score_with(|m| i32::from(m.dst.row) * 10 + i32::from(m.dst.col));
+
+        let mut previous = i32::MAX;
+        let mut count = 0;
+        while let Some(entry) = list.pick_best(count) {
+            let score = entry.score.expect("all moves scored");
+            assert!(score <= previous);
+            previous = score;
+            count += 1;
+        }
+        assert_eq!(count, 20);
+        assert_eq!(list.pick_best(20), None);
+    }
+}
@@ -0,0 +1,288 @@
+//! Opening trees: how often each move was played from each early position, and
+//! how it scored.
+//!
+//! Positions are keyed by the first four FEN fields (placement, side to move,
+//! castling rights and en passant square), so transpositions share a node and
+//! the move clocks are ignored. Moves are keyed by LAN. With the `serde`
+//! feature the tree is serializable.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::pgn::{PgnError, PgnGame, PgnResult};
+
+/// Results of the games in which a move was played.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveStats {
+    /// Games in which the move was played, including those without a result.
+    pub count: u64,
+    pub white_wins: u64,
+    pub black_wins: u64,
+    pub draws: u64,
+}
+
+#[hotpath::measure_all]
+impl MoveStats {
+    fn record(&mut self, result: PgnResult) {
+        self.count += 1;
+        match result {
+            PgnResult::WhiteWin => self.white_wins += 1,
+            PgnResult::BlackWin => self.black_wins += 1,
+            PgnResult::Draw => self.draws += 1,
+            PgnResult::Unknown => {}
+        }
+    }
+
+    /// Games with a known result.
+    pub fn decided(&self) -> u64 {
+        self.white_wins + self.black_wins + self.draws
+    }
+
+    /// Score for `color` (a win counts 1, a draw 0.5) averaged over the games
+    /// with a known result, or `None` if there are none.
+    pub fn score(&self, color: Color) -> Option<f64> {
+        let decided = self.decided();
+        if decided == 0 {
+            return None;
+        }
+        let wins = match color {
+            Color::White => self.white_wins,
+            Color::Black => self.black_wins,
+        };
+        Some((wins as f64 + 0.5 * self.draws as f64) / decided as f64)
+    }
+}
+
+/// A position in the tree and the moves played from it.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpeningNode {
+    /// Number of times the position was reached and a move played from it.
+    pub visits: u64,
+    /// Moves played from the position, by LAN.
+    pub moves: BTreeMap<String, MoveStats>,
+}
+
+#[hotpath::measure_all]
+impl OpeningNode {
+    /// Moves ordered from most to least played, ties broken by LAN.
+    pub fn moves_by_frequency(&self) -> Vec<(&str, &MoveStats)> {
+        let mut moves: Vec<(&str, &MoveStats)> = self
+            .moves
+            .iter()
+            .map(|(lan, stats)| (lan.as_str(), stats))
+            .collect();
+        moves.sort_by(|a, b| b.1.count.cmp(&a.1.count).then(a.0.cmp(b.0)));
+        moves
+    }
+
+    /// Shannon entropy (in bits) of the move choice from this position: 0 if
+    /// the same move is always played, higher for more varied play.
+    pub fn entropy(&self) -> f64 {
+        if self.visits == 0 {
+            return 0.0;
+        }
+        let total = self.visits as f64;
+        self.moves
+            .values()
+            .map(|stats| {
+                let p = stats.count as f64 / total;
+                -p * p.log2()
+            })
+            .sum()
+    }
+}
+
+/// Opening statistics over many games, limited to their first `max_plies`
+/// moves.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpeningTree {
+    max_plies: usize,
+    games: u64,
+    nodes: HashMap<String, OpeningNode>,
+}
+
+/// Key identifying a position in an `OpeningTree`: its FEN without the move
+/// clocks.
+pub fn position_key<const W: usize, const H: usize>(game: &mut Game<W, H>) -> String
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let fen = game.to_fen();
+    fen.split(' ').take(4).collect::<Vec<_>>().join(" ")
+}
+
+#[hotpath::measure_all]
+impl OpeningTree {
+    pub fn new(max_plies: usize) -> Self {
+        OpeningTree {
+            max_plies,
+            games: 0,
+            nodes: HashMap::new(),
+        }
+    }
+
+    pub fn max_plies(&self) -> usize {
+        self.max_plies
+    }
+
+    /// Number of games added.
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    /// Number of distinct positions in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Add the opening of `game`, replayed from its starting position, with the
+    /// game's final `result`.
+    pub fn add_game<const W: usize, const H: usize>(&mut self, game: &Game<W, H>, result: PgnResult)
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        let mut replay = game.clone();
+        while replay.unmake_move_silent().is_some() {}
+
+        for entry in game.move_history().iter().take(self.max_plies) {
+            let node = self.nodes.entry(position_key(&mut replay)).or_default();
+            node.visits += 1;
+            node.moves
+                .entry(entry.mv.to_lan())
+                .or_default()
+                .record(result);
+            replay.make_move_silent(&entry.mv);
+        }
+        self.games += 1;
+    }
+
+    /// Add the opening of a parsed PGN game.
+    pub fn add_pgn_game(&mut self, pgn: &PgnGame) -> Result<(), PgnError> {
+        let mut game = pgn.starting_game()?;
+        for (i, mv) in pgn.moves.iter().take(self.max_plies).enumerate() {
+            if !game.make_move(mv) {
+                return Err(PgnError::InvalidMove {
+                    move_number: (i / 2 + 1) as u32,
+                    san: mv.to_lan(),
+                    reason: "illegal move".to_string(),
+                });
+            }
+        }
+        self.add_game(&game, pgn.result);
+        Ok(())
+    }
+
+    /// The node for a position key, as produced by `position_key`.
+    pub fn get(&self, key: &str) -> Option<&OpeningNode> {
+        self.nodes.get(key)
+    }
+
+    /// The node for the current position of `game`.
+    pub fn node<const W: usize, const H: usize>(
+        &self,
+        game: &mut Game<W, H>,
+    ) -> Option<&OpeningNode>
+    where
+        [(); (W * H).div_ceil(64)]:,
+    {
+        self.nodes.get(&position_key(game))
+    }
+
+    /// All positions and their nodes, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &OpeningNode)> {
+        self.nodes.iter().map(|(key, node)| (key.as_str(), node))
+    }
+
+    /// Add the statistics of another tree. The result keeps this tree's
+    /// `max_plies`.
+    pub fn merge(&mut self, other: &OpeningTree) {
+        for (key, theirs) in &other.nodes {
+            let ours = self.nodes.entry(key.clone()).or_default();
+            ours.visits += theirs.visits;
+            for (lan, stats) in &theirs.moves {
+                let entry = ours.moves.entry(lan.clone()).or_default();
+                entry.count += stats.count;
+                entry.white_wins += stats.white_wins;
+                entry.black_wins += stats.black_wins;
+                entry.draws += stats.draws;
+            }
+        }
+        self.games += other.games;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::test_support::play;
+
+    fn played(lans: &[&str]) -> StandardGame {
+        let mut game = StandardGame::standard();
+        play(&mut game, lans);
+        game
+    }
+
+    #[test]
+    fn test_opening_tree_counts_and_scores() {
+        let mut tree = OpeningTree::new(2);
+        tree.add_game(&played(&["e2e4", "e7e5", "g1f3"]), PgnResult::WhiteWin);
+        tree.add_game(&played(&["e2e4", "c7c5"]), PgnResult::Draw);
+        tree.add_game(&played(&["d2d4", "d7d5"]), PgnResult::Unknown);
+
+        assert_eq!(tree.games(), 3);
+        // The start, after 1. e4 and after 1. d4; the third ply isn't recorded
+        assert_eq!(tree.len(), 3);
+
+        let root = tree.node(&mut StandardGame::standard()).expect("start");
+        assert_eq!(root.visits, 3);
+        let by_frequency = root.moves_by_frequency();
+        assert_eq!(by_frequency[0].0, "e2e4");
+        assert_eq!(by_frequency[0].1.count, 2);
+        assert_eq!(by_frequency[0].1.score(Color::White), Some(0.75));
+        assert_eq!(root.moves["d2d4"].score(Color::White), None);
+
+        let after_e4 = tree.node(&mut played(&["e2e4"])).expect("after e4");
+        assert_eq!(after_e4.entropy(), 1.0);
+        assert_eq!(tree.node(&mut played(&["e2e4", "e7e5"])), None);
+    }
+
+    #[test]
+    fn test_opening_tree_transpositions() {
+        let mut tree = OpeningTree::new(10);
+        tree.add_game(
+            &played(&["g1f3", "d7d5", "d2d4", "g8f6"]),
+            PgnResult::BlackWin,
+        );
+        tree.add_game(
+            &played(&["d2d4", "d7d5", "g1f3", "g8f6"]),
+            PgnResult::WhiteWin,
+        );
+
+        // Both games reach the same position after three plies
+        let mut transposed = played(&["g1f3", "d7d5", "d2d4"]);
+        let key = position_key(&mut transposed);
+        assert_eq!(
+            key,
+            "rnbqkbnr/ppp1pppp/8/3p4/3P4/5N2/PPP1PPPP/RNBQKB1R b KQkq -"
+        );
+        let node = tree.get(&key).expect("transposition");
+        assert_eq!(node.visits, 2);
+        assert_eq!(node.moves["g8f6"].score(Color::Black), Some(0.5));
+
+        let mut merged = tree.clone();
+        merged.merge(&tree);
+        assert_eq!(merged.games(), 4);
+        let root = merged.node(&mut StandardGame::standard()).expect("start");
+        assert_eq!(root.visits, 4);
+        assert_eq!(root.moves["d2d4"].white_wins, 2);
+    }
+}
@@ -1,21 +1,141 @@
 use crate::color::Color;
 use crate::r#move::Move;
 use smallvec::SmallVec;
+use std::collections::HashMap;
 use std::fmt;
+use std::str::FromStr;
 
 pub type MoveList = SmallVec<[Move; 256]>;
 
+/// How a game ended, independent of who won.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum GameOutcome {
-    WhiteWin,
-    BlackWin,
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Termination {
+    Checkmate,
     Stalemate,
     InsufficientMaterial,
+    /// A draw claimed by repetition (see `Game::claim_draw`).
     ThreefoldRepetition,
+    /// A draw claimed under the fifty-move rule (see `Game::claim_draw`).
     FiftyMoveRule,
+    /// The game reached `DrawRules::repetition_limit` occurrences of a
+    /// position and was drawn automatically.
+    FivefoldRepetition,
+    /// The game reached `DrawRules::halfmove_limit` and was drawn
+    /// automatically.
+    SeventyFiveMoveRule,
+    Resignation,
+    /// Both players agreed to a draw.
+    Agreement,
+    Timeout,
+    Adjudication,
+    /// The game reached `DrawRules::ply_limit`.
+    PlyLimit,
     Other,
 }
 
+/// The result of a finished game: the winner (`None` for a draw) and the
+/// reason the game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameOutcome {
+    pub winner: Option<Color>,
+    pub termination: Termination,
+}
+
+impl GameOutcome {
+    pub const fn new(winner: Option<Color>, termination: Termination) -> Self {
+        GameOutcome {
+            winner,
+            termination,
+        }
+    }
+
+    pub const fn win(winner: Color, termination: Termination) -> Self {
+        Self::new(Some(winner), termination)
+    }
+
+    pub const fn draw(termination: Termination) -> Self {
+        Self::new(None, termination)
+    }
+}
+
+// Compatibility constants for the former flat enum. These still work in `match`
+// patterns, but only cover the outcomes the enum could represent.
+#[allow(non_upper_case_globals)]
+impl GameOutcome {
+    pub const WhiteWin: GameOutcome = GameOutcome::win(Color::White, Termination::Checkmate);
+    pub const BlackWin: GameOutcome = GameOutcome::win(Color::Black, Termination::Checkmate);
+    pub const Stalemate: GameOutcome = GameOutcome::draw(Termination::Stalemate);
+    pub const InsufficientMaterial: GameOutcome =
+        GameOutcome::draw(Termination::InsufficientMaterial);
+    pub const ThreefoldRepetition: GameOutcome =
+        GameOutcome::draw(Termination::ThreefoldRepetition);
+    pub const FiftyMoveRule: GameOutcome = GameOutcome::draw(Termination::FiftyMoveRule);
+    pub const FivefoldRepetition: GameOutcome = GameOutcome::draw(Termination::FivefoldRepetition);
+    pub const SeventyFiveMoveRule: GameOutcome =
+        GameOutcome::draw(Termination::SeventyFiveMoveRule);
+    pub const Other: GameOutcome = GameOutcome::draw(Termination::Other);
+}
+
+/// How `GameOutcome::reward` turns an outcome into a scalar reward.
+///
+/// The default gives 1 for a win, -1 for a loss and 0 for a draw, the same as
+/// `encode_winner_from_perspective`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardConfig {
+    /// Reward for a draw, to both sides.
+    pub draw_score: f32,
+    /// Rewards for particular terminations, replacing the win reward (the
+    /// loser gets its negation) or `draw_score`.
+    pub termination_overrides: HashMap<Termination, f32>,
+    /// Decisive rewards shrink towards 0 by this much per ply played (never
+    /// crossing it), so winners prefer quick wins and losers slow losses.
+    /// Draw rewards are unaffected.
+    pub length_penalty: f32,
+}
+
+impl Default for RewardConfig {
+    fn default() -> Self {
+        RewardConfig {
+            draw_score: 0.0,
+            termination_overrides: HashMap::new(),
+            length_penalty: 0.0,
+        }
+    }
+}
+
+/// When a game is drawn automatically, set with `Game::set_draw_rules`.
+///
+/// The default is the FIDE automatic rules: a draw once 150 halfmoves pass
+/// without a capture or pawn move, or when a position occurs for the fifth
+/// time, and no ply limit. The fifty-move and threefold repetition draws
+/// before that have to be claimed with `Game::claim_draw`. Training setups
+/// often shorten games with lower limits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawRules {
+    /// Halfmove clock at which the game is drawn (`SeventyFiveMoveRule`).
+    pub halfmove_limit: u32,
+    /// Number of occurrences of a position at which the game is drawn
+    /// (`FivefoldRepetition`).
+    pub repetition_limit: usize,
+    /// Plies played in the game (since it was set up) at which it is drawn
+    /// (`PlyLimit`), if any.
+    pub ply_limit: Option<usize>,
+}
+
+impl Default for DrawRules {
+    fn default() -> Self {
+        DrawRules {
+            halfmove_limit: 150,
+            repetition_limit: 5,
+            ply_limit: None,
+        }
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TurnState {
@@ -26,53 +146,258 @@ pub enum TurnState {
 #[hotpath::measure_all]
 impl GameOutcome {
     pub fn winner(&self) -> Option<Color> {
-        match self {
-            GameOutcome::WhiteWin => Some(Color::White),
-            GameOutcome::BlackWin => Some(Color::Black),
-            _ => None,
+        self.winner
+    }
+
+    pub fn termination(&self) -> Termination {
+        self.termination
+    }
+
+    /// The PGN result token: "1-0", "0-1" or "1/2-1/2". `Game::pgn_result`
+    /// also gives "*" for unfinished games.
+    pub fn to_pgn_result(&self) -> &'static str {
+        match self.winner {
+            Some(Color::White) => "1-0",
+            Some(Color::Black) => "0-1",
+            None => "1/2-1/2",
         }
     }
 
     pub fn encode_winner_absolute(&self) -> f32 {
-        match self {
-            GameOutcome::WhiteWin => 1.0,
-            GameOutcome::BlackWin => -1.0,
-            _ => 0.0,
+        match self.winner {
+            Some(Color::White) => 1.0,
+            Some(Color::Black) => -1.0,
+            None => 0.0,
         }
     }
 
     pub fn encode_winner_from_perspective(&self, perspective: Color) -> f32 {
-        match perspective {
-            Color::White => match self {
-                GameOutcome::WhiteWin => 1.0,
-                GameOutcome::BlackWin => -1.0,
-                _ => 0.0,
-            },
-            Color::Black => match self {
-                GameOutcome::WhiteWin => -1.0,
-                GameOutcome::BlackWin => 1.0,
-                _ => 0.0,
-            },
+        match self.winner {
+            Some(winner) if winner == perspective => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
         }
     }
 
     pub fn is_draw(&self) -> bool {
-        !matches!(self, GameOutcome::WhiteWin | GameOutcome::BlackWin)
+        self.winner.is_none()
+    }
+
+    /// Reward for `perspective` after a game of `plies` plies, computed with
+    /// `config`.
+    pub fn reward(&self, perspective: Color, plies: usize, config: &RewardConfig) -> f32 {
+        let overridden = config.termination_overrides.get(&self.termination);
+        let Some(winner) = self.winner else {
+            return overridden.copied().unwrap_or(config.draw_score);
+        };
+        let win = overridden.copied().unwrap_or(1.0);
+        let penalty = config.length_penalty * plies as f32;
+        let magnitude = (win.abs() - penalty).max(0.0).copysign(win);
+        if winner == perspective {
+            magnitude
+        } else {
+            -magnitude
+        }
+    }
+
+    /// Like `reward`, from white's perspective.
+    pub fn reward_absolute(&self, plies: usize, config: &RewardConfig) -> f32 {
+        self.reward(Color::White, plies, config)
     }
 }
 
 #[hotpath::measure_all]
-impl fmt::Display for GameOutcome {
+impl fmt::Display for Termination {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
-            GameOutcome::WhiteWin => "white_win",
-            GameOutcome::BlackWin => "black_win",
-            GameOutcome::Stalemate => "stalemate",
-            GameOutcome::InsufficientMaterial => "insufficient_material",
-            GameOutcome::ThreefoldRepetition => "threefold_repetition",
-            GameOutcome::FiftyMoveRule => "fifty_move_rule",
-            GameOutcome::Other => "other_draw",
+            Termination::Checkmate => "checkmate",
+            Termination::Stalemate => "stalemate",
+            Termination::InsufficientMaterial => "insufficient_material",
+            Termination::ThreefoldRepetition => "threefold_repetition",
+            Termination::FiftyMoveRule => "fifty_move_rule",
+            Termination::FivefoldRepetition => "fivefold_repetition",
+            Termination::SeventyFiveMoveRule => "seventy_five_move_rule",
+            Termination::Resignation => "resignation",
+            Termination::Agreement => "agreement",
+            Termination::Timeout => "timeout",
+            Termination::Adjudication => "adjudication",
+            Termination::PlyLimit => "ply_limit",
+            Termination::Other => "other",
         };
         write!(f, "{}", s)
     }
 }
+
+#[hotpath::measure_all]
+impl FromStr for Termination {
+    type Err = String;
+
+    /// Parses the names printed by `Display`, e.g. `fifty_move_rule`.
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "checkmate" => Ok(Termination::Checkmate),
+            "stalemate" => Ok(Termination::Stalemate),
+            "insufficient_material" => Ok(Termination::InsufficientMaterial),
+            "threefold_repetition" => Ok(Termination::ThreefoldRepetition),
+            "fifty_move_rule" => Ok(Termination::FiftyMoveRule),
+            "fivefold_repetition" => Ok(Termination::FivefoldRepetition),
+            "seventy_five_move_rule" => Ok(Termination::SeventyFiveMoveRule),
+            "resignation" => Ok(Termination::Resignation),
+            "agreement" => Ok(Termination::Agreement),
+            "timeout" => Ok(Termination::Timeout),
+            "adjudication" => Ok(Termination::Adjudication),
+            "ply_limit" => Ok(Termination::PlyLimit),
+            "other" => Ok(Termination::Other),
+            _ => Err(format!("Unknown termination: {}", s)),
+        }
+    }
+}
+
+#[hotpath::measure_all]
+impl fmt::Display for GameOutcome {
+    /// Checkmates print as `white_win`/`black_win`, other wins append the
+    /// termination (e.g. `white_win_timeout`), and draws print the termination.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let winner = match self.winner {
+            Some(Color::White) => "white",
+            Some(Color::Black) => "black",
+            None => {
+                return match self.termination {
+                    Termination::Other => write!(f, "other_draw"),
+                    termination => write!(f, "{}", termination),
+                };
+            }
+        };
+        match self.termination {
+            Termination::Checkmate => write!(f, "{}_win", winner),
+            termination => write!(f, "{}_win_{}", winner, termination),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::pgn::PgnResult;
+
+    #[test]
+    fn test_compat_constants_match() {
+        let outcome = GameOutcome::win(Color::Black, Termination::Checkmate);
+        assert!(matches!(outcome, GameOutcome::BlackWin));
+        assert_eq!(outcome.to_string(), "black_win");
+        assert_eq!(GameOutcome::Other.to_string(), "other_draw");
+        assert_eq!(GameOutcome::FiftyMoveRule.to_string(), "fifty_move_rule");
+    }
+
+    #[test]
+    fn test_win_on_time() {
+        let outcome = GameOutcome::win(Color::White, Termination::Timeout);
+        assert_ne!(outcome, GameOutcome::WhiteWin);
+        assert_eq!(outcome.winner(), Some(Color::White));
+        assert!(!outcome.is_draw());
+        assert_eq!(outcome.encode_winner_absolute(), 1.0);
+        assert_eq!(outcome.encode_winner_from_perspective(Color::Black), -1.0);
+        assert_eq!(outcome.to_string(), "white_win_timeout");
+    }
+
+    #[test]
+    fn test_to_pgn_result() {
+        assert_eq!(GameOutcome::WhiteWin.to_pgn_result(), "1-0");
+        assert_eq!(
+            GameOutcome::win(Color::Black, Termination::Resignation).to_pgn_result(),
+            "0-1"
+        );
+        assert_eq!(
+            GameOutcome::draw(Termination::Agreement).to_pgn_result(),
+            "1/2-1/2"
+        );
+
+        for outcome in [
+            GameOutcome::WhiteWin,
+            GameOutcome::BlackWin,
+            GameOutcome::Stalemate,
+        ] {
+            assert_eq!(
+                PgnResult::from(Some(outcome)).to_string(),
+                outcome.to_pgn_result()
+            );
+        }
+        assert_eq!(PgnResult::from(None), PgnResult::Unknown);
+
+        let mut game = StandardGame::standard();
+        assert_eq!(game.pgn_result(), "*");
+        assert!(game.resign(Color::White));
+        assert_eq!(game.pgn_result(), "0-1");
+    }
+
+    #[test]
+    fn test_reward_config() {
+        let default = RewardConfig::default();
+        for outcome in [
+            GameOutcome::WhiteWin,
+            GameOutcome::BlackWin,
+            GameOutcome::Stalemate,
+        ] {
+            for color in [Color::White, Color::Black] {
+                assert_eq!(
+                    outcome.reward(color, 40, &default),
+                    outcome.encode_winner_from_perspective(color)
+                );
+            }
+            assert_eq!(
+                outcome.reward_absolute(40, &default),
+                outcome.encode_winner_absolute()
+            );
+        }
+
+        let config = RewardConfig {
+            draw_score: -0.1,
+            termination_overrides: HashMap::from([
+                (Termination::Timeout, 0.5),
+                (Termination::Stalemate, 0.0),
+            ]),
+            length_penalty: 0.01,
+        };
+        let mate = GameOutcome::WhiteWin;
+        assert!((mate.reward(Color::White, 20, &config) - 0.8).abs() < 1e-6);
+        assert!((mate.reward(Color::Black, 20, &config) + 0.8).abs() < 1e-6);
+        // The penalty never flips the sign
+        assert_eq!(mate.reward(Color::White, 500, &config), 0.0);
+
+        let timeout = GameOutcome::win(Color::Black, Termination::Timeout);
+        assert_eq!(timeout.reward(Color::Black, 0, &config), 0.5);
+        assert_eq!(timeout.reward_absolute(0, &config), -0.5);
+
+        assert_eq!(
+            GameOutcome::FiftyMoveRule.reward(Color::White, 100, &config),
+            -0.1
+        );
+        assert_eq!(
+            GameOutcome::Stalemate.reward(Color::Black, 100, &config),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_termination_from_str_round_trips() {
+        for termination in [
+            Termination::Checkmate,
+            Termination::Stalemate,
+            Termination::InsufficientMaterial,
+            Termination::ThreefoldRepetition,
+            Termination::FiftyMoveRule,
+            Termination::FivefoldRepetition,
+            Termination::SeventyFiveMoveRule,
+            Termination::Resignation,
+            Termination::Agreement,
+            Termination::Timeout,
+            Termination::Adjudication,
+            Termination::PlyLimit,
+            Termination::Other,
+        ] {
+            assert_eq!(termination.to_string().parse(), Ok(termination));
+        }
+        assert!("draw".parse::<Termination>().is_err());
+    }
+}
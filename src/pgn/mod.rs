@@ -1,9 +1,13 @@
+use std::collections::VecDeque;
 use std::fmt;
+use std::io::BufRead;
 
 use tree_sitter::{Node, Parser};
 
+use crate::color::Color;
 use crate::game::StandardGame;
 use crate::r#move::Move;
+use crate::outcome::GameOutcome;
 
 #[cfg(test)]
 mod tests;
@@ -78,8 +82,16 @@ impl PgnHeaders {
     pub fn result(&self) -> Option<&str> {
         self.get("Result")
     }
+    pub fn variant(&self) -> Option<&str> {
+        self.get("Variant")
+    }
 }
 
+/// `Variant` tag values (as written by Lichess) for games this crate can
+/// replay. Anything else, e.g. "Crazyhouse" or "Three-check", has rules or
+/// FEN fields of its own.
+const SUPPORTED_VARIANTS: [&str; 3] = ["Standard", "From Position", "Chess"];
+
 // ---------------------------------------------------------------------------
 // PGN result
 // ---------------------------------------------------------------------------
@@ -104,6 +116,18 @@ impl PgnResult {
     }
 }
 
+impl From<Option<GameOutcome>> for PgnResult {
+    /// The result of a game with `outcome`, `Unknown` if it isn't over.
+    fn from(outcome: Option<GameOutcome>) -> Self {
+        match outcome.map(|outcome| outcome.winner()) {
+            Some(Some(Color::White)) => PgnResult::WhiteWin,
+            Some(Some(Color::Black)) => PgnResult::BlackWin,
+            Some(None) => PgnResult::Draw,
+            None => PgnResult::Unknown,
+        }
+    }
+}
+
 impl fmt::Display for PgnResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -119,10 +143,30 @@ impl fmt::Display for PgnResult {
 // PGN game
 // ---------------------------------------------------------------------------
 
+/// Commentary following one move of the main line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MoveAnnotation {
+    /// Numeric annotation glyphs, with `!`, `?`, `!!`, `??`, `!?` and `?!`
+    /// stored as their standard codes 1 to 6.
+    pub nags: Vec<u8>,
+    /// Comment texts, without braces or the leading `;`.
+    pub comments: Vec<String>,
+}
+
+impl MoveAnnotation {
+    pub fn is_empty(&self) -> bool {
+        self.nags.is_empty() && self.comments.is_empty()
+    }
+}
+
 #[derive(Clone)]
 pub struct PgnGame {
     pub headers: PgnHeaders,
     pub moves: Vec<Move>,
+    /// The annotation of each move, in the same order as `moves`.
+    pub annotations: Vec<MoveAnnotation>,
+    /// Comments before the first move.
+    pub comments: Vec<String>,
     pub result: PgnResult,
     pub final_game: StandardGame,
 }
@@ -137,8 +181,18 @@ impl PgnGame {
     }
 
     pub fn starting_game(&self) -> Result<StandardGame, PgnError> {
+        if let Some(variant) = self.headers.variant()
+            && !SUPPORTED_VARIANTS
+                .iter()
+                .any(|v| v.eq_ignore_ascii_case(variant))
+        {
+            return Err(PgnError::ParseError(format!(
+                "Unsupported variant: {}",
+                variant
+            )));
+        }
         if let Some(fen) = self.starting_fen() {
-            StandardGame::new(fen, true).map_err(PgnError::ParseError)
+            StandardGame::new(fen, true).map_err(|e| PgnError::ParseError(e.to_string()))
         } else {
             Ok(StandardGame::standard())
         }
@@ -152,6 +206,9 @@ impl PgnGame {
             out.push_str(&format!("[{} \"{}\"]\n", key, value));
         }
         out.push('\n');
+        for comment in &self.comments {
+            out.push_str(&format!("{{{}}}\n", comment));
+        }
 
         // Replay moves to produce SAN
         let mut game = self
@@ -165,6 +222,14 @@ impl PgnGame {
             }
             token.push_str(&game.move_to_san(mv));
             game.make_move(mv);
+            if let Some(annotation) = self.annotations.get(i) {
+                for nag in &annotation.nags {
+                    token.push_str(&format!(" ${}", nag));
+                }
+                for comment in &annotation.comments {
+                    token.push_str(&format!(" {{{}}}", comment));
+                }
+            }
 
             if col + token.len() + 1 > 80 && col > 0 {
                 out.push('\n');
@@ -216,6 +281,48 @@ fn normalize_san_promotion(san: &str) -> String {
     san.to_string()
 }
 
+// ---------------------------------------------------------------------------
+// Annotations
+// ---------------------------------------------------------------------------
+
+/// Standard NAG codes of the move assessment glyphs.
+const GLYPH_NAGS: [(&str, u8); 6] = [
+    ("!!", 3),
+    ("??", 4),
+    ("!?", 5),
+    ("?!", 6),
+    ("!", 1),
+    ("?", 2),
+];
+
+/// Split a move's text into the move and its trailing assessment glyph, if
+/// any, as a NAG code.
+fn split_glyph(text: &str) -> (&str, Option<u8>) {
+    GLYPH_NAGS
+        .iter()
+        .find_map(|&(glyph, nag)| text.strip_suffix(glyph).map(|rest| (rest, Some(nag))))
+        .unwrap_or((text, None))
+}
+
+/// Add the comment, NAG or glyph in a movetext token to `annotation`.
+/// Returns false for any other token.
+fn parse_annotation_token(text: &str, annotation: &mut MoveAnnotation) -> bool {
+    let text = text.trim();
+    if let Some(comment) = text.strip_prefix('{') {
+        let comment = comment.strip_suffix('}').unwrap_or(comment);
+        annotation.comments.push(comment.trim().to_string());
+    } else if let Some(comment) = text.strip_prefix(';') {
+        annotation.comments.push(comment.trim().to_string());
+    } else if let Some(nag) = text.strip_prefix('$').and_then(|n| n.parse().ok()) {
+        annotation.nags.push(nag);
+    } else if let Some(&(_, nag)) = GLYPH_NAGS.iter().find(|&&(glyph, _)| glyph == text) {
+        annotation.nags.push(nag);
+    } else {
+        return false;
+    }
+    true
+}
+
 // ---------------------------------------------------------------------------
 // Tree-sitter helpers
 // ---------------------------------------------------------------------------
@@ -267,82 +374,75 @@ fn parse_game_node(game_node: &Node, source: &[u8]) -> Result<PgnGame, PgnError>
     let mut game = PgnGame {
         headers: headers.clone(),
         moves: Vec::new(),
+        annotations: Vec::new(),
+        comments: Vec::new(),
         result: PgnResult::Unknown,
         final_game: StandardGame::standard(),
     }
     .starting_game()?;
 
-    // Parse moves from movetext
+    // Parse moves from movetext, attaching the comments and NAGs that follow
+    // each move (or precede the first) to it
     let mut moves = Vec::new();
+    let mut annotations: Vec<MoveAnnotation> = Vec::new();
+    let mut leading = MoveAnnotation::default();
     if let Some(movetext_node) = child_by_field(game_node, "movetext") {
         let mut cursor = movetext_node.walk();
+        let move_number = |idx: usize| -> u32 { (idx as u32 / 2) + 1 };
 
-        // Collect san_move and lan_move nodes in document order
-        let mut move_nodes: Vec<Node> = Vec::new();
-        for i in 0..movetext_node.named_child_count() {
-            if let Some(child) = movetext_node.named_child(i) {
-                let kind = child.kind();
-                if kind == "san_move" || kind == "lan_move" {
-                    move_nodes.push(child);
-                }
-            }
-        }
-
-        // Also collect via field names (these should overlap but let's be thorough)
-        // Actually, named_child iterates all named children. The field-based access
-        // might give the same nodes. Let's just use the direct iteration approach.
-        // Re-do: iterate all children in order, picking san_move and lan_move
-        move_nodes.clear();
-        cursor.reset(movetext_node);
         if cursor.goto_first_child() {
             loop {
                 let node = cursor.node();
                 let kind = node.kind();
-                if kind == "san_move" || kind == "lan_move" {
-                    move_nodes.push(node);
+                let (raw_text, glyph) = split_glyph(node_text(&node, source).trim());
+                let idx = moves.len();
+
+                if kind == "san_move" {
+                    let san = normalize_san_promotion(raw_text);
+                    let mv = game
+                        .move_from_san(&san)
+                        .map_err(|e| PgnError::InvalidMove {
+                            move_number: move_number(idx),
+                            san: raw_text.to_string(),
+                            reason: e.to_string(),
+                        })?;
+                    game.make_move_unchecked(&mv);
+                    moves.push(mv);
+                    annotations.push(MoveAnnotation {
+                        nags: glyph.into_iter().collect(),
+                        comments: Vec::new(),
+                    });
+                } else if kind == "lan_move" {
+                    let mv =
+                        game.move_from_lan(raw_text)
+                            .map_err(|reason| PgnError::InvalidMove {
+                                move_number: move_number(idx),
+                                san: raw_text.to_string(),
+                                reason: reason.to_string(),
+                            })?;
+                    let success = game.make_move(&mv);
+                    if !success {
+                        return Err(PgnError::InvalidMove {
+                            move_number: move_number(idx),
+                            san: raw_text.to_string(),
+                            reason: "Illegal move".to_string(),
+                        });
+                    }
+                    moves.push(mv);
+                    annotations.push(MoveAnnotation {
+                        nags: glyph.into_iter().collect(),
+                        comments: Vec::new(),
+                    });
+                } else {
+                    let annotation = annotations.last_mut().unwrap_or(&mut leading);
+                    parse_annotation_token(node_text(&node, source), annotation);
                 }
+
                 if !cursor.goto_next_sibling() {
                     break;
                 }
             }
         }
-
-        let move_number = |idx: usize| -> u32 { (idx as u32 / 2) + 1 };
-
-        for (idx, move_node) in move_nodes.iter().enumerate() {
-            let raw_text = node_text(move_node, source).trim();
-            let kind = move_node.kind();
-
-            if kind == "san_move" {
-                let san = normalize_san_promotion(raw_text);
-                let mv = game
-                    .move_from_san(&san)
-                    .map_err(|reason| PgnError::InvalidMove {
-                        move_number: move_number(idx),
-                        san: raw_text.to_string(),
-                        reason,
-                    })?;
-                game.make_move_unchecked(&mv);
-                moves.push(mv);
-            } else if kind == "lan_move" {
-                let mv = game
-                    .move_from_lan(raw_text)
-                    .map_err(|reason| PgnError::InvalidMove {
-                        move_number: move_number(idx),
-                        san: raw_text.to_string(),
-                        reason,
-                    })?;
-                let success = game.make_move(&mv);
-                if !success {
-                    return Err(PgnError::InvalidMove {
-                        move_number: move_number(idx),
-                        san: raw_text.to_string(),
-                        reason: "Illegal move".to_string(),
-                    });
-                }
-                moves.push(mv);
-            }
-        }
     }
 
     // Parse result
@@ -354,6 +454,8 @@ fn parse_game_node(game_node: &Node, source: &[u8]) -> Result<PgnGame, PgnError>
     Ok(PgnGame {
         headers,
         moves,
+        annotations,
+        comments: leading.comments,
         result,
         final_game: game,
     })
@@ -363,6 +465,14 @@ fn parse_game_node(game_node: &Node, source: &[u8]) -> Result<PgnGame, PgnError>
 // Public API
 // ---------------------------------------------------------------------------
 
+fn new_parser() -> Result<Parser, PgnError> {
+    let mut parser = Parser::new();
+    parser
+        .set_language(&tree_sitter_pgn::LANGUAGE.into())
+        .map_err(|e| PgnError::ParseError(format!("Failed to set language: {}", e)))?;
+    Ok(parser)
+}
+
 /// An iterator that yields [`PgnGame`]s one at a time from a parsed PGN tree.
 ///
 /// Created by [`PgnIter::new`]. The tree-sitter parse tree is built upfront,
@@ -376,11 +486,10 @@ pub struct PgnIter {
 impl PgnIter {
     /// Parse the PGN source into a tree and return an iterator over its games.
     pub fn new(pgn: String) -> Result<Self, PgnError> {
-        let mut parser = Parser::new();
-        parser
-            .set_language(&tree_sitter_pgn::LANGUAGE.into())
-            .map_err(|e| PgnError::ParseError(format!("Failed to set language: {}", e)))?;
+        Self::with_parser(&mut new_parser()?, pgn)
+    }
 
+    fn with_parser(parser: &mut Parser, pgn: String) -> Result<Self, PgnError> {
         let tree = parser
             .parse(&pgn, None)
             .ok_or_else(|| PgnError::ParseError("Failed to parse PGN".to_string()))?;
@@ -428,6 +537,110 @@ impl Iterator for PgnIter {
     }
 }
 
+/// A streaming iterator over the games of a PGN source, e.g. a large database
+/// file, that only holds one game's text in memory at a time.
+///
+/// Games are split at the first tag pair after each game's movetext, so a
+/// game that fails to parse doesn't affect the ones after it.
+pub struct PgnReader<R: BufRead> {
+    reader: R,
+    parser: Option<Parser>,
+    /// A line read past the end of the previous game: the next game's first
+    /// tag pair.
+    carry: Option<String>,
+    pending: VecDeque<Result<PgnGame, PgnError>>,
+    done: bool,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            parser: None,
+            carry: None,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Read the text of the next game, or None at the end of the input.
+    fn next_game_text(&mut self) -> Result<Option<String>, PgnError> {
+        let mut text = self.carry.take().unwrap_or_default();
+        let mut in_movetext = false;
+        let mut comment_depth = 0usize;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = self
+                .reader
+                .read_line(&mut line)
+                .map_err(|e| PgnError::ParseError(format!("Failed to read PGN: {}", e)))?;
+            if read == 0 {
+                break;
+            }
+            let trimmed = line.trim();
+            if comment_depth == 0 && trimmed.starts_with('%') {
+                // Escape line, ignored by PGN readers
+                continue;
+            }
+            if comment_depth == 0 && trimmed.starts_with('[') {
+                if in_movetext {
+                    self.carry = Some(line.clone());
+                    return Ok(Some(text));
+                }
+            } else if !trimmed.is_empty() {
+                in_movetext = true;
+            }
+            for c in line.chars() {
+                match c {
+                    '{' => comment_depth += 1,
+                    '}' => comment_depth = comment_depth.saturating_sub(1),
+                    _ => {}
+                }
+            }
+            text.push_str(&line);
+        }
+        Ok((!text.trim().is_empty()).then_some(text))
+    }
+
+    fn parse_next(&mut self) -> Result<bool, PgnError> {
+        let Some(text) = self.next_game_text()? else {
+            return Ok(false);
+        };
+        let parser = match &mut self.parser {
+            Some(parser) => parser,
+            None => self.parser.insert(new_parser()?),
+        };
+        self.pending
+            .extend(PgnIter::with_parser(parser, text)?.collect::<Vec<_>>());
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.pending.is_empty() && !self.done {
+            match self.parse_next() {
+                Ok(more) => self.done = !more,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Open a PGN file for streaming with [`PgnReader`].
+pub fn read_pgn_file(
+    path: impl AsRef<std::path::Path>,
+) -> Result<PgnReader<std::io::BufReader<std::fs::File>>, PgnError> {
+    let file = std::fs::File::open(path.as_ref()).map_err(|e| {
+        PgnError::ParseError(format!("Failed to open {}: {}", path.as_ref().display(), e))
+    })?;
+    Ok(PgnReader::new(std::io::BufReader::new(file)))
+}
+
 /// Parse a PGN string that may contain multiple games.
 pub fn parse_pgn(pgn: &str) -> Result<Vec<PgnGame>, PgnError> {
     PgnIter::new(pgn.to_string())?.collect()
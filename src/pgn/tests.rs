@@ -116,6 +116,81 @@ fn test_comments_and_annotations_skipped() {
     assert!(game.final_game.is_checkmate());
 }
 
+#[test]
+fn test_comments_and_nags() {
+    let pgn = "[Event \"Annotated\"]\n\n{Opening} 1. e4! {Best move} $14 e5 2. Nf3?! 1-0\n";
+    let game = parse_pgn_single_game(pgn).expect("test_comments_and_nags: failed to parse PGN");
+    assert_eq!(game.comments, vec!["Opening".to_string()]);
+    assert_eq!(game.annotations.len(), 3);
+    assert_eq!(game.annotations[0].nags, vec![1, 14]);
+    assert_eq!(game.annotations[0].comments, vec!["Best move".to_string()]);
+    assert!(game.annotations[1].is_empty());
+    assert_eq!(game.annotations[2].nags, vec![6]);
+
+    let reparsed = parse_pgn_single_game(&game.to_pgn())
+        .expect("test_comments_and_nags: failed to reparse PGN");
+    assert_eq!(reparsed.annotations, game.annotations);
+    assert_eq!(reparsed.comments, game.comments);
+}
+
+#[test]
+fn test_annotation_tokens() {
+    assert_eq!(split_glyph("Nf6??"), ("Nf6", Some(4)));
+    assert_eq!(split_glyph("e4!?"), ("e4", Some(5)));
+    assert_eq!(split_glyph("O-O"), ("O-O", None));
+
+    let mut annotation = MoveAnnotation::default();
+    assert!(parse_annotation_token("{ Best move }", &mut annotation));
+    assert!(parse_annotation_token("; rest of line", &mut annotation));
+    assert!(parse_annotation_token("$18", &mut annotation));
+    assert!(parse_annotation_token("?", &mut annotation));
+    assert!(!parse_annotation_token("2.", &mut annotation));
+    assert!(!parse_annotation_token("(1... c5)", &mut annotation));
+    assert_eq!(annotation.nags, vec![18, 2]);
+    assert_eq!(
+        annotation.comments,
+        vec!["Best move".to_string(), "rest of line".to_string()]
+    );
+}
+
+#[test]
+fn test_pgn_reader_splits_games() {
+    let pgn = "% exported\n[Event \"A\"]\n[Result \"1-0\"]\n\n1. e4 {a comment\n[not a tag]} e5 1-0\n\n\
+               [Event \"B\"]\n\n1. d4 *\n\n";
+    let mut reader = PgnReader::new(std::io::Cursor::new(pgn));
+    let first = reader
+        .next_game_text()
+        .expect("test_pgn_reader_splits_games: failed to read")
+        .expect("test_pgn_reader_splits_games: missing first game");
+    assert!(first.starts_with("[Event \"A\"]"));
+    assert!(first.contains("[not a tag]"));
+    let second = reader
+        .next_game_text()
+        .expect("test_pgn_reader_splits_games: failed to read")
+        .expect("test_pgn_reader_splits_games: missing second game");
+    assert!(second.starts_with("[Event \"B\"]"));
+    assert!(
+        reader
+            .next_game_text()
+            .expect("test_pgn_reader_splits_games: failed to read")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_pgn_reader_matches_parse_pgn() {
+    let pgn = tournament_pgn!("games.pgn");
+    let streamed: Vec<PgnGame> = PgnReader::new(pgn.as_bytes())
+        .collect::<Result<_, _>>()
+        .expect("test_pgn_reader_matches_parse_pgn: failed to stream PGN");
+    let parsed = parse_pgn(pgn).expect("test_pgn_reader_matches_parse_pgn: failed to parse PGN");
+    assert_eq!(streamed.len(), parsed.len());
+    for (a, b) in streamed.iter().zip(&parsed) {
+        assert_eq!(a.moves, b.moves);
+        assert_eq!(a.result, b.result);
+    }
+}
+
 #[test]
 fn test_invalid_move() {
     let pgn = tournament_pgn!("invalid_move.pgn");
@@ -150,6 +225,27 @@ fn test_headers_case_insensitive() {
     assert_eq!(headers.get("WHITE"), Some("Kasparov"));
 }
 
+#[test]
+fn test_variant_tag() {
+    let with_variant = |variant: &str| PgnGame {
+        headers: PgnHeaders {
+            pairs: vec![("Variant".to_string(), variant.to_string())],
+        },
+        moves: Vec::new(),
+        annotations: Vec::new(),
+        comments: Vec::new(),
+        result: PgnResult::Unknown,
+        final_game: StandardGame::standard(),
+    };
+    assert!(with_variant("Standard").starting_game().is_ok());
+    assert!(with_variant("from position").starting_game().is_ok());
+    match with_variant("Crazyhouse").starting_game() {
+        Err(PgnError::ParseError(msg)) => assert!(msg.contains("Crazyhouse")),
+        Err(other) => panic!("Expected ParseError, got: {:?}", other),
+        Ok(_) => panic!("Expected error, got Ok"),
+    }
+}
+
 #[test]
 fn test_standard_start_helpers() {
     let pgn = tournament_pgn!("scholars_mate.pgn");
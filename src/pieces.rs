@@ -1,6 +1,8 @@
 use crate::color::Color;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum PieceType {
     Pawn,
     Knight,
@@ -8,6 +10,10 @@ pub enum PieceType {
     Rook,
     Queen,
     King,
+    /// Moves as a bishop or a knight, as in Capablanca chess.
+    Archbishop,
+    /// Moves as a rook or a knight, as in Capablanca chess.
+    Chancellor,
 }
 
 impl PieceType {
@@ -18,8 +24,78 @@ impl PieceType {
         PieceType::Rook,
     ];
 
+    /// The promotion pieces of a game with fairy promotions enabled (see
+    /// `Game::set_fairy_promotions`): `PROMOTABLE`, then the `FAIRY` pieces.
+    pub const PROMOTABLE_WITH_FAIRY: [PieceType; 6] = [
+        PieceType::Queen,
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Archbishop,
+        PieceType::Chancellor,
+    ];
+
     pub const DEFAULT_PROMOTION: PieceType = PieceType::Queen;
 
+    /// All piece types, from least to most valuable.
+    pub const ALL: [PieceType; 8] = [
+        PieceType::Pawn,
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Archbishop,
+        PieceType::Chancellor,
+        PieceType::Queen,
+        PieceType::King,
+    ];
+
+    /// The six piece types of standard chess, in plane encoding order.
+    pub const STANDARD: [PieceType; 6] = [
+        PieceType::Pawn,
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+        PieceType::King,
+    ];
+
+    /// The compound pieces of Capablanca chess.
+    pub const FAIRY: [PieceType; 2] = [PieceType::Archbishop, PieceType::Chancellor];
+
+    /// Whether this is one of the compound pieces in `FAIRY`.
+    pub const fn is_fairy(self) -> bool {
+        matches!(self, PieceType::Archbishop | PieceType::Chancellor)
+    }
+
+    /// Conventional material value in centipawns. The king has no material
+    /// value and returns 0.
+    pub const fn value(self) -> i32 {
+        match self {
+            PieceType::Pawn => 100,
+            PieceType::Knight => 300,
+            PieceType::Bishop => 300,
+            PieceType::Rook => 500,
+            PieceType::Queen => 900,
+            PieceType::King => 0,
+            PieceType::Archbishop => 850,
+            PieceType::Chancellor => 875,
+        }
+    }
+
+    /// Lowercase English name, e.g. "knight".
+    pub fn name(self) -> &'static str {
+        match self {
+            PieceType::Pawn => "pawn",
+            PieceType::Knight => "knight",
+            PieceType::Bishop => "bishop",
+            PieceType::Rook => "rook",
+            PieceType::Queen => "queen",
+            PieceType::King => "king",
+            PieceType::Archbishop => "archbishop",
+            PieceType::Chancellor => "chancellor",
+        }
+    }
+
     pub fn to_char(self) -> char {
         match self {
             PieceType::Pawn => 'p',
@@ -28,6 +104,8 @@ impl PieceType {
             PieceType::Rook => 'r',
             PieceType::Queen => 'q',
             PieceType::King => 'k',
+            PieceType::Archbishop => 'a',
+            PieceType::Chancellor => 'c',
         }
     }
 
@@ -39,6 +117,8 @@ impl PieceType {
             'r' => Some(PieceType::Rook),
             'q' => Some(PieceType::Queen),
             'k' => Some(PieceType::King),
+            'a' => Some(PieceType::Archbishop),
+            'c' => Some(PieceType::Chancellor),
             _ => None,
         }
     }
@@ -51,6 +131,8 @@ impl PieceType {
             PieceType::Rook => 'R',
             PieceType::Queen => 'Q',
             PieceType::King => 'K',
+            PieceType::Archbishop => 'A',
+            PieceType::Chancellor => 'C',
         }
     }
 
@@ -61,12 +143,15 @@ impl PieceType {
             'R' => Some(PieceType::Rook),
             'Q' => Some(PieceType::Queen),
             'K' => Some(PieceType::King),
+            'A' => Some(PieceType::Archbishop),
+            'C' => Some(PieceType::Chancellor),
             _ => None,
         }
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Piece {
     pub piece_type: PieceType,
     pub color: Color,
@@ -86,6 +171,26 @@ impl Piece {
         }
     }
 
+    /// Unicode chess symbol, e.g. '♘' for a white knight. Archbishops and
+    /// chancellors have no symbol and use their FEN letter.
+    pub fn to_unicode(&self) -> char {
+        match (self.color, self.piece_type) {
+            (_, PieceType::Archbishop | PieceType::Chancellor) => self.to_char(),
+            (Color::White, PieceType::Pawn) => '♙',
+            (Color::White, PieceType::Knight) => '♘',
+            (Color::White, PieceType::Bishop) => '♗',
+            (Color::White, PieceType::Rook) => '♖',
+            (Color::White, PieceType::Queen) => '♕',
+            (Color::White, PieceType::King) => '♔',
+            (Color::Black, PieceType::Pawn) => '♟',
+            (Color::Black, PieceType::Knight) => '♞',
+            (Color::Black, PieceType::Bishop) => '♝',
+            (Color::Black, PieceType::Rook) => '♜',
+            (Color::Black, PieceType::Queen) => '♛',
+            (Color::Black, PieceType::King) => '♚',
+        }
+    }
+
     pub fn from_char(c: char) -> Option<Self> {
         let color = if c.is_ascii_uppercase() {
             Color::White
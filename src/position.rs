@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::error::ChessError;
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub struct Position {
     pub col: u8,
@@ -45,9 +47,10 @@ impl Position {
         }
     }
 
-    pub fn from_algebraic(s: &str) -> Result<Self, String> {
+    pub fn from_algebraic(s: &str) -> Result<Self, ChessError> {
+        let invalid = || ChessError::InvalidSquare(s.to_string());
         if s.len() < 2 {
-            return Err("Invalid position string".to_string());
+            return Err(invalid());
         }
 
         let col_char = s.as_bytes()[0] as char;
@@ -56,16 +59,14 @@ impl Position {
         let col = if col_char.is_ascii_lowercase() {
             col_char as u8 - b'a'
         } else {
-            return Err("Invalid file character".to_string());
+            return Err(invalid());
         };
 
-        let row_num = row_str
-            .parse::<u16>()
-            .map_err(|_| "Invalid row number".to_string())?;
+        let row_num = row_str.parse::<u16>().map_err(|_| invalid())?;
         if row_num == 0 {
-            return Err("Invalid row number".to_string());
+            return Err(invalid());
         }
-        let row = u8::try_from(row_num - 1).map_err(|_| "Invalid row number".to_string())?;
+        let row = u8::try_from(row_num - 1).map_err(|_| invalid())?;
 
         Ok(Position { col, row })
     }
@@ -78,6 +79,36 @@ impl fmt::Display for Position {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Position {
+    /// Serializes as an algebraic square name, e.g. `e4`.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_algebraic())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Position {
+    /// Deserializes an algebraic square name, as `from_algebraic`.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SquareVisitor;
+
+        impl serde::de::Visitor<'_> for SquareVisitor {
+            type Value = Position;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "an algebraic square name")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, s: &str) -> Result<Position, E> {
+                Position::from_algebraic(s).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(SquareVisitor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +143,19 @@ mod tests {
         assert_eq!(pos.row, 7);
     }
 
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_position_serde() {
+        use serde::Deserialize;
+        use serde::de::IntoDeserializer;
+        use serde::de::value::{Error, StrDeserializer};
+
+        let de: StrDeserializer<'_, Error> = "j10".into_deserializer();
+        assert_eq!(Position::deserialize(de), Ok(Position::new(9, 9)));
+        let de: StrDeserializer<'_, Error> = "10".into_deserializer();
+        assert!(Position::deserialize(de).is_err());
+    }
+
     #[test]
     fn test_position_validity() {
         let pos = Position::new(3, 4);
@@ -2,28 +2,82 @@ use pyo3::prelude::*;
 
 use crate::limits;
 
-#[macro_use]
-mod dispatch;
-
+mod py_aec_env;
+mod py_arena;
+mod py_chess_env;
+mod py_dataset;
+mod py_encoder_config;
+mod py_error;
 mod py_game;
+mod py_game_batch;
+mod py_heatmap;
+mod py_history;
 mod py_move;
 mod py_outcome;
 mod py_pgn;
 mod py_piece;
 mod py_position;
+mod py_reward_config;
+mod py_selfplay;
+mod py_stats;
 mod py_turn_state;
 mod py_uci;
+mod py_vec_env;
+mod py_wdl;
 
+pub use py_aec_env::{PyAecAgentIter, PyAecEnv};
+pub use py_arena::py_play_match;
+pub use py_chess_env::PyChessEnv;
+pub use py_dataset::{PySampleReader, PySampleWriter, py_pgn_to_samples};
+pub use py_encoder_config::PyEncoderConfig;
+pub(crate) use py_error::register_exceptions;
 pub use py_game::PyGame;
+pub use py_game_batch::PyGameBatch;
+pub use py_heatmap::PyHeatmap;
+pub use py_history::PyHistoryStacker;
 pub use py_move::PyMove;
 pub use py_outcome::PyGameOutcome;
-pub use py_pgn::{PyPgnGame, py_parse_pgn};
+pub use py_pgn::{PyPgnGame, PyPgnReader, py_parse_pgn};
 pub use py_piece::PyPiece;
 pub use py_position::PyPosition;
+pub use py_reward_config::PyRewardConfig;
+pub use py_selfplay::py_self_play;
+pub use py_stats::{PyGameStats, PyMoveGenStats};
 pub use py_turn_state::PyTurnState;
-pub use py_uci::{PySearchResult, PyUciEngine};
+pub use py_uci::{PySearchResult, PyUciEngine, py_label_with_engine};
+pub use py_vec_env::PyVecEnv;
+pub use py_wdl::PyWdlModel;
+
+/// A new float32 numpy array of the given shape holding `data` in C order.
+/// numpy is imported when first needed, so it is only required by callers
+/// that ask for arrays.
+pub(crate) fn planes_array<'py>(
+    py: Python<'py>,
+    data: &[f32],
+    shape: &[usize],
+) -> PyResult<Bound<'py, PyAny>> {
+    let shape = pyo3::types::PyTuple::new(py, shape)?;
+    let array = py
+        .import("numpy")?
+        .call_method1("empty", (shape, "float32"))?;
+    write_planes(py, &array, data)?;
+    Ok(array)
+}
+
+/// Copy `data` into `out`, any writable float32 buffer (such as a numpy
+/// array) with exactly `data.len()` elements, in C order.
+pub(crate) fn write_planes(py: Python<'_>, out: &Bound<'_, PyAny>, data: &[f32]) -> PyResult<()> {
+    let buffer = pyo3::buffer::PyBuffer::<f32>::get(out)?;
+    if buffer.item_count() != data.len() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+            "expected a buffer of {} float32 values, got {}",
+            data.len(),
+            buffer.item_count()
+        )));
+    }
+    buffer.copy_from_slice(py, data)
+}
 
 pub(crate) fn validate_dimensions(width: usize, height: usize) -> PyResult<()> {
-    limits::validate_board_dimensions(width, height)
-        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    Ok(limits::validate_board_dimensions(width, height)?)
 }
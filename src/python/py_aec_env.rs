@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::py_encoder_config::PyEncoderConfig;
+use super::py_game::{PyGame, action_space};
+use super::py_reward_config::PyRewardConfig;
+use crate::action_space::ActionSpace;
+use crate::color::Color;
+use crate::dyn_game::DynGame;
+use crate::encode;
+use crate::outcome::RewardConfig;
+
+/// Agent names, indexed by side: white is `player_0`, as in PettingZoo's
+/// own chess environment.
+const AGENTS: [&str; 2] = ["player_0", "player_1"];
+
+fn agent_side(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+/// A two-player environment following PettingZoo's AEC (agent environment
+/// cycle) API: `reset`, `agent_iter`, `last`, `observe` and `step`, with the
+/// `agents`, `agent_selection`, `rewards`, `terminations`, `truncations` and
+/// `infos` attributes.
+///
+/// Actions are `EncoderConfig` action indices. Observations are dicts with an
+/// `observation` of `(data, planes, height, width)`, encoded from the side to
+/// move's perspective as `Game.encode_game_planes`, and an `action_mask` of
+/// `total_actions` 0/1 entries.
+#[pyclass(name = "AecEnv")]
+pub struct PyAecEnv {
+    initial: DynGame,
+    game: DynGame,
+    action_space: ActionSpace,
+    reward_config: RewardConfig,
+    max_plies: Option<usize>,
+    /// Agents not yet removed by a step after the game ended.
+    live: [bool; 2],
+    selection: usize,
+    rewards: [f32; 2],
+    cumulative_rewards: [f32; 2],
+    terminated: bool,
+    truncated: bool,
+}
+
+#[hotpath::measure_all]
+impl PyAecEnv {
+    fn agent_index(&self, agent: &str) -> PyResult<usize> {
+        AGENTS
+            .iter()
+            .position(|&name| name == agent)
+            .filter(|&side| self.live[side])
+            .ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyKeyError, _>(format!("unknown agent {}", agent))
+            })
+    }
+
+    fn per_agent<T: Copy>(&self, values: [T; 2]) -> HashMap<String, T> {
+        (0..2)
+            .filter(|&side| self.live[side])
+            .map(|side| (AGENTS[side].to_string(), values[side]))
+            .collect()
+    }
+
+    fn action_mask(&mut self) -> Vec<u8> {
+        let space = self.action_space;
+        let mut mask = vec![0u8; space.action_size()];
+        if !self.terminated && !self.truncated {
+            for action in dispatch_game!(&mut self.game, g => space.legal_action_indices(g)) {
+                mask[action] = 1;
+            }
+        }
+        mask
+    }
+
+    /// Remove the selected agent once the game has ended, selecting the other
+    /// one if it remains.
+    fn dead_step(&mut self) {
+        self.live[self.selection] = false;
+        self.rewards = [0.0; 2];
+        self.cumulative_rewards[self.selection] = 0.0;
+        let other = 1 - self.selection;
+        if self.live[other] {
+            self.selection = other;
+        }
+    }
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyAecEnv {
+    /// `game` is the starting position of every episode (the standard position
+    /// by default). Episodes are truncated after `max_plies` plies, if given.
+    #[new]
+    #[pyo3(signature = (game=None, encoder_config=None, reward_config=None, max_plies=None))]
+    pub fn new(
+        game: Option<PyRef<'_, PyGame>>,
+        encoder_config: Option<PyEncoderConfig>,
+        reward_config: Option<PyRewardConfig>,
+        max_plies: Option<usize>,
+    ) -> PyResult<Self> {
+        let (initial, encoder_config) = match game {
+            Some(game) => (game.inner.clone(), game.encoder_config(encoder_config)),
+            None => (DynGame::standard(), encoder_config.unwrap_or_default().config),
+        };
+        let action_space = action_space(&initial, encoder_config)?;
+
+        let mut env = PyAecEnv {
+            game: initial.clone(),
+            initial,
+            action_space,
+            reward_config: reward_config.unwrap_or_default().config,
+            max_plies,
+            live: [true; 2],
+            selection: 0,
+            rewards: [0.0; 2],
+            cumulative_rewards: [0.0; 2],
+            terminated: false,
+            truncated: false,
+        };
+        env.reset(None, None);
+        Ok(env)
+    }
+
+    /// Start a new episode from the starting position. The environment is
+    /// deterministic, so `seed` and `options` are accepted and ignored.
+    #[pyo3(signature = (seed=None, options=None))]
+    pub fn reset(&mut self, seed: Option<u64>, options: Option<Bound<'_, PyDict>>) {
+        let _ = (seed, options);
+        self.game = self.initial.clone();
+        self.live = [true; 2];
+        self.selection = dispatch_game!(&self.game, g => agent_side(g.turn()));
+        self.rewards = [0.0; 2];
+        self.cumulative_rewards = [0.0; 2];
+        self.terminated = false;
+        self.truncated = false;
+    }
+
+    #[getter]
+    pub fn possible_agents(&self) -> Vec<String> {
+        AGENTS.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[getter]
+    pub fn agents(&self) -> Vec<String> {
+        (0..2)
+            .filter(|&side| self.live[side])
+            .map(|side| AGENTS[side].to_string())
+            .collect()
+    }
+
+    #[getter]
+    pub fn agent_selection(&self) -> String {
+        AGENTS[self.selection].to_string()
+    }
+
+    #[getter]
+    pub fn rewards(&self) -> HashMap<String, f32> {
+        self.per_agent(self.rewards)
+    }
+
+    #[getter(_cumulative_rewards)]
+    pub fn cumulative_rewards(&self) -> HashMap<String, f32> {
+        self.per_agent(self.cumulative_rewards)
+    }
+
+    #[getter]
+    pub fn terminations(&self) -> HashMap<String, bool> {
+        self.per_agent([self.terminated; 2])
+    }
+
+    #[getter]
+    pub fn truncations(&self) -> HashMap<String, bool> {
+        self.per_agent([self.truncated; 2])
+    }
+
+    #[getter]
+    pub fn infos<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let infos = PyDict::new(py);
+        for agent in self.agents() {
+            infos.set_item(agent, PyDict::new(py))?;
+        }
+        Ok(infos)
+    }
+
+    /// A copy of the current game.
+    #[getter]
+    pub fn game(&self) -> PyGame {
+        PyGame {
+            inner: self.game.clone(),
+            history_length: self.action_space.config().history_length,
+        }
+    }
+
+    /// Number of actions, i.e. the length of every action mask.
+    pub fn total_actions(&self) -> usize {
+        self.action_space.action_size()
+    }
+
+    /// Shape `(planes, height, width)` of every observation.
+    pub fn observation_shape(&self) -> (usize, usize, usize) {
+        let (rows, cols) = self.action_space.plane_shape();
+        (self.action_space.input_plane_count(), rows, cols)
+    }
+
+    pub fn observe<'py>(&mut self, py: Python<'py>, agent: &str) -> PyResult<Bound<'py, PyDict>> {
+        self.agent_index(agent)?;
+        let config = *self.action_space.config();
+        let observation = dispatch_game!(&mut self.game, g => {
+            encode::encode_game_planes_with_config(g, &config)
+        });
+        let dict = PyDict::new(py);
+        dict.set_item("observation", observation)?;
+        dict.set_item("action_mask", self.action_mask())?;
+        Ok(dict)
+    }
+
+    /// `(observation, cumulative reward, termination, truncation, info)` for
+    /// the selected agent.
+    #[pyo3(signature = (observe=true))]
+    #[allow(clippy::type_complexity)]
+    pub fn last<'py>(
+        &mut self,
+        py: Python<'py>,
+        observe: bool,
+    ) -> PyResult<(
+        Option<Bound<'py, PyDict>>,
+        f32,
+        bool,
+        bool,
+        Bound<'py, PyDict>,
+    )> {
+        let observation = if observe {
+            Some(self.observe(py, AGENTS[self.selection])?)
+        } else {
+            None
+        };
+        Ok((
+            observation,
+            self.cumulative_rewards[self.selection],
+            self.terminated,
+            self.truncated,
+            PyDict::new(py),
+        ))
+    }
+
+    /// Play `action` for the selected agent. Once the game has ended, each
+    /// agent must be stepped with `None` to remove it.
+    #[pyo3(signature = (action))]
+    pub fn step(&mut self, action: Option<usize>) -> PyResult<()> {
+        if self.live == [false; 2] {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "step called with no agents left; call reset",
+            ));
+        }
+        if self.terminated || self.truncated {
+            if action.is_some() {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "the episode has ended; step with None to remove the agent",
+                ));
+            }
+            self.dead_step();
+            return Ok(());
+        }
+
+        let action = action.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("action must not be None")
+        })?;
+        let space = self.action_space;
+        if !dispatch_game!(&mut self.game, g => space.legal_action_indices(g)).contains(&action) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "illegal action {}",
+                action
+            )));
+        }
+
+        dispatch_game!(&mut self.game, g => space.apply_action(g, action));
+        self.rewards = [0.0; 2];
+        self.cumulative_rewards[self.selection] = 0.0;
+
+        let reward_config = &self.reward_config;
+        let (rewards, plies) = dispatch_game!(&mut self.game, g => {
+            let rewards = g
+                .reward(Color::White, reward_config)
+                .zip(g.reward(Color::Black, reward_config));
+            (rewards, g.move_count())
+        });
+        if let Some((white, black)) = rewards {
+            self.rewards = [white, black];
+            self.terminated = true;
+        } else if self.max_plies.is_some_and(|max| plies >= max) {
+            self.truncated = true;
+        }
+        for side in 0..2 {
+            self.cumulative_rewards[side] += self.rewards[side];
+        }
+        self.selection = 1 - self.selection;
+        Ok(())
+    }
+
+    /// Iterate over the selected agent until every agent has been removed, at
+    /// most `max_iter` times.
+    #[pyo3(signature = (max_iter=u64::MAX))]
+    pub fn agent_iter(slf: &Bound<'_, Self>, max_iter: u64) -> PyAecAgentIter {
+        PyAecAgentIter {
+            env: slf.clone().unbind(),
+            remaining: max_iter,
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        let (width, height) = dispatch_game!(&self.game, g => (g.width(), g.height()));
+        format!(
+            "AecEnv(width={}, height={}, agents={:?}, agent_selection='{}')",
+            width,
+            height,
+            self.agents(),
+            AGENTS[self.selection]
+        )
+    }
+}
+
+/// Iterator returned by `AecEnv.agent_iter`.
+#[pyclass(name = "AecAgentIter")]
+pub struct PyAecAgentIter {
+    env: Py<PyAecEnv>,
+    remaining: u64,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyAecAgentIter {
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&mut self, py: Python<'_>) -> Option<String> {
+        let env = self.env.borrow(py);
+        if self.remaining == 0 || env.live == [false; 2] {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(AGENTS[env.selection].to_string())
+    }
+}
@@ -0,0 +1,147 @@
+use std::any::Any;
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::py_game::{PyGame, downcast_like};
+use super::py_move::PyMove;
+use super::py_outcome::PyGameOutcome;
+use crate::arena::{self, ArenaConfig, DrawAdjudication, MatchReport, ResignAdjudication};
+use crate::dyn_game::DynGame;
+use crate::game::Game;
+use crate::r#move::Move;
+
+/// Ask the Python `player` for a move in `game`, keeping its exception in
+/// `error` so it can be raised unchanged.
+fn ask<const W: usize, const H: usize>(
+    player: &Bound<'_, PyAny>,
+    game: &mut Game<W, H>,
+    error: &mut Option<PyErr>,
+) -> Result<Option<Move>, String>
+where
+    [(); (W * H).div_ceil(64)]:,
+    DynGame: From<Game<W, H>>,
+{
+    let inner = DynGame::from(game.clone());
+    let result = player
+        .call1((PyGame::from(inner),))
+        .and_then(|mv| mv.extract::<Option<PyMove>>().map_err(PyErr::from));
+    match result {
+        Ok(mv) => Ok(mv.map(|mv| mv.move_)),
+        Err(e) => {
+            *error = Some(e);
+            Err("player failed".to_string())
+        }
+    }
+}
+
+fn play<const W: usize, const H: usize>(
+    first_opening: &Game<W, H>,
+    openings: &[PyRef<'_, PyGame>],
+    config: &ArenaConfig,
+    first: &Bound<'_, PyAny>,
+    second: &Bound<'_, PyAny>,
+) -> PyResult<MatchReport>
+where
+    [(); (W * H).div_ceil(64)]:,
+    DynGame: From<Game<W, H>>,
+{
+    let mut games = vec![first_opening.clone()];
+    for opening in openings.iter().skip(1) {
+        let other: &dyn Any = dispatch_game!(&opening.inner, g => g);
+        let game = downcast_like(first_opening, other).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "all openings must have the same board size",
+            )
+        })?;
+        games.push(game.clone());
+    }
+
+    let mut error = None;
+    let result = {
+        let error = std::cell::RefCell::new(&mut error);
+        arena::play_match(
+            &games,
+            config,
+            |game| ask(first, game, &mut error.borrow_mut()),
+            |game| ask(second, game, &mut error.borrow_mut()),
+        )
+    };
+    match (result, error) {
+        (_, Some(e)) => Err(e),
+        (report, None) => report.map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>),
+    }
+}
+
+/// Play `num_games` games between the callables `first` and `second`,
+/// alternating colors and cycling through `openings` (the standard position
+/// by default), each opening played twice with the colors swapped.
+///
+/// A player is called with a copy of the game on its turn and returns its
+/// move, or None to resign; an illegal move loses. Unfinished games are
+/// adjudicated a draw after `max_plies` plies. With `resign=(score, plies)`,
+/// a side the static evaluation has favored by `score` centipawns for
+/// `plies` plies in a row is adjudicated the winner; with `draw=(score,
+/// plies, min_ply)`, an evaluation within `score` of 0 for `plies` plies in a
+/// row from ply `min_ply` on is adjudicated a draw.
+///
+/// Returns a dict with the first player's "wins", "losses", "draws",
+/// "score", "elo" and "elo_error" (None if unbounded), "los", and "games": a
+/// list of `(opening, first_color, moves, outcome)`.
+#[pyfunction(name = "play_match")]
+#[pyo3(signature = (first, second, num_games=2, openings=None, max_plies=512, resign=None, draw=None))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_play_match<'py>(
+    py: Python<'py>,
+    first: Bound<'py, PyAny>,
+    second: Bound<'py, PyAny>,
+    num_games: usize,
+    openings: Option<Vec<PyRef<'py, PyGame>>>,
+    max_plies: usize,
+    resign: Option<(i32, usize)>,
+    draw: Option<(i32, usize, usize)>,
+) -> PyResult<Bound<'py, PyDict>> {
+    let config = ArenaConfig {
+        num_games,
+        max_plies,
+        resign: resign.map(|(score, plies)| ResignAdjudication { score, plies }),
+        draw: draw.map(|(score, plies, min_ply)| DrawAdjudication {
+            score,
+            plies,
+            min_ply,
+        }),
+    };
+    let openings = openings.unwrap_or_default();
+    let standard = DynGame::standard();
+    let start = openings.first().map_or(&standard, |opening| &opening.inner);
+    let report = dispatch_game!(start, g => play(g, &openings, &config, &first, &second))?;
+
+    let games: Vec<_> = report
+        .games
+        .iter()
+        .map(|game| {
+            (
+                game.opening,
+                game.first_color as i8,
+                game.moves
+                    .iter()
+                    .map(|&move_| PyMove { move_ })
+                    .collect::<Vec<_>>(),
+                PyGameOutcome {
+                    outcome: game.outcome,
+                },
+            )
+        })
+        .collect();
+    let elo = report.elo();
+    let dict = PyDict::new(py);
+    dict.set_item("wins", report.wins())?;
+    dict.set_item("losses", report.losses())?;
+    dict.set_item("draws", report.draws())?;
+    dict.set_item("score", report.score())?;
+    dict.set_item("elo", elo.map(|(elo, _)| elo))?;
+    dict.set_item("elo_error", elo.map(|(_, margin)| margin))?;
+    dict.set_item("los", report.los())?;
+    dict.set_item("games", games)?;
+    Ok(dict)
+}
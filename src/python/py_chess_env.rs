@@ -0,0 +1,192 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::py_encoder_config::PyEncoderConfig;
+use super::py_game::{PyGame, action_space};
+use super::py_reward_config::PyRewardConfig;
+use crate::action_space::ActionSpace;
+use crate::dyn_game::DynGame;
+use crate::encode;
+use crate::outcome::RewardConfig;
+
+/// A single-agent environment following the Gym API: `reset() -> obs` and
+/// `step(action) -> (obs, reward, done, info)`, with `action_space` and
+/// `observation_space` described as dicts.
+///
+/// One policy plays both sides. Actions are `EncoderConfig` action indices.
+/// Observations are float32 numpy arrays of shape `(planes, height, width)`,
+/// encoded from the side to move's perspective as `Game.encode_game_planes`.
+/// The reward of a step is for the side that made the move, and is nonzero
+/// only when the move ends the game.
+#[pyclass(name = "ChessEnv")]
+pub struct PyChessEnv {
+    initial: DynGame,
+    game: DynGame,
+    action_space: ActionSpace,
+    reward_config: RewardConfig,
+    max_plies: Option<usize>,
+    done: bool,
+}
+
+#[hotpath::measure_all]
+impl PyChessEnv {
+    fn observation<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let config = *self.action_space.config();
+        let (data, planes, height, width) = dispatch_game!(&mut self.game, g => {
+            encode::encode_game_planes_with_config(g, &config)
+        });
+        super::planes_array(py, &data, &[planes, height, width])
+    }
+
+    fn legal_actions(&mut self) -> Vec<usize> {
+        if self.done {
+            return Vec::new();
+        }
+        let space = self.action_space;
+        dispatch_game!(&mut self.game, g => space.legal_action_indices(g))
+    }
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyChessEnv {
+    /// `game` is the starting position of every episode (the standard position
+    /// by default). Episodes are truncated after `max_plies` plies, if given.
+    #[new]
+    #[pyo3(signature = (game=None, encoder_config=None, reward_config=None, max_plies=None))]
+    pub fn new(
+        game: Option<PyRef<'_, PyGame>>,
+        encoder_config: Option<PyEncoderConfig>,
+        reward_config: Option<PyRewardConfig>,
+        max_plies: Option<usize>,
+    ) -> PyResult<Self> {
+        let (initial, encoder_config) = match game {
+            Some(game) => (game.inner.clone(), game.encoder_config(encoder_config)),
+            None => (DynGame::standard(), encoder_config.unwrap_or_default().config),
+        };
+        let action_space = action_space(&initial, encoder_config)?;
+        Ok(PyChessEnv {
+            game: initial.clone(),
+            initial,
+            action_space,
+            reward_config: reward_config.unwrap_or_default().config,
+            max_plies,
+            done: false,
+        })
+    }
+
+    /// Start a new episode from the starting position and return its
+    /// observation. The environment is deterministic, so `seed` and `options`
+    /// are accepted and ignored.
+    #[pyo3(signature = (seed=None, options=None))]
+    pub fn reset<'py>(
+        &mut self,
+        py: Python<'py>,
+        seed: Option<u64>,
+        options: Option<Bound<'_, PyDict>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let _ = (seed, options);
+        self.game = self.initial.clone();
+        self.done = false;
+        self.observation(py)
+    }
+
+    /// Play `action` for the side to move. `info` holds the `legal_actions` of
+    /// the new position, the `termination` reason (or None) and whether the
+    /// episode was `truncated` by `max_plies`.
+    #[allow(clippy::type_complexity)]
+    pub fn step<'py>(
+        &mut self,
+        py: Python<'py>,
+        action: usize,
+    ) -> PyResult<(Bound<'py, PyAny>, f32, bool, Bound<'py, PyDict>)> {
+        if self.done {
+            return Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
+                "step called after the episode ended; call reset",
+            ));
+        }
+        if !self.legal_actions().contains(&action) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "illegal action {}",
+                action
+            )));
+        }
+
+        let space = self.action_space;
+        let reward_config = &self.reward_config;
+        let (reward, termination, plies) = dispatch_game!(&mut self.game, g => {
+            let mover = g.turn();
+            space.apply_action(g, action);
+            let termination = g.outcome().map(|outcome| outcome.termination().to_string());
+            (g.reward(mover, reward_config), termination, g.move_count())
+        });
+        let truncated = termination.is_none() && self.max_plies.is_some_and(|max| plies >= max);
+        self.done = termination.is_some() || truncated;
+
+        let info = PyDict::new(py);
+        info.set_item("legal_actions", self.legal_actions())?;
+        info.set_item("termination", termination)?;
+        info.set_item("truncated", truncated)?;
+        Ok((
+            self.observation(py)?,
+            reward.unwrap_or(0.0),
+            self.done,
+            info,
+        ))
+    }
+
+    /// `{"type": "Discrete", "n": total_actions}`.
+    #[getter]
+    pub fn action_space<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let space = PyDict::new(py);
+        space.set_item("type", "Discrete")?;
+        space.set_item("n", self.action_space.action_size())?;
+        Ok(space)
+    }
+
+    /// `{"type": "Box", "shape": (planes, height, width), "dtype": "float32"}`.
+    #[getter]
+    pub fn observation_space<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (rows, cols) = self.action_space.plane_shape();
+        let space = PyDict::new(py);
+        space.set_item("type", "Box")?;
+        space.set_item("shape", (self.action_space.input_plane_count(), rows, cols))?;
+        space.set_item("dtype", "float32")?;
+        Ok(space)
+    }
+
+    /// Whether the current episode has ended.
+    #[getter]
+    pub fn done(&self) -> bool {
+        self.done
+    }
+
+    /// A copy of the current game.
+    #[getter]
+    pub fn game(&self) -> PyGame {
+        PyGame {
+            inner: self.game.clone(),
+            history_length: self.action_space.config().history_length,
+        }
+    }
+
+    /// `total_actions` 0/1 entries marking the legal actions, all 0 once the
+    /// episode has ended.
+    pub fn action_mask(&mut self) -> Vec<u8> {
+        let mut mask = vec![0u8; self.action_space.action_size()];
+        for action in self.legal_actions() {
+            mask[action] = 1;
+        }
+        mask
+    }
+
+    pub fn __repr__(&self) -> String {
+        let (width, height) = dispatch_game!(&self.game, g => (g.width(), g.height()));
+        format!(
+            "ChessEnv(width={}, height={}, done={})",
+            width,
+            height,
+            if self.done { "True" } else { "False" }
+        )
+    }
+}
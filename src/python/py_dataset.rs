@@ -0,0 +1,193 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+use pyo3::prelude::*;
+
+use super::py_encoder_config::PyEncoderConfig;
+use crate::action_space::ActionSpace;
+use crate::dataset::{PgnSampleConfig, PgnSamples, SampleReader, SampleWriter, TrainingSample};
+
+fn value_err(e: String) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(e)
+}
+
+/// Writes training samples to an indexed binary file; see `SampleReader`. The
+/// index is written by `close` (or on leaving a `with` block), and a file that
+/// was never closed can't be read.
+#[pyclass(name = "SampleWriter", unsendable)]
+pub struct PySampleWriter {
+    inner: Option<SampleWriter<BufWriter<File>>>,
+}
+
+#[pymethods]
+impl PySampleWriter {
+    #[new]
+    pub fn new(path: &str, num_planes: usize, height: usize, width: usize) -> PyResult<Self> {
+        let inner = SampleWriter::create(path, num_planes, height, width).map_err(value_err)?;
+        Ok(PySampleWriter { inner: Some(inner) })
+    }
+
+    /// Append a sample: `planes` is any float32 buffer (such as a numpy array)
+    /// or sequence of `num_planes * height * width` values, `policy` a list of
+    /// (action index, probability) pairs and `value` in [-1, 1].
+    pub fn write(
+        &mut self,
+        py: Python<'_>,
+        planes: &Bound<'_, PyAny>,
+        policy: Vec<(u32, f32)>,
+        value: f32,
+    ) -> PyResult<()> {
+        let planes = match pyo3::buffer::PyBuffer::<f32>::get(planes) {
+            Ok(buffer) => buffer.to_vec(py)?,
+            Err(_) => planes.extract()?,
+        };
+        let writer = self.inner.as_mut().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("SampleWriter is closed")
+        })?;
+        writer
+            .write(&TrainingSample {
+                planes,
+                policy,
+                value,
+            })
+            .map_err(value_err)
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.as_ref().map_or(0, SampleWriter::len)
+    }
+
+    /// Write the index and close the file. Closing twice does nothing.
+    pub fn close(&mut self) -> PyResult<()> {
+        match self.inner.take() {
+            Some(writer) => writer.finish().map(|_| ()).map_err(value_err),
+            None => Ok(()),
+        }
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _exc_type: Option<&Bound<'_, PyAny>>,
+        _exc_value: Option<&Bound<'_, PyAny>>,
+        _traceback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<bool> {
+        self.close()?;
+        Ok(false)
+    }
+}
+
+/// Reads a file written by `SampleWriter`. Samples are
+/// `(planes, policy, value)`, with `planes` a float32 numpy array of shape
+/// `(num_planes, height, width)`.
+#[pyclass(name = "SampleReader", unsendable)]
+pub struct PySampleReader {
+    inner: SampleReader<BufReader<File>>,
+    next: usize,
+}
+
+#[allow(clippy::type_complexity)]
+#[pymethods]
+impl PySampleReader {
+    #[new]
+    pub fn new(path: &str) -> PyResult<Self> {
+        let inner = SampleReader::open(path).map_err(value_err)?;
+        Ok(PySampleReader { inner, next: 0 })
+    }
+
+    /// `(num_planes, height, width)` of every sample's planes.
+    pub fn plane_shape(&self) -> (usize, usize, usize) {
+        self.inner.plane_shape()
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn __getitem__<'py>(
+        &mut self,
+        py: Python<'py>,
+        index: isize,
+    ) -> PyResult<(Bound<'py, PyAny>, Vec<(u32, f32)>, f32)> {
+        let len = self.inner.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&index) {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
+                "sample index out of range",
+            ));
+        }
+        let sample = self.inner.get(index as usize).map_err(value_err)?;
+        let (num_planes, height, width) = self.inner.plane_shape();
+        let planes = super::planes_array(py, &sample.planes, &[num_planes, height, width])?;
+        Ok((planes, sample.policy, sample.value))
+    }
+
+    pub fn __iter__(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.next = 0;
+        slf
+    }
+
+    pub fn __next__<'py>(
+        &mut self,
+        py: Python<'py>,
+    ) -> PyResult<Option<(Bound<'py, PyAny>, Vec<(u32, f32)>, f32)>> {
+        if self.next >= self.inner.len() {
+            return Ok(None);
+        }
+        self.next += 1;
+        self.__getitem__(py, self.next as isize - 1).map(Some)
+    }
+
+    pub fn __repr__(&self) -> String {
+        let (num_planes, height, width) = self.inner.plane_shape();
+        format!(
+            "SampleReader(len={}, plane_shape=({}, {}, {}))",
+            self.inner.len(),
+            num_planes,
+            height,
+            width
+        )
+    }
+}
+
+/// Replay every game of the PGN file at `pgn_path` and write a training
+/// sample for its positions to a new sample file at `output_path` (read it
+/// with `SampleReader`): the planes, the move played as a one-hot policy and
+/// the game's result for the side to move as the value. Keeps every
+/// `every_nth` position after the first `skip_plies` plies of each game.
+/// Games that fail to parse or replay, or have no result, are skipped.
+/// Returns `(samples written, games skipped)`.
+#[pyfunction(name = "pgn_to_samples")]
+#[pyo3(signature = (pgn_path, output_path, every_nth=1, skip_plies=0, encoder_config=None))]
+pub fn py_pgn_to_samples(
+    py: Python<'_>,
+    pgn_path: &str,
+    output_path: &str,
+    every_nth: usize,
+    skip_plies: usize,
+    encoder_config: Option<PyEncoderConfig>,
+) -> PyResult<(usize, usize)> {
+    let config = PgnSampleConfig {
+        every_nth,
+        skip_plies,
+        encoder: encoder_config.unwrap_or_default().config,
+    };
+    let space = ActionSpace::new(8, 8, config.encoder)?;
+    let (height, width) = space.plane_shape();
+    py.detach(|| {
+        let games = crate::pgn::read_pgn_file(pgn_path).map_err(|e| e.to_string())?;
+        let mut writer =
+            SampleWriter::create(output_path, space.input_plane_count(), height, width)?;
+        let mut samples = PgnSamples::new(games, config);
+        for sample in samples.by_ref() {
+            writer.write(&sample)?;
+        }
+        let written = writer.len();
+        writer.finish()?;
+        Ok((written, samples.skipped_games()))
+    })
+    .map_err(value_err)
+}
@@ -0,0 +1,171 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::encode::{EncoderConfig, HISTORY_LENGTH, PlaneNormalization};
+use crate::error::ChessError;
+use crate::limits::{MAX_BOARD_DIM, MIN_BOARD_DIM, board_dimension_is_valid};
+
+#[pyclass(name = "EncoderConfig", from_py_object)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PyEncoderConfig {
+    pub(super) config: EncoderConfig,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyEncoderConfig {
+    #[new]
+    #[pyo3(signature = (last_move_planes=false, pass_action=false, padded_size=None, flip_perspective=false, history_length=HISTORY_LENGTH, fairy_piece_planes=false, repetition_planes=false))]
+    pub fn new(
+        last_move_planes: bool,
+        pass_action: bool,
+        padded_size: Option<usize>,
+        flip_perspective: bool,
+        history_length: usize,
+        fairy_piece_planes: bool,
+        repetition_planes: bool,
+    ) -> PyResult<Self> {
+        if history_length == 0 {
+            return Err(ChessError::InvalidDimensions(
+                "history_length must be at least 1".to_string(),
+            )
+            .into());
+        }
+        if let Some(size) = padded_size
+            && !board_dimension_is_valid(size)
+        {
+            return Err(ChessError::InvalidDimensions(format!(
+                "padded_size must be between {} and {}",
+                MIN_BOARD_DIM, MAX_BOARD_DIM
+            ))
+            .into());
+        }
+        Ok(PyEncoderConfig {
+            config: EncoderConfig {
+                history_length,
+                last_move_planes,
+                fairy_piece_planes,
+                repetition_planes,
+                pass_action,
+                padded_size,
+                flip_perspective,
+            },
+        })
+    }
+
+    #[getter]
+    pub fn history_length(&self) -> usize {
+        self.config.history_length
+    }
+
+    #[getter]
+    pub fn last_move_planes(&self) -> bool {
+        self.config.last_move_planes
+    }
+
+    #[getter]
+    pub fn fairy_piece_planes(&self) -> bool {
+        self.config.fairy_piece_planes
+    }
+
+    #[getter]
+    pub fn repetition_planes(&self) -> bool {
+        self.config.repetition_planes
+    }
+
+    #[getter]
+    pub fn pass_action(&self) -> bool {
+        self.config.pass_action
+    }
+
+    #[getter]
+    pub fn padded_size(&self) -> Option<usize> {
+        self.config.padded_size
+    }
+
+    #[getter]
+    pub fn flip_perspective(&self) -> bool {
+        self.config.flip_perspective
+    }
+
+    pub fn num_planes(&self) -> usize {
+        self.config.num_planes()
+    }
+
+    pub fn plane_shape(&self, width: usize, height: usize) -> (usize, usize) {
+        self.config.plane_shape(width, height)
+    }
+
+    pub fn total_actions(&self, width: usize, height: usize) -> usize {
+        self.config.total_actions(width, height)
+    }
+
+    pub fn pass_action_index(&self, width: usize, height: usize) -> Option<usize> {
+        self.config.pass_action_index(width, height)
+    }
+
+    /// Layout of the input planes and action space for a `width` x `height`
+    /// board, as a dict with "plane_shape", "planes" and "actions" entries.
+    pub fn schema<'py>(
+        &self,
+        py: Python<'py>,
+        width: usize,
+        height: usize,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let schema = self.config.schema(width, height);
+
+        let mut planes = Vec::with_capacity(schema.planes.len());
+        for plane in &schema.planes {
+            let (normalization, scale) = match plane.normalization {
+                PlaneNormalization::Binary => ("binary", None),
+                PlaneNormalization::Constant => ("constant", None),
+                PlaneNormalization::Scaled(scale) => ("scaled", Some(scale)),
+            };
+            let dict = PyDict::new(py);
+            dict.set_item("index", plane.index)?;
+            dict.set_item("name", &plane.name)?;
+            dict.set_item("description", &plane.description)?;
+            dict.set_item("normalization", normalization)?;
+            dict.set_item("scale", scale)?;
+            planes.push(dict);
+        }
+
+        let mut actions = Vec::with_capacity(schema.actions.len());
+        for block in &schema.actions {
+            let dict = PyDict::new(py);
+            dict.set_item("name", &block.name)?;
+            dict.set_item("description", &block.description)?;
+            dict.set_item("start", block.start)?;
+            dict.set_item("len", block.len)?;
+            actions.push(dict);
+        }
+
+        let dict = PyDict::new(py);
+        dict.set_item("plane_shape", schema.plane_shape)?;
+        dict.set_item("planes", planes)?;
+        dict.set_item("actions", actions)?;
+        Ok(dict)
+    }
+
+    pub fn __repr__(&self) -> String {
+        let py_bool = |b: bool| if b { "True" } else { "False" };
+        let padded_size = match self.config.padded_size {
+            Some(size) => size.to_string(),
+            None => "None".to_string(),
+        };
+        format!(
+            "EncoderConfig(last_move_planes={}, pass_action={}, padded_size={}, flip_perspective={}, history_length={}, fairy_piece_planes={}, repetition_planes={})",
+            py_bool(self.config.last_move_planes),
+            py_bool(self.config.pass_action),
+            padded_size,
+            py_bool(self.config.flip_perspective),
+            self.config.history_length,
+            py_bool(self.config.fairy_piece_planes),
+            py_bool(self.config.repetition_planes)
+        )
+    }
+
+    pub fn __eq__(&self, other: &PyEncoderConfig) -> bool {
+        self.config == other.config
+    }
+}
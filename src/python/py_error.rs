@@ -0,0 +1,90 @@
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::error;
+
+create_exception!(
+    spooky_chess,
+    ChessError,
+    PyValueError,
+    "A FEN, square, move or position edit was rejected."
+);
+create_exception!(
+    spooky_chess,
+    InvalidFenError,
+    ChessError,
+    "A malformed FEN."
+);
+create_exception!(
+    spooky_chess,
+    InvalidDimensionsError,
+    ChessError,
+    "Board dimensions outside the supported range."
+);
+create_exception!(
+    spooky_chess,
+    InvalidSquareError,
+    ChessError,
+    "A square name that can't be parsed."
+);
+create_exception!(
+    spooky_chess,
+    InvalidMoveError,
+    ChessError,
+    "A move string that can't be parsed, or that leaves the board."
+);
+create_exception!(
+    spooky_chess,
+    IllegalMoveError,
+    ChessError,
+    "A well-formed move that can't be played in the position."
+);
+create_exception!(
+    spooky_chess,
+    InvalidPositionError,
+    ChessError,
+    "A malformed board diagram, or an edit that would leave the position invalid."
+);
+create_exception!(
+    spooky_chess,
+    InvalidRulesError,
+    ChessError,
+    "Castling columns or draw rules the game can't be played with."
+);
+
+impl From<error::ChessError> for PyErr {
+    fn from(e: error::ChessError) -> Self {
+        let msg = e.to_string();
+        match e {
+            error::ChessError::InvalidFen { .. } => InvalidFenError::new_err(msg),
+            error::ChessError::InvalidDimensions(_) => InvalidDimensionsError::new_err(msg),
+            error::ChessError::InvalidSquare(_) => InvalidSquareError::new_err(msg),
+            error::ChessError::InvalidMove(_) => InvalidMoveError::new_err(msg),
+            error::ChessError::IllegalMove(_) => IllegalMoveError::new_err(msg),
+            error::ChessError::InvalidPosition(_) => InvalidPositionError::new_err(msg),
+            error::ChessError::InvalidRules(_) => InvalidRulesError::new_err(msg),
+            error::ChessError::Unsupported(_) => ChessError::new_err(msg),
+        }
+    }
+}
+
+/// Add the exception types to the module.
+pub(crate) fn register_exceptions(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add("ChessError", py.get_type::<ChessError>())?;
+    m.add("InvalidFenError", py.get_type::<InvalidFenError>())?;
+    m.add(
+        "InvalidDimensionsError",
+        py.get_type::<InvalidDimensionsError>(),
+    )?;
+    m.add("InvalidSquareError", py.get_type::<InvalidSquareError>())?;
+    m.add("InvalidMoveError", py.get_type::<InvalidMoveError>())?;
+    m.add("IllegalMoveError", py.get_type::<IllegalMoveError>())?;
+    m.add(
+        "InvalidPositionError",
+        py.get_type::<InvalidPositionError>(),
+    )?;
+    m.add("InvalidRulesError", py.get_type::<InvalidRulesError>())?;
+    Ok(())
+}
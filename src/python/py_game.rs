@@ -1,44 +1,241 @@
+use std::any::Any;
+
 use pyo3::prelude::*;
 
-use super::dispatch::*;
+use super::py_encoder_config::PyEncoderConfig;
 use super::py_move::PyMove;
 use super::py_outcome::PyGameOutcome;
 use super::py_piece::PyPiece;
 use super::py_position::PyPosition;
+use super::py_reward_config::PyRewardConfig;
+use super::py_stats::{PyGameStats, PyMoveGenStats};
 use super::py_turn_state::PyTurnState;
 use super::validate_dimensions;
+use crate::action_space::ActionSpace;
 use crate::color::Color;
-use crate::encode;
+use crate::dyn_game::DynGame;
+use crate::encode::{self, EncoderConfig};
+use crate::game::GameRecord;
+use crate::r#move::Move;
+use crate::outcome::{DrawRules, GameOutcome, Termination};
 use crate::pieces::PieceType;
 use crate::position::Position;
+use crate::render::{self, RenderMode};
+use crate::stats;
+
+/// A `GameRecord` in a form Python can pickle: `(width, height, start_fen,
+/// moves, castling_enabled, self_capture, fairy_promotions,
+/// castling_king_cols, double_push_row, draw_rules, outcome)`, with the moves
+/// in LAN ("0000" for a null move), the draw rules as `(halfmove_limit,
+/// repetition_limit, ply_limit)` and the outcome as `(winner, termination)`.
+type PickleState = (
+    usize,
+    usize,
+    String,
+    Vec<String>,
+    bool,
+    bool,
+    bool,
+    Option<(u8, u8)>,
+    Option<u8>,
+    (u32, usize, Option<usize>),
+    Option<(Option<i8>, String)>,
+);
+
+fn pickle_state(record: GameRecord) -> PickleState {
+    let GameRecord {
+        width,
+        height,
+        start_fen,
+        moves,
+        castling_enabled,
+        self_capture,
+        fairy_promotions,
+        castling_king_cols,
+        double_push_row,
+        draw_rules,
+        outcome,
+    } = record;
+    (
+        width,
+        height,
+        start_fen,
+        moves.iter().map(|mv| mv.to_lan()).collect(),
+        castling_enabled,
+        self_capture,
+        fairy_promotions,
+        castling_king_cols,
+        double_push_row,
+        (
+            draw_rules.halfmove_limit,
+            draw_rules.repetition_limit,
+            draw_rules.ply_limit,
+        ),
+        outcome.map(|outcome| {
+            (
+                outcome.winner.map(|color| color as i8),
+                outcome.termination.to_string(),
+            )
+        }),
+    )
+}
 
-#[pyclass(name = "Game")]
+fn record_from_pickle_state(state: PickleState) -> PyResult<GameRecord> {
+    let (
+        width,
+        height,
+        start_fen,
+        moves,
+        castling_enabled,
+        self_capture,
+        fairy_promotions,
+        castling_king_cols,
+        double_push_row,
+        (halfmove_limit, repetition_limit, ply_limit),
+        outcome,
+    ) = state;
+    validate_dimensions(width, height)?;
+    let moves = moves
+        .iter()
+        .map(|lan| Move::from_lan(lan, width, height))
+        .collect::<Result<Vec<_>, _>>()?;
+    let outcome = outcome
+        .map(|(winner, termination)| {
+            let winner = winner
+                .map(|w| {
+                    Color::from_int(w).ok_or_else(|| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                            "color must be 1 (white) or -1 (black)",
+                        )
+                    })
+                })
+                .transpose()?;
+            let termination: Termination = termination
+                .parse()
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            Ok::<_, PyErr>(GameOutcome::new(winner, termination))
+        })
+        .transpose()?;
+    Ok(GameRecord {
+        width,
+        height,
+        start_fen,
+        moves,
+        castling_enabled,
+        self_capture,
+        fairy_promotions,
+        castling_king_cols,
+        double_push_row,
+        draw_rules: DrawRules {
+            halfmove_limit,
+            repetition_limit,
+            ply_limit,
+        },
+        outcome,
+    })
+}
+
+/// `other` as the same type as `_like`, if it is one.
+pub(super) fn downcast_like<'a, T: Any>(_like: &T, other: &'a dyn Any) -> Option<&'a T> {
+    other.downcast_ref()
+}
+
+/// The action space of the game's board encoded with `config`.
+pub(super) fn action_space(inner: &DynGame, config: EncoderConfig) -> PyResult<ActionSpace> {
+    Ok(dispatch_game!(inner, g => ActionSpace::for_game(g, config))?)
+}
+
+#[pyclass(name = "Game", module = "spooky_chess")]
 pub struct PyGame {
-    pub(super) inner: GameInner,
+    pub(super) inner: DynGame,
+    /// History steps of the encoder configuration used when none is given.
+    pub(super) history_length: usize,
+}
+
+impl From<DynGame> for PyGame {
+    fn from(inner: DynGame) -> Self {
+        PyGame {
+            inner,
+            history_length: encode::HISTORY_LENGTH,
+        }
+    }
+}
+
+impl PyGame {
+    /// `config`, or the default configuration with this game's history
+    /// length.
+    pub(super) fn encoder_config(&self, config: Option<PyEncoderConfig>) -> EncoderConfig {
+        config.map_or(
+            EncoderConfig {
+                history_length: self.history_length,
+                ..EncoderConfig::default()
+            },
+            |config| config.config,
+        )
+    }
+
+    /// Another game with this one's history length.
+    fn with_inner(&self, inner: DynGame) -> PyGame {
+        PyGame {
+            inner,
+            history_length: self.history_length,
+        }
+    }
 }
 
 #[hotpath::measure_all]
 #[pymethods]
 impl PyGame {
+    /// `history_length` is the number of positions encoded when no encoder
+    /// configuration is given (see `EncoderConfig.history_length`).
     #[new]
-    pub fn new(width: usize, height: usize, fen: &str, castling_enabled: bool) -> PyResult<Self> {
+    #[pyo3(signature = (width, height, fen, castling_enabled, history_length=encode::HISTORY_LENGTH))]
+    pub fn new(
+        width: usize,
+        height: usize,
+        fen: &str,
+        castling_enabled: bool,
+        history_length: usize,
+    ) -> PyResult<Self> {
         validate_dimensions(width, height)?;
-        let inner = make_game_inner(width, height, fen, castling_enabled)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e))?;
-        Ok(PyGame { inner })
+        EncoderConfig {
+            history_length,
+            ..EncoderConfig::default()
+        }
+        .check_board(width, height)?;
+        let inner = DynGame::new(width, height, fen, castling_enabled)?;
+        Ok(PyGame {
+            inner,
+            history_length,
+        })
     }
 
     #[staticmethod]
     pub fn standard() -> Self {
-        PyGame {
-            inner: make_standard_game_inner(),
-        }
+        PyGame::from(DynGame::standard())
+    }
+
+    #[staticmethod]
+    pub fn capablanca() -> Self {
+        PyGame::from(DynGame::capablanca())
+    }
+
+    #[staticmethod]
+    pub fn from_uci_position_string(width: usize, height: usize, position: &str) -> PyResult<Self> {
+        validate_dimensions(width, height)?;
+        let inner = DynGame::from_uci_position_string(width, height, position)?;
+        Ok(PyGame::from(inner))
     }
 
     // ---------------------------------------------------------------------
     // Game Methods
     // ---------------------------------------------------------------------
 
+    #[getter]
+    pub fn history_length(&self) -> usize {
+        self.history_length
+    }
+
     pub fn turn(&self) -> i8 {
         dispatch_game!(&self.inner, g => g.turn() as i8)
     }
@@ -52,13 +249,94 @@ impl PyGame {
     }
 
     pub fn ply(&self) -> usize {
+        dispatch_game!(&self.inner, g => g.ply())
+    }
+
+    pub fn move_count(&self) -> usize {
         dispatch_game!(&self.inner, g => g.move_count())
     }
 
+    pub fn last_move(&self) -> Option<PyMove> {
+        dispatch_game!(&self.inner, g => g.last_move().map(|m| PyMove { move_: *m }))
+    }
+
+    pub fn last_capture(&self) -> Option<PyPiece> {
+        dispatch_game!(&self.inner, g => g.last_capture().map(|piece| PyPiece { piece }))
+    }
+
     pub fn castling_enabled(&self) -> bool {
         dispatch_game!(&self.inner, g => g.castling_enabled())
     }
 
+    pub fn self_capture_enabled(&self) -> bool {
+        dispatch_game!(&self.inner, g => g.self_capture_enabled())
+    }
+
+    pub fn set_self_capture(&mut self, enabled: bool) {
+        dispatch_game!(&mut self.inner, g => g.set_self_capture(enabled))
+    }
+
+    pub fn fairy_promotions_enabled(&self) -> bool {
+        dispatch_game!(&self.inner, g => g.fairy_promotions_enabled())
+    }
+
+    /// Let pawns also promote to archbishops and chancellors, as in
+    /// Capablanca chess.
+    pub fn set_fairy_promotions(&mut self, enabled: bool) {
+        dispatch_game!(&mut self.inner, g => g.set_fairy_promotions(enabled))
+    }
+
+    pub fn castling_king_cols(&self) -> Option<(u8, u8)> {
+        dispatch_game!(&self.inner, g => g.castling_king_cols())
+    }
+
+    /// Columns `(kingside, queenside)` the king lands on when castling, or
+    /// None for two squares towards the rook.
+    #[pyo3(signature = (cols))]
+    pub fn set_castling_king_cols(&mut self, cols: Option<(u8, u8)>) -> PyResult<()> {
+        dispatch_game!(&mut self.inner, g => Ok(g.set_castling_king_cols(cols)?))
+    }
+
+    pub fn double_push_row(&self) -> Option<u8> {
+        dispatch_game!(&self.inner, g => g.double_push_row())
+    }
+
+    /// Row, counted from each side's back row, from which pawns may double
+    /// push, or None for the second row.
+    #[pyo3(signature = (row))]
+    pub fn set_double_push_row(&mut self, row: Option<u8>) -> PyResult<()> {
+        dispatch_game!(&mut self.inner, g => Ok(g.set_double_push_row(row)?))
+    }
+
+    /// `(halfmove_limit, repetition_limit, ply_limit)`: when the game is
+    /// drawn automatically.
+    pub fn draw_rules(&self) -> (u32, usize, Option<usize>) {
+        let rules = dispatch_game!(&self.inner, g => *g.draw_rules());
+        (
+            rules.halfmove_limit,
+            rules.repetition_limit,
+            rules.ply_limit,
+        )
+    }
+
+    /// Draw the game once the halfmove clock reaches `halfmove_limit`, a
+    /// position occurs `repetition_limit` times, or `ply_limit` plies have
+    /// been played (if given).
+    #[pyo3(signature = (halfmove_limit=150, repetition_limit=5, ply_limit=None))]
+    pub fn set_draw_rules(
+        &mut self,
+        halfmove_limit: u32,
+        repetition_limit: usize,
+        ply_limit: Option<usize>,
+    ) -> PyResult<()> {
+        let rules = DrawRules {
+            halfmove_limit,
+            repetition_limit,
+            ply_limit,
+        };
+        dispatch_game!(&mut self.inner, g => Ok(g.set_draw_rules(rules)?))
+    }
+
     pub fn has_kingside_castling_rights(&self, color: i8) -> PyResult<bool> {
         let color = Color::from_int(color).ok_or_else(|| {
             PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
@@ -83,6 +361,10 @@ impl PyGame {
         dispatch_game!(&mut self.inner, g => g.make_move_unchecked(&move_.move_))
     }
 
+    pub fn make_null_move(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.make_null_move())
+    }
+
     pub fn move_history(&self) -> Vec<PyMove> {
         dispatch_game!(&self.inner, g => {
             g.move_history()
@@ -96,6 +378,16 @@ impl PyGame {
         dispatch_game!(&mut self.inner, g => g.unmake_move())
     }
 
+    pub fn truncate_to_ply(&mut self, ply: usize) -> bool {
+        dispatch_game!(&mut self.inner, g => g.truncate_to_ply(ply))
+    }
+
+    pub fn annotate_move(&self, move_: PyMove) -> PyMove {
+        PyMove {
+            move_: dispatch_game!(&self.inner, g => g.annotate_move(&move_.move_)),
+        }
+    }
+
     pub fn is_legal_move(&mut self, move_: PyMove) -> bool {
         dispatch_game!(&mut self.inner, g => g.is_legal_move(&move_.move_))
     }
@@ -118,6 +410,15 @@ impl PyGame {
         })
     }
 
+    pub fn legal_moves_sorted(&mut self) -> Vec<PyMove> {
+        dispatch_game!(&mut self.inner, g => {
+            g.legal_moves_sorted()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
     pub fn legal_moves_for_position(&mut self, col: u8, row: u8) -> Vec<PyMove> {
         let pos = Position::new(col, row);
         dispatch_game!(&mut self.inner, g => {
@@ -133,29 +434,160 @@ impl PyGame {
     }
 
     pub fn move_from_lan(&self, lan: &str) -> PyResult<PyMove> {
-        dispatch_game!(&self.inner, g => {
-            match g.move_from_lan(lan) {
-                Ok(move_) => Ok(PyMove { move_ }),
-                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(e)),
-            }
-        })
+        dispatch_game!(&self.inner, g => Ok(PyMove { move_: g.move_from_lan(lan)? }))
     }
 
     pub fn move_to_san(&mut self, move_: PyMove) -> String {
         dispatch_game!(&mut self.inner, g => g.move_to_san(&move_.move_))
     }
 
+    pub fn legal_moves_san(&mut self) -> Vec<String> {
+        dispatch_game!(&mut self.inner, g => g.legal_moves_san())
+    }
+
     pub fn move_from_san(&mut self, san: &str) -> PyResult<PyMove> {
+        dispatch_game!(&mut self.inner, g => Ok(PyMove { move_: g.move_from_san(san)? }))
+    }
+
+    pub fn is_check(&self) -> bool {
+        dispatch_game!(&self.inner, g => g.is_check())
+    }
+
+    /// Descriptions of the rules the position breaks, empty if it's legal.
+    pub fn validate(&self) -> Vec<String> {
+        dispatch_game!(&self.inner, g => g.validate().iter().map(ToString::to_string).collect())
+    }
+
+    pub fn checks(&mut self) -> Vec<PyMove> {
+        dispatch_game!(&mut self.inner, g => {
+            g.checks()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
+    pub fn legal_moves_with_checks(&mut self) -> Vec<PyMove> {
+        dispatch_game!(&mut self.inner, g => {
+            g.legal_moves_with_checks()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
+    pub fn gives_check(&mut self, move_: PyMove) -> bool {
+        dispatch_game!(&mut self.inner, g => g.gives_check(&move_.move_))
+    }
+
+    pub fn is_capture(&self, move_: PyMove) -> bool {
+        dispatch_game!(&self.inner, g => g.is_capture(&move_.move_))
+    }
+
+    pub fn is_zeroing(&self, move_: PyMove) -> bool {
+        dispatch_game!(&self.inner, g => g.is_zeroing(&move_.move_))
+    }
+
+    pub fn captures(&mut self) -> Vec<PyMove> {
         dispatch_game!(&mut self.inner, g => {
-            match g.move_from_san(san) {
-                Ok(move_) => Ok(PyMove { move_ }),
-                Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(e)),
+            g.captures()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
+    pub fn capture_moves(&mut self) -> Vec<PyMove> {
+        dispatch_game!(&mut self.inner, g => {
+            g.capture_moves()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
+    pub fn evasion_moves(&mut self) -> Vec<PyMove> {
+        dispatch_game!(&mut self.inner, g => {
+            g.evasion_moves()
+                .into_iter()
+                .map(|m| PyMove { move_: m })
+                .collect()
+        })
+    }
+
+    pub fn move_gen_stats(&mut self) -> PyMoveGenStats {
+        PyMoveGenStats {
+            stats: dispatch_game!(&mut self.inner, g => g.move_gen_stats()),
+        }
+    }
+
+    pub fn threatened_pieces(&self, color: i8) -> PyResult<Vec<(PyPosition, PyPiece)>> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => {
+            g.threatened_pieces(color)
+                .into_iter()
+                .map(|(pos, piece)| (PyPosition { pos }, PyPiece { piece }))
+                .collect()
+        }))
+    }
+
+    pub fn attack_map(&self, color: i8) -> PyResult<Vec<PyPosition>> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => {
+            g.attack_map(color)
+                .iter_ones()
+                .map(|idx| PyPosition { pos: Position::from_index(idx, g.width()) })
+                .collect()
+        }))
+    }
+
+    /// Pieces giving check to the side to move.
+    pub fn checkers(&self) -> Vec<PyPosition> {
+        dispatch_game!(&self.inner, g => {
+            g.checkers()
+                .iter_ones()
+                .map(|idx| PyPosition { pos: Position::from_index(idx, g.width()) })
+                .collect()
+        })
+    }
+
+    /// Pieces of `color` pinned against their own king.
+    pub fn pinned(&self, color: i8) -> PyResult<Vec<PyPosition>> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => {
+            g.pinned(color)
+                .iter_ones()
+                .map(|idx| PyPosition { pos: Position::from_index(idx, g.width()) })
+                .collect()
+        }))
+    }
+
+    pub fn attacks_from(&self, col: u8, row: u8) -> PyResult<Vec<PyPosition>> {
+        let pos = Position::new(col, row);
+        dispatch_game!(&self.inner, g => {
+            if !pos.is_valid(g.width(), g.height()) {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                    "position is off the board",
+                ));
             }
+            Ok(g.attacks_from(&pos)
+                .iter_ones()
+                .map(|idx| PyPosition { pos: Position::from_index(idx, g.width()) })
+                .collect())
         })
     }
 
-    pub fn is_check(&self) -> bool {
-        dispatch_game!(&self.inner, g => g.is_check())
+    pub fn attack_counts(&self, color: i8) -> PyResult<Vec<u8>> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => g.attack_counts(color)))
     }
 
     pub fn is_checkmate(&mut self) -> bool {
@@ -170,6 +602,87 @@ impl PyGame {
         dispatch_game!(&mut self.inner, g => g.is_over())
     }
 
+    pub fn has_any_legal_move(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.has_any_legal_move())
+    }
+
+    pub fn perft(&mut self, depth: u32) -> u64 {
+        dispatch_game!(&mut self.inner, g => g.perft(depth))
+    }
+
+    /// `(move, perft(depth - 1))` for each legal move.
+    pub fn perft_divide(&mut self, depth: u32) -> Vec<(PyMove, u64)> {
+        dispatch_game!(&mut self.inner, g => {
+            g.perft_divide(depth)
+                .into_iter()
+                .map(|(m, nodes)| (PyMove { move_: m }, nodes))
+                .collect()
+        })
+    }
+
+    pub fn repetition_count(&mut self) -> usize {
+        dispatch_game!(&mut self.inner, g => g.repetition_count())
+    }
+
+    pub fn is_threefold_repetition(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.is_threefold_repetition())
+    }
+
+    pub fn is_fivefold_repetition(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.is_fivefold_repetition())
+    }
+
+    pub fn can_claim_fifty_moves(&self) -> bool {
+        dispatch_game!(&self.inner, g => g.can_claim_fifty_moves())
+    }
+
+    pub fn can_claim_threefold(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.can_claim_threefold())
+    }
+
+    pub fn can_claim_draw(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.can_claim_draw())
+    }
+
+    /// Claim a draw for the side to move, ending the game. Returns the
+    /// outcome, or None if no draw can be claimed.
+    pub fn claim_draw(&mut self) -> Option<PyGameOutcome> {
+        dispatch_game!(&mut self.inner, g => g.claim_draw().map(|outcome| PyGameOutcome { outcome }))
+    }
+
+    /// `color` resigns. Returns False if the game is already over.
+    pub fn resign(&mut self, color: i8) -> PyResult<bool> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&mut self.inner, g => g.resign(color)))
+    }
+
+    /// End the game as an agreed draw. Returns False if it is already over.
+    pub fn agree_draw(&mut self) -> bool {
+        dispatch_game!(&mut self.inner, g => g.agree_draw())
+    }
+
+    /// End the game with `winner` (1, -1, or None for a draw) and
+    /// `termination` (e.g. "timeout"). Returns False if it is already over.
+    #[pyo3(signature = (winner, termination="adjudication"))]
+    pub fn adjudicate(&mut self, winner: Option<i8>, termination: &str) -> PyResult<bool> {
+        let winner = winner
+            .map(|w| {
+                Color::from_int(w).ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                        "winner must be 1 (white), -1 (black) or None",
+                    )
+                })
+            })
+            .transpose()?;
+        let termination = termination
+            .parse::<Termination>()
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        let outcome = GameOutcome::new(winner, termination);
+        Ok(dispatch_game!(&mut self.inner, g => g.adjudicate(outcome)))
+    }
+
     // ---------------------------------------------------------------------
     // Unified Game Protocol Methods
     // ---------------------------------------------------------------------
@@ -204,6 +717,31 @@ impl PyGame {
         dispatch_game!(&mut self.inner, g => g.set_piece(&pos, piece.map(|p| p.piece)))
     }
 
+    pub fn set_turn(&mut self, color: i8) -> PyResult<()> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        dispatch_game!(&mut self.inner, g => Ok(g.set_turn(color)?))
+    }
+
+    pub fn set_castling_rights(
+        &mut self,
+        color: i8,
+        kingside: bool,
+        queenside: bool,
+    ) -> PyResult<()> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        dispatch_game!(&mut self.inner, g => Ok(g.set_castling_rights(color, kingside, queenside)?))
+    }
+
+    #[pyo3(signature = (square=None))]
+    pub fn set_en_passant(&mut self, square: Option<PyPosition>) -> PyResult<()> {
+        let square = square.map(|square| square.pos);
+        dispatch_game!(&mut self.inner, g => Ok(g.set_en_passant(square)?))
+    }
+
     pub fn piece_count(&self, piece_type: &str, color: i8) -> PyResult<u8> {
         let pt = piece_type
             .chars()
@@ -216,11 +754,35 @@ impl PyGame {
         Ok(dispatch_game!(&self.inner, g => g.piece_counts().get(pt, c)))
     }
 
+    /// Total standard value of the pieces of `color`.
+    pub fn material(&self, color: i8) -> PyResult<i32> {
+        let c = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => g.material(c, PieceType::value)))
+    }
+
+    pub fn remove_all(&mut self, piece_type: &str, color: i8) -> PyResult<usize> {
+        let pt = piece_type
+            .chars()
+            .next()
+            .and_then(PieceType::from_char)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid piece type"))?;
+        let c = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        dispatch_game!(&mut self.inner, g => Ok(g.remove_all(pt, c)?))
+    }
+
+    pub fn shift_board(&mut self, dx: i32, dy: i32) -> PyResult<()> {
+        dispatch_game!(&mut self.inner, g => Ok(g.shift_board(dx, dy)?))
+    }
+
     pub fn __getitem__(&self, key: &Bound<'_, PyAny>) -> PyResult<Option<PyPiece>> {
         // Accept either a string like "e4" or a tuple like (col, row)
         if let Ok(s) = key.extract::<String>() {
             let pos = Position::from_algebraic(&s)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyKeyError, _>(e))?;
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyKeyError, _>(e.to_string()))?;
             Ok(dispatch_game!(&self.inner, g => g.get_piece(&pos).map(|p| PyPiece { piece: p })))
         } else if let Ok((col, row)) = key.extract::<(u8, u8)>() {
             let pos = Position::new(col, row);
@@ -232,27 +794,89 @@ impl PyGame {
         }
     }
 
-    pub fn legal_action_indices(&mut self) -> Vec<usize> {
-        dispatch_game!(&mut self.inner, g => {
-            let width = g.width();
-            let height = g.height();
-            g.legal_moves()
-                .into_iter()
-                .filter_map(|m| encode::encode_action(&m, width, height))
-                .collect()
-        })
+    /// With a pass action in `config`, it is included whenever passing is
+    /// allowed (the side to move isn't in check), after the move actions.
+    #[pyo3(signature = (config=None))]
+    pub fn legal_action_indices(
+        &mut self,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<Vec<usize>> {
+        let space = action_space(&self.inner, self.encoder_config(config))?;
+        Ok(dispatch_game!(&mut self.inner, g => space.legal_action_indices(g)))
     }
 
-    pub fn apply_action(&mut self, action: usize) -> bool {
-        dispatch_game!(&mut self.inner, g => g.apply_action(action))
+    pub fn filter_legal_actions(&mut self, actions: Vec<usize>) -> Vec<bool> {
+        dispatch_game!(&mut self.inner, g => g.filter_legal_actions(&actions))
+    }
+
+    pub fn filter_legal(&mut self, moves: Vec<PyMove>) -> Vec<bool> {
+        let moves: Vec<_> = moves.into_iter().map(|m| m.move_).collect();
+        dispatch_game!(&mut self.inner, g => g.filter_legal(&moves))
+    }
+
+    #[pyo3(signature = (action, config=None))]
+    pub fn apply_action(
+        &mut self,
+        action: usize,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<bool> {
+        let space = action_space(&self.inner, self.encoder_config(config))?;
+        Ok(dispatch_game!(&mut self.inner, g => space.apply_action(g, action)))
     }
 
     // ---------------------------------------------------------------------
     // Encoding/decoding
     // ---------------------------------------------------------------------
 
-    pub fn encode_game_planes(&mut self) -> (Vec<f32>, usize, usize, usize) {
-        dispatch_game!(&mut self.inner, g => encode::encode_game_planes(g))
+    #[pyo3(signature = (config=None))]
+    pub fn encode_game_planes(
+        &mut self,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<(Vec<f32>, usize, usize, usize)> {
+        let config = self.encoder_config(config);
+        dispatch_game!(&self.inner, g => config.check_game(g))?;
+        Ok(dispatch_game!(&mut self.inner, g => encode::encode_game_planes_with_config(g, &config)))
+    }
+
+    /// `encode_game_planes` as a float32 numpy array of shape
+    /// `(planes, height, width)`, filled directly from the encoded buffer
+    /// rather than through a list of floats.
+    #[pyo3(signature = (config=None))]
+    pub fn encode_game_planes_array<'py>(
+        &mut self,
+        py: Python<'py>,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let config = self.encoder_config(config);
+        dispatch_game!(&self.inner, g => config.check_game(g))?;
+        let (data, planes, height, width) = dispatch_game!(&mut self.inner, g => encode::encode_game_planes_with_config(g, &config));
+        super::planes_array(py, &data, &[planes, height, width])
+    }
+
+    /// Write `encode_game_planes` into `out`, a writable float32 buffer such
+    /// as a numpy array (or a slice of a larger batch) with
+    /// `planes * height * width` elements.
+    #[pyo3(signature = (out, config=None))]
+    pub fn encode_game_planes_into(
+        &mut self,
+        py: Python<'_>,
+        out: &Bound<'_, PyAny>,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<()> {
+        let config = self.encoder_config(config);
+        dispatch_game!(&self.inner, g => config.check_game(g))?;
+        let (data, ..) = dispatch_game!(&mut self.inner, g => encode::encode_game_planes_with_config(g, &config));
+        super::write_planes(py, out, &data)
+    }
+
+    /// One-hot piece planes of the current position only, as a float32 numpy
+    /// array of shape `(planes, height, width)`: white pawn to king, then
+    /// black, then white and black archbishop and chancellor in games with
+    /// fairy pieces.
+    pub fn board_planes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let (data, planes, height, width) =
+            dispatch_game!(&self.inner, g => encode::encode_board_planes(g));
+        super::planes_array(py, &data, &[planes, height, width])
     }
 
     pub fn action_planes_count(&self) -> usize {
@@ -261,44 +885,57 @@ impl PyGame {
         })
     }
 
-    pub fn decode_action(&self, action: usize) -> Option<PyMove> {
-        dispatch_game!(&self.inner, g => {
-            g.decode_action(action).map(|m| PyMove { move_: m })
-        })
+    /// The action index of `move_` in the current position, which differs from
+    /// `Move.encode` when `config` flips the perspective and black is to move.
+    #[pyo3(signature = (move_, config=None))]
+    pub fn encode_action(&self, move_: PyMove, config: Option<PyEncoderConfig>) -> Option<usize> {
+        let config = self.encoder_config(config);
+        dispatch_game!(&self.inner, g => g.encode_action_with_config(&move_.move_, &config))
     }
 
-    pub fn total_actions(&self) -> usize {
+    #[pyo3(signature = (action, config=None))]
+    pub fn decode_action(&self, action: usize, config: Option<PyEncoderConfig>) -> Option<PyMove> {
+        let config = self.encoder_config(config);
         dispatch_game!(&self.inner, g => {
-            encode::get_total_actions(g.width(), g.height())
+            g.decode_action_with_config(action, &config).map(|m| PyMove { move_: m })
         })
     }
 
+    #[pyo3(signature = (config=None))]
+    pub fn action_size(&self, config: Option<PyEncoderConfig>) -> PyResult<usize> {
+        Ok(action_space(&self.inner, self.encoder_config(config))?.action_size())
+    }
+
+    /// Alias of `action_size`.
+    #[pyo3(signature = (config=None))]
+    pub fn total_actions(&self, config: Option<PyEncoderConfig>) -> PyResult<usize> {
+        self.action_size(config)
+    }
+
     pub fn board_shape(&self) -> (usize, usize) {
         dispatch_game!(&self.inner, g => (g.height(), g.width()))
     }
 
-    pub fn input_plane_count(&self) -> usize {
-        encode::TOTAL_INPUT_PLANES
+    #[pyo3(signature = (config=None))]
+    pub fn input_plane_count(&self, config: Option<PyEncoderConfig>) -> PyResult<usize> {
+        Ok(action_space(&self.inner, self.encoder_config(config))?.input_plane_count())
     }
 
-    pub fn reward_absolute(&mut self) -> f32 {
-        dispatch_game!(&mut self.inner, g => {
-            g.outcome()
-                .map(|o| o.encode_winner_absolute())
-                .unwrap_or(0.0)
-        })
+    #[pyo3(signature = (config=None))]
+    pub fn reward_absolute(&mut self, config: Option<PyRewardConfig>) -> f32 {
+        let config = config.unwrap_or_default().config;
+        dispatch_game!(&mut self.inner, g => g.reward(Color::White, &config).unwrap_or(0.0))
     }
 
-    pub fn reward_from_perspective(&mut self, perspective: i8) -> f32 {
-        dispatch_game!(&mut self.inner, g => {
-            g.outcome()
-                .map(|o| {
-                    o.encode_winner_from_perspective(
-                        Color::from_int(perspective).expect("Invalid perspective"),
-                    )
-                })
-                .unwrap_or(0.0)
-        })
+    #[pyo3(signature = (perspective, config=None))]
+    pub fn reward_from_perspective(
+        &mut self,
+        perspective: i8,
+        config: Option<PyRewardConfig>,
+    ) -> f32 {
+        let perspective = Color::from_int(perspective).expect("Invalid perspective");
+        let config = config.unwrap_or_default().config;
+        dispatch_game!(&mut self.inner, g => g.reward(perspective, &config).unwrap_or(0.0))
     }
 
     pub fn is_insufficient_material(&self) -> bool {
@@ -313,10 +950,30 @@ impl PyGame {
         dispatch_game!(&self.inner, g => g.en_passant_square().map(|pos| PyPosition { pos }))
     }
 
+    /// "1-0", "0-1", "1/2-1/2", or "*" while the game is going.
+    pub fn pgn_result(&mut self) -> &'static str {
+        dispatch_game!(&mut self.inner, g => g.pgn_result())
+    }
+
     pub fn outcome(&mut self) -> Option<PyGameOutcome> {
         dispatch_game!(&mut self.inner, g => g.outcome().map(|outcome| PyGameOutcome { outcome }))
     }
 
+    /// Play random legal moves until the game ends or `max_plies` more plies
+    /// have been played, on this game and with the GIL released. Returns the
+    /// outcome, or None if the ply limit was reached first.
+    #[pyo3(signature = (rng_seed, max_plies=10_000))]
+    pub fn random_playout(
+        &mut self,
+        py: Python<'_>,
+        rng_seed: u64,
+        max_plies: usize,
+    ) -> Option<PyGameOutcome> {
+        let inner = &mut self.inner;
+        py.detach(|| dispatch_game!(inner, g => g.random_playout(rng_seed, max_plies)))
+            .map(|outcome| PyGameOutcome { outcome })
+    }
+
     pub fn turn_state(&mut self) -> PyTurnState {
         dispatch_game!(&mut self.inner, g => PyTurnState { state: g.turn_state() })
     }
@@ -325,22 +982,76 @@ impl PyGame {
         dispatch_game!(&mut self.inner, g => g.to_fen())
     }
 
-    pub fn clone(&self) -> PyGame {
-        PyGame {
-            inner: self.inner.clone(),
+    pub fn to_uci_position_string(&self) -> String {
+        dispatch_game!(&self.inner, g => g.to_uci_position_string())
+    }
+
+    #[pyo3(name = "to_json_state")]
+    pub fn json_state(&mut self) -> String {
+        dispatch_game!(&mut self.inner, g => g.to_json_state())
+    }
+
+    pub fn stats(&self) -> PyGameStats {
+        PyGameStats {
+            stats: dispatch_game!(&self.inner, g => stats::game_stats(g)),
         }
     }
 
+    /// Returns `(game, color_swapped, mirrored)`.
+    pub fn mirror(&self) -> PyGame {
+        self.with_inner(self.inner.mirror())
+    }
+
+    pub fn canonical_form(&self) -> (PyGame, bool, bool) {
+        let (inner, transform) = self.inner.canonical_form();
+        (self.with_inner(inner), transform.color_swap, transform.mirror)
+    }
+
+    pub fn clone(&self) -> PyGame {
+        self.with_inner(self.inner.clone())
+    }
+
+    /// Pickle as the game's `GameRecord` and history length, so the unpickled
+    /// game keeps its rule options, move history and declared outcome.
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, (PickleState, usize))> {
+        let game = slf.borrow();
+        let state = pickle_state(game.inner.to_record());
+        Ok((
+            slf.get_type().getattr("_from_pickle_state")?,
+            (state, game.history_length),
+        ))
+    }
+
+    #[staticmethod]
+    #[pyo3(signature = (state, history_length=encode::HISTORY_LENGTH))]
+    pub fn _from_pickle_state(state: PickleState, history_length: usize) -> PyResult<PyGame> {
+        let record = record_from_pickle_state(state)?;
+        Ok(PyGame {
+            inner: DynGame::from_record(&record)?,
+            history_length,
+        })
+    }
+
+    pub fn __copy__(&self) -> PyGame {
+        self.clone()
+    }
+
+    pub fn __deepcopy__(&self, _memo: &Bound<'_, PyAny>) -> PyGame {
+        self.clone()
+    }
+
+    /// Structural equality of the current positions: board, turn, castling
+    /// rights, en passant square, clocks and rule options. The move history
+    /// isn't compared. Games of different sizes are never equal.
     pub fn __eq__(&self, other: &PyGame) -> bool {
-        // Width and height are encoded in the enum variant, so different
-        // dimensions are always unequal. For same dimensions, we compare
-        // the position-identity hash (board, turn, castling, en passant,
-        // halfmove clock) — the same fields used by __hash__. This mirrors
-        // how chess engines use Zobrist hashes for position identity.
-        dispatch_game!(&self.inner, g => g.width()) == dispatch_game!(&other.inner, g => g.width())
-            && dispatch_game!(&self.inner, g => g.height())
-                == dispatch_game!(&other.inner, g => g.height())
-            && self.__hash__() == other.__hash__()
+        // Width and height are encoded in the enum variant, so the other game
+        // only downcasts to this one's type if the dimensions match
+        let other: &dyn Any = dispatch_game!(&other.inner, g => g);
+        dispatch_game!(&self.inner, g => {
+            downcast_like(g, other).is_some_and(|o| g.position_state() == o.position_state())
+        })
     }
 
     pub fn __hash__(&self) -> u64 {
@@ -358,6 +1069,19 @@ impl PyGame {
         })
     }
 
+    /// Draw the position as "ascii", "unicode" or "svg", from `perspective`'s
+    /// side of the board.
+    #[pyo3(signature = (mode="ascii", perspective=1))]
+    pub fn render(&self, mode: &str, perspective: i8) -> PyResult<String> {
+        let mode: RenderMode = mode
+            .parse()
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        let perspective = Color::from_int(perspective).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(dispatch_game!(&self.inner, g => render::render(g, mode, perspective)))
+    }
+
     // ---------------------------------------------------------------------
     // Dunder Methods
     // ---------------------------------------------------------------------
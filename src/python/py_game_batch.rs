@@ -0,0 +1,164 @@
+use pyo3::prelude::*;
+
+use super::py_encoder_config::PyEncoderConfig;
+use super::py_game::PyGame;
+use crate::dyn_game::DynGame;
+use crate::encode::{self, EncoderConfig};
+
+/// A batch of games of one board size, encoded together into a single
+/// `(len, planes, height, width)` array so self-play workers can feed a
+/// network without stacking per-game arrays in Python.
+#[pyclass(name = "GameBatch")]
+pub struct PyGameBatch {
+    games: Vec<DynGame>,
+    width: usize,
+    height: usize,
+}
+
+#[hotpath::measure_all]
+impl PyGameBatch {
+    /// Check `config` against every game of the batch and encode them all
+    /// with the GIL released.
+    fn encode_data(
+        &mut self,
+        py: Python<'_>,
+        config: &EncoderConfig,
+        parallel: bool,
+    ) -> PyResult<(Vec<f32>, [usize; 4])> {
+        config.check_board(self.width, self.height)?;
+        for game in &self.games {
+            dispatch_game!(game, g => config.check_game(g))?;
+        }
+        let (height, width) = config.plane_shape(self.width, self.height);
+        let shape = [self.games.len(), config.num_planes(), height, width];
+        let games = &mut self.games;
+        let data = py.detach(|| {
+            let mut data = vec![0.0f32; shape.iter().product()];
+            encode::fill_batch(games, &mut data, parallel, |game, out| {
+                let (planes, ..) = dispatch_game!(game, g => {
+                    encode::encode_game_planes_with_config(g, config)
+                });
+                out.copy_from_slice(&planes);
+            });
+            data
+        });
+        Ok((data, shape))
+    }
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyGameBatch {
+    /// A batch of copies of `games`, which must all have the same board size.
+    #[new]
+    pub fn new(games: Vec<PyRef<'_, PyGame>>) -> PyResult<Self> {
+        let mut sizes = games
+            .iter()
+            .map(|game| dispatch_game!(&game.inner, g => (g.width(), g.height())));
+        let (width, height) = sizes.next().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("a batch needs at least one game")
+        })?;
+        if let Some((w, h)) = sizes.find(|&size| size != (width, height)) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "all games must be {}x{}, got a {}x{} game",
+                width, height, w, h
+            )));
+        }
+        Ok(PyGameBatch {
+            games: games.iter().map(|game| game.inner.clone()).collect(),
+            width,
+            height,
+        })
+    }
+
+    #[getter]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[getter]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.games.len()
+    }
+
+    /// A copy of the game at `index`.
+    pub fn __getitem__(&self, index: isize) -> PyResult<PyGame> {
+        let len = self.games.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&index) {
+            return Err(PyErr::new::<pyo3::exceptions::PyIndexError, _>(
+                "batch index out of range",
+            ));
+        }
+        Ok(PyGame::from(self.games[index as usize].clone()))
+    }
+
+    /// Encode every game as `Game.encode_game_planes` into one float32 numpy
+    /// array of shape `(len, planes, height, width)`. With `parallel`, the
+    /// games are split between the available cores.
+    #[pyo3(signature = (config=None, parallel=true))]
+    pub fn encode<'py>(
+        &mut self,
+        py: Python<'py>,
+        config: Option<PyEncoderConfig>,
+        parallel: bool,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let config = config.unwrap_or_default().config;
+        let (data, shape) = self.encode_data(py, &config, parallel)?;
+        super::planes_array(py, &data, &shape)
+    }
+
+    /// Write `encode` into `out`, a writable float32 buffer with
+    /// `len * planes * height * width` elements.
+    #[pyo3(signature = (out, config=None, parallel=true))]
+    pub fn encode_into(
+        &mut self,
+        py: Python<'_>,
+        out: &Bound<'_, PyAny>,
+        config: Option<PyEncoderConfig>,
+        parallel: bool,
+    ) -> PyResult<()> {
+        let config = config.unwrap_or_default().config;
+        let (data, _) = self.encode_data(py, &config, parallel)?;
+        super::write_planes(py, out, &data)
+    }
+
+    /// Play one action index in each game. Returns, per game, whether its
+    /// action was legal and played.
+    #[pyo3(signature = (actions, config=None))]
+    pub fn apply_actions(
+        &mut self,
+        actions: Vec<usize>,
+        config: Option<PyEncoderConfig>,
+    ) -> PyResult<Vec<bool>> {
+        if actions.len() != self.games.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "expected {} actions, got {}",
+                self.games.len(),
+                actions.len()
+            )));
+        }
+        let config = config.unwrap_or_default().config;
+        Ok(self
+            .games
+            .iter_mut()
+            .zip(actions)
+            .map(|(game, action)| {
+                dispatch_game!(game, g => g.apply_action_with_config(action, &config))
+            })
+            .collect())
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "GameBatch(len={}, width={}, height={})",
+            self.games.len(),
+            self.width,
+            self.height
+        )
+    }
+}
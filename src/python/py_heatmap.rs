@@ -0,0 +1,101 @@
+use pyo3::prelude::*;
+
+use super::py_game::PyGame;
+use super::py_move::PyMove;
+use super::validate_dimensions;
+use crate::color::Color;
+use crate::heatmap::Heatmap;
+
+fn parse_color(color: i8) -> PyResult<Color> {
+    Color::from_int(color).ok_or_else(|| {
+        PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+    })
+}
+
+#[pyclass(name = "Heatmap", skip_from_py_object)]
+#[derive(Clone, Debug)]
+pub struct PyHeatmap {
+    heatmap: Heatmap,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyHeatmap {
+    #[new]
+    pub fn new(width: usize, height: usize) -> PyResult<Self> {
+        validate_dimensions(width, height)?;
+        Ok(PyHeatmap {
+            heatmap: Heatmap::new(width, height),
+        })
+    }
+
+    #[getter]
+    pub fn width(&self) -> usize {
+        self.heatmap.width()
+    }
+
+    #[getter]
+    pub fn height(&self) -> usize {
+        self.heatmap.height()
+    }
+
+    #[getter]
+    pub fn games(&self) -> u64 {
+        self.heatmap.games()
+    }
+
+    #[getter]
+    pub fn positions(&self) -> u64 {
+        self.heatmap.positions()
+    }
+
+    pub fn add_game(&mut self, game: &PyGame) -> PyResult<()> {
+        dispatch_game!(&game.inner, g => self.heatmap.add_game(g))
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    pub fn add_position(&mut self, game: &PyGame) -> PyResult<()> {
+        dispatch_game!(&game.inner, g => self.heatmap.add_position(g))
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    pub fn add_move(&mut self, mv: &PyMove) -> PyResult<()> {
+        self.heatmap
+            .add_move(&mv.move_)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    pub fn merge(&mut self, other: &PyHeatmap) -> PyResult<()> {
+        self.heatmap
+            .merge(&other.heatmap)
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    pub fn occupancy(&self, color: i8) -> PyResult<Vec<u64>> {
+        Ok(self.heatmap.occupancy(parse_color(color)?).to_vec())
+    }
+
+    pub fn destinations(&self) -> Vec<u64> {
+        self.heatmap.destinations().to_vec()
+    }
+
+    pub fn attacks(&self, color: i8) -> PyResult<Vec<u64>> {
+        Ok(self.heatmap.attacks(parse_color(color)?).to_vec())
+    }
+
+    /// Returns `(data, planes, height, width)`; reshape `data` to
+    /// `(planes, height, width)` for a numpy array.
+    pub fn to_planes(&self) -> (Vec<f32>, usize, usize, usize) {
+        self.heatmap.to_planes()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Heatmap(width={}, height={}, games={}, positions={})",
+            self.heatmap.width(),
+            self.heatmap.height(),
+            self.heatmap.games(),
+            self.heatmap.positions(),
+        )
+    }
+}
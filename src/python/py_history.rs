@@ -0,0 +1,79 @@
+use pyo3::prelude::*;
+
+use super::py_game::PyGame;
+use super::validate_dimensions;
+use crate::color::Color;
+use crate::encode::HISTORY_LENGTH;
+use crate::history::HistoryStacker;
+
+#[pyclass(name = "HistoryStacker", skip_from_py_object)]
+#[derive(Clone, Debug)]
+pub struct PyHistoryStacker {
+    stacker: HistoryStacker,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyHistoryStacker {
+    #[new]
+    #[pyo3(signature = (width, height, length=HISTORY_LENGTH))]
+    pub fn new(width: usize, height: usize, length: usize) -> PyResult<Self> {
+        validate_dimensions(width, height)?;
+        Ok(PyHistoryStacker {
+            stacker: HistoryStacker::new(width, height, length),
+        })
+    }
+
+    #[getter]
+    pub fn width(&self) -> usize {
+        self.stacker.width()
+    }
+
+    #[getter]
+    pub fn height(&self) -> usize {
+        self.stacker.height()
+    }
+
+    #[getter]
+    pub fn length(&self) -> usize {
+        self.stacker.length()
+    }
+
+    pub fn push_position(&mut self, game: &PyGame) -> PyResult<()> {
+        dispatch_game!(&game.inner, g => self.stacker.push_position(g))
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+    }
+
+    /// Returns `(data, planes, height, width)`; reshape `data` to
+    /// `(planes, height, width)` for a numpy array.
+    pub fn get_stacked(&self, perspective: i8) -> PyResult<(Vec<f32>, usize, usize, usize)> {
+        let perspective = Color::from_int(perspective).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(self.stacker.get_stacked(perspective))
+    }
+
+    pub fn clear(&mut self) {
+        self.stacker.clear();
+    }
+
+    pub fn clone(&self) -> PyHistoryStacker {
+        PyHistoryStacker {
+            stacker: self.stacker.clone(),
+        }
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.stacker.len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "HistoryStacker(width={}, height={}, length={}, positions={})",
+            self.stacker.width(),
+            self.stacker.height(),
+            self.stacker.length(),
+            self.stacker.len(),
+        )
+    }
+}
@@ -3,11 +3,17 @@ use pyo3::types::PyType;
 
 use crate::encode;
 use crate::r#move::{Move, MoveFlags};
+use crate::pieces::PieceType;
 use crate::position::Position;
 
+use super::py_encoder_config::PyEncoderConfig;
 use super::py_position::PyPosition;
 
-#[pyclass(name = "Move")]
+/// What a `Move` is pickled as: source column and row, destination column
+/// and row, flag bits and promotion piece letter.
+type PickleState = (u8, u8, u8, u8, u8, Option<char>);
+
+#[pyclass(name = "Move", module = "spooky_chess")]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PyMove {
     pub(super) move_: Move,
@@ -27,6 +33,52 @@ impl PyMove {
         }
     }
 
+    #[staticmethod]
+    pub fn null() -> Self {
+        PyMove {
+            move_: Move::null(),
+        }
+    }
+
+    /// Pickle with the flags, which the LAN alone doesn't carry.
+    pub fn __reduce__<'py>(
+        slf: &Bound<'py, Self>,
+    ) -> PyResult<(Bound<'py, PyAny>, (PickleState,))> {
+        let mv = slf.borrow().move_;
+        let state = (
+            mv.src.col,
+            mv.src.row,
+            mv.dst.col,
+            mv.dst.row,
+            mv.flags.bits(),
+            mv.promotion.map(PieceType::to_char),
+        );
+        Ok((slf.get_type().getattr("_from_pickle_state")?, (state,)))
+    }
+
+    #[staticmethod]
+    pub fn _from_pickle_state(state: PickleState) -> PyResult<Self> {
+        let (src_col, src_row, dst_col, dst_row, flags, promotion) = state;
+        let promotion = promotion
+            .map(|c| {
+                PieceType::from_char(c).ok_or_else(|| {
+                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "invalid promotion piece: {}",
+                        c
+                    ))
+                })
+            })
+            .transpose()?;
+        Ok(PyMove {
+            move_: Move {
+                src: Position::new(src_col, src_row),
+                dst: Position::new(dst_col, dst_row),
+                flags: MoveFlags::from_bits_truncate(flags),
+                promotion,
+            },
+        })
+    }
+
     #[classmethod]
     pub fn from_lan(
         _cls: &Bound<'_, PyType>,
@@ -34,10 +86,9 @@ impl PyMove {
         board_width: usize,
         board_height: usize,
     ) -> PyResult<Self> {
-        match Move::from_lan(lan, board_width, board_height) {
-            Ok(move_) => Ok(PyMove { move_ }),
-            Err(e) => Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(e)),
-        }
+        Ok(PyMove {
+            move_: Move::from_lan(lan, board_width, board_height)?,
+        })
     }
 
     #[getter]
@@ -112,12 +163,24 @@ impl PyMove {
         self.move_.flags.contains(MoveFlags::DOUBLE_PUSH)
     }
 
+    #[getter]
+    pub fn is_null(&self) -> bool {
+        self.move_.is_null()
+    }
+
     // ---------------------------------------------------------------------
     // Encoding/decoding
     // ---------------------------------------------------------------------
 
-    pub fn encode(&self, width: usize, height: usize) -> Option<usize> {
-        encode::encode_action(&self.move_, width, height)
+    #[pyo3(signature = (width, height, config=None))]
+    pub fn encode(
+        &self,
+        width: usize,
+        height: usize,
+        config: Option<PyEncoderConfig>,
+    ) -> Option<usize> {
+        let config = config.unwrap_or_default().config;
+        encode::encode_action_with_config(&self.move_, width, height, &config)
     }
 
     // ---------------------------------------------------------------------
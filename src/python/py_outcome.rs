@@ -1,7 +1,8 @@
 use pyo3::prelude::*;
 
+use super::py_reward_config::PyRewardConfig;
 use crate::color::Color;
-use crate::outcome::GameOutcome;
+use crate::outcome::{GameOutcome, Termination};
 
 #[pyclass(name = "GameOutcome")]
 #[derive(Clone, Copy, Debug)]
@@ -26,28 +27,59 @@ impl PyGameOutcome {
         )
     }
 
+    #[pyo3(signature = (perspective, plies=0, config=None))]
+    pub fn reward(
+        &self,
+        perspective: i8,
+        plies: usize,
+        config: Option<PyRewardConfig>,
+    ) -> PyResult<f32> {
+        let perspective = Color::from_int(perspective).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        let config = config.unwrap_or_default().config;
+        Ok(self.outcome.reward(perspective, plies, &config))
+    }
+
     pub fn is_draw(&self) -> bool {
         self.outcome.is_draw()
     }
 
+    pub fn termination(&self) -> String {
+        self.outcome.termination.to_string()
+    }
+
     pub fn is_checkmate(&self) -> bool {
-        matches!(self.outcome, GameOutcome::WhiteWin | GameOutcome::BlackWin)
+        self.outcome.termination == Termination::Checkmate
     }
 
     pub fn is_stalemate(&self) -> bool {
-        self.outcome == GameOutcome::Stalemate
+        self.outcome.termination == Termination::Stalemate
     }
 
     pub fn is_insufficient_material(&self) -> bool {
-        self.outcome == GameOutcome::InsufficientMaterial
+        self.outcome.termination == Termination::InsufficientMaterial
     }
 
     pub fn is_threefold_repetition(&self) -> bool {
-        self.outcome == GameOutcome::ThreefoldRepetition
+        self.outcome.termination == Termination::ThreefoldRepetition
     }
 
     pub fn is_fifty_move_rule(&self) -> bool {
-        self.outcome == GameOutcome::FiftyMoveRule
+        self.outcome.termination == Termination::FiftyMoveRule
+    }
+
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.outcome.termination == Termination::FivefoldRepetition
+    }
+
+    pub fn is_seventy_five_move_rule(&self) -> bool {
+        self.outcome.termination == Termination::SeventyFiveMoveRule
+    }
+
+    #[pyo3(name = "to_pgn_result")]
+    pub fn pgn_result(&self) -> &'static str {
+        self.outcome.to_pgn_result()
     }
 
     pub fn reason(&self) -> String {
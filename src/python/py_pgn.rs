@@ -1,8 +1,12 @@
+use std::fs::File;
+use std::io::BufReader;
+
 use pyo3::prelude::*;
 
-use super::dispatch::GameInner;
 use super::py_game::PyGame;
 use super::py_move::PyMove;
+use crate::dyn_game::DynGame;
+use crate::pgn::PgnReader;
 
 #[pyclass(name = "PgnGame")]
 pub struct PyPgnGame {
@@ -43,6 +47,20 @@ impl PyPgnGame {
         self.inner.result.to_string()
     }
 
+    /// `(nags, comments)` of each move, in the same order as `moves`.
+    pub fn annotations(&self) -> Vec<(Vec<u8>, Vec<String>)> {
+        self.inner
+            .annotations
+            .iter()
+            .map(|a| (a.nags.clone(), a.comments.clone()))
+            .collect()
+    }
+
+    /// Comments before the first move.
+    pub fn comments(&self) -> Vec<String> {
+        self.inner.comments.clone()
+    }
+
     pub fn moves(&self) -> Vec<PyMove> {
         self.inner
             .moves
@@ -60,15 +78,11 @@ impl PyPgnGame {
             .inner
             .starting_game()
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-        Ok(PyGame {
-            inner: GameInner::W8H8(game),
-        })
+        Ok(PyGame::from(DynGame::W8H8(game)))
     }
 
     pub fn game(&self) -> PyGame {
-        PyGame {
-            inner: GameInner::W8H8(self.inner.final_game.clone()),
-        }
+        PyGame::from(DynGame::W8H8(self.inner.final_game.clone()))
     }
 
     pub fn __str__(&self) -> String {
@@ -92,3 +106,33 @@ pub fn py_parse_pgn(pgn: &str) -> PyResult<Vec<PyPgnGame>> {
         .map(|games| games.into_iter().map(|g| PyPgnGame { inner: g }).collect())
         .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
 }
+
+/// Streams the games of a PGN file one at a time.
+#[pyclass(name = "PgnReader", unsendable)]
+pub struct PyPgnReader {
+    inner: PgnReader<BufReader<File>>,
+}
+
+#[pymethods]
+impl PyPgnReader {
+    #[new]
+    pub fn new(path: &str) -> PyResult<Self> {
+        let inner = crate::pgn::read_pgn_file(path)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+        Ok(PyPgnReader { inner })
+    }
+
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// The next game, raising ValueError if it fails to parse. Iteration can
+    /// continue with the game after it.
+    pub fn __next__(&mut self) -> PyResult<Option<PyPgnGame>> {
+        self.inner
+            .next()
+            .transpose()
+            .map(|game| game.map(|g| PyPgnGame { inner: g }))
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+}
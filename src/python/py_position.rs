@@ -21,9 +21,9 @@ impl PyPosition {
 
     #[classmethod]
     pub fn from_algebraic(_cls: &Bound<'_, PyType>, s: &str) -> PyResult<Self> {
-        Position::from_algebraic(s)
-            .map(|pos| PyPosition { pos })
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e))
+        Ok(PyPosition {
+            pos: Position::from_algebraic(s)?,
+        })
     }
 
     pub fn to_algebraic(&self) -> String {
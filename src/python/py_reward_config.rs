@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+use crate::outcome::{RewardConfig, Termination};
+
+#[pyclass(name = "RewardConfig", from_py_object)]
+#[derive(Clone, Debug, Default)]
+pub struct PyRewardConfig {
+    pub(super) config: RewardConfig,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyRewardConfig {
+    /// `termination_overrides` maps termination names (as returned by
+    /// `GameOutcome.termination()`) to rewards.
+    #[new]
+    #[pyo3(signature = (draw_score=0.0, termination_overrides=None, length_penalty=0.0))]
+    pub fn new(
+        draw_score: f32,
+        termination_overrides: Option<HashMap<String, f32>>,
+        length_penalty: f32,
+    ) -> PyResult<Self> {
+        let termination_overrides = termination_overrides
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, reward)| Ok((name.parse::<Termination>()?, reward)))
+            .collect::<Result<_, String>>()
+            .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        Ok(PyRewardConfig {
+            config: RewardConfig {
+                draw_score,
+                termination_overrides,
+                length_penalty,
+            },
+        })
+    }
+
+    #[getter]
+    pub fn draw_score(&self) -> f32 {
+        self.config.draw_score
+    }
+
+    #[getter]
+    pub fn termination_overrides(&self) -> HashMap<String, f32> {
+        self.config
+            .termination_overrides
+            .iter()
+            .map(|(termination, &reward)| (termination.to_string(), reward))
+            .collect()
+    }
+
+    #[getter]
+    pub fn length_penalty(&self) -> f32 {
+        self.config.length_penalty
+    }
+
+    pub fn __repr__(&self) -> String {
+        let mut overrides: Vec<String> = self
+            .config
+            .termination_overrides
+            .iter()
+            .map(|(termination, reward)| format!("'{}': {:?}", termination, reward))
+            .collect();
+        overrides.sort();
+        format!(
+            "RewardConfig(draw_score={:?}, termination_overrides={{{}}}, length_penalty={:?})",
+            self.config.draw_score,
+            overrides.join(", "),
+            self.config.length_penalty
+        )
+    }
+
+    pub fn __eq__(&self, other: &PyRewardConfig) -> bool {
+        self.config == other.config
+    }
+}
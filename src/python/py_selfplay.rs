@@ -0,0 +1,116 @@
+use pyo3::prelude::*;
+
+use super::py_encoder_config::PyEncoderConfig;
+use super::py_game::PyGame;
+use super::py_move::PyMove;
+use super::py_outcome::PyGameOutcome;
+use crate::dyn_game::DynGame;
+use crate::game::Game;
+use crate::self_play::{self, SelfPlayConfig, SelfPlayGame};
+
+/// Samples of a game: `(planes, fens, policy, value)`, with `planes` a float32
+/// numpy array of shape `(samples, planes, height, width)`.
+type PySamples<'py> = (Bound<'py, PyAny>, Vec<String>, Vec<usize>, Vec<f32>);
+
+type PySelfPlayGame<'py> = (Vec<PyMove>, Option<PyGameOutcome>, Option<PySamples<'py>>);
+
+fn play<const W: usize, const H: usize>(
+    py: Python<'_>,
+    start: &Game<W, H>,
+    config: &SelfPlayConfig,
+    policy: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Vec<SelfPlayGame>>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    config.encoder.check_game(start)?;
+    let Some(policy) = policy else {
+        return Ok(py.detach(|| self_play::self_play(start, config, self_play::uniform_random)));
+    };
+
+    // Keep the policy's own exception to raise it unchanged
+    let mut policy_err = None;
+    let games = self_play::self_play_batched(start, config, |planes, shape, legal| {
+        let result = super::planes_array(py, planes, &shape)
+            .and_then(|planes| policy.call1((planes, legal.to_vec())))
+            .and_then(|actions| actions.extract::<Vec<usize>>());
+        result.map_err(|e| {
+            policy_err = Some(e);
+            "policy failed".to_string()
+        })
+    });
+    match (games, policy_err) {
+        (_, Some(e)) => Err(e),
+        (games, None) => games.map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>),
+    }
+}
+
+/// Play `num_games` games from `game` (the standard position by default),
+/// stopping unfinished games after `max_plies` plies.
+///
+/// Without a `policy`, moves are uniformly random and the games are played
+/// with the GIL released, spread over the available cores if `parallel`.
+/// Otherwise the games are played in lockstep, and every ply
+/// `policy(planes, legal_actions)` is called with the planes of each
+/// unfinished game as one `(games, planes, height, width)` numpy array and
+/// each game's legal action indices, and returns the action to play in each.
+///
+/// Returns `(moves, outcome, samples)` per game; the outcome is None for a
+/// stopped game. With `record_samples`, samples are `(planes, fens, policy,
+/// value)` with one entry per position played: the action played as the
+/// policy target and the result for the side to move as the value.
+#[pyfunction(name = "self_play")]
+#[pyo3(signature = (num_games, policy=None, game=None, max_plies=512, seed=0, encoder_config=None, record_samples=false, parallel=true))]
+#[allow(clippy::too_many_arguments)]
+pub fn py_self_play<'py>(
+    py: Python<'py>,
+    num_games: usize,
+    policy: Option<Bound<'py, PyAny>>,
+    game: Option<PyRef<'_, PyGame>>,
+    max_plies: usize,
+    seed: u64,
+    encoder_config: Option<PyEncoderConfig>,
+    record_samples: bool,
+    parallel: bool,
+) -> PyResult<Vec<PySelfPlayGame<'py>>> {
+    let (start, encoder) = match game {
+        Some(game) => (game.inner.clone(), game.encoder_config(encoder_config)),
+        None => (DynGame::standard(), encoder_config.unwrap_or_default().config),
+    };
+    let config = SelfPlayConfig {
+        num_games,
+        max_plies,
+        seed,
+        record_samples,
+        encoder,
+        parallel,
+    };
+    let games = dispatch_game!(&start, g => play(py, g, &config, policy.as_ref()))?;
+
+    games
+        .into_iter()
+        .map(|game| {
+            let samples = match game.samples {
+                Some(samples) => {
+                    let shape = [
+                        samples.len(),
+                        samples.num_planes,
+                        samples.height,
+                        samples.width,
+                    ];
+                    let planes = super::planes_array(py, &samples.planes, &shape)?;
+                    Some((planes, samples.fens, samples.policy, samples.value))
+                }
+                None => None,
+            };
+            Ok((
+                game.moves
+                    .into_iter()
+                    .map(|move_| PyMove { move_ })
+                    .collect(),
+                game.outcome.map(|outcome| PyGameOutcome { outcome }),
+                samples,
+            ))
+        })
+        .collect()
+}
@@ -0,0 +1,118 @@
+use pyo3::prelude::*;
+
+use crate::color::Color;
+use crate::game::MoveGenStats;
+use crate::pieces::PieceType;
+use crate::stats::{GameStats, SideStats};
+
+#[pyclass(name = "GameStats", skip_from_py_object)]
+#[derive(Clone, Debug)]
+pub struct PyGameStats {
+    pub(super) stats: GameStats,
+}
+
+impl PyGameStats {
+    fn side(&self, color: i8) -> PyResult<&SideStats> {
+        let color = Color::from_int(color).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>("color must be 1 (white) or -1 (black)")
+        })?;
+        Ok(self.stats.side(color))
+    }
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyGameStats {
+    #[getter]
+    pub fn length(&self) -> usize {
+        self.stats.length
+    }
+
+    #[getter]
+    pub fn material(&self) -> Vec<i32> {
+        self.stats.material.clone()
+    }
+
+    pub fn moves(&self, color: i8) -> PyResult<u32> {
+        Ok(self.side(color)?.moves)
+    }
+
+    pub fn captures(&self, color: i8) -> PyResult<u32> {
+        Ok(self.side(color)?.captures)
+    }
+
+    pub fn checks(&self, color: i8) -> PyResult<u32> {
+        Ok(self.side(color)?.checks)
+    }
+
+    pub fn average_mobility(&self, color: i8) -> PyResult<f64> {
+        Ok(self.side(color)?.average_mobility)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "GameStats(length={}, captures=({}, {}), checks=({}, {}))",
+            self.stats.length,
+            self.stats.white.captures,
+            self.stats.black.captures,
+            self.stats.white.checks,
+            self.stats.black.checks,
+        )
+    }
+}
+
+#[pyclass(name = "MoveGenStats", skip_from_py_object)]
+#[derive(Clone, Debug)]
+pub struct PyMoveGenStats {
+    pub(super) stats: MoveGenStats,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyMoveGenStats {
+    #[getter]
+    pub fn total(&self) -> u32 {
+        self.stats.total
+    }
+
+    #[getter]
+    pub fn captures(&self) -> u32 {
+        self.stats.captures
+    }
+
+    #[getter]
+    pub fn checks(&self) -> u32 {
+        self.stats.checks
+    }
+
+    #[getter]
+    pub fn promotions(&self) -> u32 {
+        self.stats.promotions
+    }
+
+    #[getter]
+    pub fn castles(&self) -> u32 {
+        self.stats.castles
+    }
+
+    /// Moves made by pieces of `piece_type` ("p", "n", "b", "r", "q" or "k").
+    pub fn piece_type(&self, piece_type: &str) -> PyResult<u32> {
+        let pt = piece_type
+            .chars()
+            .next()
+            .and_then(PieceType::from_char)
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Invalid piece type"))?;
+        Ok(self.stats.piece_type(pt))
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "MoveGenStats(total={}, captures={}, checks={}, promotions={}, castles={})",
+            self.stats.total,
+            self.stats.captures,
+            self.stats.checks,
+            self.stats.promotions,
+            self.stats.castles,
+        )
+    }
+}
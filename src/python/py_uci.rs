@@ -1,5 +1,6 @@
 use pyo3::prelude::*;
 
+use crate::dataset;
 use crate::uci::{SearchResult, UciEngine, UciError};
 
 use super::py_move::PyMove;
@@ -8,6 +9,7 @@ use super::py_pgn::PyPgnGame;
 use super::py_piece::PyPiece;
 use super::py_position::PyPosition;
 use super::py_turn_state::PyTurnState;
+use super::py_wdl::PyWdlModel;
 use crate::color::Color;
 use crate::position::Position;
 
@@ -338,7 +340,7 @@ impl PyUciEngine {
         self.engine()?
             .move_from_lan(lan)
             .map(|move_| PyMove { move_ })
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e))
+            .map_err(PyErr::from)
     }
 
     fn move_to_san(&mut self, mv: PyMove) -> PyResult<String> {
@@ -349,7 +351,7 @@ impl PyUciEngine {
         self.engine_mut()?
             .move_from_san(san)
             .map(|move_| PyMove { move_ })
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e))
+            .map_err(PyErr::from)
     }
 
     fn width(&self) -> PyResult<usize> {
@@ -428,3 +430,28 @@ impl PyUciEngine {
         false
     }
 }
+
+/// Label FEN positions with the best move and evaluation of the UCI engine at
+/// `engine_path`. Returns `((planes, num_planes, height, width), policy,
+/// value)`, with one policy and value entry per labelled position; finished
+/// positions are skipped. Scores become values with `model` (by default a
+/// `WdlModel()`).
+#[pyfunction(name = "label_with_engine")]
+#[pyo3(signature = (engine_path, fens, depth, model=None))]
+#[allow(clippy::type_complexity)]
+pub fn py_label_with_engine(
+    engine_path: &str,
+    fens: Vec<String>,
+    depth: u32,
+    model: Option<PyWdlModel>,
+) -> PyResult<((Vec<f32>, usize, usize, usize), Vec<usize>, Vec<f32>)> {
+    let fens: Vec<&str> = fens.iter().map(String::as_str).collect();
+    let model = model.unwrap_or_default().model;
+    let shard = dataset::label_with_engine_with_model(engine_path, &fens, depth, &model)
+        .map_err(uci_err_to_py)?;
+    Ok((
+        (shard.planes, shard.num_planes, shard.height, shard.width),
+        shard.policy,
+        shard.value,
+    ))
+}
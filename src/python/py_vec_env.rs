@@ -0,0 +1,263 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use super::py_encoder_config::PyEncoderConfig;
+use super::py_game::{PyGame, action_space};
+use super::py_reward_config::PyRewardConfig;
+use crate::action_space::ActionSpace;
+use crate::dyn_game::DynGame;
+use crate::encode::{self, EncoderConfig};
+use crate::outcome::RewardConfig;
+
+/// One environment of a `VecEnv`, with the result of its last step.
+#[derive(Clone)]
+struct EnvSlot {
+    game: DynGame,
+    reward: f32,
+    done: bool,
+}
+
+/// `num_envs` `ChessEnv`s stepped together. Observations, rewards and done
+/// flags come back stacked as numpy arrays, and the environments are stepped
+/// and encoded with the GIL released, spread over the available cores if
+/// `parallel`.
+///
+/// An environment whose episode ends is reset straight away, so the
+/// observation returned for it is the first of its next episode.
+#[pyclass(name = "VecEnv")]
+pub struct PyVecEnv {
+    initial: DynGame,
+    envs: Vec<EnvSlot>,
+    action_space: ActionSpace,
+    reward_config: RewardConfig,
+    max_plies: Option<usize>,
+    parallel: bool,
+}
+
+/// Play `action` in `slot`, resetting it to `initial` if the episode ends,
+/// then encode its observation into `out`.
+fn step_slot(
+    slot: &mut EnvSlot,
+    action: usize,
+    initial: &DynGame,
+    config: &EncoderConfig,
+    reward_config: &RewardConfig,
+    max_plies: Option<usize>,
+    out: &mut [f32],
+) {
+    let (reward, over, plies) = dispatch_game!(&mut slot.game, g => {
+        let mover = g.turn();
+        g.apply_action_with_config(action, config);
+        let reward = g.reward(mover, reward_config);
+        (reward.unwrap_or(0.0), reward.is_some(), g.move_count())
+    });
+    slot.reward = reward;
+    slot.done = over || max_plies.is_some_and(|max| plies >= max);
+    if slot.done {
+        slot.game = initial.clone();
+    }
+    encode_slot(slot, config, out);
+}
+
+fn encode_slot(slot: &mut EnvSlot, config: &EncoderConfig, out: &mut [f32]) {
+    let (data, ..) = dispatch_game!(&mut slot.game, g => {
+        encode::encode_game_planes_with_config(g, config)
+    });
+    out.copy_from_slice(&data);
+}
+
+#[hotpath::measure_all]
+impl PyVecEnv {
+    fn observation_shape_array(&self) -> [usize; 4] {
+        let (rows, cols) = self.action_space.plane_shape();
+        [
+            self.envs.len(),
+            self.action_space.input_plane_count(),
+            rows,
+            cols,
+        ]
+    }
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyVecEnv {
+    /// `num_envs` environments starting from `game` (the standard position by
+    /// default); see `ChessEnv`.
+    #[new]
+    #[pyo3(signature = (num_envs, game=None, encoder_config=None, reward_config=None, max_plies=None, parallel=true))]
+    pub fn new(
+        num_envs: usize,
+        game: Option<PyRef<'_, PyGame>>,
+        encoder_config: Option<PyEncoderConfig>,
+        reward_config: Option<PyRewardConfig>,
+        max_plies: Option<usize>,
+        parallel: bool,
+    ) -> PyResult<Self> {
+        if num_envs == 0 {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "num_envs must be at least 1",
+            ));
+        }
+        let (initial, encoder_config) = match game {
+            Some(game) => (game.inner.clone(), game.encoder_config(encoder_config)),
+            None => (DynGame::standard(), encoder_config.unwrap_or_default().config),
+        };
+        let action_space = action_space(&initial, encoder_config)?;
+        let slot = EnvSlot {
+            game: initial.clone(),
+            reward: 0.0,
+            done: false,
+        };
+        Ok(PyVecEnv {
+            initial,
+            envs: vec![slot; num_envs],
+            action_space,
+            reward_config: reward_config.unwrap_or_default().config,
+            max_plies,
+            parallel,
+        })
+    }
+
+    #[getter]
+    pub fn num_envs(&self) -> usize {
+        self.envs.len()
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.envs.len()
+    }
+
+    /// Reset every environment and return the stacked observations, of shape
+    /// `(num_envs, planes, height, width)`.
+    pub fn reset_all<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let shape = self.observation_shape_array();
+        let config = *self.action_space.config();
+        let (initial, envs, parallel) = (&self.initial, &mut self.envs, self.parallel);
+        let observations = py.detach(|| {
+            let mut observations = vec![0.0f32; shape.iter().product()];
+            encode::fill_batch(envs, &mut observations, parallel, |slot, out| {
+                *slot = EnvSlot {
+                    game: initial.clone(),
+                    reward: 0.0,
+                    done: false,
+                };
+                encode_slot(slot, &config, out);
+            });
+            observations
+        });
+        super::planes_array(py, &observations, &shape)
+    }
+
+    /// Play one action index in each environment. Returns `(observations,
+    /// rewards, dones)`: a float32 array of shape `(num_envs, planes, height,
+    /// width)`, and float32 and bool arrays of shape `(num_envs,)`. Each
+    /// reward is for the side that moved. Raises ValueError, stepping nothing,
+    /// if any action is illegal.
+    #[allow(clippy::type_complexity)]
+    pub fn step_batch<'py>(
+        &mut self,
+        py: Python<'py>,
+        actions: Vec<usize>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        if actions.len() != self.envs.len() {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                "expected {} actions, got {}",
+                self.envs.len(),
+                actions.len()
+            )));
+        }
+        let shape = self.observation_shape_array();
+        let config = *self.action_space.config();
+        let (initial, reward_config, max_plies, parallel) = (
+            &self.initial,
+            &self.reward_config,
+            self.max_plies,
+            self.parallel,
+        );
+        let mut steps: Vec<(&mut EnvSlot, usize)> = self.envs.iter_mut().zip(actions).collect();
+
+        let observations = py.detach(|| {
+            let illegal = steps.iter_mut().position(|(slot, action)| {
+                !dispatch_game!(&mut slot.game, g => g.is_legal_action_with_config(*action, &config))
+            });
+            if let Some(index) = illegal {
+                return Err(format!(
+                    "illegal action {} in environment {}",
+                    steps[index].1, index
+                ));
+            }
+            let mut observations = vec![0.0f32; shape.iter().product()];
+            encode::fill_batch(&mut steps, &mut observations, parallel, |(slot, action), out| {
+                step_slot(slot, *action, initial, &config, reward_config, max_plies, out);
+            });
+            Ok(observations)
+        })
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+
+        let rewards: Vec<f32> = self.envs.iter().map(|slot| slot.reward).collect();
+        let dones: Vec<bool> = self.envs.iter().map(|slot| slot.done).collect();
+        Ok((
+            super::planes_array(py, &observations, &shape)?,
+            super::planes_array(py, &rewards, &[rewards.len()])?,
+            py.import("numpy")?.call_method1("array", (dones, "bool"))?,
+        ))
+    }
+
+    /// `total_actions` 0/1 entries per environment marking its legal actions.
+    pub fn action_masks(&mut self) -> Vec<Vec<u8>> {
+        let space = self.action_space;
+        self.envs
+            .iter_mut()
+            .map(|slot| {
+                let mut mask = vec![0u8; space.action_size()];
+                for action in dispatch_game!(&mut slot.game, g => space.legal_action_indices(g)) {
+                    mask[action] = 1;
+                }
+                mask
+            })
+            .collect()
+    }
+
+    /// `{"type": "Discrete", "n": total_actions}`, for a single environment.
+    #[getter]
+    pub fn action_space<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let space = PyDict::new(py);
+        space.set_item("type", "Discrete")?;
+        space.set_item("n", self.action_space.action_size())?;
+        Ok(space)
+    }
+
+    /// `{"type": "Box", "shape": (planes, height, width), "dtype": "float32"}`,
+    /// for a single environment.
+    #[getter]
+    pub fn observation_space<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let [_, planes, rows, cols] = self.observation_shape_array();
+        let space = PyDict::new(py);
+        space.set_item("type", "Box")?;
+        space.set_item("shape", (planes, rows, cols))?;
+        space.set_item("dtype", "float32")?;
+        Ok(space)
+    }
+
+    /// A copy of the current game of environment `index`.
+    pub fn game(&self, index: usize) -> PyResult<PyGame> {
+        let slot = self.envs.get(index).ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyIndexError, _>("environment index out of range")
+        })?;
+        Ok(PyGame {
+            inner: slot.game.clone(),
+            history_length: self.action_space.config().history_length,
+        })
+    }
+
+    pub fn __repr__(&self) -> String {
+        let (width, height) = dispatch_game!(&self.initial, g => (g.width(), g.height()));
+        format!(
+            "VecEnv(num_envs={}, width={}, height={})",
+            self.envs.len(),
+            width,
+            height
+        )
+    }
+}
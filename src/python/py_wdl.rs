@@ -0,0 +1,68 @@
+use pyo3::prelude::*;
+
+use crate::wdl::{Wdl, WdlModel};
+
+fn wdl_tuple(wdl: Wdl) -> (f32, f32, f32) {
+    (wdl.win, wdl.draw, wdl.loss)
+}
+
+#[pyclass(name = "WdlModel", from_py_object)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PyWdlModel {
+    pub(super) model: WdlModel,
+}
+
+#[hotpath::measure_all]
+#[pymethods]
+impl PyWdlModel {
+    /// Defaults to the draw-free Elo logistic curve.
+    #[new]
+    #[pyo3(signature = (draw_offset=None, scale=None))]
+    pub fn new(draw_offset: Option<f32>, scale: Option<f32>) -> PyResult<Self> {
+        let default = WdlModel::default();
+        let model = WdlModel::new(
+            draw_offset.unwrap_or(default.draw_offset),
+            scale.unwrap_or(default.scale),
+        )
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+        Ok(PyWdlModel { model })
+    }
+
+    #[getter]
+    pub fn draw_offset(&self) -> f32 {
+        self.model.draw_offset
+    }
+
+    #[getter]
+    pub fn scale(&self) -> f32 {
+        self.model.scale
+    }
+
+    /// `(win, draw, loss)` probabilities for the side with a `cp` centipawn
+    /// score.
+    pub fn wdl(&self, cp: i32) -> (f32, f32, f32) {
+        wdl_tuple(self.model.wdl(cp))
+    }
+
+    /// Expected result for a `cp` centipawn score on the [-1, 1] scale.
+    pub fn value(&self, cp: i32) -> f32 {
+        self.model.value(cp)
+    }
+
+    /// Like `wdl`, for an engine score that may be a mate in `mate` moves.
+    #[pyo3(signature = (cp=None, mate=None))]
+    pub fn score_wdl(&self, cp: Option<i32>, mate: Option<i32>) -> Option<(f32, f32, f32)> {
+        self.model.score_wdl(cp, mate).map(wdl_tuple)
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "WdlModel(draw_offset={:?}, scale={:?})",
+            self.model.draw_offset, self.model.scale
+        )
+    }
+
+    pub fn __eq__(&self, other: &PyWdlModel) -> bool {
+        self.model == other.model
+    }
+}
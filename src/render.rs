@@ -0,0 +1,196 @@
+//! Text and SVG diagrams of a position.
+//!
+//! The text modes follow the layout of the board's `Display` diagram (rank
+//! numbers on the left, file letters underneath); the SVG is a standalone
+//! image with the pieces drawn as Unicode glyphs. All modes can be drawn from
+//! either side's perspective.
+
+use std::fmt::Write;
+use std::str::FromStr;
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::pieces::Piece;
+use crate::position::Position;
+
+/// Size in pixels of one square in SVG diagrams.
+const SVG_SQUARE: usize = 45;
+
+const SVG_LIGHT: &str = "#f0d9b5";
+const SVG_DARK: &str = "#b58863";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RenderMode {
+    /// FEN letters, `.` for empty squares.
+    Ascii,
+    /// Chess symbols such as ♘, `·` for empty squares.
+    Unicode,
+    /// A standalone SVG image.
+    Svg,
+}
+
+#[hotpath::measure_all]
+impl FromStr for RenderMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "ascii" => Ok(RenderMode::Ascii),
+            "unicode" => Ok(RenderMode::Unicode),
+            "svg" => Ok(RenderMode::Svg),
+            _ => Err(format!(
+                "Unknown render mode '{}', expected 'ascii', 'unicode' or 'svg'",
+                s
+            )),
+        }
+    }
+}
+
+/// Label for file `col`: a letter for the first 26 files, the index after that.
+fn file_label(col: usize) -> String {
+    if col < 26 {
+        ((b'a' + col as u8) as char).to_string()
+    } else {
+        col.to_string()
+    }
+}
+
+/// Rows from top to bottom and columns from left to right, as seen by
+/// `perspective`.
+fn screen_order(width: usize, height: usize, perspective: Color) -> (Vec<usize>, Vec<usize>) {
+    match perspective {
+        Color::White => ((0..height).rev().collect(), (0..width).collect()),
+        Color::Black => ((0..height).collect(), (0..width).rev().collect()),
+    }
+}
+
+/// Draw the current position of `game` as seen by `perspective`.
+#[hotpath::measure]
+pub fn render<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    mode: RenderMode,
+    perspective: Color,
+) -> String
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    match mode {
+        RenderMode::Ascii => render_text(game, perspective, |piece| match piece {
+            Some(piece) => piece.to_char(),
+            None => '.',
+        }),
+        RenderMode::Unicode => render_text(game, perspective, |piece| match piece {
+            Some(piece) => piece.to_unicode(),
+            None => '·',
+        }),
+        RenderMode::Svg => render_svg(game, perspective),
+    }
+}
+
+fn render_text<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    perspective: Color,
+    symbol: impl Fn(Option<Piece>) -> char,
+) -> String
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let (rows, cols) = screen_order(W, H, perspective);
+    let mut out = String::new();
+    for &row in &rows {
+        let _ = write!(out, "{:2} ", row + 1);
+        for &col in &cols {
+            let piece = game.get_piece(&Position::from_usize(col, row));
+            let _ = write!(out, "{} ", symbol(piece));
+        }
+        out.push('\n');
+    }
+    out.push_str("   ");
+    for &col in &cols {
+        let _ = write!(out, "{} ", file_label(col));
+    }
+    out
+}
+
+fn render_svg<const W: usize, const H: usize>(game: &Game<W, H>, perspective: Color) -> String
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let (rows, cols) = screen_order(W, H, perspective);
+    let (width, height) = (W * SVG_SQUARE, H * SVG_SQUARE);
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\">",
+        width, height
+    );
+    for (y, &row) in rows.iter().enumerate() {
+        for (x, &col) in cols.iter().enumerate() {
+            // a1 is a dark square
+            let fill = if (row + col) % 2 == 0 {
+                SVG_DARK
+            } else {
+                SVG_LIGHT
+            };
+            let (px, py) = (x * SVG_SQUARE, y * SVG_SQUARE);
+            let _ = writeln!(
+                out,
+                "<rect x=\"{}\" y=\"{}\" width=\"{2}\" height=\"{2}\" fill=\"{3}\"/>",
+                px, py, SVG_SQUARE, fill
+            );
+            if let Some(piece) = game.get_piece(&Position::from_usize(col, row)) {
+                let _ = writeln!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>",
+                    px + SVG_SQUARE / 2,
+                    py + SVG_SQUARE / 2,
+                    SVG_SQUARE * 4 / 5,
+                    piece.to_unicode()
+                );
+            }
+        }
+    }
+    out.push_str("</svg>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    #[test]
+    fn test_render_text_modes() {
+        let game = StandardGame::new("4k3/8/8/8/8/8/4P3/4K2R w K - 0 1", true).expect("valid FEN");
+
+        let ascii = render(&game, RenderMode::Ascii, Color::White);
+        let lines: Vec<&str> = ascii.lines().collect();
+        assert_eq!(lines[0], " 8 . . . . k . . . ");
+        assert_eq!(lines[7], " 1 . . . . K . . R ");
+        assert_eq!(lines[8], "   a b c d e f g h ");
+
+        // From black's side the board is turned around
+        let flipped = render(&game, RenderMode::Ascii, Color::Black);
+        let lines: Vec<&str> = flipped.lines().collect();
+        assert_eq!(lines[0], " 1 R . . K . . . . ");
+        assert_eq!(lines[1], " 2 . . . P . . . . ");
+        assert_eq!(lines[8], "   h g f e d c b a ");
+
+        let unicode = render(&game, RenderMode::Unicode, Color::White);
+        assert_eq!(unicode.lines().next(), Some(" 8 · · · · ♚ · · · "));
+        assert_eq!("unicode".parse::<RenderMode>(), Ok(RenderMode::Unicode));
+        assert!("png".parse::<RenderMode>().is_err());
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let game = StandardGame::standard();
+        let svg = render(&game, RenderMode::Svg, Color::White);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect ").count(), 64);
+        assert_eq!(svg.matches("<text ").count(), 32);
+        // a8 (black rook) is drawn in the top-left square
+        assert!(svg.contains("<text x=\"22\" y=\"22\" font-size=\"36\" text-anchor=\"middle\" dominant-baseline=\"central\">♜</text>"));
+    }
+}
@@ -0,0 +1,577 @@
+//! A small alpha-beta engine: iterative deepening over a negamax alpha-beta
+//! search, with a quiescence search of captures and promotions at the leaves
+//! and a material plus piece-square evaluation.
+//!
+//! It is weak, but plays sound chess on any board size, which is enough to
+//! adjudicate self-play games, sanity-check move generation and give GUIs
+//! something to play against (`AlphaBeta` serves it over UCI).
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::r#move::{Move, MoveFlags};
+use crate::outcome::MoveList;
+use crate::pieces::PieceType;
+use crate::position::Position;
+use crate::uci::{GoLimits, InfoLine, SearchBackend};
+
+/// Score of being checkmated now. Mate in `n` plies scores `MATE_SCORE - n`
+/// for the side that mates.
+pub const MATE_SCORE: i32 = 1_000_000;
+
+/// Deepest ply the search reaches, quiescence included.
+pub const MAX_PLY: usize = 128;
+
+const INFINITY: i32 = MATE_SCORE + 1;
+
+/// How often, in nodes, the clock and stop flag are checked.
+const CHECK_INTERVAL: u64 = 1024;
+
+/// When to stop searching. The search stops at whichever limit it reaches
+/// first and reports the deepest iteration it completed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth: u32,
+    pub nodes: Option<u64>,
+    pub time: Option<Duration>,
+}
+
+impl Default for SearchLimits {
+    fn default() -> Self {
+        SearchLimits {
+            depth: 5,
+            nodes: None,
+            time: None,
+        }
+    }
+}
+
+/// The result of the deepest completed iteration of a search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchResult {
+    /// None only if the side to move has no legal moves.
+    pub best_move: Option<Move>,
+    /// Centipawns from the side to move's perspective, or a mate score.
+    pub score: i32,
+    pub depth: u32,
+    /// Nodes searched by all iterations so far, quiescence included.
+    pub nodes: u64,
+    pub elapsed: Duration,
+    /// Principal variation, starting with `best_move`.
+    pub pv: Vec<Move>,
+}
+
+impl SearchResult {
+    /// Moves until mate if `score` is a mate score: positive if the side to
+    /// move mates, negative if it is mated.
+    pub fn mate_in(&self) -> Option<i32> {
+        let plies = MATE_SCORE - self.score.abs();
+        if plies > MAX_PLY as i32 {
+            return None;
+        }
+        let moves = (plies + 1) / 2;
+        Some(if self.score > 0 { moves } else { -moves })
+    }
+
+    /// The result as a UCI `info` line.
+    pub fn info_line(&self) -> InfoLine {
+        let time_ms = self.elapsed.as_millis() as u64;
+        let mate = self.mate_in();
+        InfoLine {
+            depth: Some(self.depth),
+            score_cp: if mate.is_none() {
+                Some(self.score)
+            } else {
+                None
+            },
+            score_mate: mate,
+            nodes: Some(self.nodes),
+            nps: Some(self.nodes * 1000 / time_ms.max(1)),
+            time_ms: Some(time_ms),
+            pv: self.pv.iter().map(Move::to_lan).collect(),
+        }
+    }
+}
+
+/// Bonus for the piece's square, from its owner's perspective. Boards vary
+/// in size, so the tables are computed from how central a square is and how
+/// far a pawn has advanced, rather than stored. `endgame` is 0 with all the
+/// starting material on the board and 256 with none of it.
+fn square_bonus<const W: usize, const H: usize>(
+    piece_type: PieceType,
+    color: Color,
+    pos: &Position,
+    endgame: i32,
+) -> i32 {
+    // Distance from the center in half squares, so even sizes have no middle
+    let col_offset = (2 * pos.col as i32 - (W as i32 - 1)).abs();
+    let row_offset = (2 * pos.row as i32 - (H as i32 - 1)).abs();
+    let centrality = (W as i32 - 1) + (H as i32 - 1) - col_offset - row_offset;
+
+    match piece_type {
+        PieceType::Pawn => {
+            let advanced = match color {
+                Color::White => pos.row as i32 - 1,
+                Color::Black => H as i32 - 2 - pos.row as i32,
+            };
+            advanced * 10 + (W as i32 - 1 - col_offset) * 2
+        }
+        PieceType::Knight => centrality * 4,
+        PieceType::Bishop => centrality * 2,
+        PieceType::Rook => 0,
+        PieceType::Queen => centrality,
+        // Part knight, so nearly as keen on the center as one
+        PieceType::Archbishop | PieceType::Chancellor => centrality * 3,
+        // Sheltered while there are pieces to attack it, central after
+        PieceType::King => (centrality * 4 * (2 * endgame - 256)) / 256,
+    }
+}
+
+/// Static evaluation of `game` in centipawns, from the side to move's
+/// perspective: material plus piece-square bonuses.
+#[hotpath::measure]
+pub fn evaluate<const W: usize, const H: usize>(game: &Game<W, H>) -> i32
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let counts = game.piece_counts();
+    let starting = 2
+        * (2 * PieceType::Knight.value()
+            + 2 * PieceType::Bishop.value()
+            + 2 * PieceType::Rook.value()
+            + PieceType::Queen.value());
+    let material: i32 = [
+        PieceType::Knight,
+        PieceType::Bishop,
+        PieceType::Rook,
+        PieceType::Queen,
+    ]
+    .iter()
+    .map(|&pt| pt.value() * i32::from(counts.get(pt, Color::White) + counts.get(pt, Color::Black)))
+    .sum();
+    let endgame = 256 - (256 * material / starting).min(256);
+
+    let mut score = 0;
+    for color in [Color::White, Color::Black] {
+        let sign = if color == game.turn() { 1 } else { -1 };
+        for (pos, piece) in game.pieces_iter(color) {
+            let value = piece.piece_type.value()
+                + square_bonus::<W, H>(piece.piece_type, color, &pos, endgame);
+            score += sign * value;
+        }
+    }
+    score
+}
+
+/// Search `game` within `limits`, returning the best move found and its
+/// principal variation. The game is left as it was.
+pub fn search<const W: usize, const H: usize>(
+    game: &mut Game<W, H>,
+    limits: &SearchLimits,
+) -> SearchResult
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    search_with(game, limits, &AtomicBool::new(false), &mut |_| {})
+}
+
+/// Like `search`, but also stopping once `stop` is set, and passing the
+/// result of each completed iteration to `report`.
+#[hotpath::measure]
+pub fn search_with<const W: usize, const H: usize>(
+    game: &mut Game<W, H>,
+    limits: &SearchLimits,
+    stop: &AtomicBool,
+    report: &mut dyn FnMut(&SearchResult),
+) -> SearchResult
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let start = Instant::now();
+    let mut searcher = Searcher {
+        game,
+        stop,
+        deadline: limits.time.map(|time| start + time),
+        max_nodes: limits.nodes,
+        nodes: 0,
+        aborted: false,
+        pv_hint: Vec::new(),
+    };
+
+    let mut root_moves = MoveList::new();
+    searcher.game.legal_moves_into(&mut root_moves);
+    let mut result = SearchResult {
+        best_move: None,
+        score: if root_moves.is_empty() && searcher.game.is_check() {
+            -MATE_SCORE
+        } else {
+            0
+        },
+        depth: 0,
+        nodes: 0,
+        elapsed: Duration::ZERO,
+        pv: Vec::new(),
+    };
+    if root_moves.is_empty() {
+        return result;
+    }
+
+    for depth in 1..=limits.depth.max(1) {
+        let mut pv = Vec::new();
+        let score = searcher.negamax(depth, 0, -INFINITY, INFINITY, &mut pv);
+        if searcher.aborted {
+            break;
+        }
+        result = SearchResult {
+            best_move: pv.first().copied(),
+            score,
+            depth,
+            nodes: searcher.nodes,
+            elapsed: start.elapsed(),
+            pv: pv.clone(),
+        };
+        report(&result);
+        searcher.pv_hint = pv;
+        // A mate found can't be improved on by searching deeper
+        if result.mate_in().is_some() {
+            break;
+        }
+    }
+
+    // Stopped before the first iteration finished: any legal move will do
+    if result.best_move.is_none() {
+        result.best_move = root_moves.first().copied();
+        result.pv = root_moves.first().copied().into_iter().collect();
+    }
+    result.nodes = searcher.nodes;
+    result.elapsed = start.elapsed();
+    result
+}
+
+struct Searcher<'a, const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    game: &'a mut Game<W, H>,
+    stop: &'a AtomicBool,
+    deadline: Option<Instant>,
+    max_nodes: Option<u64>,
+    nodes: u64,
+    /// Set once a limit is hit; the scores of the iteration are then garbage.
+    aborted: bool,
+    /// Principal variation of the previous iteration, searched first.
+    pv_hint: Vec<Move>,
+}
+
+#[hotpath::measure_all]
+impl<const W: usize, const H: usize> Searcher<'_, W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn visit(&mut self) -> bool {
+        self.nodes += 1;
+        if self.max_nodes.is_some_and(|max| self.nodes > max) {
+            self.aborted = true;
+        } else if self.nodes.is_multiple_of(CHECK_INTERVAL) {
+            self.aborted = self.stop.load(Ordering::Relaxed)
+                || self
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline);
+        }
+        !self.aborted
+    }
+
+    /// Draws that can happen inside the search. A single repetition is
+    /// scored as a draw, as the side repeating could repeat again.
+    fn is_draw(&mut self) -> bool {
+        self.game.halfmove_clock() >= 100
+            || self.game.is_insufficient_material()
+            || self.game.repetition_count() >= 2
+    }
+
+    fn negamax(
+        &mut self,
+        depth: u32,
+        ply: usize,
+        mut alpha: i32,
+        beta: i32,
+        pv: &mut Vec<Move>,
+    ) -> i32 {
+        pv.clear();
+        if !self.visit() {
+            return 0;
+        }
+        if ply > 0 && self.is_draw() {
+            return 0;
+        }
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiesce(ply, alpha, beta);
+        }
+
+        let mut moves = MoveList::new();
+        self.game.legal_moves_into(&mut moves);
+        if moves.is_empty() {
+            return if self.game.is_check() {
+                -MATE_SCORE + ply as i32
+            } else {
+                0
+            };
+        }
+        self.order(&mut moves, ply);
+
+        let mut line = Vec::new();
+        for mv in moves {
+            self.game.make_move_silent(&mv);
+            let score = -self.negamax(depth - 1, ply + 1, -beta, -alpha, &mut line);
+            self.game.unmake_move_silent();
+            if self.aborted {
+                return 0;
+            }
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(mv);
+                pv.extend_from_slice(&line);
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        alpha
+    }
+
+    /// Search captures and promotions until the position is quiet, so the
+    /// evaluation isn't taken in the middle of an exchange. In check every
+    /// evasion is searched instead.
+    fn quiesce(&mut self, ply: usize, mut alpha: i32, beta: i32) -> i32 {
+        let in_check = self.game.is_check();
+        if !in_check || ply >= MAX_PLY {
+            let stand_pat = evaluate(self.game);
+            if stand_pat >= beta || ply >= MAX_PLY {
+                return stand_pat;
+            }
+            alpha = alpha.max(stand_pat);
+        }
+
+        let mut moves = if in_check {
+            self.game.evasion_moves()
+        } else {
+            self.game.capture_moves()
+        };
+        if in_check && moves.is_empty() {
+            return -MATE_SCORE + ply as i32;
+        }
+        self.order(&mut moves, ply);
+
+        for mv in moves {
+            if !self.visit() {
+                return 0;
+            }
+            self.game.make_move_silent(&mv);
+            let score = -self.quiesce(ply + 1, -beta, -alpha);
+            self.game.unmake_move_silent();
+            if self.aborted {
+                return 0;
+            }
+            if score > alpha {
+                alpha = score;
+                if alpha >= beta {
+                    break;
+                }
+            }
+        }
+        alpha
+    }
+
+    /// Sort `moves` best first: the previous iteration's move at this ply,
+    /// then captures by most valuable victim and least valuable attacker,
+    /// then promotions, then the rest.
+    fn order(&self, moves: &mut MoveList, ply: usize) {
+        let hint = self.pv_hint.get(ply);
+        moves.sort_by_cached_key(|mv| {
+            if Some(mv) == hint {
+                return i32::MIN;
+            }
+            let mut key = 0;
+            if mv.flags.contains(MoveFlags::CAPTURE) {
+                let victim = self
+                    .game
+                    .get_piece(&mv.dst)
+                    .map_or(PieceType::Pawn.value(), |piece| piece.piece_type.value());
+                let attacker = self
+                    .game
+                    .get_piece(&mv.src)
+                    .map_or(0, |piece| piece.piece_type.value());
+                key -= 1_000_000 + 10 * victim - attacker;
+            }
+            if let Some(promotion) = mv.promotion {
+                key -= 10_000 + promotion.value();
+            }
+            key
+        });
+    }
+}
+
+/// Serves `search` over UCI. A `go` without limits searches to the default
+/// depth.
+pub struct AlphaBeta {
+    pub default_depth: u32,
+}
+
+impl Default for AlphaBeta {
+    fn default() -> Self {
+        AlphaBeta {
+            default_depth: SearchLimits::default().depth,
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> SearchBackend<W, H> for AlphaBeta
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn name(&self) -> String {
+        format!("spooky_chess alphabeta {}", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn search(
+        &mut self,
+        game: &mut Game<W, H>,
+        limits: &GoLimits,
+        stop: &AtomicBool,
+        info: &mut dyn FnMut(&InfoLine),
+    ) -> Option<Move> {
+        let time = limits
+            .time_budget_ms(game.turn())
+            .filter(|_| !limits.infinite)
+            .map(Duration::from_millis);
+        let unlimited = limits.infinite || limits.nodes.is_some() || time.is_some();
+        let depth = match limits.depth {
+            Some(depth) => depth,
+            None if unlimited => MAX_PLY as u32,
+            None => self.default_depth,
+        };
+        let search_limits = SearchLimits {
+            depth,
+            nodes: limits.nodes,
+            time,
+        };
+        search_with(game, &search_limits, stop, &mut |result| {
+            info(&result.info_line())
+        })
+        .best_move
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    fn lans(moves: &[Move]) -> Vec<String> {
+        moves.iter().map(Move::to_lan).collect()
+    }
+
+    #[test]
+    fn test_evaluate() {
+        // Symmetric positions are level, whoever is to move
+        let game = StandardGame::standard();
+        assert_eq!(evaluate(&game), 0);
+
+        let white = StandardGame::new("4k3/8/8/8/8/8/8/3QK3 w - - 0 1", true).expect("valid FEN");
+        let black = StandardGame::new("4k3/8/8/8/8/8/8/3QK3 b - - 0 1", true).expect("valid FEN");
+        assert!(evaluate(&white) > 800);
+        assert_eq!(evaluate(&black), -evaluate(&white));
+
+        // Knights are better in the center
+        let center = StandardGame::new("4k3/8/8/8/3N4/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+        let corner = StandardGame::new("4k3/8/8/8/8/8/8/N3K3 w - - 0 1", true).expect("valid FEN");
+        assert!(evaluate(&center) > evaluate(&corner));
+    }
+
+    #[test]
+    fn test_search_finds_mates_and_material() {
+        // Back rank mate
+        let mut game =
+            StandardGame::new("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", true).expect("valid FEN");
+        let fen = game.to_fen();
+        let result = search(&mut game, &SearchLimits::default());
+        assert_eq!(
+            result.best_move.map(|mv| mv.to_lan()).as_deref(),
+            Some("a1a8")
+        );
+        assert_eq!(result.mate_in(), Some(1));
+        assert_eq!(result.score, MATE_SCORE - 1);
+        assert_eq!(lans(&result.pv), vec!["a1a8"]);
+        // The game is left as it was
+        assert_eq!(game.to_fen(), fen);
+        assert_eq!(game.move_count(), 0);
+
+        // Take the hanging queen, not the defended pawn
+        let mut game =
+            StandardGame::new("4k3/8/2p5/1p1q4/2P5/8/8/4K3 w - - 0 1", true).expect("valid FEN");
+        let result = search(&mut game, &SearchLimits::default());
+        assert_eq!(result.pv[0].to_lan(), "c4d5");
+        assert!(result.score > evaluate(&game) + 500);
+        assert_eq!(result.mate_in(), None);
+
+        // Every move of the principal variation is legal in turn
+        let mut game = StandardGame::standard();
+        let result = search(
+            &mut game,
+            &SearchLimits {
+                depth: 4,
+                ..SearchLimits::default()
+            },
+        );
+        assert_eq!(result.depth, 4);
+        assert_eq!(result.best_move, result.pv.first().copied());
+        for mv in &result.pv {
+            assert!(game.make_move(mv));
+        }
+    }
+
+    #[test]
+    fn test_search_limits() {
+        let mut game = StandardGame::standard();
+
+        // Each completed iteration is reported, deepest last
+        let mut depths = Vec::new();
+        let limits = SearchLimits {
+            depth: 3,
+            ..SearchLimits::default()
+        };
+        let result = search_with(&mut game, &limits, &AtomicBool::new(false), &mut |r| {
+            depths.push(r.depth)
+        });
+        assert_eq!(depths, vec![1, 2, 3]);
+        assert_eq!(result.depth, 3);
+
+        // A search stopped early still returns a legal move
+        let stopped = search_with(&mut game, &limits, &AtomicBool::new(true), &mut |_| {});
+        let mv = stopped.best_move.expect("a move");
+        assert!(game.legal_moves().contains(&mv));
+
+        let limited = search(
+            &mut game,
+            &SearchLimits {
+                depth: 64,
+                nodes: Some(2000),
+                time: None,
+            },
+        );
+        assert!(limited.best_move.is_some());
+        assert!(limited.depth < 64);
+        assert_eq!(game.move_count(), 0);
+
+        // No moves, no best move
+        let mut mated: StandardGame =
+            StandardGame::new("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1", true).expect("valid FEN");
+        let result = search(&mut mated, &SearchLimits::default());
+        assert_eq!(result.best_move, None);
+        assert_eq!(result.score, -MATE_SCORE);
+        assert_eq!(result.mate_in(), Some(0));
+    }
+}
@@ -0,0 +1,386 @@
+//! Generating games by self-play.
+//!
+//! `self_play` plays a batch of games from one starting position, spread over
+//! the available cores, choosing each move with a callback (`uniform_random`
+//! for random games). `self_play_batched` plays the games in lockstep instead,
+//! handing the encoded positions of every unfinished game to one callback per
+//! ply, so a network can evaluate them as a batch.
+//!
+//! Games can record a training sample for every position played: its planes,
+//! the action played as the policy target and the game's result for the side
+//! to move as the value target.
+
+use crate::color::Color;
+use crate::dataset::LabeledShard;
+use crate::encode::{self, EncoderConfig};
+use crate::game::{Game, PlayoutRng};
+use crate::r#move::Move;
+use crate::outcome::{GameOutcome, MoveList};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelfPlayConfig {
+    pub num_games: usize,
+    /// Plies after which an unfinished game is stopped, without an outcome.
+    pub max_plies: usize,
+    /// Seed of the random draws passed to the move selection callback; each
+    /// game draws from its own stream.
+    pub seed: u64,
+    /// Encode a training sample for every position played.
+    pub record_samples: bool,
+    /// Encoding of the samples' planes and actions, and of the positions
+    /// passed to `self_play_batched`'s callback.
+    pub encoder: EncoderConfig,
+    /// Spread `self_play`'s games over the available cores.
+    pub parallel: bool,
+}
+
+impl Default for SelfPlayConfig {
+    fn default() -> Self {
+        SelfPlayConfig {
+            num_games: 1,
+            max_plies: 512,
+            seed: 0,
+            record_samples: false,
+            encoder: EncoderConfig::default(),
+            parallel: true,
+        }
+    }
+}
+
+/// A game played by self-play.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SelfPlayGame {
+    pub moves: Vec<Move>,
+    /// None if the game was stopped at `max_plies`.
+    pub outcome: Option<GameOutcome>,
+    /// With `record_samples`, one sample per position played. The value of a
+    /// stopped game's samples is 0.
+    pub samples: Option<LabeledShard>,
+}
+
+/// A move selection callback for random games: picks uniformly with `draw`.
+pub fn uniform_random<const W: usize, const H: usize>(
+    _game: &mut Game<W, H>,
+    moves: &[Move],
+    draw: u64,
+) -> usize
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    ((u128::from(draw) * moves.len() as u128) >> 64) as usize
+}
+
+/// One game in progress, with its record.
+struct Recorder<'a, const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    game: Game<W, H>,
+    config: &'a SelfPlayConfig,
+    moves: Vec<Move>,
+    samples: Option<LabeledShard>,
+    /// Side to move of each sample, for its value once the game is over.
+    sample_sides: Vec<Color>,
+}
+
+#[hotpath::measure_all]
+impl<'a, const W: usize, const H: usize> Recorder<'a, W, H>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn new(start: &Game<W, H>, config: &'a SelfPlayConfig) -> Self {
+        Recorder {
+            game: start.clone(),
+            config,
+            moves: Vec::new(),
+            samples: config.record_samples.then(LabeledShard::default),
+            sample_sides: Vec::new(),
+        }
+    }
+
+    /// The legal moves to choose from, or false if the game is over or has
+    /// reached `max_plies`.
+    fn legal_moves_into(&mut self, moves: &mut MoveList) -> bool {
+        if self.moves.len() >= self.config.max_plies || self.game.is_over() {
+            return false;
+        }
+        self.game.legal_moves_into(moves);
+        true
+    }
+
+    /// Planes of the current position.
+    fn encode(&mut self) -> Vec<f32> {
+        encode::encode_game_planes_with_config(&mut self.game, &self.config.encoder).0
+    }
+
+    /// Play `mv`, first recording a sample of the position with `planes`, or
+    /// freshly encoded ones.
+    fn play(&mut self, mv: Move, planes: Option<Vec<f32>>) {
+        if self.samples.is_some()
+            && let Some(action) = self
+                .game
+                .encode_action_with_config(&mv, &self.config.encoder)
+        {
+            let planes = planes.unwrap_or_else(|| self.encode());
+            let (num_planes, (height, width)) = (
+                self.config.encoder.num_planes(),
+                self.config.encoder.plane_shape(W, H),
+            );
+            let fen = self.game.to_fen();
+            let samples = self.samples.as_mut().expect("play: recording samples");
+            samples.planes.extend_from_slice(&planes);
+            (samples.num_planes, samples.height, samples.width) = (num_planes, height, width);
+            samples.fens.push(fen);
+            samples.policy.push(action);
+            self.sample_sides.push(self.game.turn());
+        }
+        self.game.make_move_unchecked(&mv);
+        self.moves.push(mv);
+    }
+
+    fn finish(mut self) -> SelfPlayGame {
+        let outcome = self.game.outcome();
+        if let Some(samples) = &mut self.samples {
+            samples.value = self
+                .sample_sides
+                .iter()
+                .map(|&side| outcome.map_or(0.0, |o| o.encode_winner_from_perspective(side)))
+                .collect();
+        }
+        SelfPlayGame {
+            moves: self.moves,
+            outcome,
+            samples: self.samples,
+        }
+    }
+}
+
+/// Play `config.num_games` games from `start`. Each move is
+/// `moves[select(game, moves, draw)]`, where `draw` is a random number from
+/// the game's own stream, so a deterministic `select` makes the games
+/// reproducible for a seed.
+///
+/// Panics if `select` returns an index out of range, or if the board doesn't
+/// fit the encoder configuration while recording samples.
+#[hotpath::measure]
+pub fn self_play<const W: usize, const H: usize>(
+    start: &Game<W, H>,
+    config: &SelfPlayConfig,
+    select: impl Fn(&mut Game<W, H>, &[Move], u64) -> usize + Sync,
+) -> Vec<SelfPlayGame>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    if config.record_samples
+        && let Err(e) = config.encoder.check_game(start)
+    {
+        panic!("self_play: {}", e);
+    }
+    let mut indices: Vec<usize> = (0..config.num_games).collect();
+    let mut games = vec![SelfPlayGame::default(); config.num_games];
+    encode::fill_batch(
+        &mut indices,
+        &mut games,
+        config.parallel,
+        |&mut index, out| {
+            let mut rng = PlayoutRng(config.seed.wrapping_add(index as u64));
+            let mut recorder = Recorder::new(start, config);
+            let mut moves = MoveList::new();
+            while recorder.legal_moves_into(&mut moves) {
+                let choice = select(&mut recorder.game, &moves, rng.next_u64());
+                recorder.play(moves[choice], None);
+            }
+            out[0] = recorder.finish();
+        },
+    );
+    games
+}
+
+/// Play `config.num_games` games from `start` in lockstep. Every ply, `select`
+/// is called with the planes of each unfinished game's position stacked into
+/// one `(games, planes, height, width)` buffer (see
+/// `encode::encode_games_batch`) and each one's legal action indices, and
+/// returns the action to play in each. `config.parallel` is ignored.
+///
+/// Fails if the board doesn't fit the encoder configuration, if `select`
+/// fails, or if it returns the wrong number of actions or an illegal one.
+#[hotpath::measure]
+pub fn self_play_batched<const W: usize, const H: usize>(
+    start: &Game<W, H>,
+    config: &SelfPlayConfig,
+    mut select: impl FnMut(&[f32], [usize; 4], &[Vec<usize>]) -> Result<Vec<usize>, String>,
+) -> Result<Vec<SelfPlayGame>, String>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    config.encoder.check_game(start)?;
+    let (height, width) = config.encoder.plane_shape(W, H);
+    let num_planes = config.encoder.num_planes();
+    let mut recorders: Vec<Recorder<W, H>> = (0..config.num_games)
+        .map(|_| Recorder::new(start, config))
+        .collect();
+    let mut moves = MoveList::new();
+
+    loop {
+        let mut active = Vec::new();
+        let mut candidates: Vec<Vec<(usize, Move)>> = Vec::new();
+        for (index, recorder) in recorders.iter_mut().enumerate() {
+            if !recorder.legal_moves_into(&mut moves) {
+                continue;
+            }
+            let game = &recorder.game;
+            candidates.push(
+                moves
+                    .iter()
+                    .filter_map(|mv| {
+                        game.encode_action_with_config(mv, &config.encoder)
+                            .map(|action| (action, *mv))
+                    })
+                    .collect(),
+            );
+            active.push(index);
+        }
+        if active.is_empty() {
+            break;
+        }
+
+        let size = num_planes * height * width;
+        let mut planes = vec![0.0f32; active.len() * size];
+        for (&index, out) in active.iter().zip(planes.chunks_exact_mut(size)) {
+            out.copy_from_slice(&recorders[index].encode());
+        }
+        let legal_actions: Vec<Vec<usize>> = candidates
+            .iter()
+            .map(|moves| moves.iter().map(|&(action, _)| action).collect())
+            .collect();
+        let actions = select(
+            &planes,
+            [active.len(), num_planes, height, width],
+            &legal_actions,
+        )?;
+        if actions.len() != active.len() {
+            return Err(format!(
+                "expected {} actions, got {}",
+                active.len(),
+                actions.len()
+            ));
+        }
+
+        for (i, (&index, action)) in active.iter().zip(actions).enumerate() {
+            let mv = candidates[i]
+                .iter()
+                .find(|&&(legal, _)| legal == action)
+                .map(|&(_, mv)| mv)
+                .ok_or_else(|| format!("illegal action {} in game {}", action, index))?;
+            let own_planes = planes[i * size..(i + 1) * size].to_vec();
+            recorders[index].play(mv, Some(own_planes));
+        }
+    }
+
+    Ok(recorders.into_iter().map(Recorder::finish).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+
+    #[test]
+    fn test_self_play_random_is_reproducible() {
+        let start = StandardGame::standard();
+        let config = SelfPlayConfig {
+            num_games: 6,
+            max_plies: 60,
+            seed: 3,
+            ..SelfPlayConfig::default()
+        };
+        let games = self_play(&start, &config, uniform_random);
+        assert_eq!(games.len(), 6);
+        for game in &games {
+            assert!(game.outcome.is_some() || game.moves.len() == 60);
+            assert!(game.samples.is_none());
+        }
+        assert_ne!(games[0].moves, games[1].moves);
+
+        let serial = SelfPlayConfig {
+            parallel: false,
+            ..config
+        };
+        assert_eq!(self_play(&start, &serial, uniform_random), games);
+
+        // Replaying the moves reaches the same outcome
+        let mut replay = start.clone();
+        for mv in &games[0].moves {
+            assert!(replay.make_move(mv));
+        }
+        assert_eq!(replay.outcome(), games[0].outcome);
+    }
+
+    #[test]
+    fn test_self_play_samples() {
+        // White mates with Rh8
+        let mut start: Game<8, 8> =
+            Game::new("k7/7R/1K6/8/8/8/8/8 w - - 0 1", true).expect("valid FEN");
+        let mate = start.move_from_lan("h7h8").expect("valid LAN");
+        let config = SelfPlayConfig {
+            num_games: 2,
+            record_samples: true,
+            ..SelfPlayConfig::default()
+        };
+        let games = self_play(&start, &config, |_, moves, _| {
+            moves
+                .iter()
+                .position(|mv| *mv == mate)
+                .expect("h7h8 is legal")
+        });
+        for game in &games {
+            assert_eq!(game.moves, vec![mate]);
+            let samples = game.samples.as_ref().expect("recorded");
+            assert_eq!(samples.len(), 1);
+            assert_eq!(samples.value, vec![1.0]);
+            assert_eq!(samples.fens[0], start.to_fen());
+            assert_eq!(
+                samples.policy[0],
+                start
+                    .encode_action_with_config(&mate, &config.encoder)
+                    .expect("encodable")
+            );
+            assert_eq!(
+                samples.planes,
+                encode::encode_game_planes_with_config(&mut start.clone(), &config.encoder).0
+            );
+        }
+    }
+
+    #[test]
+    fn test_self_play_batched() {
+        let start = StandardGame::standard();
+        let config = SelfPlayConfig {
+            num_games: 3,
+            max_plies: 10,
+            record_samples: true,
+            ..SelfPlayConfig::default()
+        };
+        let mut calls = 0;
+        let games = self_play_batched(&start, &config, |planes, shape, legal| {
+            calls += 1;
+            assert_eq!(planes.len(), shape.iter().product::<usize>());
+            assert_eq!(shape[0], legal.len());
+            Ok(legal.iter().map(|actions| actions[0]).collect())
+        })
+        .expect("legal actions");
+        assert_eq!(calls, 10);
+        for game in &games {
+            assert_eq!(game.moves.len(), 10);
+            assert_eq!(game.outcome, None);
+            let samples = game.samples.as_ref().expect("recorded");
+            assert_eq!(samples.len(), 10);
+            assert!(samples.value.iter().all(|&v| v == 0.0));
+        }
+
+        let illegal = self_play_batched(&start, &config, |_, shape, _| Ok(vec![0; shape[0]]));
+        assert!(illegal.is_err());
+        let short = self_play_batched(&start, &config, |_, _, _| Ok(Vec::new()));
+        assert!(short.is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Per-game statistics for aggregating over many (e.g. self-play) games.
+//!
+//! `game_stats` replays a game's move history from its starting position and
+//! summarises it. With the `serde` feature the summary is serializable.
+
+use crate::color::Color;
+use crate::game::Game;
+use crate::r#move::MoveFlags;
+use crate::pieces::PieceType;
+
+/// Statistics for the moves played by one side.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SideStats {
+    /// Moves played.
+    pub moves: u32,
+    /// Moves that captured a piece, including en passant.
+    pub captures: u32,
+    /// Moves that gave check.
+    pub checks: u32,
+    /// Mean number of legal moves available when this side was to move, or 0
+    /// if it never moved.
+    pub average_mobility: f64,
+}
+
+/// Summary of a game's move history.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameStats {
+    /// Number of plies played.
+    pub length: usize,
+    pub white: SideStats,
+    pub black: SideStats,
+    /// Material balance (white minus black, in centipawns) in the starting
+    /// position and after each ply, so it has `length + 1` entries.
+    pub material: Vec<i32>,
+}
+
+#[hotpath::measure_all]
+impl GameStats {
+    pub fn side(&self, color: Color) -> &SideStats {
+        match color {
+            Color::White => &self.white,
+            Color::Black => &self.black,
+        }
+    }
+
+    fn side_mut(&mut self, color: Color) -> &mut SideStats {
+        match color {
+            Color::White => &mut self.white,
+            Color::Black => &mut self.black,
+        }
+    }
+}
+
+/// Material balance of the current position, white minus black.
+fn material_balance<const W: usize, const H: usize>(game: &Game<W, H>) -> i32
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    game.material(Color::White, PieceType::value) - game.material(Color::Black, PieceType::value)
+}
+
+/// Compute statistics for the moves played in `game`, finished or not.
+#[hotpath::measure]
+pub fn game_stats<const W: usize, const H: usize>(game: &Game<W, H>) -> GameStats
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    let mut replay = game.clone();
+    while replay.unmake_move_silent().is_some() {}
+
+    let mut stats = GameStats {
+        length: game.move_history().len(),
+        material: vec![material_balance(&replay)],
+        ..GameStats::default()
+    };
+    let mut total_mobility = [0usize; 2];
+
+    for entry in game.move_history() {
+        let mover = replay.turn();
+        total_mobility[usize::from(mover == Color::Black)] += replay.legal_moves().len();
+
+        replay.make_move_silent(&entry.mv);
+
+        let side = stats.side_mut(mover);
+        side.moves += 1;
+        if entry.mv.flags.contains(MoveFlags::CAPTURE) {
+            side.captures += 1;
+        }
+        if replay.is_check() {
+            side.checks += 1;
+        }
+        stats.material.push(material_balance(&replay));
+    }
+
+    for (color, total) in [Color::White, Color::Black].into_iter().zip(total_mobility) {
+        let side = stats.side_mut(color);
+        if side.moves > 0 {
+            side.average_mobility = total as f64 / f64::from(side.moves);
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::StandardGame;
+    use crate::test_support::play;
+
+    #[test]
+    fn test_game_stats() {
+        // Scholar's mate
+        let mut game = StandardGame::standard();
+        play(
+            &mut game,
+            &["e2e4", "e7e5", "f1c4", "b8c6", "d1h5", "g8f6", "h5f7"],
+        );
+        let stats = game_stats(&game);
+
+        assert_eq!(stats.length, 7);
+        assert_eq!(stats.white.moves, 4);
+        assert_eq!(stats.black.moves, 3);
+        assert_eq!(stats.white.captures, 1);
+        assert_eq!(stats.black.captures, 0);
+        assert_eq!(stats.white.checks, 1);
+        assert_eq!(stats.side(Color::Black).checks, 0);
+        assert_eq!(stats.material.len(), 8);
+        assert_eq!(stats.material[0], 0);
+        assert_eq!(*stats.material.last().expect("non-empty"), 100);
+
+        // Both sides have 20 moves in the starting position
+        assert!(stats.white.average_mobility > 20.0);
+        assert!(stats.black.average_mobility >= 20.0);
+    }
+
+    #[test]
+    fn test_game_stats_from_fen() {
+        // Replay starts from the game's starting FEN, not the standard position
+        let mut game =
+            StandardGame::new("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1", true).expect("valid FEN");
+        play(&mut game, &["e1c1"]);
+        let stats = game_stats(&game);
+        assert_eq!(stats.length, 1);
+        assert_eq!(stats.material, vec![500, 500]);
+        assert_eq!(stats.white.average_mobility, 16.0);
+        assert_eq!(stats.black, SideStats::default());
+
+        assert_eq!(game_stats(&StandardGame::standard()).material, vec![0]);
+    }
+}
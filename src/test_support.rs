@@ -0,0 +1,22 @@
+//! Helpers shared by the unit tests.
+
+use crate::game::Game;
+use crate::r#move::Move;
+
+/// Play the LAN moves `lans` on `game` and return them. Panics if a move
+/// doesn't parse or isn't legal.
+pub(crate) fn play<const W: usize, const H: usize>(
+    game: &mut Game<W, H>,
+    lans: &[&str],
+) -> Vec<Move>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    lans.iter()
+        .map(|lan| {
+            let mv = game.move_from_lan(lan).expect("valid LAN");
+            assert!(game.make_move(&mv), "illegal move {}", lan);
+            mv
+        })
+        .collect()
+}
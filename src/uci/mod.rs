@@ -1,8 +1,11 @@
 mod protocol;
+mod server;
 
-pub use protocol::{InfoLine, SearchResult, UciError};
+pub use protocol::{GoLimits, InfoLine, SearchResult, UciError};
+pub use server::{RandomMover, SearchBackend, UciServer};
 
 use crate::color::Color;
+use crate::error::ChessError;
 use crate::game::StandardGame;
 use crate::r#move::Move;
 use crate::outcome::{GameOutcome, MoveList, TurnState};
@@ -175,7 +178,7 @@ impl UciEngine {
         let mv = self
             .game
             .move_from_lan(lan)
-            .map_err(UciError::IllegalMove)?;
+            .map_err(|e| UciError::IllegalMove(e.to_string()))?;
         self.make_move(&mv)
     }
 
@@ -309,7 +312,7 @@ impl UciEngine {
         self.game.move_to_lan(mv)
     }
 
-    pub fn move_from_lan(&self, lan: &str) -> Result<Move, String> {
+    pub fn move_from_lan(&self, lan: &str) -> Result<Move, ChessError> {
         self.game.move_from_lan(lan)
     }
 
@@ -317,7 +320,7 @@ impl UciEngine {
         self.game.move_to_san(mv)
     }
 
-    pub fn move_from_san(&mut self, san: &str) -> Result<Move, String> {
+    pub fn move_from_san(&mut self, san: &str) -> Result<Move, ChessError> {
         self.game.move_from_san(san)
     }
 
@@ -435,16 +438,16 @@ impl UciEngine {
                 let best_move = self
                     .game
                     .move_from_lan(&best_lan)
-                    .map_err(UciError::IllegalMove)?;
+                    .map_err(|e| UciError::IllegalMove(e.to_string()))?;
 
                 let ponder_move = if let Some(ref ponder_str) = ponder_lan {
                     // Temporarily apply best move to parse ponder in that context
-                    self.game.make_move_unchecked(&best_move);
+                    self.game.make_move_silent(&best_move);
                     let pm = self
                         .game
                         .move_from_lan(ponder_str)
-                        .map_err(UciError::IllegalMove)?;
-                    self.game.unmake_move();
+                        .map_err(|e| UciError::IllegalMove(e.to_string()))?;
+                    self.game.unmake_move_silent();
                     Some(pm)
                 } else {
                     None
@@ -1,3 +1,4 @@
+use crate::color::Color;
 use crate::r#move::Move;
 use std::fmt;
 use std::io;
@@ -24,6 +25,42 @@ pub struct InfoLine {
     pub pv: Vec<String>,
 }
 
+/// The limits of a `go` command received from a GUI. All times are in
+/// milliseconds.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+    pub movetime_ms: Option<u64>,
+    pub wtime_ms: Option<u64>,
+    pub btime_ms: Option<u64>,
+    pub winc_ms: Option<u64>,
+    pub binc_ms: Option<u64>,
+    pub movestogo: Option<u32>,
+    /// Search until `stop`.
+    pub infinite: bool,
+}
+
+#[hotpath::measure_all]
+impl GoLimits {
+    /// Time to spend on this move for `turn`: `movetime` if given, otherwise
+    /// an even share of the remaining clock over `movestogo` (or 30) moves
+    /// plus half the increment, never more than the clock minus a small
+    /// margin. None if the search isn't timed.
+    pub fn time_budget_ms(&self, turn: Color) -> Option<u64> {
+        if let Some(movetime) = self.movetime_ms {
+            return Some(movetime);
+        }
+        let (time, inc) = match turn {
+            Color::White => (self.wtime_ms?, self.winc_ms.unwrap_or(0)),
+            Color::Black => (self.btime_ms?, self.binc_ms.unwrap_or(0)),
+        };
+        let moves = u64::from(self.movestogo.unwrap_or(30).max(1));
+        let budget = time / moves + inc / 2;
+        Some(budget.min(time.saturating_sub(50)).max(1))
+    }
+}
+
 /// Errors that can occur during UCI communication.
 #[derive(Debug)]
 pub enum UciError {
@@ -100,6 +137,37 @@ pub fn cmd_setoption(name: &str, value: &str) -> String {
     format!("setoption name {} value {}", name, value)
 }
 
+/// Format `info` as a UCI `info` line, the inverse of `parse_info_line`.
+#[hotpath::measure]
+pub fn format_info_line(info: &InfoLine) -> String {
+    let mut line = String::from("info");
+    if let Some(depth) = info.depth {
+        line.push_str(&format!(" depth {}", depth));
+    }
+    if let Some(mate) = info.score_mate {
+        line.push_str(&format!(" score mate {}", mate));
+    } else if let Some(cp) = info.score_cp {
+        line.push_str(&format!(" score cp {}", cp));
+    }
+    if let Some(nodes) = info.nodes {
+        line.push_str(&format!(" nodes {}", nodes));
+    }
+    if let Some(nps) = info.nps {
+        line.push_str(&format!(" nps {}", nps));
+    }
+    if let Some(time_ms) = info.time_ms {
+        line.push_str(&format!(" time {}", time_ms));
+    }
+    if !info.pv.is_empty() {
+        line.push_str(" pv");
+        for mv in &info.pv {
+            line.push(' ');
+            line.push_str(mv);
+        }
+    }
+    line
+}
+
 // --- Response parsers ---
 
 /// Parse a `id name ...` or `id author ...` line.
@@ -192,3 +260,32 @@ pub fn parse_info_line(line: &str) -> Option<InfoLine> {
         pv,
     })
 }
+
+/// Parse a `go ...` command from a GUI. Unknown tokens, such as `ponder` and
+/// `searchmoves` with its moves, are skipped.
+#[hotpath::measure]
+pub fn parse_go_line(line: &str) -> Option<GoLimits> {
+    let rest = line.trim().strip_prefix("go")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut tokens = rest.split_ascii_whitespace();
+    let mut limits = GoLimits::default();
+
+    while let Some(token) = tokens.next() {
+        let mut number = || tokens.next().and_then(|t| t.parse::<u64>().ok());
+        match token {
+            "depth" => limits.depth = number().and_then(|n| u32::try_from(n).ok()),
+            "nodes" => limits.nodes = number(),
+            "movetime" => limits.movetime_ms = number(),
+            "wtime" => limits.wtime_ms = number(),
+            "btime" => limits.btime_ms = number(),
+            "winc" => limits.winc_ms = number(),
+            "binc" => limits.binc_ms = number(),
+            "movestogo" => limits.movestogo = number().and_then(|n| u32::try_from(n).ok()),
+            "infinite" => limits.infinite = true,
+            _ => {}
+        }
+    }
+    Some(limits)
+}
@@ -0,0 +1,230 @@
+use std::io::{self, BufRead, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{Scope, ScopedJoinHandle};
+use std::time::Duration;
+
+use super::protocol::{self, GoLimits, InfoLine};
+use crate::game::{Game, PlayoutRng};
+use crate::r#move::Move;
+use crate::outcome::MoveList;
+
+/// A search `UciServer` can run for a GUI.
+pub trait SearchBackend<const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// Engine name reported for `id name`.
+    fn name(&self) -> String;
+
+    /// Engine author reported for `id author`.
+    fn author(&self) -> String {
+        String::new()
+    }
+
+    /// Called for `ucinewgame`, to clear anything kept between searches.
+    fn new_game(&mut self) {}
+
+    /// Best move in `game` within `limits`, or None if there are no legal
+    /// moves. Progress can be reported with `info`. The search must return
+    /// promptly once `stop` is set, with the best move found so far.
+    fn search(
+        &mut self,
+        game: &mut Game<W, H>,
+        limits: &GoLimits,
+        stop: &AtomicBool,
+        info: &mut dyn FnMut(&InfoLine),
+    ) -> Option<Move>;
+}
+
+/// Plays a uniformly random legal move, from a seeded stream. Useful as a
+/// sparring partner and to test GUI integration.
+pub struct RandomMover {
+    rng: PlayoutRng,
+}
+
+impl RandomMover {
+    pub fn new(seed: u64) -> Self {
+        RandomMover {
+            rng: PlayoutRng(seed),
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> SearchBackend<W, H> for RandomMover
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    fn name(&self) -> String {
+        format!("spooky_chess random {}", env!("CARGO_PKG_VERSION"))
+    }
+
+    fn search(
+        &mut self,
+        game: &mut Game<W, H>,
+        _limits: &GoLimits,
+        _stop: &AtomicBool,
+        _info: &mut dyn FnMut(&InfoLine),
+    ) -> Option<Move> {
+        let mut moves = MoveList::new();
+        game.legal_moves_into(&mut moves);
+        if moves.is_empty() {
+            return None;
+        }
+        let draw = self.rng.next_u64();
+        Some(moves[((u128::from(draw) * moves.len() as u128) >> 64) as usize])
+    }
+}
+
+/// The engine side of the UCI protocol: reads GUI commands, keeps track of
+/// the position and runs a `SearchBackend` for `go`.
+///
+/// Searches run on their own thread, so `stop` and `isready` are answered
+/// while searching. `uci`, `isready`, `ucinewgame`, `position`, `go`, `stop`
+/// and `quit` are understood; other commands are ignored, as the protocol
+/// asks.
+pub struct UciServer<B, const W: usize, const H: usize>
+where
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// None while a search has it.
+    backend: Option<B>,
+    name: String,
+    author: String,
+    game: Game<W, H>,
+}
+
+/// Write `line` to the GUI and flush it.
+fn send<Wr: Write>(output: &Mutex<Wr>, line: &str) -> io::Result<()> {
+    let mut output = output.lock().expect("send: output lock poisoned");
+    writeln!(output, "{}", line)?;
+    output.flush()
+}
+
+#[hotpath::measure_all]
+impl<B, const W: usize, const H: usize> UciServer<B, W, H>
+where
+    B: SearchBackend<W, H> + Send,
+    [(); (W * H).div_ceil(64)]:,
+{
+    /// A server searching `game` until the GUI sends a `position`.
+    pub fn new(backend: B, game: Game<W, H>) -> Self {
+        UciServer {
+            name: backend.name(),
+            author: backend.author(),
+            backend: Some(backend),
+            game,
+        }
+    }
+
+    /// The position the next `go` searches.
+    pub fn game(&self) -> &Game<W, H> {
+        &self.game
+    }
+
+    /// Answer the commands read from `input` on `output` until `quit` or the
+    /// end of the input, stopping any search still running.
+    pub fn run<R: BufRead, Wr: Write + Send>(&mut self, input: R, output: Wr) -> io::Result<()> {
+        let output = Mutex::new(output);
+        let stop = AtomicBool::new(false);
+        std::thread::scope(|scope| {
+            let mut search = None;
+            let result = self.serve(scope, input, &output, &stop, &mut search);
+            self.finish_search(&stop, &mut search);
+            result
+        })
+    }
+
+    fn serve<'scope, 'env, R: BufRead, Wr: Write + Send>(
+        &mut self,
+        scope: &'scope Scope<'scope, 'env>,
+        input: R,
+        output: &'env Mutex<Wr>,
+        stop: &'env AtomicBool,
+        search: &mut Option<ScopedJoinHandle<'scope, B>>,
+    ) -> io::Result<()>
+    where
+        B: 'scope,
+    {
+        for line in input.lines() {
+            let line = line?;
+            let line = line.trim();
+            let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            match command {
+                "uci" => {
+                    send(output, &format!("id name {}", self.name))?;
+                    if !self.author.is_empty() {
+                        send(output, &format!("id author {}", self.author))?;
+                    }
+                    send(output, "uciok")?;
+                }
+                "isready" => send(output, "readyok")?,
+                "ucinewgame" => {
+                    self.finish_search(stop, search);
+                    self.backend
+                        .as_mut()
+                        .expect("serve: backend is idle")
+                        .new_game();
+                }
+                "position" => {
+                    self.finish_search(stop, search);
+                    match Game::from_uci_position_string(rest) {
+                        Ok(game) => self.game = game,
+                        Err(e) => send(output, &format!("info string {}", e))?,
+                    }
+                }
+                "go" => {
+                    self.finish_search(stop, search);
+                    let limits = protocol::parse_go_line(line).unwrap_or_default();
+                    *search = Some(self.start_search(scope, limits, output, stop));
+                }
+                "stop" => self.finish_search(stop, search),
+                "quit" => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Search the current position on a thread of `scope`, which reports the
+    /// best move and hands the backend back when joined.
+    fn start_search<'scope, 'env, Wr: Write + Send>(
+        &mut self,
+        scope: &'scope Scope<'scope, 'env>,
+        limits: GoLimits,
+        output: &'env Mutex<Wr>,
+        stop: &'env AtomicBool,
+    ) -> ScopedJoinHandle<'scope, B>
+    where
+        B: 'scope,
+    {
+        let mut backend = self.backend.take().expect("start_search: backend is idle");
+        let mut game = self.game.clone();
+        stop.store(false, Ordering::Relaxed);
+        scope.spawn(move || {
+            // A GUI that loses the output can't be told anything more
+            let mut report = |info: &InfoLine| {
+                send(output, &protocol::format_info_line(info)).ok();
+            };
+            let best = backend.search(&mut game, &limits, stop, &mut report);
+            // An infinite search only reports its move once stopped
+            while limits.infinite && !stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            let lan = best.map_or_else(|| "0000".to_string(), |mv| mv.to_lan());
+            send(output, &format!("bestmove {}", lan)).ok();
+            backend
+        })
+    }
+
+    /// Stop the running search, if any, and wait for it to report its move.
+    fn finish_search(&mut self, stop: &AtomicBool, search: &mut Option<ScopedJoinHandle<'_, B>>) {
+        if let Some(handle) = search.take() {
+            stop.store(true, Ordering::Relaxed);
+            match handle.join() {
+                Ok(backend) => self.backend = Some(backend),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+    }
+}
@@ -77,6 +77,125 @@ fn test_parse_info_rejects_non_info() {
     assert!(parse_info_line("bestmove e2e4").is_none());
 }
 
+#[test]
+fn test_format_info_line_round_trip() {
+    let line = "info depth 7 score cp -35 nodes 1200 nps 40000 time 30 pv e2e4 e7e5";
+    let info = parse_info_line(line).expect("should parse");
+    assert_eq!(format_info_line(&info), line);
+}
+
+#[test]
+fn test_parse_go_line() {
+    let limits =
+        parse_go_line("go wtime 60000 btime 30000 winc 1000 binc 0 movestogo 20 searchmoves e2e4")
+            .expect("should parse");
+    assert_eq!(limits.wtime_ms, Some(60000));
+    assert_eq!(limits.btime_ms, Some(30000));
+    assert_eq!(limits.movestogo, Some(20));
+    assert_eq!(limits.time_budget_ms(Color::White), Some(3500));
+    assert_eq!(limits.time_budget_ms(Color::Black), Some(1500));
+
+    let limits = parse_go_line("go depth 6 infinite").expect("should parse");
+    assert_eq!(limits.depth, Some(6));
+    assert!(limits.infinite);
+    assert_eq!(limits.time_budget_ms(Color::White), None);
+    assert_eq!(
+        parse_go_line("go movetime 250").and_then(|l| l.time_budget_ms(Color::Black)),
+        Some(250)
+    );
+    assert_eq!(parse_go_line("go"), Some(GoLimits::default()));
+    assert!(parse_go_line("gone").is_none());
+}
+
+// -----------------------------------------------------------------------------
+// Engine-side (server) tests
+// -----------------------------------------------------------------------------
+
+/// Plays the first legal move, or waits for `stop` when told to search
+/// forever, reporting one info line.
+struct FirstMove;
+
+impl SearchBackend<8, 8> for FirstMove {
+    fn name(&self) -> String {
+        "first".to_string()
+    }
+
+    fn author(&self) -> String {
+        "tests".to_string()
+    }
+
+    fn search(
+        &mut self,
+        game: &mut StandardGame,
+        limits: &GoLimits,
+        stop: &std::sync::atomic::AtomicBool,
+        info: &mut dyn FnMut(&InfoLine),
+    ) -> Option<Move> {
+        while limits.infinite && !stop.load(Ordering::Relaxed) {
+            std::thread::yield_now();
+        }
+        let best = game.legal_moves().first().copied();
+        info(&InfoLine {
+            depth: limits.depth,
+            score_cp: Some(0),
+            score_mate: None,
+            nodes: None,
+            nps: None,
+            time_ms: None,
+            pv: best.iter().map(Move::to_lan).collect(),
+        });
+        best
+    }
+}
+
+fn run_server(input: &str) -> (Vec<String>, StandardGame) {
+    let mut server = UciServer::new(FirstMove, StandardGame::standard());
+    let mut output = Vec::new();
+    server
+        .run(input.as_bytes(), &mut output)
+        .expect("in-memory IO");
+    let output = String::from_utf8(output).expect("UTF-8 output");
+    (
+        output.lines().map(str::to_string).collect(),
+        server.game().clone(),
+    )
+}
+
+#[test]
+fn test_server_handshake_and_search() {
+    let (lines, game) = run_server(
+        "uci\nisready\nucinewgame\nposition startpos moves e2e4 e7e5\ngo depth 3\nquit\n",
+    );
+    assert_eq!(
+        lines[..4],
+        ["id name first", "id author tests", "uciok", "readyok"]
+    );
+    assert_eq!(game.move_count(), 2);
+    let best = StandardGame::from_uci_position_string("startpos moves e2e4 e7e5")
+        .expect("valid position")
+        .legal_moves()[0]
+        .to_lan();
+    assert_eq!(lines[4], format!("info depth 3 score cp 0 pv {}", best));
+    assert_eq!(lines[5], format!("bestmove {}", best));
+    assert_eq!(lines.len(), 6);
+}
+
+#[test]
+fn test_server_stop_and_errors() {
+    // An infinite search reports its move only once stopped
+    let (lines, _) = run_server("go infinite\nisready\nstop\n");
+    assert_eq!(lines[0], "readyok");
+    assert!(lines[2].starts_with("bestmove "));
+
+    // Mated positions have no best move; bad positions are reported and kept
+    let (lines, mut game) = run_server(
+        "position fen k7/1Q6/1K6/8/8/8/8/8 b - - 0 1\ngo\nposition nonsense\nfrobnicate\n",
+    );
+    assert!(lines.contains(&"bestmove 0000".to_string()));
+    assert!(lines.iter().any(|l| l.starts_with("info string")));
+    assert_eq!(game.to_fen(), "k7/1Q6/1K6/8/8/8/8/8 b - - 0 1");
+}
+
 // -----------------------------------------------------------------------------
 // Integration tests (require stockfish)
 // -----------------------------------------------------------------------------
@@ -0,0 +1,193 @@
+//! Converting engine scores to win/draw/loss probabilities and value targets.
+//!
+//! A `WdlModel` maps a centipawn score to the probabilities of a win, draw and
+//! loss for the side it is from. `Wdl::value` puts those on the [-1, 1] scale
+//! of `GameOutcome::encode_winner_from_perspective` (a win is 1, a draw 0 and
+//! a loss -1), so engine evaluations and game results can be mixed as value
+//! targets.
+
+use crate::color::Color;
+use crate::outcome::GameOutcome;
+
+/// Win, draw and loss probabilities, summing to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wdl {
+    pub win: f32,
+    pub draw: f32,
+    pub loss: f32,
+}
+
+#[hotpath::measure_all]
+impl Wdl {
+    /// The certain result of a finished game, for `perspective`.
+    pub fn from_outcome(outcome: &GameOutcome, perspective: Color) -> Self {
+        match outcome.winner() {
+            Some(winner) if winner == perspective => Wdl::WIN,
+            Some(_) => Wdl::LOSS,
+            None => Wdl::DRAW,
+        }
+    }
+
+    pub const WIN: Wdl = Wdl {
+        win: 1.0,
+        draw: 0.0,
+        loss: 0.0,
+    };
+    pub const DRAW: Wdl = Wdl {
+        win: 0.0,
+        draw: 1.0,
+        loss: 0.0,
+    };
+    pub const LOSS: Wdl = Wdl {
+        win: 0.0,
+        draw: 0.0,
+        loss: 1.0,
+    };
+
+    /// Expected result on the [-1, 1] value scale: `win - loss`.
+    pub fn value(&self) -> f32 {
+        self.win - self.loss
+    }
+
+    /// Expected score on the [0, 1] scale, counting a draw as half a win.
+    pub fn expected_score(&self) -> f32 {
+        self.win + 0.5 * self.draw
+    }
+
+    /// The same probabilities from the opponent's side.
+    pub fn flipped(&self) -> Self {
+        Wdl {
+            win: self.loss,
+            draw: self.draw,
+            loss: self.win,
+        }
+    }
+}
+
+/// Logistic model of win and loss probabilities:
+/// `win = 1 / (1 + exp((draw_offset - cp) / scale))` and
+/// `loss = 1 / (1 + exp((draw_offset + cp) / scale))`, with the draw
+/// probability making up the rest.
+///
+/// The default has no draws and the usual Elo scale, so a 400-centipawn
+/// advantage is a 10:1 favourite. A positive `draw_offset` moves probability
+/// from both decisive results to the draw; engines publish fitted values for
+/// their own scores.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WdlModel {
+    /// Advantage, in centipawns, at which winning becomes as likely as not.
+    pub draw_offset: f32,
+    /// Centipawns per logistic unit; larger values give flatter curves.
+    pub scale: f32,
+}
+
+impl Default for WdlModel {
+    fn default() -> Self {
+        WdlModel {
+            draw_offset: 0.0,
+            scale: 400.0 / std::f32::consts::LN_10,
+        }
+    }
+}
+
+#[hotpath::measure_all]
+impl WdlModel {
+    pub fn new(draw_offset: f32, scale: f32) -> Result<Self, String> {
+        if !(scale > 0.0 && scale.is_finite()) {
+            return Err(format!("scale must be positive, got {}", scale));
+        }
+        if !(draw_offset >= 0.0 && draw_offset.is_finite()) {
+            return Err(format!(
+                "draw_offset must be non-negative, got {}",
+                draw_offset
+            ));
+        }
+        Ok(WdlModel { draw_offset, scale })
+    }
+
+    /// Probabilities for the side with a `cp` centipawn score.
+    pub fn wdl(&self, cp: i32) -> Wdl {
+        let logistic = |x: f32| 1.0 / (1.0 + (x / self.scale).exp());
+        let cp = cp as f32;
+        let win = logistic(self.draw_offset - cp);
+        let loss = logistic(self.draw_offset + cp);
+        Wdl {
+            win,
+            draw: (1.0 - win - loss).max(0.0),
+            loss,
+        }
+    }
+
+    /// `wdl(cp)` on the [-1, 1] value scale.
+    pub fn value(&self, cp: i32) -> f32 {
+        self.wdl(cp).value()
+    }
+
+    /// Probabilities for an engine score: a mate in `mate` moves (negative if
+    /// the side is being mated) is a certain result, otherwise `wdl(cp)`.
+    pub fn score_wdl(&self, cp: Option<i32>, mate: Option<i32>) -> Option<Wdl> {
+        match (mate, cp) {
+            (Some(mate), _) => Some(if mate > 0 { Wdl::WIN } else { Wdl::LOSS }),
+            (None, Some(cp)) => Some(self.wdl(cp)),
+            (None, None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::outcome::Termination;
+
+    fn close(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-5
+    }
+
+    #[test]
+    fn test_default_model_is_elo_logistic() {
+        let model = WdlModel::default();
+        let even = model.wdl(0);
+        assert!(close(even.win, 0.5) && close(even.loss, 0.5));
+        assert!(close(even.draw, 0.0));
+
+        // 400 centipawns is a 10:1 favourite
+        let ahead = model.wdl(400);
+        assert!(close(ahead.win, 10.0 / 11.0));
+        assert!(close(ahead.value(), 9.0 / 11.0));
+        assert!(close(model.value(-400), -9.0 / 11.0));
+        let behind = model.wdl(-400).flipped();
+        assert!(close(behind.win, ahead.win) && close(behind.loss, ahead.loss));
+    }
+
+    #[test]
+    fn test_draw_offset_and_outcomes() {
+        let model = WdlModel::new(200.0, 100.0).expect("valid model");
+        let even = model.wdl(0);
+        assert!(close(even.win, even.loss));
+        assert!(even.draw > 0.7);
+        assert!(close(even.win + even.draw + even.loss, 1.0));
+        assert!(close(even.expected_score(), 0.5));
+        // Winning is as likely as not at the draw offset
+        assert!(close(model.wdl(200).win, 0.5));
+
+        assert_eq!(model.score_wdl(Some(50), Some(-2)), Some(Wdl::LOSS));
+        assert_eq!(model.score_wdl(None, Some(3)), Some(Wdl::WIN));
+        assert_eq!(model.score_wdl(None, None), None);
+        assert!(WdlModel::new(0.0, 0.0).is_err());
+        assert!(WdlModel::new(-1.0, 100.0).is_err());
+
+        // Certain results match encode_winner_from_perspective
+        let outcomes = [
+            GameOutcome::win(Color::White, Termination::Checkmate),
+            GameOutcome::draw(Termination::Stalemate),
+        ];
+        for outcome in outcomes {
+            for color in [Color::White, Color::Black] {
+                assert_eq!(
+                    Wdl::from_outcome(&outcome, color).value(),
+                    outcome.encode_winner_from_perspective(color)
+                );
+            }
+        }
+    }
+}
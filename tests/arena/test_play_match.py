@@ -0,0 +1,100 @@
+import pytest
+
+import spooky_chess
+
+
+def first_legal(game: spooky_chess.Game) -> spooky_chess.Move | None:
+    return game.legal_moves()[0]
+
+
+def resign(game: spooky_chess.Game) -> spooky_chess.Move | None:
+    return None
+
+
+def test_play_match_resignation() -> None:
+    report = spooky_chess.play_match(first_legal, resign, num_games=4)
+    assert (report["wins"], report["losses"], report["draws"]) == (4, 0, 0)
+    assert report["score"] == 1.0
+    # Scoring every point makes the Elo difference unbounded
+    assert report["elo"] is None
+    assert report["elo_error"] is None
+    assert report["los"] > 0.95
+
+    games = report["games"]
+    assert [first_color for _, first_color, _, _ in games] == [spooky_chess.WHITE, spooky_chess.BLACK] * 2
+    for _, first_color, moves, outcome in games:
+        assert len(moves) == (1 if first_color == spooky_chess.WHITE else 0)
+        assert outcome.termination() == "resignation"
+        assert outcome.winner() == first_color
+
+
+def test_play_match_ply_limit_draws() -> None:
+    report = spooky_chess.play_match(first_legal, first_legal, num_games=2, max_plies=6)
+    assert (report["wins"], report["losses"], report["draws"]) == (0, 0, 2)
+    assert report["score"] == 0.5
+    assert report["elo"] == pytest.approx(0.0)
+    assert report["los"] is None
+    for _, _, moves, outcome in report["games"]:
+        assert len(moves) == 6
+        assert outcome.is_draw()
+        assert outcome.termination() == "adjudication"
+
+
+def test_play_match_illegal_move_forfeits() -> None:
+    def illegal(game: spooky_chess.Game) -> spooky_chess.Move | None:
+        return spooky_chess.Move.from_lan("e2e5", 8, 8)
+
+    report = spooky_chess.play_match(illegal, first_legal)
+    assert (report["wins"], report["losses"], report["draws"]) == (0, 2, 0)
+    for _, _, _, outcome in report["games"]:
+        assert outcome.termination() == "adjudication"
+
+
+def test_play_match_openings() -> None:
+    opening = spooky_chess.Game.standard()
+    opening.make_move(opening.move_from_lan("e2e4"))
+    seen = []
+
+    def recording(game: spooky_chess.Game) -> spooky_chess.Move | None:
+        seen.append(game.move_count())
+        # Players get a copy, so changing it doesn't affect the match
+        game.make_move(game.legal_moves()[0])
+        return None
+
+    openings = [spooky_chess.Game.standard(), opening]
+    report = spooky_chess.play_match(first_legal, recording, num_games=4, openings=openings)
+    assert [opening for opening, _, _, _ in report["games"]] == [0, 0, 1, 1]
+    assert report["wins"] == 4
+    assert seen == [1, 0, 1, 2]
+
+    with pytest.raises(ValueError, match="same board size"):
+        spooky_chess.play_match(first_legal, first_legal, openings=[opening, spooky_chess.Game.capablanca()])
+
+
+def test_play_match_resign_adjudication() -> None:
+    opening = spooky_chess.Game(8, 8, "4k3/8/8/8/8/8/8/QQQ1K3 w - - 0 1", True)
+    report = spooky_chess.play_match(first_legal, first_legal, openings=[opening], resign=(500, 2))
+    # White wins both games, so each player wins once
+    assert (report["wins"], report["losses"], report["draws"]) == (1, 1, 0)
+    for _, _, moves, outcome in report["games"]:
+        assert len(moves) == 2
+        assert outcome.winner() == spooky_chess.WHITE
+        assert outcome.termination() == "adjudication"
+
+
+def test_play_match_draw_adjudication() -> None:
+    report = spooky_chess.play_match(first_legal, first_legal, draw=(50, 4, 10))
+    for _, _, moves, outcome in report["games"]:
+        assert len(moves) >= 10
+        assert outcome.is_draw()
+
+
+def test_play_match_player_errors() -> None:
+    class PlayerError(Exception):
+        pass
+
+    def failing(game: spooky_chess.Game) -> spooky_chess.Move | None:
+        raise PlayerError
+
+    with pytest.raises(PlayerError):
+        spooky_chess.play_match(first_legal, failing)
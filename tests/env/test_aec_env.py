@@ -0,0 +1,72 @@
+import pytest
+
+import spooky_chess
+from tests.utilities import step_lans
+
+FOOLS_MATE = ["f2f3", "e7e5", "g2g4", "d8h4"]
+
+
+def test_aec_env_initial_state() -> None:
+    env = spooky_chess.AecEnv()
+    assert env.possible_agents == ["player_0", "player_1"]
+    assert env.agents == ["player_0", "player_1"]
+    assert env.agent_selection == "player_0"
+    assert env.terminations == {"player_0": False, "player_1": False}
+    assert env.truncations == {"player_0": False, "player_1": False}
+    assert env.rewards == {"player_0": 0.0, "player_1": 0.0}
+
+    observation = env.observe("player_0")
+    data, planes, height, width = observation["observation"]
+    assert (planes, height, width) == env.observation_shape()
+    assert len(data) == planes * height * width
+    assert len(observation["action_mask"]) == env.total_actions()
+    assert sum(observation["action_mask"]) == 20
+
+    with pytest.raises(KeyError):
+        env.observe("player_2")
+
+
+def test_aec_env_checkmate() -> None:
+    env = spooky_chess.AecEnv()
+    step_lans(env, FOOLS_MATE)
+    assert env.terminations == {"player_0": True, "player_1": True}
+    assert env.rewards == {"player_0": -1.0, "player_1": 1.0}
+
+    # Each agent is stepped with None to remove it
+    seen = []
+    for agent in env.agent_iter():
+        observation, reward, termination, truncation, _ = env.last()
+        assert observation is not None
+        assert sum(observation["action_mask"]) == 0
+        assert termination
+        assert not truncation
+        seen.append((agent, reward))
+        env.step(None)
+    assert seen == [("player_0", -1.0), ("player_1", 1.0)]
+    assert env.agents == []
+
+    with pytest.raises(RuntimeError):
+        env.step(None)
+
+    env.reset()
+    assert env.agents == ["player_0", "player_1"]
+    assert env.game.to_fen() == spooky_chess.Game.standard().to_fen()
+
+
+def test_aec_env_truncation() -> None:
+    env = spooky_chess.AecEnv(max_plies=2)
+    step_lans(env, ["e2e4", "e7e5"])
+    assert env.truncations == {"player_0": True, "player_1": True}
+    assert env.terminations == {"player_0": False, "player_1": False}
+    with pytest.raises(ValueError):  # noqa: PT011
+        env.step(0)
+
+
+def test_aec_env_rejects_illegal_actions() -> None:
+    env = spooky_chess.AecEnv()
+    illegal = env.game.move_from_lan("e2e4")
+    step_lans(env, ["e2e4"])
+    with pytest.raises(ValueError):  # noqa: PT011
+        env.step(illegal.encode(8, 8))
+    with pytest.raises(ValueError):  # noqa: PT011
+        env.step(None)
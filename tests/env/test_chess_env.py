@@ -0,0 +1,99 @@
+import pytest
+
+import spooky_chess
+
+FOOLS_MATE = ["f2f3", "e7e5", "g2g4", "d8h4"]
+
+
+def action(env: spooky_chess.ChessEnv, lan: str) -> int:
+    return env.game.move_from_lan(lan).encode(8, 8)
+
+
+def test_chess_env_spaces() -> None:
+    env = spooky_chess.ChessEnv()
+    assert env.action_space["type"] == "Discrete"
+    assert env.action_space["n"] == spooky_chess.Game.standard().total_actions()
+    assert env.observation_space["type"] == "Box"
+    assert env.observation_space["dtype"] == "float32"
+    assert len(env.observation_space["shape"]) == 3
+    assert not env.done
+    assert repr(env) == "ChessEnv(width=8, height=8, done=False)"
+
+
+def test_chess_env_action_mask() -> None:
+    env = spooky_chess.ChessEnv()
+    mask = env.action_mask()
+    assert len(mask) == env.action_space["n"]
+    assert sum(mask) == 20
+    assert mask[action(env, "e2e4")] == 1
+    assert mask[action(env, "e2e5")] == 0
+
+
+def test_chess_env_reset_and_step() -> None:
+    np = pytest.importorskip("numpy")
+    env = spooky_chess.ChessEnv()
+    obs = env.reset()
+    assert obs.shape == env.observation_space["shape"]
+    assert obs.dtype == np.float32
+
+    obs, reward, done, info = env.step(action(env, "e2e4"))
+    assert obs.shape == env.observation_space["shape"]
+    assert reward == 0.0
+    assert not done
+    assert len(info["legal_actions"]) == 20
+    assert info["termination"] is None
+    assert not info["truncated"]
+    expected = spooky_chess.Game.standard()
+    expected.make_move(expected.move_from_lan("e2e4"))
+    assert env.game.to_fen() == expected.to_fen()
+
+
+def test_chess_env_checkmate() -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.ChessEnv()
+    env.reset()
+    for lan in FOOLS_MATE:
+        _, reward, done, info = env.step(action(env, lan))
+    assert done
+    assert reward == 1.0
+    assert info["termination"] == "checkmate"
+    assert info["legal_actions"] == []
+    assert env.done
+    assert sum(env.action_mask()) == 0
+
+    with pytest.raises(RuntimeError):
+        env.step(0)
+
+    env.reset()
+    assert not env.done
+    assert env.game.to_fen() == spooky_chess.Game.standard().to_fen()
+
+
+def test_chess_env_truncation() -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.ChessEnv(max_plies=2)
+    env.reset()
+    _, _, done, _ = env.step(action(env, "e2e4"))
+    assert not done
+    _, reward, done, info = env.step(action(env, "e7e5"))
+    assert done
+    assert reward == 0.0
+    assert info["truncated"]
+    assert info["termination"] is None
+
+
+def test_chess_env_rejects_illegal_actions() -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.ChessEnv()
+    env.reset()
+    with pytest.raises(ValueError):  # noqa: PT011
+        env.step(action(env, "e2e5"))
+    assert not env.done
+
+
+def test_chess_env_custom_start() -> None:
+    game = spooky_chess.Game.standard()
+    game.make_move(game.move_from_lan("e2e4"))
+    env = spooky_chess.ChessEnv(game)
+    assert sum(env.action_mask()) == 20
+    assert env.game.to_fen() == game.to_fen()
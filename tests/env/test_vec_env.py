@@ -0,0 +1,82 @@
+import pytest
+
+import spooky_chess
+
+FOOLS_MATE = ["f2f3", "e7e5", "g2g4", "d8h4"]
+KNIGHTS = ["g1f3", "g8f6", "f3g1", "f6g8"]
+
+
+def actions(env: spooky_chess.VecEnv, lans: list[str]) -> list[int]:
+    return [env.game(i).move_from_lan(lan).encode(8, 8) for i, lan in enumerate(lans)]
+
+
+def test_vec_env_spaces() -> None:
+    env = spooky_chess.VecEnv(3)
+    assert env.num_envs == 3
+    assert len(env) == 3
+    assert env.action_space == spooky_chess.ChessEnv().action_space
+    assert env.observation_space == spooky_chess.ChessEnv().observation_space
+    assert repr(env) == "VecEnv(num_envs=3, width=8, height=8)"
+
+    masks = env.action_masks()
+    assert len(masks) == 3
+    assert all(sum(mask) == 20 for mask in masks)
+
+    with pytest.raises(IndexError):
+        env.game(3)
+    with pytest.raises(ValueError):  # noqa: PT011
+        spooky_chess.VecEnv(0)
+
+
+@pytest.mark.parametrize("parallel", [True, False])
+def test_vec_env_step_batch(parallel: bool) -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.VecEnv(2, parallel=parallel)
+    observations = env.reset_all()
+    assert observations.shape == (2, *env.observation_space["shape"])
+
+    for step, pair in enumerate(zip(FOOLS_MATE, KNIGHTS, strict=True)):
+        observations, rewards, dones = env.step_batch(actions(env, list(pair)))
+        assert observations.shape == (2, *env.observation_space["shape"])
+        if step < len(FOOLS_MATE) - 1:
+            assert rewards.tolist() == [0.0, 0.0]
+            assert dones.tolist() == [False, False]
+
+    assert rewards.tolist() == [1.0, 0.0]
+    assert dones.tolist() == [True, False]
+
+    # The finished environment was reset straight away
+    standard = spooky_chess.Game.standard().to_fen()
+    assert env.game(0).to_fen() == standard
+    assert env.game(1).to_fen().split()[0] == standard.split()[0]
+    assert env.game(1).move_count() == 4
+
+
+def test_vec_env_truncation() -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.VecEnv(1, max_plies=2)
+    env.reset_all()
+    _, _, dones = env.step_batch(actions(env, ["e2e4"]))
+    assert dones.tolist() == [False]
+    _, rewards, dones = env.step_batch(actions(env, ["e7e5"]))
+    assert dones.tolist() == [True]
+    assert rewards.tolist() == [0.0]
+    assert env.game(0).move_count() == 0
+
+
+def test_vec_env_rejects_illegal_actions() -> None:
+    pytest.importorskip("numpy")
+    env = spooky_chess.VecEnv(2)
+    env.reset_all()
+    legal = actions(env, ["e2e4", "e2e4"])
+    illegal = actions(env, ["e2e4", "e2e5"])
+
+    with pytest.raises(ValueError, match="environment 1"):
+        env.step_batch(illegal)
+    with pytest.raises(ValueError):  # noqa: PT011
+        env.step_batch(legal[:1])
+
+    # Nothing was stepped
+    assert env.game(0).move_count() == 0
+    env.step_batch(legal)
+    assert env.game(0).move_count() == 1
@@ -0,0 +1,63 @@
+import copy
+
+import spooky_chess
+from tests.utilities import play
+
+
+def test_copies_are_independent() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["e2e4"])
+    for copied in [copy.copy(game), copy.deepcopy(game)]:
+        assert copied == game
+        assert copied is not game
+        play(copied, ["e7e5"])
+        assert copied != game
+        assert game.move_count() == 1
+
+
+def test_equality_ignores_move_history() -> None:
+    # Knights out and back reach the start position with different clocks
+    game = spooky_chess.Game.standard()
+    play(game, ["g1f3", "g8f6", "f3g1", "f6g8"])
+    assert game != spooky_chess.Game.standard()
+
+    # The same position by different move orders is equal
+    first = spooky_chess.Game.standard()
+    play(first, ["e2e3", "g8f6", "g1f3", "b8c6"])
+    second = spooky_chess.Game.standard()
+    play(second, ["e2e3", "b8c6", "g1f3", "g8f6"])
+    assert first == second
+    assert hash(first) == hash(second)
+
+
+def test_equality_compares_turn_castling_and_en_passant() -> None:
+    fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1"
+    game = spooky_chess.Game(8, 8, fen, True)
+    assert game == spooky_chess.Game(8, 8, fen, True)
+    assert game != spooky_chess.Game(8, 8, fen.replace(" w ", " b "), True)
+    assert game != spooky_chess.Game(8, 8, fen.replace("KQkq", "Kkq"), True)
+
+    first = spooky_chess.Game.standard()
+    play(first, ["e2e4", "a7a6", "e4e5", "d7d5"])
+    second = spooky_chess.Game.standard()
+    play(second, ["e2e4", "d7d6", "e4e5", "a7a6", "g1f3", "d6d5", "f3g1"])
+    # Same board and turn, but only the first can capture en passant
+    assert first.to_fen().split()[0] == second.to_fen().split()[0]
+    assert first != second
+
+
+def test_games_of_different_sizes_are_never_equal() -> None:
+    assert spooky_chess.Game.standard() != spooky_chess.Game.capablanca()
+    assert spooky_chess.Game.standard() != "not a game"
+
+
+def test_games_in_sets_and_dicts() -> None:
+    first = spooky_chess.Game.standard()
+    play(first, ["e2e3", "g8f6", "g1f3", "b8c6"])
+    second = spooky_chess.Game.standard()
+    play(second, ["e2e3", "b8c6", "g1f3", "g8f6"])
+
+    assert len({first, second, spooky_chess.Game.standard()}) == 2
+    visits = {first: 1}
+    visits[second] = visits.get(second, 0) + 1
+    assert visits == {first: 2}
@@ -0,0 +1,89 @@
+import pytest
+
+import spooky_chess
+
+
+def test_error_hierarchy() -> None:
+    for error in [
+        spooky_chess.InvalidFenError,
+        spooky_chess.InvalidDimensionsError,
+        spooky_chess.InvalidSquareError,
+        spooky_chess.InvalidMoveError,
+        spooky_chess.IllegalMoveError,
+        spooky_chess.InvalidPositionError,
+        spooky_chess.InvalidRulesError,
+    ]:
+        assert issubclass(error, spooky_chess.ChessError)
+    assert issubclass(spooky_chess.ChessError, ValueError)
+
+
+def test_invalid_fen() -> None:
+    with pytest.raises(spooky_chess.InvalidFenError, match="row 1"):
+        spooky_chess.Game(8, 8, "rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", True)
+
+    # Still catchable as the ValueError it used to be
+    with pytest.raises(ValueError):  # noqa: PT011
+        spooky_chess.Game(8, 8, "not a fen", True)
+
+
+def test_invalid_dimensions() -> None:
+    with pytest.raises(spooky_chess.InvalidDimensionsError):
+        spooky_chess.Game(4, 4, "4/4/4/4 w - - 0 1", False)
+
+    game = spooky_chess.Game.standard()
+    with pytest.raises(spooky_chess.InvalidDimensionsError, match="padded"):
+        game.encode_game_planes(spooky_chess.EncoderConfig(padded_size=6))
+    with pytest.raises(spooky_chess.InvalidDimensionsError, match="history_length"):
+        spooky_chess.EncoderConfig(history_length=0)
+    with pytest.raises(spooky_chess.InvalidDimensionsError, match="history_length"):
+        spooky_chess.Game(8, 8, game.to_fen(), True, history_length=0)
+
+
+def test_invalid_square() -> None:
+    with pytest.raises(spooky_chess.InvalidSquareError):
+        spooky_chess.Position.from_algebraic("9a")
+
+
+def test_invalid_and_illegal_moves() -> None:
+    with pytest.raises(spooky_chess.InvalidMoveError):
+        spooky_chess.Move.from_lan("e2e9", 8, 8)
+
+    game = spooky_chess.Game.standard()
+    with pytest.raises(spooky_chess.IllegalMoveError):
+        game.move_from_lan("e3e4")
+    with pytest.raises(spooky_chess.IllegalMoveError):
+        game.move_from_san("e5")
+    with pytest.raises(spooky_chess.InvalidMoveError):
+        game.move_from_san("Zz9")
+
+
+def test_invalid_position_edits() -> None:
+    game = spooky_chess.Game.standard()
+    with pytest.raises(spooky_chess.InvalidPositionError):
+        game.remove_all("k", spooky_chess.WHITE)
+    with pytest.raises(spooky_chess.InvalidPositionError):
+        game.shift_board(0, 1)
+
+    # The rejected edits leave the game unchanged
+    assert game.to_fen() == spooky_chess.Game.standard().to_fen()
+
+
+def test_invalid_rules() -> None:
+    game = spooky_chess.Game.standard()
+    with pytest.raises(spooky_chess.InvalidRulesError):
+        game.set_draw_rules(halfmove_limit=0)
+    with pytest.raises(spooky_chess.InvalidRulesError):
+        game.set_castling_king_cols((3, 3))
+
+    # Fairy promotions have no actions without the fairy planes
+    capablanca = spooky_chess.Game.capablanca()
+    with pytest.raises(spooky_chess.InvalidRulesError):
+        capablanca.legal_action_indices()
+    config = spooky_chess.EncoderConfig(fairy_piece_planes=True)
+    assert len(capablanca.legal_action_indices(config)) == len(capablanca.legal_moves())
+
+    # Nor can its archbishops and chancellors be encoded
+    with pytest.raises(spooky_chess.InvalidRulesError):
+        capablanca.encode_game_planes()
+    _, planes, _, _ = capablanca.encode_game_planes(config)
+    assert planes == config.num_planes()
@@ -1,5 +1,7 @@
 import spooky_chess
 
+from tests.utilities import play
+
 
 def test_standard_game_initial_position_legal_moves() -> None:
     game = spooky_chess.Game.standard()
@@ -122,3 +124,24 @@ def test_standard_game_promotion_moves() -> None:
 
     assert promotion is not None
     assert promotion == "q"
+
+
+def test_capablanca_fairy_promotions() -> None:
+    fen = "4k5/P9/10/10/10/10/10/4K5 w - - 0 1"
+    game = spooky_chess.Game(10, 8, fen, True)
+    assert not game.fairy_promotions_enabled()
+    promotions = [m.to_lan() for m in game.legal_moves() if m.promotion() is not None]
+    assert promotions == ["a7a8q", "a7a8n", "a7a8b", "a7a8r"]
+
+    game.set_fairy_promotions(True)
+    promotions = [m.to_lan() for m in game.legal_moves() if m.promotion() is not None]
+    assert promotions == ["a7a8q", "a7a8n", "a7a8b", "a7a8r", "a7a8a", "a7a8c"]
+    assert spooky_chess.Game.capablanca().fairy_promotions_enabled()
+
+
+def test_legal_moves_flag_checks() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["e2e4", "d7d6"])
+    checks = [m.to_lan() for m in game.legal_moves() if m.is_check]
+    assert checks == ["f1b5"]
+    assert [m.to_lan() for m in game.checks()] == checks
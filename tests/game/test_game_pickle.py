@@ -0,0 +1,90 @@
+import multiprocessing
+import pickle
+
+import spooky_chess
+from tests.utilities import play
+
+
+def test_pickle_keeps_move_history() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["e2e4", "e7e5", "g1f3", "b8c6"])
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.to_fen() == game.to_fen()
+    assert [m.to_lan() for m in restored.move_history()] == ["e2e4", "e7e5", "g1f3", "b8c6"]
+    assert restored.unmake_move()
+    assert restored.to_fen() != game.to_fen()
+
+
+def test_pickle_keeps_rule_options() -> None:
+    game = spooky_chess.Game(10, 8, spooky_chess.Game.capablanca().to_fen(), False)
+    game.set_self_capture(True)
+    game.set_draw_rules(halfmove_limit=80, repetition_limit=3, ply_limit=200)
+    game.set_double_push_row(2)
+    game.set_fairy_promotions(True)
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert (restored.width(), restored.height()) == (10, 8)
+    assert not restored.castling_enabled()
+    assert restored.self_capture_enabled()
+    assert restored.draw_rules() == (80, 3, 200)
+    assert restored.double_push_row() == 2
+    assert restored.fairy_promotions_enabled()
+    assert restored.to_fen() == game.to_fen()
+
+
+def test_pickle_from_custom_start() -> None:
+    fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"
+    game = spooky_chess.Game(8, 8, fen, True)
+    play(game, ["e2e4"])
+    assert game.make_null_move()
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.to_fen() == game.to_fen()
+    assert restored.move_count() == 2
+    assert restored.unmake_move()
+    assert restored.unmake_move()
+    assert restored.to_fen() == fen
+
+
+def test_pickle_through_a_process_queue() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["d2d4", "d7d5"])
+    context = multiprocessing.get_context("spawn")
+    queue = context.SimpleQueue()
+    queue.put(game)
+    assert queue.get().to_fen() == game.to_fen()
+
+
+def test_pickle_keeps_resignation() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["e2e4", "e7e5"])
+    assert game.resign(spooky_chess.WHITE)
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.is_over()
+    assert restored.outcome() == game.outcome()
+    assert restored.outcome().termination() == "resignation"
+    assert restored.outcome().winner() == spooky_chess.BLACK
+
+
+def test_pickle_keeps_adjudication() -> None:
+    game = spooky_chess.Game.standard()
+    play(game, ["d2d4"])
+    assert game.adjudicate(None)
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.is_over()
+    assert restored.outcome() == game.outcome()
+    assert restored.outcome().winner() is None
+    assert restored.unmake_move()
+    assert not restored.is_over()
+
+
+def test_pickle_keeps_history_length() -> None:
+    game = spooky_chess.Game(8, 8, spooky_chess.Game.standard().to_fen(), True, history_length=2)
+    restored = pickle.loads(pickle.dumps(game))
+
+    assert restored.history_length == 2
+    _, planes, _, _ = restored.encode_game_planes()
+    assert planes == spooky_chess.EncoderConfig(history_length=2).num_planes()
@@ -1,5 +1,7 @@
 import spooky_chess
 
+from tests.utilities import play
+
 
 def test_standard_game_turn_alternation() -> None:
     game = spooky_chess.Game.standard()
@@ -160,3 +162,21 @@ def test_turn_state_terminal_exposes_outcome() -> None:
     outcome = turn_state.outcome()
     assert outcome is not None
     assert str(outcome) == "stalemate"
+
+
+def test_claimed_and_automatic_repetition_draws_differ() -> None:
+    shuffle = ["g1f3", "g8f6", "f3g1", "f6g8"]
+    claimed = spooky_chess.Game.standard()
+    play(claimed, shuffle * 2)
+    automatic = spooky_chess.Game.standard()
+    play(automatic, shuffle * 4)
+
+    outcome = claimed.claim_draw()
+    assert outcome is not None
+    assert outcome.is_threefold_repetition()
+    assert outcome.termination() == "threefold_repetition"
+
+    outcome = automatic.outcome()
+    assert outcome is not None
+    assert outcome.is_fivefold_repetition()
+    assert outcome.termination() == "fivefold_repetition"
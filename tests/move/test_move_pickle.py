@@ -0,0 +1,25 @@
+import pickle
+
+import spooky_chess
+
+
+def test_pickle_move() -> None:
+    move = spooky_chess.Move.from_lan("e2e4", 8, 8)
+    restored = pickle.loads(pickle.dumps(move))
+    assert restored == move
+    assert restored.to_lan() == "e2e4"
+
+
+def test_pickle_keeps_flags_and_promotion() -> None:
+    game = spooky_chess.Game(8, 8, "4k3/1P6/8/8/8/8/8/R3K3 w Q - 0 1", True)
+    for lan in ["b7b8n", "e1c1"]:
+        move = game.move_from_lan(lan)
+        restored = pickle.loads(pickle.dumps(move))
+        assert restored == move
+        assert restored.to_lan() == lan
+        assert game.is_legal_move(restored)
+
+
+def test_pickle_null_move() -> None:
+    restored = pickle.loads(pickle.dumps(spooky_chess.Move.null()))
+    assert restored == spooky_chess.Move.null()
@@ -0,0 +1,92 @@
+import array
+
+import pytest
+
+import spooky_chess
+
+
+def games() -> list[spooky_chess.Game]:
+    first = spooky_chess.Game.standard()
+    second = spooky_chess.Game.standard()
+    second.make_move(second.move_from_lan("e2e4"))
+    return [first, second]
+
+
+def test_game_batch_basics() -> None:
+    batch = spooky_chess.GameBatch(games())
+    assert len(batch) == 2
+    assert (batch.width, batch.height) == (8, 8)
+    assert repr(batch) == "GameBatch(len=2, width=8, height=8)"
+    assert batch[1].to_fen() == games()[1].to_fen()
+    assert batch[-1].to_fen() == batch[1].to_fen()
+    with pytest.raises(IndexError):
+        _game = batch[2]
+
+
+def test_game_batch_rejects_mixed_sizes() -> None:
+    with pytest.raises(ValueError):  # noqa: PT011
+        spooky_chess.GameBatch([])
+    with pytest.raises(ValueError, match="8x8"):
+        spooky_chess.GameBatch([spooky_chess.Game.standard(), spooky_chess.Game.capablanca()])
+
+
+def test_game_batch_holds_copies() -> None:
+    game = spooky_chess.Game.standard()
+    batch = spooky_chess.GameBatch([game])
+    game.make_move(game.move_from_lan("e2e4"))
+    assert batch[0].move_count() == 0
+
+
+@pytest.mark.parametrize("parallel", [True, False])
+def test_game_batch_encode_into_matches_single_games(parallel: bool) -> None:
+    batch = spooky_chess.GameBatch(games())
+    expected = []
+    for game in games():
+        data, _, _, _ = game.encode_game_planes()
+        expected += data
+
+    out = array.array("f", [0.0] * len(expected))
+    batch.encode_into(out, parallel=parallel)
+    assert out.tolist() == pytest.approx(expected)
+
+    with pytest.raises(ValueError):  # noqa: PT011
+        batch.encode_into(array.array("f", [0.0] * (len(expected) - 1)))
+
+
+def test_game_batch_encode() -> None:
+    pytest.importorskip("numpy")
+    batch = spooky_chess.GameBatch(games())
+    _, planes, height, width = games()[1].encode_game_planes()
+    encoded = batch.encode()
+    assert encoded.shape == (2, planes, height, width)
+    assert encoded[1].tolist() == batch[1].encode_game_planes_array().tolist()
+
+
+def test_game_batch_apply_actions() -> None:
+    batch = spooky_chess.GameBatch(games())
+    e2e4 = spooky_chess.Move.from_lan("e2e4", 8, 8).encode(8, 8)
+    e7e5 = spooky_chess.Move.from_lan("e7e5", 8, 8).encode(8, 8)
+
+    # The first game has already moved its pawn in the second
+    assert batch.apply_actions([e2e4, e2e4]) == [True, False]
+    assert batch.apply_actions([e7e5, e7e5]) == [True, True]
+    assert batch[0].to_fen() == batch[1].to_fen()
+
+    with pytest.raises(ValueError):  # noqa: PT011
+        batch.apply_actions([e2e4])
+
+
+def test_board_planes() -> None:
+    pytest.importorskip("numpy")
+    game = spooky_chess.Game.standard()
+    game.make_move(game.move_from_lan("e2e4"))
+    planes = game.board_planes()
+    assert planes.shape == (12, 8, 8)
+    assert planes.dtype == "float32"
+    assert planes.sum() == 32
+    # White pawn plane, row 4 (index 3), e-file
+    assert planes[0, 3, 4] == 1
+
+    capablanca = spooky_chess.Game.capablanca()
+    assert capablanca.board_planes().shape == (16, 8, 10)
+    assert capablanca.board_planes().sum() == 40
@@ -0,0 +1,78 @@
+from pathlib import Path
+
+import pytest
+
+import spooky_chess
+
+PGN = """[Event "Fool's mate"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+
+[Event "Knight shuffle"]
+[Result "1/2-1/2"]
+
+1. Nf3 Nf6 2. Ng1 Ng8 1/2-1/2
+
+[Event "Unfinished"]
+[Result "*"]
+
+1. e4 e5 *
+
+[Event "Illegal"]
+[Result "1-0"]
+
+1. e5 1-0
+"""
+
+FOOLS_MATE = ["f2f3", "e7e5", "g2g4", "d8h4"]
+
+
+@pytest.fixture
+def pgn_path(tmp_path: Path) -> Path:
+    path = tmp_path / "games.pgn"
+    path.write_text(PGN)
+    return path
+
+
+def test_pgn_to_samples(pgn_path: Path, tmp_path: Path) -> None:
+    output = tmp_path / "samples.bin"
+    written, skipped = spooky_chess.pgn_to_samples(str(pgn_path), str(output))
+    # Four positions from each finished game; no result or an illegal move
+    # skips the game
+    assert (written, skipped) == (8, 2)
+
+    reader = spooky_chess.SampleReader(str(output))
+    assert len(reader) == 8
+    assert reader.plane_shape() == spooky_chess.ChessEnv().observation_space["shape"]
+
+
+def test_pgn_to_samples_every_nth_and_skip_plies(pgn_path: Path, tmp_path: Path) -> None:
+    output = tmp_path / "samples.bin"
+    assert spooky_chess.pgn_to_samples(str(pgn_path), str(output), every_nth=2) == (4, 2)
+    assert spooky_chess.pgn_to_samples(str(pgn_path), str(output), skip_plies=3) == (2, 2)
+    assert spooky_chess.pgn_to_samples(str(pgn_path), str(output), every_nth=2, skip_plies=1) == (4, 2)
+    assert len(spooky_chess.SampleReader(str(output))) == 4
+
+
+def test_pgn_to_samples_policy_and_value(pgn_path: Path, tmp_path: Path) -> None:
+    pytest.importorskip("numpy")
+    output = tmp_path / "samples.bin"
+    spooky_chess.pgn_to_samples(str(pgn_path), str(output))
+    reader = spooky_chess.SampleReader(str(output))
+
+    # The move played is the policy target, and the result is from the side
+    # to move's perspective
+    samples = list(reader)
+    for (_, policy, value), lan, expected in zip(samples, FOOLS_MATE, [-1.0, 1.0, -1.0, 1.0], strict=True):
+        assert policy == [(spooky_chess.Move.from_lan(lan, 8, 8).encode(8, 8), 1.0)]
+        assert value == expected
+    assert [value for _, _, value in samples[4:]] == [0.0] * 4
+
+    game = spooky_chess.Game.standard()
+    assert samples[0][0].tolist() == game.encode_game_planes_array().tolist()
+
+
+def test_pgn_to_samples_missing_file(tmp_path: Path) -> None:
+    with pytest.raises(ValueError, match="Failed to open"):
+        spooky_chess.pgn_to_samples(str(tmp_path / "missing.pgn"), str(tmp_path / "samples.bin"))
@@ -0,0 +1,73 @@
+import array
+from pathlib import Path
+
+import pytest
+
+import spooky_chess
+
+SHAPE = (2, 3, 4)
+SIZE = 2 * 3 * 4
+
+
+def write_samples(path: Path) -> None:
+    with spooky_chess.SampleWriter(str(path), *SHAPE) as writer:
+        writer.write([0.5] * SIZE, [(3, 1.0)], 1.0)
+        writer.write(array.array("f", range(SIZE)), [(0, 0.25), (7, 0.75)], -0.5)
+        writer.write([0.0] * SIZE, [], 0.0)
+        assert len(writer) == 3
+
+
+def test_sample_file_round_trip(tmp_path: Path) -> None:
+    pytest.importorskip("numpy")
+    path = tmp_path / "samples.bin"
+    write_samples(path)
+
+    reader = spooky_chess.SampleReader(str(path))
+    assert len(reader) == 3
+    assert reader.plane_shape() == SHAPE
+    assert repr(reader) == "SampleReader(len=3, plane_shape=(2, 3, 4))"
+
+    planes, policy, value = reader[1]
+    assert planes.shape == SHAPE
+    assert [v for plane in planes.tolist() for row in plane for v in row] == [float(v) for v in range(SIZE)]
+    assert policy == [(0, 0.25), (7, 0.75)]
+    assert value == -0.5
+
+    assert reader[-1][1:] == ([], 0.0)
+    with pytest.raises(IndexError):
+        _sample = reader[3]
+
+    values = [value for _, _, value in reader]
+    assert values == [1.0, -0.5, 0.0]
+    # Iterating again starts over
+    assert len(list(reader)) == 3
+
+
+def test_sample_writer_checks_plane_count(tmp_path: Path) -> None:
+    with spooky_chess.SampleWriter(str(tmp_path / "samples.bin"), *SHAPE) as writer:
+        with pytest.raises(ValueError, match=f"expected {SIZE} plane values"):
+            writer.write([0.0] * (SIZE - 1), [], 0.0)
+        assert len(writer) == 0
+
+
+def test_sample_writer_close(tmp_path: Path) -> None:
+    path = tmp_path / "samples.bin"
+    writer = spooky_chess.SampleWriter(str(path), *SHAPE)
+    writer.write([0.0] * SIZE, [(1, 1.0)], 0.0)
+    writer.close()
+    writer.close()
+    with pytest.raises(RuntimeError):
+        writer.write([0.0] * SIZE, [], 0.0)
+
+    reader = spooky_chess.SampleReader(str(path))
+    assert len(reader) == 1
+    assert reader.plane_shape() == SHAPE
+
+
+def test_sample_reader_rejects_bad_files(tmp_path: Path) -> None:
+    path = tmp_path / "not_samples.bin"
+    path.write_bytes(b"definitely not a sample file")
+    with pytest.raises(ValueError, match="Not a training sample file"):
+        spooky_chess.SampleReader(str(path))
+    with pytest.raises(ValueError, match="Failed to open"):
+        spooky_chess.SampleReader(str(tmp_path / "missing.bin"))
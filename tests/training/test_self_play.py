@@ -0,0 +1,87 @@
+from typing import Any
+
+import pytest
+
+import spooky_chess
+
+
+def replay(moves: list[spooky_chess.Move], start: spooky_chess.Game | None = None) -> spooky_chess.Game:
+    game = spooky_chess.Game.standard() if start is None else start.clone()
+    for move in moves:
+        assert game.make_move(move)
+    return game
+
+
+def test_self_play_random() -> None:
+    games = spooky_chess.self_play(4, max_plies=40, seed=7)
+    assert len(games) == 4
+    for moves, outcome, samples in games:
+        assert samples is None
+        game = replay(moves)
+        assert outcome == game.outcome()
+        if outcome is None:
+            assert len(moves) == 40
+
+
+def test_self_play_is_reproducible() -> None:
+    def lans(games: list[Any]) -> list[list[str]]:
+        return [[move.to_lan() for move in moves] for moves, _, _ in games]
+
+    first = lans(spooky_chess.self_play(3, max_plies=30, seed=1))
+    assert lans(spooky_chess.self_play(3, max_plies=30, seed=1, parallel=False)) == first
+    assert lans(spooky_chess.self_play(3, max_plies=30, seed=2)) != first
+    # Each game draws from its own stream
+    assert len({tuple(moves) for moves in first}) == 3
+
+
+def test_self_play_from_custom_start() -> None:
+    start = spooky_chess.Game(8, 8, "7k/8/8/8/8/8/6Q1/K5R1 w - - 0 1", True)
+    for moves, outcome, _ in spooky_chess.self_play(4, game=start, max_plies=200):
+        assert outcome == replay(moves, start).outcome()
+
+
+def test_self_play_record_samples() -> None:
+    pytest.importorskip("numpy")
+    [(moves, outcome, samples)] = spooky_chess.self_play(1, max_plies=12, record_samples=True)
+    assert samples is not None
+    planes, fens, policy, value = samples
+    assert planes.shape[0] == len(moves) == len(fens) == len(policy) == len(value)
+    assert planes.shape[1:] == spooky_chess.ChessEnv().observation_space["shape"]
+    assert fens[0] == spooky_chess.Game.standard().to_fen()
+    assert policy[0] == moves[0].encode(8, 8)
+    if outcome is None:
+        assert value == [0.0] * len(moves)
+
+
+def test_self_play_with_policy() -> None:
+    pytest.importorskip("numpy")
+    calls = []
+
+    def first_legal(planes: Any, legal_actions: list[list[int]]) -> list[int]:
+        assert planes.shape[0] == len(legal_actions)
+        calls.append(len(legal_actions))
+        return [min(actions) for actions in legal_actions]
+
+    games = spooky_chess.self_play(3, policy=first_legal, max_plies=10)
+    assert calls == [3] * 10
+    assert len({tuple(move.to_lan() for move in moves) for moves, _, _ in games}) == 1
+    for moves, _, _ in games:
+        assert len(moves) == 10
+        replay(moves)
+
+
+def test_self_play_policy_errors() -> None:
+    pytest.importorskip("numpy")
+
+    class PolicyError(Exception):
+        pass
+
+    def failing(planes: Any, legal_actions: list[list[int]]) -> list[int]:
+        raise PolicyError
+
+    with pytest.raises(PolicyError):
+        spooky_chess.self_play(2, policy=failing)
+    with pytest.raises(ValueError, match="expected 2 actions"):
+        spooky_chess.self_play(2, policy=lambda planes, legal: [legal[0][0]])
+    with pytest.raises(ValueError, match="illegal action"):
+        spooky_chess.self_play(2, policy=lambda planes, legal: [0, 0])
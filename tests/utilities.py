@@ -0,0 +1,12 @@
+import spooky_chess
+
+
+def play(game: spooky_chess.Game, lans: list[str]) -> None:
+    for lan in lans:
+        assert game.make_move(game.move_from_lan(lan)), f"illegal move {lan}"
+
+
+def step_lans(env: spooky_chess.AecEnv, lans: list[str]) -> None:
+    for lan in lans:
+        game = env.game
+        env.step(game.move_from_lan(lan).encode(game.width(), game.height()))